@@ -0,0 +1,118 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Where a `#[winnow(...)]` attribute was found, controlling which keys are accepted
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Position {
+    Struct,
+    Enum,
+    Variant,
+    Field,
+}
+
+#[derive(Default)]
+pub(crate) struct Attrs {
+    pub(crate) input: Option<syn::Type>,
+    pub(crate) error: Option<syn::Type>,
+    pub(crate) tag: Option<syn::Lit>,
+    pub(crate) sep: Option<syn::Lit>,
+    pub(crate) endian: Option<Endian>,
+    pub(crate) parser: Option<syn::Expr>,
+}
+
+impl Attrs {
+    pub(crate) fn parse(attrs: &[syn::Attribute], position: Position) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+        for attr in attrs {
+            if !attr.path().is_ident("winnow") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                let key = meta
+                    .path
+                    .get_ident()
+                    .map(|i| i.to_string())
+                    .unwrap_or_default();
+                let allowed = match key.as_str() {
+                    "input" | "error" => matches!(position, Position::Struct | Position::Enum),
+                    "tag" => matches!(position, Position::Struct | Position::Variant),
+                    "sep" => matches!(
+                        position,
+                        Position::Struct | Position::Enum | Position::Variant
+                    ),
+                    "endian" => true,
+                    "parser" => position == Position::Field,
+                    _ => return Err(meta.error(format!("unknown `winnow` attribute `{key}`"))),
+                };
+                if !allowed {
+                    return Err(meta.error(format!("`{key}` is not supported here")));
+                }
+                let value = meta.value()?;
+                match key.as_str() {
+                    "input" => parsed.input = Some(value.parse()?),
+                    "error" => parsed.error = Some(value.parse()?),
+                    "tag" => parsed.tag = Some(literal(value.parse()?)?),
+                    "sep" => parsed.sep = Some(literal(value.parse()?)?),
+                    "endian" => parsed.endian = Some(Endian::parse(&value.parse()?)?),
+                    "parser" => parsed.parser = Some(value.parse()?),
+                    _ => unreachable!("unknown keys are rejected above"),
+                }
+                Ok(())
+            })?;
+        }
+        Ok(parsed)
+    }
+}
+
+fn literal(lit: syn::Lit) -> syn::Result<syn::Lit> {
+    match lit {
+        syn::Lit::Str(_) | syn::Lit::ByteStr(_) | syn::Lit::Char(_) | syn::Lit::Byte(_) => Ok(lit),
+        _ => Err(syn::Error::new(
+            lit.span(),
+            "expected a string, byte string, char, or byte literal",
+        )),
+    }
+}
+
+/// The type [`winnow::stream::Compare`] is called with for a literal
+pub(crate) fn literal_type(lit: &syn::Lit) -> TokenStream {
+    match lit {
+        syn::Lit::Str(_) => quote!(&'static str),
+        syn::Lit::ByteStr(bytes) => {
+            let len = bytes.value().len();
+            quote!(&'static [u8; #len])
+        }
+        syn::Lit::Char(_) => quote!(char),
+        syn::Lit::Byte(_) => quote!(u8),
+        _ => unreachable!("non-literals are rejected by `Attrs::parse`"),
+    }
+}
+
+#[derive(Copy, Clone)]
+pub(crate) enum Endian {
+    Big,
+    Little,
+    Native,
+}
+
+impl Endian {
+    fn parse(lit: &syn::LitStr) -> syn::Result<Self> {
+        match lit.value().as_str() {
+            "big" => Ok(Self::Big),
+            "little" => Ok(Self::Little),
+            "native" => Ok(Self::Native),
+            _ => Err(syn::Error::new(
+                lit.span(),
+                "expected `\"big\"`, `\"little\"`, or `\"native\"`",
+            )),
+        }
+    }
+
+    pub(crate) fn to_tokens(self) -> TokenStream {
+        match self {
+            Self::Big => quote!(::winnow::binary::Endianness::Big),
+            Self::Little => quote!(::winnow::binary::Endianness::Little),
+            Self::Native => quote!(::winnow::binary::Endianness::Native),
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use quote::ToTokens as _;
+use syn::visit::Visit;
+
+use crate::attr::literal_type;
+use crate::attr::Attrs;
+use crate::attr::Endian;
+use crate::attr::Position;
+
+pub(crate) fn derive_parse(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let position = match &input.data {
+        syn::Data::Struct(_) => Position::Struct,
+        syn::Data::Enum(_) => Position::Enum,
+        syn::Data::Union(_) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`Parse` cannot be derived for unions",
+            ))
+        }
+    };
+    let attrs = Attrs::parse(&input.attrs, position)?;
+    let mut ctx = Context::new(input, &attrs);
+
+    let name = &input.ident;
+    let trace_name = name.to_string();
+    let body = match &input.data {
+        syn::Data::Struct(data) => {
+            let group = Group {
+                path: quote!(Self),
+                tag: attrs.tag.as_ref(),
+                sep: attrs.sep.as_ref(),
+                endian: attrs.endian,
+            };
+            group.expand(&data.fields, &mut ctx)?
+        }
+        syn::Data::Enum(data) => expand_enum(name, data, &attrs, &mut ctx)?,
+        syn::Data::Union(_) => unreachable!("unions are rejected above"),
+    };
+
+    let Context {
+        input: input_ty,
+        error: error_ty,
+        generic_input: _,
+        mut impl_generics,
+        predicates,
+    } = ctx;
+    impl_generics
+        .make_where_clause()
+        .predicates
+        .extend(predicates);
+    let (impl_generics, _, where_clause) = impl_generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::winnow::Parse<#input_ty, #error_ty> for #name #ty_generics #where_clause {
+            fn parse_next(input: &mut #input_ty) -> ::winnow::PResult<Self, #error_ty> {
+                ::winnow::Parser::parse_next(
+                    &mut ::winnow::combinator::trace(
+                        #trace_name,
+                        |input: &mut #input_ty| -> ::winnow::PResult<Self, #error_ty> { #body },
+                    ),
+                    input,
+                )
+            }
+        }
+    })
+}
+
+fn expand_enum(
+    name: &syn::Ident,
+    data: &syn::DataEnum,
+    attrs: &Attrs,
+    ctx: &mut Context,
+) -> syn::Result<TokenStream> {
+    if data.variants.is_empty() {
+        return Err(syn::Error::new(
+            name.span(),
+            "`Parse` cannot be derived for enums without variants",
+        ));
+    }
+
+    let input_ty = ctx.input.clone();
+    let error_ty = ctx.error.clone();
+    let mut branches = Vec::new();
+    for variant in &data.variants {
+        let variant_attrs = Attrs::parse(&variant.attrs, Position::Variant)?;
+        let ident = &variant.ident;
+        let trace_name = format!("{name}::{ident}");
+        let group = Group {
+            path: quote!(Self::#ident),
+            tag: variant_attrs.tag.as_ref(),
+            sep: variant_attrs.sep.as_ref().or(attrs.sep.as_ref()),
+            endian: variant_attrs.endian.or(attrs.endian),
+        };
+        let body = group.expand(&variant.fields, ctx)?;
+        branches.push(quote! {
+            ::winnow::stream::Stream::reset(input, &start);
+            match ::winnow::Parser::parse_next(
+                &mut ::winnow::combinator::trace(
+                    #trace_name,
+                    |input: &mut #input_ty| -> ::winnow::PResult<Self, #error_ty> { #body },
+                ),
+                input,
+            ) {
+                ::core::result::Result::Err(::winnow::error::ErrMode::Backtrack(e)) => {
+                    error = ::core::option::Option::Some(match error {
+                        ::core::option::Option::Some(error) => {
+                            <#error_ty as ::winnow::error::ParserError<#input_ty>>::or(error, e)
+                        }
+                        ::core::option::Option::None => e,
+                    });
+                }
+                res => return res,
+            }
+        });
+    }
+
+    Ok(quote! {
+        let start = ::winnow::stream::Stream::checkpoint(input);
+        let mut error: ::core::option::Option<#error_ty> = ::core::option::Option::None;
+        #(#branches)*
+        match error {
+            ::core::option::Option::Some(e) => ::core::result::Result::Err(
+                ::winnow::error::ErrMode::Backtrack(<#error_ty as ::winnow::error::ParserError<#input_ty>>::append(
+                    e,
+                    input,
+                    &start,
+                    ::winnow::error::ErrorKind::Alt,
+                )),
+            ),
+            ::core::option::Option::None => ::core::result::Result::Err(
+                <::winnow::error::ErrMode<#error_ty> as ::winnow::error::ParserError<#input_ty>>::assert(
+                    input,
+                    "`Parse` needs at least one variant",
+                ),
+            ),
+        }
+    })
+}
+
+/// The shared state for generating a single `impl`
+struct Context {
+    input: TokenStream,
+    error: TokenStream,
+    generic_input: bool,
+    impl_generics: syn::Generics,
+    predicates: Vec<syn::WherePredicate>,
+}
+
+impl Context {
+    fn new(input: &syn::DeriveInput, attrs: &Attrs) -> Self {
+        let mut impl_generics = input.generics.clone();
+        let mut predicates = Vec::new();
+
+        let input_ty = match &attrs.input {
+            Some(ty) => quote!(#ty),
+            None => {
+                impl_generics.params.push(syn::parse_quote!(__I));
+                predicates.push(syn::parse_quote!(
+                    __I: ::winnow::stream::Stream + ::winnow::stream::StreamIsPartial
+                ));
+                quote!(__I)
+            }
+        };
+        let error_ty = match (&attrs.error, &attrs.input) {
+            (Some(ty), _) => quote!(#ty),
+            (None, Some(_)) => quote!(::winnow::error::ContextError),
+            (None, None) => {
+                impl_generics.params.push(syn::parse_quote!(__E));
+                predicates.push(syn::parse_quote!(__E: ::winnow::error::ParserError<__I>));
+                quote!(__E)
+            }
+        };
+
+        // Concrete `input` and `error` types may borrow with lifetimes that need declaring
+        let mut lifetimes = Lifetimes::default();
+        if let Some(ty) = &attrs.input {
+            lifetimes.visit_type(ty);
+        }
+        if let Some(ty) = &attrs.error {
+            lifetimes.visit_type(ty);
+        }
+        for (i, lifetime) in lifetimes.0.into_iter().enumerate() {
+            let declared = input
+                .generics
+                .lifetimes()
+                .any(|param| param.lifetime == lifetime);
+            if !declared {
+                impl_generics.params.insert(
+                    i,
+                    syn::GenericParam::Lifetime(syn::LifetimeParam::new(lifetime)),
+                );
+            }
+        }
+
+        Self {
+            input: input_ty,
+            error: error_ty,
+            generic_input: attrs.input.is_none(),
+            impl_generics,
+            predicates,
+        }
+    }
+
+    fn is_generic(&self) -> bool {
+        self.generic_input
+    }
+
+    fn push_predicate(&mut self, predicate: TokenStream) {
+        let key = predicate.to_string();
+        if !self
+            .predicates
+            .iter()
+            .any(|p| p.to_token_stream().to_string() == key)
+        {
+            self.predicates.push(syn::parse_quote!(#predicate));
+        }
+    }
+
+    fn literal(&mut self, lit: &syn::Lit) -> TokenStream {
+        if self.is_generic() {
+            let ty = literal_type(lit);
+            self.push_predicate(quote!(__I: ::winnow::stream::Compare<#ty>));
+        }
+        let input_ty = &self.input;
+        let error_ty = &self.error;
+        quote! {
+            let _ = ::winnow::Parser::parse_next(
+                &mut ::winnow::token::literal::<_, #input_ty, #error_ty>(#lit),
+                input,
+            )?;
+        }
+    }
+}
+
+#[derive(Default)]
+struct Lifetimes(Vec<syn::Lifetime>);
+
+impl<'ast> Visit<'ast> for Lifetimes {
+    fn visit_lifetime(&mut self, lifetime: &'ast syn::Lifetime) {
+        if lifetime.ident != "static" && lifetime.ident != "_" && !self.0.contains(lifetime) {
+            self.0.push(lifetime.clone());
+        }
+    }
+}
+
+/// A struct or enum variant's fields, parsed in sequence
+struct Group<'a> {
+    path: TokenStream,
+    tag: Option<&'a syn::Lit>,
+    sep: Option<&'a syn::Lit>,
+    endian: Option<Endian>,
+}
+
+impl Group<'_> {
+    fn expand(&self, fields: &syn::Fields, ctx: &mut Context) -> syn::Result<TokenStream> {
+        let mut stmts = Vec::new();
+        if let Some(tag) = self.tag {
+            stmts.push(ctx.literal(tag));
+        }
+
+        let mut bindings = Vec::new();
+        for (i, field) in fields.iter().enumerate() {
+            if i != 0 || self.tag.is_some() {
+                if let Some(sep) = self.sep {
+                    stmts.push(ctx.literal(sep));
+                }
+            }
+            let attrs = Attrs::parse(&field.attrs, Position::Field)?;
+            let binding = format_ident!("__field{}", i);
+            let ty = &field.ty;
+            let parser = field_parser(field, &attrs, self.endian, ctx)?;
+            stmts.push(quote!(let #binding: #ty = #parser?;));
+            bindings.push(binding);
+        }
+
+        let path = &self.path;
+        let value = match fields {
+            syn::Fields::Named(_) => {
+                let names = fields.iter().map(|f| &f.ident);
+                quote!(#path { #(#names: #bindings),* })
+            }
+            syn::Fields::Unnamed(_) => quote!(#path(#(#bindings),*)),
+            syn::Fields::Unit => quote!(#path),
+        };
+        Ok(quote! {
+            #(#stmts)*
+            ::core::result::Result::Ok(#value)
+        })
+    }
+}
+
+/// Generate an expression for parsing `field`, evaluating to a `PResult`
+fn field_parser(
+    field: &syn::Field,
+    attrs: &Attrs,
+    endian: Option<Endian>,
+    ctx: &mut Context,
+) -> syn::Result<TokenStream> {
+    let input_ty = ctx.input.clone();
+    let error_ty = ctx.error.clone();
+    let ty = &field.ty;
+
+    if let Some(parser) = &attrs.parser {
+        return Ok(quote!(
+            ::winnow::Parser::<#input_ty, #ty, #error_ty>::parse_next(&mut (#parser), input)
+        ));
+    }
+
+    let primitive = primitive(ty);
+    if let Some(endian) = attrs.endian.or(endian) {
+        if let Some(primitive) = primitive {
+            if ctx.is_generic() {
+                ctx.push_predicate(quote!(__I: ::winnow::stream::Stream<Token = u8>));
+            }
+            let func = format_ident!("{}", primitive);
+            return if matches!(primitive, "u8" | "i8") {
+                Ok(quote!(::winnow::binary::#func::<#input_ty, #error_ty>(input)))
+            } else {
+                let endian = endian.to_tokens();
+                Ok(quote!(::winnow::Parser::parse_next(
+                    &mut ::winnow::binary::#func::<#input_ty, #error_ty>(#endian),
+                    input,
+                )))
+            };
+        } else if attrs.endian.is_some() {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "`endian` is only supported for integer and float fields",
+            ));
+        }
+    }
+
+    ctx.push_predicate(quote!(#ty: ::winnow::Parse<#input_ty, #error_ty>));
+    Ok(quote!(<#ty as ::winnow::Parse<#input_ty, #error_ty>>::parse_next(input)))
+}
+
+/// The name of the `winnow::binary` parser for `ty`, if any
+fn primitive(ty: &syn::Type) -> Option<&'static str> {
+    const PRIMITIVES: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "f32", "f64",
+    ];
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    if path.qself.is_some() {
+        return None;
+    }
+    let ident = path.path.get_ident()?;
+    PRIMITIVES.iter().copied().find(|p| ident == p)
+}
@@ -0,0 +1,26 @@
+//! Derive macros for [`winnow`](https://docs.rs/winnow)
+//!
+//! This crate is re-exported by `winnow` with the `derive` feature; depend on that rather than
+//! this crate directly.
+
+#![warn(missing_docs)]
+
+mod attr;
+mod expand;
+
+use proc_macro::TokenStream;
+
+/// Generate a `winnow::Parse` implementation
+///
+/// Structs parse their fields in order and enums try each variant in order, like
+/// `winnow::combinator::alt`.
+///
+/// See [`winnow::Parse`](https://docs.rs/winnow/latest/winnow/trait.Parse.html) for the
+/// supported `#[winnow(...)]` attributes.
+#[proc_macro_derive(Parse, attributes(winnow))]
+pub fn derive_parse(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    expand::derive_parse(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
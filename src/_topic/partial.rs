@@ -18,11 +18,18 @@
 //! If the chunks are not homogeneous, a state machine will be needed to track what the expected
 //! parser is for the next chunk.
 //!
+//! For a [`std::io::Read`] source, [`ReadStream`] can take care of refilling the buffer on
+//! [`Incomplete`] and re-running the parser.
+//! [`IterStream`] does the same for the items of an [`Iterator`], like the output of another parser.
+//!
 //! Caveats:
 //! - `winnow` takes the approach of re-parsing from scratch. Chunks should be relatively small to
 //!   prevent the re-parsing overhead from dominating.
 //! - Parsers like [`repeat`] do not know when an `eof` is from insufficient data or the end of the
 //!   stream, causing them to always report [`Incomplete`].
+//! - Checkpoints do not outlive a top-level parse: on [`Incomplete`], parsing restarts from where
+//!   that parse started, so data before it can be released from your buffers.  [`ReadStream`]
+//!   reports this offset as [`ReadStream::watermark`].
 //!
 //! # Example
 //!
@@ -42,5 +49,9 @@ use crate::binary::length_and_then;
 use crate::combinator::repeat;
 use crate::error::ErrMode::Incomplete;
 use crate::error::Needed;
+#[cfg(feature = "alloc")]
+use crate::stream::IterStream;
 use crate::stream::Partial;
+#[cfg(feature = "std")]
+use crate::stream::ReadStream;
 use crate::stream::StreamIsPartial;
@@ -6,6 +6,10 @@
 //!
 //! Tips
 //! - Try `cargo add winnow -F simd`. For some it offers significant performance improvements
+//! - Scanning for a few ASCII tokens with [`take_till`][crate::token::take_till] (e.g.
+//!   `take_till(0.., ('\r', '\n'))`) or for a literal with [`take_until`][crate::token::take_until]
+//!   uses `memchr`/`memmem` on `&[u8]` and `&str` streams, where a closure has to be called for
+//!   every token
 //! - When enough cases of an [`alt`] have unique prefixes, prefer [`dispatch`]
 //! - When parsing text, try to parse as bytes (`u8`) rather than `char`s ([`BStr`] can make
 //!   debugging easier)
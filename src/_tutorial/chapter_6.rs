@@ -34,7 +34,9 @@
 //!     your error, [rendering it as you wish][chapter_7#error-adaptation-and-rendering].
 //!
 //! However, [`ParseError`] will still need some level of adaptation to integrate with your
-//! application's error type (like with `?`).
+//! application's error type (like with `?`).  As it borrows the input,
+//! [`into_owned`][ParseError::into_owned] it first.  The [source][std::error::Error::source] of
+//! the error is then the one captured by [`Parser::try_map`], if any.
 //!
 //! ```rust
 //! # use winnow::prelude::*;
@@ -54,7 +56,7 @@
 //!         parse_digits
 //!             .map(Hex)
 //!             .parse(input)
-//!             .map_err(|e| anyhow::format_err!("{e}"))
+//!             .map_err(|e| anyhow::Error::from(e.into_owned()))
 //!     }
 //! }
 //!
@@ -173,9 +173,10 @@
 //! }
 //! ```
 //!
-//! If you remember back to [`chapter_3`], [`alt`] will only report the last error.
+//! If you remember back to [`chapter_3`], [`alt`] only reports the last error, though
+//! [`ContextError`] also keeps what the other cases expected when they failed at the same place.
 //! So if the parsers fail for any reason, like a bad radix, it will be reported as an invalid
-//! hexadecimal value:
+//! digit:
 //! ```rust
 //! # use winnow::prelude::*;
 //! # use winnow::token::take_while;
@@ -256,7 +257,7 @@
 //! 100
 //! ^
 //! invalid digit
-//! expected hexadecimal";
+//! expected binary, octal, decimal, or hexadecimal";
 //!     assert_eq!(input.parse::<Hex>().unwrap_err(), error);
 //! }
 //! ```
@@ -308,11 +309,7 @@
 //!           .context(StrContext::Label("digit"))
 //!           .context(StrContext::Expected(StrContextValue::Description("hexadecimal"))),
 //!         fail
-//!           .context(StrContext::Label("radix prefix"))
-//!           .context(StrContext::Expected(StrContextValue::StringLiteral("0b")))
-//!           .context(StrContext::Expected(StrContextValue::StringLiteral("0o")))
-//!           .context(StrContext::Expected(StrContextValue::StringLiteral("0d")))
-//!           .context(StrContext::Expected(StrContextValue::StringLiteral("0x"))),
+//!           .context(StrContext::Label("radix prefix")),
 //!     )).parse_next(input)
 //! }
 //!
@@ -350,7 +347,7 @@
 //! 100
 //! ^
 //! invalid radix prefix
-//! expected `0b`, `0o`, `0d`, `0x`";
+//! expected binary, octal, decimal, or hexadecimal";
 //!     assert_eq!(input.parse::<Hex>().unwrap_err(), error);
 //! }
 //! ```
@@ -406,11 +403,7 @@
 //! #           .context(StrContext::Label("digit"))
 //! #           .context(StrContext::Expected(StrContextValue::Description("hexadecimal"))),
 //! #         fail
-//! #           .context(StrContext::Label("radix prefix"))
-//! #           .context(StrContext::Expected(StrContextValue::StringLiteral("0b")))
-//! #           .context(StrContext::Expected(StrContextValue::StringLiteral("0o")))
-//! #           .context(StrContext::Expected(StrContextValue::StringLiteral("0d")))
-//! #           .context(StrContext::Expected(StrContextValue::StringLiteral("0x"))),
+//! #           .context(StrContext::Label("radix prefix")),
 //! #     )).parse_next(input)
 //! # }
 //! #
@@ -445,7 +438,7 @@
 //! 0b5
 //! ^
 //! invalid radix prefix
-//! expected `0b`, `0o`, `0d`, `0x`";
+//! expected octal, decimal, or hexadecimal";
 //!     assert_eq!(input.parse::<Hex>().unwrap_err(), error);
 //! }
 //! ```
@@ -513,11 +506,7 @@
 //!           .context(StrContext::Label("digit"))
 //!           .context(StrContext::Expected(StrContextValue::Description("hexadecimal"))),
 //!         fail
-//!           .context(StrContext::Label("radix prefix"))
-//!           .context(StrContext::Expected(StrContextValue::StringLiteral("0b")))
-//!           .context(StrContext::Expected(StrContextValue::StringLiteral("0o")))
-//!           .context(StrContext::Expected(StrContextValue::StringLiteral("0d")))
-//!           .context(StrContext::Expected(StrContextValue::StringLiteral("0x"))),
+//!           .context(StrContext::Label("radix prefix")),
 //!     )).parse_next(input)
 //! }
 //!
@@ -663,11 +652,7 @@
 //! #           .context(StrContext::Label("digit"))
 //! #           .context(StrContext::Expected(StrContextValue::Description("hexadecimal"))),
 //! #         fail
-//! #           .context(StrContext::Label("radix prefix"))
-//! #           .context(StrContext::Expected(StrContextValue::StringLiteral("0b")))
-//! #           .context(StrContext::Expected(StrContextValue::StringLiteral("0o")))
-//! #           .context(StrContext::Expected(StrContextValue::StringLiteral("0d")))
-//! #           .context(StrContext::Expected(StrContextValue::StringLiteral("0x"))),
+//! #           .context(StrContext::Label("radix prefix")),
 //! #     )).parse_next(input)
 //! # }
 //! #
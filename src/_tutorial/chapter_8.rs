@@ -30,6 +30,17 @@
 //! #     assert_eq!(output, "");
 //! # }
 //! ```
+//!
+//! For parses too large to read in a terminal, `winnow::combinator::set_trace_sink` sends the
+//! trace elsewhere.  `JsonLinesTrace` writes one JSON object per parser call and return for
+//! scripting while `ChromeTrace` writes a file to open in `chrome://tracing` or
+//! [Perfetto](https://ui.perfetto.dev), showing each parser as a slice on a timeline.
+//! When looking for what is slow, `ProfileTrace` instead counts calls, backtracks, and input
+//! consumed for each traced parser and reports the most called first.
+//!
+//! To see parsing alongside the rest of your application's logs, enable `winnow/tracing` instead.
+//! Each traced parser call then becomes a [`tracing`](https://docs.rs/tracing) span with a target
+//! of `winnow`, see [`trace`][crate::combinator::trace] for its fields.
 
 pub use super::chapter_7 as previous;
 pub use crate::_tutorial as table_of_contents;
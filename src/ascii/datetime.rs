@@ -0,0 +1,489 @@
+use crate::combinator::opt;
+use crate::combinator::preceded;
+use crate::combinator::repeat;
+use crate::combinator::trace;
+use crate::error::{ErrMode, ErrorKind, ParserError};
+use crate::stream::{AsChar, Compare, Stream, StreamIsPartial};
+use crate::token::one_of;
+use crate::PResult;
+use crate::Parser;
+
+/// A calendar date, see [`date_ymd`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// The year, like `2024`
+    pub year: u16,
+    /// The month, from `1` to `12`
+    pub month: u8,
+    /// The day of the month, from `1` to `31`
+    pub day: u8,
+}
+
+impl Default for Date {
+    /// 1970-01-01, the Unix epoch
+    fn default() -> Self {
+        Self {
+            year: 1970,
+            month: 1,
+            day: 1,
+        }
+    }
+}
+
+/// A time of day, see [`time_hms`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    /// The hour, from `0` to `23`
+    pub hour: u8,
+    /// The minute, from `0` to `59`
+    pub minute: u8,
+    /// The second, from `0` to `60` for a leap second
+    pub second: u8,
+    /// The fraction of the second, in nanoseconds
+    pub nanosecond: u32,
+}
+
+/// A timestamp, see [`datetime`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DateTime {
+    /// The calendar date
+    pub date: Date,
+    /// The time of day
+    pub time: Time,
+    /// The offset from UTC in seconds, if the format has one
+    pub utc_offset: Option<i32>,
+}
+
+/// Recognizes a `YYYY-MM-DD` date, like `2024-02-29`
+///
+/// The day must exist in the month, accounting for leap years.
+///
+/// *Complete version*: Will return an error if there's not enough input data.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there's not enough input data.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// # use winnow::ascii::Date;
+/// pub fn date_ymd(input: &mut &str) -> PResult<Date>
+/// # {
+/// #     winnow::ascii::date_ymd.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::InputError;
+/// use winnow::ascii::date_ymd;
+/// use winnow::ascii::Date;
+///
+/// fn parser<'s>(input: &mut &'s str) -> PResult<Date, InputError<&'s str>> {
+///     date_ymd(input)
+/// }
+///
+/// assert_eq!(parser.parse_peek("2024-02-29T12:00"), Ok(("T12:00", Date { year: 2024, month: 2, day: 29 })));
+/// assert!(parser.parse_peek("2023-02-29").is_err());
+/// assert!(parser.parse_peek("2024-2-29").is_err());
+/// ```
+pub fn date_ymd<Input, Error>(input: &mut Input) -> PResult<Date, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    trace("date_ymd", |input: &mut Input| {
+        let start = input.checkpoint();
+        let year = year(input)?;
+        '-'.parse_next(input)?;
+        let month = month(input)?;
+        '-'.parse_next(input)?;
+        let day = day(input)?;
+        if days_in_month(Some(year), month) < day {
+            input.reset(&start);
+            return Err(ErrMode::from_error_kind(input, ErrorKind::Verify));
+        }
+        Ok(Date { year, month, day })
+    })
+    .parse_next(input)
+}
+
+/// Recognizes an `HH:MM:SS` time with an optional fraction of a second, like `23:59:60.5`
+///
+/// Up to nanoseconds are kept from the fraction; further digits are consumed and ignored.
+///
+/// *Complete version*: Will return an error if there's not enough input data.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there's not enough input data.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// # use winnow::ascii::Time;
+/// pub fn time_hms(input: &mut &str) -> PResult<Time>
+/// # {
+/// #     winnow::ascii::time_hms.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::InputError;
+/// use winnow::ascii::time_hms;
+/// use winnow::ascii::Time;
+///
+/// fn parser<'s>(input: &mut &'s str) -> PResult<Time, InputError<&'s str>> {
+///     time_hms(input)
+/// }
+///
+/// assert_eq!(
+///     parser.parse_peek("12:34:56.789Z"),
+///     Ok(("Z", Time { hour: 12, minute: 34, second: 56, nanosecond: 789_000_000 }))
+/// );
+/// assert_eq!(
+///     parser.parse_peek("00:00:00"),
+///     Ok(("", Time { hour: 0, minute: 0, second: 0, nanosecond: 0 }))
+/// );
+/// assert!(parser.parse_peek("24:00:00").is_err());
+/// ```
+pub fn time_hms<Input, Error>(input: &mut Input) -> PResult<Time, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    trace("time_hms", |input: &mut Input| {
+        let hour = hour(input)?;
+        ':'.parse_next(input)?;
+        let minute = minute(input)?;
+        ':'.parse_next(input)?;
+        let second = second(input)?;
+        let nanosecond = opt(preceded('.', fraction))
+            .map(Option::unwrap_or_default)
+            .parse_next(input)?;
+        Ok(Time {
+            hour,
+            minute,
+            second,
+            nanosecond,
+        })
+    })
+    .parse_next(input)
+}
+
+/// Recognizes a timestamp laid out by `format`, like `"%Y-%m-%dT%H:%M:%S%.f%z"`
+///
+/// Other characters in `format` must match exactly.  The specifiers are:
+///
+/// | Specifier | Matches                                                  | Example   |
+/// |-----------|----------------------------------------------------------|-----------|
+/// | `%Y`      | Four-digit year                                          | `2024`    |
+/// | `%m`      | Two-digit month                                          | `07`      |
+/// | `%b`      | Abbreviated English month name, in any case              | `Jul`     |
+/// | `%d`      | Two-digit day of the month                               | `08`      |
+/// | `%e`      | Day of the month, as two digits or a space and one digit | ` 8`      |
+/// | `%H`      | Two-digit hour                                           | `09`      |
+/// | `%M`      | Two-digit minute                                         | `05`      |
+/// | `%S`      | Two-digit second                                         | `60`      |
+/// | `%.f`     | Optional `.` and fraction of a second                    | `.123`    |
+/// | `%z`      | UTC offset as `Z`, `+HH:MM`, or `+HHMM`                  | `-05:00`  |
+/// | `%%`      | A literal `%`                                            | `%`       |
+///
+/// Components missing from `format` default to `1970-01-01 00:00:00`.  The day must exist in
+/// the month, allowing February 29th when there is no year.
+///
+/// *Complete version*: Will return an error if there's not enough input data.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there's not enough input data.
+///
+/// When `format` has an unknown specifier, this always fails with an [`ErrMode::Cut`] and
+/// [`ErrorKind::Assert`], without consuming any input.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// # use winnow::error::ContextError;
+/// # use winnow::ascii::DateTime;
+/// pub fn datetime<'i>(format: &'static str) -> impl Parser<&'i str, DateTime, ContextError>
+/// # {
+/// #     winnow::ascii::datetime(format)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::datetime;
+/// use winnow::ascii::{Date, DateTime, Time};
+///
+/// // An RFC 3339 timestamp
+/// let mut rfc3339 = datetime::<_, ContextError>("%Y-%m-%dT%H:%M:%S%.f%z");
+/// assert_eq!(
+///     rfc3339.parse("2024-07-08T09:05:00.25-05:00"),
+///     Ok(DateTime {
+///         date: Date { year: 2024, month: 7, day: 8 },
+///         time: Time { hour: 9, minute: 5, second: 0, nanosecond: 250_000_000 },
+///         utc_offset: Some(-5 * 60 * 60),
+///     })
+/// );
+///
+/// // A syslog timestamp, without a year
+/// let mut syslog = datetime::<_, ContextError>("%b %e %H:%M:%S");
+/// let (rest, stamp) = syslog.parse_peek("Jul  8 09:05:00 host sshd[42]: ...").unwrap();
+/// assert_eq!(rest, " host sshd[42]: ...");
+/// assert_eq!((stamp.date.month, stamp.date.day), (7, 8));
+/// ```
+pub fn datetime<Input, Error>(format: &'static str) -> impl Parser<Input, DateTime, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    let valid = is_valid_format(format);
+
+    trace("datetime", move |input: &mut Input| {
+        if !valid {
+            return Err(ErrMode::Cut(Error::from_error_kind(
+                input,
+                ErrorKind::Assert,
+            )));
+        }
+        let start = input.checkpoint();
+        let mut stamp = DateTime::default();
+        let mut has_year = false;
+        let mut format = format.chars();
+        while let Some(mut c) = format.next() {
+            if c != '%' {
+                c.parse_next(input)?;
+                continue;
+            }
+            match format.next() {
+                Some('Y') => {
+                    stamp.date.year = year(input)?;
+                    has_year = true;
+                }
+                Some('m') => stamp.date.month = month(input)?,
+                Some('b') => stamp.date.month = month_abbreviation(input)?,
+                Some('d') => stamp.date.day = day(input)?,
+                Some('e') => {
+                    stamp.date.day = if opt(' ').parse_next(input)?.is_some() {
+                        digits(1)
+                            .verify(|d| (1..=9).contains(d))
+                            .parse_next(input)? as u8
+                    } else {
+                        day(input)?
+                    }
+                }
+                Some('H') => stamp.time.hour = hour(input)?,
+                Some('M') => stamp.time.minute = minute(input)?,
+                Some('S') => stamp.time.second = second(input)?,
+                Some('.') => {
+                    format.next();
+                    if let Some(f) = opt(preceded('.', fraction)).parse_next(input)? {
+                        stamp.time.nanosecond = f;
+                    }
+                }
+                Some('z') => stamp.utc_offset = Some(utc_offset(input)?),
+                Some('%') => {
+                    '%'.parse_next(input)?;
+                }
+                _ => unreachable!("checked by `is_valid_format`"),
+            }
+        }
+        let year = has_year.then_some(stamp.date.year);
+        if days_in_month(year, stamp.date.month) < stamp.date.day {
+            input.reset(&start);
+            return Err(ErrMode::from_error_kind(input, ErrorKind::Verify));
+        }
+        Ok(stamp)
+    })
+}
+
+/// Whether every `%` in `format` starts a specifier [`datetime`] knows
+fn is_valid_format(format: &str) -> bool {
+    let mut specifiers = format.chars();
+    while let Some(c) = specifiers.next() {
+        if c == '%' {
+            match specifiers.next() {
+                Some('Y' | 'm' | 'b' | 'd' | 'e' | 'H' | 'M' | 'S' | 'z' | '%') => {}
+                Some('.') if specifiers.next() == Some('f') => {}
+                _ => return false,
+            }
+        }
+    }
+    true
+}
+
+fn year<Input, Error>(input: &mut Input) -> PResult<u16, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    digits(4).map(|d| d as u16).parse_next(input)
+}
+
+fn month<Input, Error>(input: &mut Input) -> PResult<u8, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    two_digits(1..=12).parse_next(input)
+}
+
+fn day<Input, Error>(input: &mut Input) -> PResult<u8, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    two_digits(1..=31).parse_next(input)
+}
+
+fn hour<Input, Error>(input: &mut Input) -> PResult<u8, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    two_digits(0..=23).parse_next(input)
+}
+
+fn minute<Input, Error>(input: &mut Input) -> PResult<u8, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    two_digits(0..=59).parse_next(input)
+}
+
+fn second<Input, Error>(input: &mut Input) -> PResult<u8, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    two_digits(0..=60).parse_next(input)
+}
+
+/// The digits after the `.`, in nanoseconds
+fn fraction<Input, Error>(input: &mut Input) -> PResult<u32, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    repeat(1.., one_of(AsChar::is_dec_digit))
+        .fold(
+            || (0, 100_000_000),
+            |(nanos, scale), d: <Input as Stream>::Token| {
+                (nanos + digit_value(d) * scale, scale / 10)
+            },
+        )
+        .map(|(nanos, _)| nanos)
+        .parse_next(input)
+}
+
+/// `Z`, `+HH:MM`, or `+HHMM`, in seconds
+fn utc_offset<Input, Error>(input: &mut Input) -> PResult<i32, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    if opt('Z').parse_next(input)?.is_some() {
+        return Ok(0);
+    }
+    let sign = one_of(['+', '-']).parse_next(input)?;
+    let hours = hour(input)?;
+    opt(':').parse_next(input)?;
+    let minutes = minute(input)?;
+    let offset = (i32::from(hours) * 60 + i32::from(minutes)) * 60;
+    Ok(if sign.as_char() == '-' {
+        -offset
+    } else {
+        offset
+    })
+}
+
+/// Jan, Feb, ...
+fn month_abbreviation<Input, Error>(input: &mut Input) -> PResult<u8, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    const MONTHS: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+
+    let start = input.checkpoint();
+    let mut name = ['\0'; 3];
+    for c in &mut name {
+        *c = one_of(AsChar::is_alpha)
+            .map(|t: <Input as Stream>::Token| t.as_char().to_ascii_lowercase())
+            .parse_next(input)?;
+    }
+    match MONTHS.iter().position(|m| m.chars().eq(name)) {
+        Some(i) => Ok(i as u8 + 1),
+        None => {
+            input.reset(&start);
+            Err(ErrMode::from_error_kind(input, ErrorKind::Verify))
+        }
+    }
+}
+
+fn two_digits<Input, Error>(
+    range: crate::lib::std::ops::RangeInclusive<u8>,
+) -> impl Parser<Input, u8, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    digits(2)
+        .map(|d| d as u8)
+        .verify(move |d| range.contains(d))
+}
+
+fn digits<Input, Error>(count: usize) -> impl Parser<Input, u32, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    repeat(count, one_of(AsChar::is_dec_digit)).fold(
+        || 0,
+        |acc, d: <Input as Stream>::Token| acc * 10 + digit_value(d),
+    )
+}
+
+fn digit_value<T: AsChar>(d: T) -> u32 {
+    u32::from(d.as_char()) - u32::from('0')
+}
+
+/// The last day of `month`, assuming a leap year when the year is unknown
+fn days_in_month(year: Option<u16>, month: u8) -> u8 {
+    match month {
+        2 => match year {
+            Some(year) if !(year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)) => 28,
+            _ => 29,
+        },
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
@@ -2,9 +2,17 @@
 //!
 //! Functions recognizing specific characters
 
+mod datetime;
 #[cfg(test)]
 mod tests;
 
+pub use datetime::date_ymd;
+pub use datetime::datetime;
+pub use datetime::time_hms;
+pub use datetime::Date;
+pub use datetime::DateTime;
+pub use datetime::Time;
+
 use crate::lib::std::ops::{Add, Shl};
 
 use crate::combinator::alt;
@@ -20,6 +28,7 @@ use crate::stream::FindSlice;
 use crate::stream::{AsBStr, AsChar, ParseSlice, Stream, StreamIsPartial};
 use crate::stream::{Compare, CompareResult};
 use crate::token::any;
+use crate::token::literal;
 use crate::token::one_of;
 use crate::token::take_until;
 use crate::token::take_while;
@@ -28,6 +37,8 @@ use crate::Parser;
 
 /// Mark a value as case-insensitive for ASCII characters
 ///
+/// For other case folding or token types, see [`Folded`][crate::stream::Folded].
+///
 /// # Example
 /// ```rust
 /// # use winnow::prelude::*;
@@ -1491,15 +1502,144 @@ where
     Error: ParserError<Input>,
 {
     trace("float", move |input: &mut Input| {
-        let s = take_float_or_exceptions(input)?;
+        let s = take_float_or_exceptions(input, FloatPolicy::new())?;
         s.parse_slice()
             .ok_or_else(|| ErrMode::from_error_kind(input, ErrorKind::Verify))
     })
     .parse_next(input)
 }
 
+/// Which forms of floating point numbers [`float_with`] accepts
+///
+/// The default matches [`float`].
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::ascii::FloatPolicy;
+///
+/// // JSON numbers: no leading `+`, `.5`, `5.`, `inf`, or `nan`
+/// let json = FloatPolicy::new()
+///     .leading_plus(false)
+///     .leading_dot(false)
+///     .trailing_dot(false)
+///     .special_values(false);
+/// # let _ = json;
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FloatPolicy {
+    leading_plus: bool,
+    leading_dot: bool,
+    trailing_dot: bool,
+    exponent_markers: &'static [char],
+    special_values: bool,
+}
+
+impl FloatPolicy {
+    /// Accept the same forms as [`float`]
+    pub const fn new() -> Self {
+        Self {
+            leading_plus: true,
+            leading_dot: true,
+            trailing_dot: true,
+            exponent_markers: &['e', 'E'],
+            special_values: true,
+        }
+    }
+
+    /// Allow a leading `+`, like `+1.5`
+    ///
+    /// A leading `-` is always allowed.
+    pub const fn leading_plus(mut self, yes: bool) -> Self {
+        self.leading_plus = yes;
+        self
+    }
+
+    /// Allow omitting the integer part, like `.5`
+    pub const fn leading_dot(mut self, yes: bool) -> Self {
+        self.leading_dot = yes;
+        self
+    }
+
+    /// Allow omitting the fractional part after a `.`, like `5.`
+    ///
+    /// When disallowed, the `.` is left unparsed.
+    pub const fn trailing_dot(mut self, yes: bool) -> Self {
+        self.trailing_dot = yes;
+        self
+    }
+
+    /// The characters that can start an exponent, like the `e` in `1e5`
+    ///
+    /// Pass an empty slice to disallow exponents.
+    ///
+    /// Only `e` and `E` are supported, as the number is converted with [`FromStr`][core::str::FromStr]
+    /// which only understands decimal exponents; a number using another marker fails with
+    /// [`ErrorKind::Verify`].  For binary `p` exponents, like `0x1.8p3`, see [`hex_float`].
+    pub const fn exponent_markers(mut self, markers: &'static [char]) -> Self {
+        self.exponent_markers = markers;
+        self
+    }
+
+    /// Allow `nan`, `inf`, and `infinity`, ignoring case
+    pub const fn special_values(mut self, yes: bool) -> Self {
+        self.special_values = yes;
+        self
+    }
+}
+
+impl Default for FloatPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Recognizes floating point number in text format, accepting the forms allowed by `policy`
+///
+/// See [`float`] for details.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::float_with;
+/// use winnow::ascii::FloatPolicy;
+///
+/// let policy = FloatPolicy::new()
+///     .leading_plus(false)
+///     .trailing_dot(false)
+///     .exponent_markers(&['e']);
+/// let mut parser = float_with::<_, f64, ContextError>(policy);
+///
+/// assert_eq!(parser.parse_peek("-1.5e3"), Ok(("", -1500.0)));
+/// assert_eq!(parser.parse_peek(".5"), Ok(("", 0.5)));
+/// assert_eq!(parser.parse_peek("5."), Ok((".", 5.0)));
+/// assert_eq!(parser.parse_peek("1E3"), Ok(("E3", 1.0)));
+/// assert!(parser.parse_peek("+1").is_err());
+/// ```
+#[inline(always)]
+#[allow(clippy::trait_duplication_in_bounds)] // HACK: clippy 1.64.0 bug
+pub fn float_with<Input, Output, Error>(policy: FloatPolicy) -> impl Parser<Input, Output, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<Caseless<&'static str>> + Compare<char> + AsBStr,
+    <Input as Stream>::Slice: ParseSlice<Output>,
+    <Input as Stream>::Token: AsChar + Clone,
+    <Input as Stream>::IterOffsets: Clone,
+    Error: ParserError<Input>,
+{
+    trace("float_with", move |input: &mut Input| {
+        let s = take_float_or_exceptions(input, policy)?;
+        s.parse_slice()
+            .ok_or_else(|| ErrMode::from_error_kind(input, ErrorKind::Verify))
+    })
+}
+
 #[allow(clippy::trait_duplication_in_bounds)] // HACK: clippy 1.64.0 bug
-fn take_float_or_exceptions<I, E: ParserError<I>>(input: &mut I) -> PResult<<I as Stream>::Slice, E>
+fn take_float_or_exceptions<I, E: ParserError<I>>(
+    input: &mut I,
+    policy: FloatPolicy,
+) -> PResult<<I as Stream>::Slice, E>
 where
     I: StreamIsPartial,
     I: Stream,
@@ -1509,16 +1649,19 @@ where
     <I as Stream>::IterOffsets: Clone,
     I: AsBStr,
 {
+    if !policy.special_values {
+        return take_float(input, policy);
+    }
     alt((
-        take_float,
+        |input: &mut I| take_float(input, policy),
         crate::token::literal(Caseless("nan")),
         (
-            opt(one_of(['+', '-'])),
+            |input: &mut I| take_sign(input, policy),
             crate::token::literal(Caseless("infinity")),
         )
             .take(),
         (
-            opt(one_of(['+', '-'])),
+            |input: &mut I| take_sign(input, policy),
             crate::token::literal(Caseless("inf")),
         )
             .take(),
@@ -1527,7 +1670,10 @@ where
 }
 
 #[allow(clippy::trait_duplication_in_bounds)] // HACK: clippy 1.64.0 bug
-fn take_float<I, E: ParserError<I>>(input: &mut I) -> PResult<<I as Stream>::Slice, E>
+fn take_float<I, E: ParserError<I>>(
+    input: &mut I,
+    policy: FloatPolicy,
+) -> PResult<<I as Stream>::Slice, E>
 where
     I: StreamIsPartial,
     I: Stream,
@@ -1536,18 +1682,576 @@ where
     <I as Stream>::IterOffsets: Clone,
     I: AsBStr,
 {
-    (
-        opt(one_of(['+', '-'])),
-        alt((
-            (digit1, opt(('.', opt(digit1)))).void(),
-            ('.', digit1).void(),
-        )),
-        opt((one_of(['e', 'E']), opt(one_of(['+', '-'])), cut_err(digit1))),
-    )
+    let fraction = move |input: &mut I| -> PResult<(), E> {
+        '.'.void().parse_next(input)?;
+        let digits = opt(digit1).parse_next(input)?;
+        if digits.is_none() && !policy.trailing_dot {
+            return Err(ErrMode::from_error_kind(input, ErrorKind::Verify));
+        }
+        Ok(())
+    };
+    let mantissa = move |input: &mut I| -> PResult<(), E> {
+        if policy.leading_dot {
+            alt(((digit1, opt(fraction)).void(), ('.', digit1).void())).parse_next(input)
+        } else {
+            (digit1, opt(fraction)).void().parse_next(input)
+        }
+    };
+    let exponent = move |input: &mut I| -> PResult<(), E> {
+        if policy.exponent_markers.is_empty() {
+            return Ok(());
+        }
+        opt((
+            one_of(|t: <I as Stream>::Token| policy.exponent_markers.contains(&t.as_char())),
+            opt(one_of(['+', '-'])),
+            cut_err(digit1),
+        ))
+        .void()
+        .parse_next(input)
+    };
+    (|input: &mut I| take_sign(input, policy), mantissa, exponent)
         .take()
         .parse_next(input)
 }
 
+fn take_sign<I, E: ParserError<I>>(input: &mut I, policy: FloatPolicy) -> PResult<(), E>
+where
+    I: StreamIsPartial,
+    I: Stream,
+    <I as Stream>::Token: AsChar + Clone,
+{
+    opt(one_of(|t: <I as Stream>::Token| {
+        let c = t.as_char();
+        c == '-' || (policy.leading_plus && c == '+')
+    }))
+    .void()
+    .parse_next(input)
+}
+
+/// Recognizes a hexadecimal floating point literal, like `0x1.8p3`, and returns a [`f32`] or
+/// [`f64`]
+///
+/// This is the syntax of C's `printf("%a")` and `strtod`, the WebAssembly text format, and
+/// Rust's `hexf` crate: an optional sign, `0x`, hex digits with an optional `.`, and a required
+/// binary exponent of `p` followed by signed decimal digits.  The value is the mantissa times
+/// two to the power of the exponent, rounded to the nearest representable value with ties to
+/// even, so it is exact whenever the literal fits in `Output`.
+///
+/// Values too large for `Output` become infinity and values too small become zero, like
+/// [`float`].
+///
+/// *Complete version*: Can parse until the end of input.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there is not enough data.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream] into an `f64`:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// pub fn hex_float(input: &mut &str) -> PResult<f64>
+/// # {
+/// #     winnow::ascii::hex_float.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::{error::ErrMode, error::ErrorKind, error::InputError};
+/// use winnow::ascii::hex_float;
+///
+/// fn parser<'s>(s: &mut &'s str) -> PResult<f64, InputError<&'s str>> {
+///   hex_float(s)
+/// }
+///
+/// assert_eq!(parser.parse_peek("0x1.8p3"), Ok(("", 12.0)));
+/// assert_eq!(parser.parse_peek("-0X.Cp-1;"), Ok((";", -0.375)));
+/// assert_eq!(parser.parse_peek("0x1p-1074"), Ok(("", f64::from_bits(1))));
+/// assert_eq!(parser.parse_peek("0x1.8"), Err(ErrMode::Backtrack(InputError::new("", ErrorKind::Token))));
+/// ```
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::{error::ErrMode, error::ErrorKind, error::InputError, error::Needed};
+/// # use winnow::Partial;
+/// use winnow::ascii::hex_float;
+///
+/// fn parser<'s>(s: &mut Partial<&'s str>) -> PResult<f32, InputError<Partial<&'s str>>> {
+///   hex_float(s)
+/// }
+///
+/// assert_eq!(parser.parse_peek(Partial::new("0x1.8p3 ")), Ok((Partial::new(" "), 12.0)));
+/// assert_eq!(parser.parse_peek(Partial::new("0x1.8p3")), Err(ErrMode::Incomplete(Needed::new(1))));
+/// ```
+#[inline(always)]
+#[doc(alias = "hexf")]
+pub fn hex_float<Input, Output, Error>(input: &mut Input) -> PResult<Output, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    <Input as Stream>::Slice: AsBStr,
+    Error: ParserError<Input>,
+    Output: HexFloat,
+{
+    trace("hex_float", move |input: &mut Input| {
+        let literal = (
+            opt(one_of(['+', '-'])),
+            '0',
+            one_of(['x', 'X']),
+            alt((
+                (hex_digit1, opt(('.', hex_digit0))).void(),
+                ('.', hex_digit1).void(),
+            )),
+            one_of(['p', 'P']),
+            opt(one_of(['+', '-'])),
+            cut_err(digit1),
+        )
+            .take()
+            .parse_next(input)?;
+
+        let mut bytes = literal.as_bstr();
+        let negative = bytes[0] == b'-';
+        if matches!(bytes[0], b'+' | b'-') {
+            bytes = &bytes[1..];
+        }
+        // Skip `0x`
+        bytes = &bytes[2..];
+
+        let mut mantissa = 0u64;
+        let mut sticky = false;
+        let mut exponent = 0i64;
+        let mut fraction = false;
+        while let Some((&b, rest)) = bytes.split_first() {
+            bytes = rest;
+            if b == b'.' {
+                fraction = true;
+                continue;
+            }
+            let Some(digit) = char::from(b).to_digit(16) else {
+                break;
+            };
+            // Keep at least two bits past any mantissa's precision for rounding
+            if mantissa >> 60 == 0 {
+                mantissa = mantissa << 4 | u64::from(digit);
+                if fraction {
+                    exponent -= 4;
+                }
+            } else {
+                sticky |= digit != 0;
+                if !fraction {
+                    exponent += 4;
+                }
+            }
+        }
+
+        let exponent_negative = bytes.first() == Some(&b'-');
+        let power = bytes
+            .iter()
+            .filter_map(|b| char::from(*b).to_digit(10))
+            .fold(0i64, |power, digit| {
+                power.saturating_mul(10).saturating_add(i64::from(digit))
+            });
+        let exponent = if exponent_negative {
+            exponent.saturating_sub(power)
+        } else {
+            exponent.saturating_add(power)
+        };
+
+        Ok(Output::from_binary(
+            negative,
+            mantissa,
+            sticky,
+            exponent,
+            sealed::SealedMarker,
+        ))
+    })
+    .parse_next(input)
+}
+
+/// Metadata for parsing hex floats, see [`hex_float`]
+pub trait HexFloat: Sized {
+    #[doc(hidden)]
+    fn from_binary(
+        negative: bool,
+        mantissa: u64,
+        sticky: bool,
+        exponent: i64,
+        _: sealed::SealedMarker,
+    ) -> Self;
+}
+
+impl HexFloat for f32 {
+    #[inline]
+    fn from_binary(
+        negative: bool,
+        mantissa: u64,
+        sticky: bool,
+        exponent: i64,
+        _: sealed::SealedMarker,
+    ) -> Self {
+        let bits = round_binary_float(mantissa, sticky, exponent, 23, 8) as u32;
+        f32::from_bits(bits | u32::from(negative) << 31)
+    }
+}
+
+impl HexFloat for f64 {
+    #[inline]
+    fn from_binary(
+        negative: bool,
+        mantissa: u64,
+        sticky: bool,
+        exponent: i64,
+        _: sealed::SealedMarker,
+    ) -> Self {
+        let bits = round_binary_float(mantissa, sticky, exponent, 52, 11);
+        f64::from_bits(bits | u64::from(negative) << 63)
+    }
+}
+
+/// The IEEE 754 bits, without the sign, of `mantissa * 2^exponent` rounded to nearest, ties to
+/// even
+///
+/// `sticky` is whether any nonzero bits were dropped below `mantissa`.
+fn round_binary_float(
+    mantissa: u64,
+    sticky: bool,
+    exponent: i64,
+    mantissa_bits: u32,
+    exponent_bits: u32,
+) -> u64 {
+    if mantissa == 0 {
+        return 0;
+    }
+    let infinity = ((1u64 << exponent_bits) - 1) << mantissa_bits;
+    let bias = (1i64 << (exponent_bits - 1)) - 1;
+    let min_exponent = 1 - bias;
+
+    // Exponent of the leading bit
+    let top = 63 - i64::from(mantissa.leading_zeros());
+    let mut exponent = exponent.saturating_add(top);
+    if bias < exponent {
+        return infinity;
+    }
+    let precision = i64::from(mantissa_bits) + 1;
+    let keep = if min_exponent <= exponent {
+        precision
+    } else {
+        // Subnormal, losing a bit per step below the minimum exponent
+        precision.saturating_sub(min_exponent - exponent)
+    };
+
+    let shift = (top + 1).saturating_sub(keep);
+    let mantissa = u128::from(mantissa);
+    let mut rounded = if shift <= 0 {
+        mantissa << -shift
+    } else if 128 <= shift {
+        0
+    } else {
+        let rounded = mantissa >> shift;
+        let rest = mantissa & ((1 << shift) - 1);
+        let half = 1 << (shift - 1);
+        if half < rest || (rest == half && (sticky || rounded & 1 == 1)) {
+            rounded + 1
+        } else {
+            rounded
+        }
+    } as u64;
+
+    if exponent < min_exponent {
+        // Rounding up to the smallest normal carries into the exponent field on its own
+        return rounded;
+    }
+    if rounded >> precision != 0 {
+        rounded >>= 1;
+        exponent += 1;
+    }
+    let biased = (exponent + bias) as u64;
+    if infinity >> mantissa_bits <= biased {
+        return infinity;
+    }
+    biased << mantissa_bits | rounded & ((1 << mantissa_bits) - 1)
+}
+
+/// Which numeric literals [`number`] accepts
+///
+/// The default accepts decimal integers and floats with an optional sign, like `-12` or
+/// `+1.5e3`.
+///
+/// Separators, the decimal point, and exponent markers must be ASCII.
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::ascii::NumberPolicy;
+///
+/// // Rust-like literals, where the sign is an operator
+/// let rust = NumberPolicy::new()
+///     .sign(false)
+///     .separators(&['_'])
+///     .radix_prefixes(true);
+/// # let _ = rust;
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NumberPolicy {
+    sign: bool,
+    separators: &'static [char],
+    radix_prefixes: bool,
+    decimal_point: Option<char>,
+    exponent_markers: &'static [char],
+}
+
+impl NumberPolicy {
+    /// Accept decimal integers and floats
+    pub const fn new() -> Self {
+        Self {
+            sign: true,
+            separators: &[],
+            radix_prefixes: false,
+            decimal_point: Some('.'),
+            exponent_markers: &['e', 'E'],
+        }
+    }
+
+    /// Allow a leading `+` or `-`
+    pub const fn sign(mut self, yes: bool) -> Self {
+        self.sign = yes;
+        self
+    }
+
+    /// The characters that can separate digits, like the `_` in `1_000` or the `,` in `1,000`
+    ///
+    /// A separator must be between two digits, so `1_` leaves the `_` unparsed.
+    pub const fn separators(mut self, separators: &'static [char]) -> Self {
+        self.separators = separators;
+        self
+    }
+
+    /// Allow integers in another radix, like `0xff`, `0o17`, or `0b1010`, ignoring the case of
+    /// the prefix
+    ///
+    /// A prefix must be followed by a digit in its radix, so `0b2` fails.
+    pub const fn radix_prefixes(mut self, yes: bool) -> Self {
+        self.radix_prefixes = yes;
+        self
+    }
+
+    /// The character between the integer and fractional parts of a float, like the `,` in `1,5`
+    ///
+    /// Digits are required on both sides, so `1.` leaves the `.` unparsed, like for a method
+    /// call.  Pass `None` to only accept integers, which also disallows exponents.
+    pub const fn decimal_point(mut self, point: Option<char>) -> Self {
+        self.decimal_point = point;
+        self
+    }
+
+    /// The characters that can start an exponent, like the `e` in `1e5`
+    ///
+    /// Pass an empty slice to disallow exponents.
+    ///
+    /// Only `e` and `E` are supported, as the number is converted with [`FromStr`][core::str::FromStr]
+    /// which only understands decimal exponents; a number using another marker fails with
+    /// [`ErrorKind::Verify`].  For binary `p` exponents, like `0x1.8p3`, see [`hex_float`].
+    pub const fn exponent_markers(mut self, markers: &'static [char]) -> Self {
+        self.exponent_markers = markers;
+        self
+    }
+}
+
+impl Default for NumberPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The value of a literal parsed by [`number`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Number {
+    /// A literal without a fractional part or exponent, like `42` or `0xff`
+    Integer(i128),
+    /// A literal with a fractional part or exponent, like `1.5` or `1e3`
+    Float(f64),
+}
+
+/// Recognizes a numeric literal, in the forms allowed by `policy`, returning it as written along
+/// with its value
+///
+/// An integer that doesn't fit in an [`i128`] fails with [`ErrorKind::Verify`].
+///
+/// *Complete version*: Can parse until the end of input.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if the literal may continue.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::number;
+/// use winnow::ascii::Number;
+/// use winnow::ascii::NumberPolicy;
+///
+/// let policy = NumberPolicy::new()
+///     .separators(&['_'])
+///     .radix_prefixes(true);
+/// let mut literal = number::<_, ContextError>(policy);
+///
+/// assert_eq!(literal.parse_peek("1_000;"), Ok((";", ("1_000", Number::Integer(1000)))));
+/// assert_eq!(literal.parse_peek("-0xFF_FF"), Ok(("", ("-0xFF_FF", Number::Integer(-0xffff)))));
+/// assert_eq!(literal.parse_peek("0b10.5"), Ok((".5", ("0b10", Number::Integer(2)))));
+/// assert_eq!(literal.parse_peek("2.5e-3x"), Ok(("x", ("2.5e-3", Number::Float(0.0025)))));
+/// assert_eq!(literal.parse_peek("1..2"), Ok(("..2", ("1", Number::Integer(1)))));
+///
+/// // European style
+/// let mut european = number::<_, ContextError>(
+///     NumberPolicy::new().separators(&['.']).decimal_point(Some(',')),
+/// );
+/// assert_eq!(european.parse_peek("1.234,5"), Ok(("", ("1.234,5", Number::Float(1234.5)))));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn number<Input, Error>(
+    policy: NumberPolicy,
+) -> impl Parser<Input, (<Input as Stream>::Slice, Number), Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    <Input as Stream>::Slice: AsBStr,
+    Error: ParserError<Input>,
+{
+    trace("number", move |input: &mut Input| {
+        let start = input.checkpoint();
+        let ((sign, radix, float), literal) = (
+            |input: &mut Input| {
+                if policy.sign {
+                    opt(one_of(['+', '-'])).parse_next(input)
+                } else {
+                    Ok(None)
+                }
+            },
+            |input: &mut Input| take_number_body(input, policy),
+        )
+            .map(|(sign, (radix, float))| (sign.is_some(), radix, float))
+            .with_taken()
+            .parse_next(input)?;
+
+        let bytes = literal.as_bstr();
+        let value = if float {
+            let mut text = crate::lib::std::string::String::with_capacity(bytes.len());
+            for c in bytes.iter().map(|b| char::from(*b)) {
+                if policy.decimal_point == Some(c) {
+                    text.push('.');
+                } else if policy.exponent_markers.contains(&c) {
+                    text.push('e');
+                } else if !policy.separators.contains(&c) {
+                    text.push(c);
+                }
+            }
+            text.parse().ok().map(Number::Float)
+        } else {
+            let prefix = if radix == 10 { 0 } else { 2 };
+            let negative = sign && bytes[0] == b'-';
+            bytes[usize::from(sign) + prefix..]
+                .iter()
+                .filter_map(|b| char::from(*b).to_digit(radix))
+                .try_fold(0i128, |value, digit| {
+                    let value = value.checked_mul(i128::from(radix))?;
+                    if negative {
+                        value.checked_sub(i128::from(digit))
+                    } else {
+                        value.checked_add(i128::from(digit))
+                    }
+                })
+                .map(Number::Integer)
+        };
+        match value {
+            Some(value) => Ok((literal, value)),
+            None => {
+                input.reset(&start);
+                Err(ErrMode::from_error_kind(input, ErrorKind::Verify))
+            }
+        }
+    })
+}
+
+/// The digits of a [`number`] after the sign, returning the radix and whether it is a float
+#[cfg(feature = "alloc")]
+fn take_number_body<I, E: ParserError<I>>(
+    input: &mut I,
+    policy: NumberPolicy,
+) -> PResult<(u32, bool), E>
+where
+    I: StreamIsPartial,
+    I: Stream,
+    I: Compare<char>,
+    <I as Stream>::Token: AsChar + Clone,
+{
+    if policy.radix_prefixes {
+        let prefix = opt(crate::combinator::preceded(
+            '0',
+            one_of(['x', 'X', 'o', 'O', 'b', 'B']),
+        ))
+        .parse_next(input)?;
+        if let Some(prefix) = prefix {
+            let radix = match prefix.as_char() {
+                'x' | 'X' => 16,
+                'o' | 'O' => 8,
+                _ => 2,
+            };
+            take_separated_digits(input, policy, radix)?;
+            return Ok((radix, false));
+        }
+    }
+
+    take_separated_digits(input, policy, 10)?;
+    let mut float = false;
+    if let Some(point) = policy.decimal_point {
+        let fraction = opt((point, |input: &mut I| {
+            take_separated_digits(input, policy, 10)
+        }))
+        .parse_next(input)?;
+        float |= fraction.is_some();
+
+        if !policy.exponent_markers.is_empty() {
+            let exponent = opt((
+                one_of(|t: <I as Stream>::Token| policy.exponent_markers.contains(&t.as_char())),
+                opt(one_of(['+', '-'])),
+                |input: &mut I| take_separated_digits(input, policy, 10),
+            ))
+            .parse_next(input)?;
+            float |= exponent.is_some();
+        }
+    }
+    Ok((10, float))
+}
+
+/// Digits in `radix`, with [separators][NumberPolicy::separators] between them
+#[cfg(feature = "alloc")]
+fn take_separated_digits<I, E: ParserError<I>>(
+    input: &mut I,
+    policy: NumberPolicy,
+    radix: u32,
+) -> PResult<(), E>
+where
+    I: StreamIsPartial,
+    I: Stream,
+    <I as Stream>::Token: AsChar + Clone,
+{
+    let digit = move |t: <I as Stream>::Token| t.as_char().is_digit(radix);
+    take_while(1.., digit).parse_next(input)?;
+    if !policy.separators.is_empty() {
+        crate::combinator::repeat::<_, _, (), _, _>(
+            0..,
+            (
+                one_of(|t: <I as Stream>::Token| policy.separators.contains(&t.as_char())),
+                take_while(1.., digit),
+            ),
+        )
+        .parse_next(input)?;
+    }
+    Ok(())
+}
+
 /// Recognize the input slice with escaped characters.
 ///
 /// Arguments:
@@ -1832,7 +2536,7 @@ where
         let current_len = input.eof_offset();
         match opt(normal.by_ref()).parse_next(input)? {
             Some(o) => {
-                res.accumulate(o);
+                crate::stream::accumulate(&mut res, o, input)?;
                 if input.eof_offset() == current_len {
                     return Ok(res);
                 }
@@ -1840,7 +2544,7 @@ where
             None => {
                 if opt(control_char).parse_next(input)?.is_some() {
                     let o = transform.parse_next(input)?;
-                    res.accumulate(o);
+                    crate::stream::accumulate(&mut res, o, input)?;
                 } else {
                     return Ok(res);
                 }
@@ -1872,7 +2576,7 @@ where
 
         match opt(normal.by_ref()).parse_next(input)? {
             Some(o) => {
-                res.accumulate(o);
+                crate::stream::accumulate(&mut res, o, input)?;
                 if input.eof_offset() == current_len {
                     return Ok(res);
                 }
@@ -1880,7 +2584,7 @@ where
             None => {
                 if opt(control_char).parse_next(input)?.is_some() {
                     let o = transform.parse_next(input)?;
-                    res.accumulate(o);
+                    crate::stream::accumulate(&mut res, o, input)?;
                 } else {
                     return Ok(res);
                 }
@@ -1890,6 +2594,857 @@ where
     Ok(res)
 }
 
+/// Parse escaped characters, unescaping them only if there are any
+///
+/// Like [`escaped_transform`] but the result is borrowed from the input when there were no
+/// escapes, only allocating once an escape is found.
+///
+/// Arguments:
+/// - `normal`: unescapeable characters
+///   - Must not include `control`
+/// - `control_char`: e.g. `\` for strings in most languages
+/// - `escape`: parse the escaped character, returning what it stands for, like [`escape_table`]
+///
+/// Parsing ends when:
+/// - `alt(normal, control_char)` [`Backtrack`s][crate::error::ErrMode::Backtrack]
+/// - `normal` doesn't advance the input stream
+/// - *(complete)* input stream is exhausted
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use std::borrow::Cow;
+/// use winnow::ascii::escape_table;
+/// use winnow::ascii::escaped_cow;
+/// use winnow::ascii::hex_uint;
+/// use winnow::combinator::alt;
+/// use winnow::combinator::delimited;
+/// use winnow::token::take_till;
+///
+/// const ESCAPES: &[(char, char)] = &[('n', '\n'), ('t', '\t'), ('"', '"'), ('\\', '\\')];
+///
+/// fn string<'i>(input: &mut &'i str) -> PResult<Cow<'i, str>> {
+///     let unicode = delimited("u{", hex_uint, '}').verify_map(char::from_u32);
+///     delimited(
+///         '"',
+///         escaped_cow(take_till(1.., ['"', '\\']), '\\', alt((escape_table(ESCAPES), unicode))),
+///         '"',
+///     )
+///     .parse_next(input)
+/// }
+///
+/// assert_eq!(string.parse_peek(r#""plain" x"#), Ok((" x", Cow::Borrowed("plain"))));
+/// assert_eq!(
+///     string.parse_peek(r#""a\tb\u{e9}\"" x"#),
+///     Ok((" x", Cow::Owned("a\tb\u{e9}\"".into())))
+/// );
+/// assert!(string.parse_peek(r#""a\qb""#).is_err());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn escaped_cow<'i, Input, Error, Normal, Escape>(
+    mut normal: Normal,
+    control_char: char,
+    mut escape: Escape,
+) -> impl Parser<Input, crate::lib::std::borrow::Cow<'i, str>, Error>
+where
+    Input: StreamIsPartial + Stream<Slice = &'i str> + Compare<char>,
+    Normal: Parser<Input, &'i str, Error>,
+    Escape: Parser<Input, char, Error>,
+    Error: ParserError<Input>,
+{
+    trace("escaped_cow", move |input: &mut Input| {
+        let start = input.checkpoint();
+        let mut owned: Option<crate::lib::std::string::String> = None;
+
+        while input.eof_offset() > 0 {
+            let current_len = input.eof_offset();
+            match opt(normal.by_ref()).parse_next(input)? {
+                Some(o) => {
+                    if let Some(owned) = owned.as_mut() {
+                        owned.push_str(o);
+                    }
+                    if input.eof_offset() == current_len {
+                        break;
+                    }
+                }
+                None => {
+                    let prefix_len = input.offset_from(&start);
+                    if opt(control_char).parse_next(input)?.is_none() {
+                        break;
+                    }
+                    let c = escape.parse_next(input)?;
+                    let owned = owned.get_or_insert_with(|| {
+                        let after_escape = input.checkpoint();
+                        input.reset(&start);
+                        let prefix = input.next_slice(prefix_len);
+                        input.reset(&after_escape);
+                        crate::lib::std::string::String::from(prefix)
+                    });
+                    owned.push(c);
+                }
+            }
+        }
+        if input.eof_offset() == 0
+            && <Input as StreamIsPartial>::is_partial_supported()
+            && input.is_partial()
+        {
+            return Err(ErrMode::Incomplete(Needed::Unknown));
+        }
+
+        match owned {
+            Some(owned) => Ok(crate::lib::std::borrow::Cow::Owned(owned)),
+            None => {
+                let len = input.offset_from(&start);
+                input.reset(&start);
+                Ok(crate::lib::std::borrow::Cow::Borrowed(
+                    input.next_slice(len),
+                ))
+            }
+        }
+    })
+}
+
+/// Parse the escaped character after a control character, looking it up in `table`
+///
+/// `table` pairs each escaped character with what it stands for, like `('n', '\n')`.  Other
+/// characters backtrack with [`ErrorKind::Verify`], so more escapes, like for code points, can
+/// follow in an [`alt`][crate::combinator::alt].
+///
+/// See [`escaped_cow`] for an example.
+pub fn escape_table<Input, Error, Table>(table: Table) -> impl Parser<Input, char, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar,
+    Table: AsRef<[(char, char)]>,
+    Error: ParserError<Input>,
+{
+    trace(
+        "escape_table",
+        any.verify_map(move |t: <Input as Stream>::Token| {
+            let c = t.as_char();
+            table
+                .as_ref()
+                .iter()
+                .find(|(escaped, _)| *escaped == c)
+                .map(|(_, value)| *value)
+        }),
+    )
+}
+
+/// How [`utf8`] handles bytes that are not valid UTF-8
+#[cfg(feature = "alloc")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Utf8Policy {
+    /// Backtrack, leaving the input untouched
+    Strict,
+    /// Replace invalid sequences with [`U+FFFD`][char::REPLACEMENT_CHARACTER]
+    Lossy,
+}
+
+/// Decode the bytes recognized by `parser` as UTF-8
+///
+/// The parsers in this module work over `&[u8]` as well as `&str`.  Parsing bytes first, like
+/// `&[u8]` or [`&BStr`][crate::BStr], and then decoding each field with this lets a parser
+/// survive corrupted data, like a log with a line of binary junk, where the whole input could
+/// never be turned into a `&str`.  Interior NULs are valid UTF-8 and are passed through.
+///
+/// Valid UTF-8 is borrowed from the input.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use std::borrow::Cow;
+/// use winnow::ascii::till_line_ending;
+/// use winnow::ascii::line_ending;
+/// use winnow::ascii::utf8;
+/// use winnow::ascii::Utf8Policy;
+/// use winnow::combinator::repeat;
+/// use winnow::combinator::terminated;
+///
+/// fn line<'i>(input: &mut &'i [u8]) -> PResult<Cow<'i, str>> {
+///     terminated(utf8(Utf8Policy::Lossy, till_line_ending), line_ending).parse_next(input)
+/// }
+///
+/// let log = b"start\nbad \xff\x00 line\nend\n";
+/// let lines: Vec<_> = repeat(0.., line).parse(&log[..]).unwrap();
+/// assert_eq!(lines, ["start", "bad \u{fffd}\0 line", "end"]);
+///
+/// let mut strict = utf8(Utf8Policy::Strict, till_line_ending::<_, winnow::error::ContextError>);
+/// assert!(strict.parse_peek(&b"bad \xff"[..]).is_err());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn utf8<'i, Input, Error, ParseNext>(
+    policy: Utf8Policy,
+    mut parser: ParseNext,
+) -> impl Parser<Input, crate::lib::std::borrow::Cow<'i, str>, Error>
+where
+    Input: Stream,
+    ParseNext: Parser<Input, &'i [u8], Error>,
+    Error: ParserError<Input>,
+{
+    trace("utf8", move |input: &mut Input| {
+        let start = input.checkpoint();
+        let bytes = parser.parse_next(input)?;
+        match policy {
+            Utf8Policy::Strict => match crate::lib::std::str::from_utf8(bytes) {
+                Ok(s) => Ok(crate::lib::std::borrow::Cow::Borrowed(s)),
+                Err(_) => {
+                    input.reset(&start);
+                    Err(ErrMode::from_error_kind(input, ErrorKind::Verify))
+                }
+            },
+            Utf8Policy::Lossy => Ok(crate::lib::std::string::String::from_utf8_lossy(bytes)),
+        }
+    })
+}
+
+/// Decode the `&name;` references in the text recognized by `parser`, looking names up in `table`
+///
+/// Numeric character references, like `&#233;` and `&#xE9;`, are always decoded, so `table`
+/// only needs the names for the format, like the five predefined by XML,
+/// [HTML's](https://html.spec.whatwg.org/multipage/named-characters.html), or a template
+/// language's.  Names are ASCII letters and digits.  References that aren't recognized, like
+/// `&nbsp;` when it isn't in `table` or a `&` on its own, are left as they are.
+///
+/// Text without references is borrowed from the input.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use std::borrow::Cow;
+/// use winnow::ascii::decode_entities;
+/// use winnow::combinator::delimited;
+/// use winnow::token::take_until;
+///
+/// const ENTITIES: &[(&str, &str)] = &[("amp", "&"), ("nbsp", "\u{a0}"), ("hellip", "...")];
+///
+/// fn text<'i>(input: &mut &'i str) -> PResult<Cow<'i, str>> {
+///     decode_entities(take_until(0.., '<'), ENTITIES).parse_next(input)
+/// }
+///
+/// assert_eq!(
+///     text.parse_peek("Fish&nbsp;&amp;&#32;chips&hellip;<br>"),
+///     Ok(("<br>", Cow::Owned("Fish\u{a0}& chips...".into())))
+/// );
+/// assert_eq!(text.parse_peek("R&D &copy;<br>"), Ok(("<br>", Cow::Borrowed("R&D &copy;"))));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_entities<'i, Input, Error, ParseNext, Table, Name, Value>(
+    mut parser: ParseNext,
+    table: Table,
+) -> impl Parser<Input, crate::lib::std::borrow::Cow<'i, str>, Error>
+where
+    Input: Stream,
+    ParseNext: Parser<Input, &'i str, Error>,
+    Table: AsRef<[(Name, Value)]>,
+    Name: AsRef<str>,
+    Value: AsRef<str>,
+    Error: ParserError<Input>,
+{
+    trace("decode_entities", move |input: &mut Input| {
+        let text = parser.parse_next(input)?;
+        Ok(decode_entity_refs(text, table.as_ref()))
+    })
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn decode_entity_refs<'i, Name, Value>(
+    text: &'i str,
+    table: &[(Name, Value)],
+) -> crate::lib::std::borrow::Cow<'i, str>
+where
+    Name: AsRef<str>,
+    Value: AsRef<str>,
+{
+    if !text.contains('&') {
+        return crate::lib::std::borrow::Cow::Borrowed(text);
+    }
+    let mut decoded = crate::lib::std::string::String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '#'))
+            .filter(|len| rest[*len..].starts_with(';'));
+        let name = len.map(|len| &rest[..len]).unwrap_or_default();
+        let replaced = if let Some(digits) = name.strip_prefix('#') {
+            let (digits, radix) = match digits.strip_prefix(['x', 'X']) {
+                Some(digits) => (digits, 16),
+                None => (digits, 10),
+            };
+            char_ref(digits.as_bytes(), radix)
+                .map(|c| decoded.push(c))
+                .is_some()
+        } else {
+            table
+                .iter()
+                .find(|(n, _)| !name.is_empty() && n.as_ref() == name)
+                .map(|(_, value)| decoded.push_str(value.as_ref()))
+                .is_some()
+        };
+        if replaced {
+            rest = &rest[name.len() + 1..];
+        } else {
+            decoded.push('&');
+        }
+    }
+    decoded.push_str(rest);
+    crate::lib::std::borrow::Cow::Owned(decoded)
+}
+
+/// The `char` for the digits of a character reference, like `&#233;`, which can't be NUL
+pub(crate) fn char_ref(digits: &[u8], radix: u32) -> Option<char> {
+    if digits.is_empty() {
+        return None;
+    }
+    let mut value = 0u32;
+    for digit in digits {
+        value = value
+            .checked_mul(radix)?
+            .checked_add((*digit as char).to_digit(radix)?)?;
+    }
+    char::from_u32(value).filter(|c| *c != '\0')
+}
+
+/// A set of reserved words for [`ident_except`]
+///
+/// Lookups first reject words by their length and leading byte before searching the sorted
+/// table, so the common case of an ordinary identifier is cheap.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Keywords {
+    words: crate::lib::std::vec::Vec<crate::lib::std::vec::Vec<u8>>,
+    /// Bit `n` is set when a word of length `n` exists, with longer words sharing bit 63
+    lengths: u64,
+    /// Bit `b` is set when a word starts with byte `b`
+    first: [u64; 4],
+}
+
+#[cfg(feature = "alloc")]
+impl Keywords {
+    /// Build the table from a list of words
+    pub fn new<W: AsRef<[u8]>>(words: impl IntoIterator<Item = W>) -> Self {
+        let mut words = words
+            .into_iter()
+            .map(|w| w.as_ref().to_vec())
+            .collect::<crate::lib::std::vec::Vec<_>>();
+        words.sort_unstable();
+        words.dedup();
+        let mut lengths = 0;
+        let mut first = [0; 4];
+        for word in &words {
+            lengths |= Self::length_bit(word.len());
+            if let Some(&b) = word.first() {
+                first[usize::from(b / 64)] |= 1 << (b % 64);
+            }
+        }
+        Self {
+            words,
+            lengths,
+            first,
+        }
+    }
+
+    /// Whether `word` is reserved
+    pub fn contains(&self, word: &[u8]) -> bool {
+        if self.lengths & Self::length_bit(word.len()) == 0 {
+            return false;
+        }
+        if let Some(&b) = word.first() {
+            if self.first[usize::from(b / 64)] & (1 << (b % 64)) == 0 {
+                return false;
+            }
+        }
+        self.words
+            .binary_search_by(|w| w.as_slice().cmp(word))
+            .is_ok()
+    }
+
+    /// The number of reserved words
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Whether there are no reserved words
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    fn length_bit(len: usize) -> u64 {
+        1 << len.min(63)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<W: AsRef<[u8]>> FromIterator<W> for Keywords {
+    fn from_iter<T: IntoIterator<Item = W>>(iter: T) -> Self {
+        Self::new(iter)
+    }
+}
+
+/// Parse an identifier with `ident`, rejecting any that are in `keywords`
+///
+/// A reserved word backtracks with [`ErrorKind::Verify`] and a
+/// [`ReservedWord`][crate::error::ReservedWord] external error, so an
+/// [`alt`][crate::combinator::alt] can move on to a branch for the keyword itself.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::ident_except;
+/// use winnow::ascii::Keywords;
+/// use winnow::token::take_while;
+///
+/// let keywords = Keywords::new(["fn", "let", "match"]);
+/// let mut ident = ident_except::<_, _, ContextError, _, _>(
+///     take_while(1.., |c: char| c.is_ascii_alphanumeric() || c == '_'),
+///     &keywords,
+/// );
+///
+/// assert_eq!(ident.parse_peek("letter = 1"), Ok((" = 1", "letter")));
+/// assert!(ident.parse_peek("let x = 1").is_err());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn ident_except<Input, Output, Error, ParseNext, Table>(
+    mut ident: ParseNext,
+    keywords: Table,
+) -> impl Parser<Input, Output, Error>
+where
+    Input: Stream,
+    Output: AsBStr,
+    ParseNext: Parser<Input, Output, Error>,
+    Table: crate::lib::std::borrow::Borrow<Keywords>,
+    Error: ParserError<Input> + crate::error::FromExternalError<Input, crate::error::ReservedWord>,
+{
+    trace("ident_except", move |input: &mut Input| {
+        let start = input.checkpoint();
+        let word = ident.parse_next(input)?;
+        let bytes = word.as_bstr();
+        if keywords.borrow().contains(bytes) {
+            input.reset(&start);
+            let word = crate::lib::std::string::String::from_utf8_lossy(bytes).into_owned();
+            return Err(crate::error::FromExternalError::from_external_error(
+                input,
+                ErrorKind::Verify,
+                crate::error::ReservedWord(word),
+            ));
+        }
+        Ok(word)
+    })
+}
+
+/// Parse an identifier with `ident`, succeeding only if it is `keyword` and `active` holds
+///
+/// Contextual keywords, like `await` within an `async` block, are ordinary identifiers
+/// elsewhere.  `active` is checked against the input before parsing, so it typically reads a
+/// flag from [`Stateful::state`][crate::stream::Stateful] that a
+/// [`scoped_state`][crate::combinator::scoped_state] sets for the part of the grammar where the
+/// keyword applies.  Use [`contextual_ident`] so identifiers reject the keyword in the same places.
+///
+/// Otherwise, this backtracks with [`ErrorKind::Tag`].
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// use winnow::ascii::alpha1;
+/// use winnow::ascii::contextual_ident;
+/// use winnow::ascii::contextual_keyword;
+/// use winnow::ascii::space1;
+/// use winnow::combinator::alt;
+/// use winnow::combinator::preceded;
+/// use winnow::combinator::scoped_state;
+/// use winnow::stream::Stateful;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Expr<'i> {
+///     Var(&'i str),
+///     Await(Box<Expr<'i>>),
+/// }
+///
+/// type Stream<'i> = Stateful<&'i str, bool>;
+///
+/// fn in_async(input: &Stream<'_>) -> bool {
+///     input.state
+/// }
+///
+/// fn expr<'i>(input: &mut Stream<'i>) -> PResult<Expr<'i>> {
+///     alt((
+///         preceded((contextual_keyword(alpha1, "await", in_async), space1), expr)
+///             .map(|e| Expr::Await(Box::new(e))),
+///         contextual_ident(alpha1, "await", in_async).map(Expr::Var),
+///     ))
+///     .parse_next(input)
+/// }
+///
+/// fn async_block<'i>(input: &mut Stream<'i>) -> PResult<Expr<'i>> {
+///     preceded("async ", scoped_state(|i: &mut Stream<'i>| &mut i.state, true, expr))
+///         .parse_next(input)
+/// }
+///
+/// // `await` is an identifier outside of `async`
+/// assert_eq!(
+///     expr.parse(Stream { input: "await", state: false }),
+///     Ok(Expr::Var("await"))
+/// );
+/// assert_eq!(
+///     async_block.parse(Stream { input: "async await fut", state: false }),
+///     Ok(Expr::Await(Box::new(Expr::Var("fut"))))
+/// );
+/// assert!(async_block.parse(Stream { input: "async await", state: false }).is_err());
+/// ```
+pub fn contextual_keyword<Input, Output, Key, Error, ParseNext, Active>(
+    mut ident: ParseNext,
+    keyword: Key,
+    active: Active,
+) -> impl Parser<Input, Output, Error>
+where
+    Input: Stream,
+    Output: AsBStr,
+    Key: AsRef<[u8]>,
+    ParseNext: Parser<Input, Output, Error>,
+    Active: Fn(&Input) -> bool,
+    Error: ParserError<Input>,
+{
+    trace("contextual_keyword", move |input: &mut Input| {
+        if !active(input) {
+            return Err(ErrMode::from_error_kind(input, ErrorKind::Tag));
+        }
+        let start = input.checkpoint();
+        let word = ident.parse_next(input)?;
+        if word.as_bstr() != keyword.as_ref() {
+            input.reset(&start);
+            return Err(ErrMode::from_error_kind(input, ErrorKind::Tag));
+        }
+        Ok(word)
+    })
+}
+
+/// Parse an identifier with `ident`, rejecting `keyword` while `active` holds
+///
+/// This is the counterpart to [`contextual_keyword`], see it for an example.  Where the keyword
+/// is active, it backtracks with [`ErrorKind::Verify`].
+pub fn contextual_ident<Input, Output, Key, Error, ParseNext, Active>(
+    mut ident: ParseNext,
+    keyword: Key,
+    active: Active,
+) -> impl Parser<Input, Output, Error>
+where
+    Input: Stream,
+    Output: AsBStr,
+    Key: AsRef<[u8]>,
+    ParseNext: Parser<Input, Output, Error>,
+    Active: Fn(&Input) -> bool,
+    Error: ParserError<Input>,
+{
+    trace("contextual_ident", move |input: &mut Input| {
+        let reserved = active(input);
+        let start = input.checkpoint();
+        let word = ident.parse_next(input)?;
+        if reserved && word.as_bstr() == keyword.as_ref() {
+            input.reset(&start);
+            return Err(ErrMode::from_error_kind(input, ErrorKind::Verify));
+        }
+        Ok(word)
+    })
+}
+
+/// Parse a number with `number`, followed by an optional suffix from `suffixes`
+///
+/// This covers literals like `255u8` and `1.5f32` in programming languages or `10px` in
+/// CSS-like grammars.  The longest matching suffix wins, so `"s"` and `"ms"` can both be listed.
+/// Suffixes are matched as-is; include any separator, like `"_px"`, in the suffix itself.
+///
+/// `number` runs first, so a suffix it would consume, like `em` after a [`float`], has to be
+/// excluded by `number` itself.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if a suffix may still follow.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// use winnow::ascii::dec_uint;
+/// use winnow::ascii::float;
+/// use winnow::ascii::suffixed;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq)]
+/// enum Unit {
+///     Px,
+///     Pt,
+/// }
+///
+/// const UNITS: &[(&str, Unit)] = &[("px", Unit::Px), ("pt", Unit::Pt)];
+///
+/// fn length(input: &mut &str) -> PResult<(f64, Option<Unit>)> {
+///     suffixed(float, UNITS).parse_next(input)
+/// }
+///
+/// assert_eq!(length.parse_peek("1.5pt;"), Ok((";", (1.5, Some(Unit::Pt)))));
+/// assert_eq!(length.parse_peek("0;"), Ok((";", (0.0, None))));
+///
+/// fn int(input: &mut &str) -> PResult<(u64, Option<&'static str>)> {
+///     suffixed(dec_uint, [("u8", "u8"), ("u16", "u16"), ("u64", "u64")]).parse_next(input)
+/// }
+///
+/// assert_eq!(int.parse_peek("255u8"), Ok(("", (255, Some("u8")))));
+/// ```
+pub fn suffixed<Input, Output, Key, Suffix, Error, ParseNext, Table>(
+    mut number: ParseNext,
+    suffixes: Table,
+) -> impl Parser<Input, (Output, Option<Suffix>), Error>
+where
+    Input: StreamIsPartial + Stream + Compare<Key>,
+    Key: Clone,
+    Suffix: Clone,
+    ParseNext: Parser<Input, Output, Error>,
+    Table: AsRef<[(Key, Suffix)]>,
+    Error: ParserError<Input>,
+{
+    trace("suffixed", move |input: &mut Input| {
+        let value = number.parse_next(input)?;
+        let mut longest: Option<(usize, &Suffix)> = None;
+        let mut incomplete = false;
+        for (key, suffix) in suffixes.as_ref() {
+            match input.compare(key.clone()) {
+                CompareResult::Ok(len) => {
+                    if !matches!(longest, Some((l, _)) if len <= l) {
+                        longest = Some((len, suffix));
+                    }
+                }
+                CompareResult::Incomplete => incomplete = true,
+                CompareResult::Error => {}
+            }
+        }
+        if incomplete && input.is_partial() {
+            return Err(ErrMode::Incomplete(Needed::Unknown));
+        }
+        let suffix = longest.map(|(len, suffix)| {
+            let _ = input.next_slice(len);
+            suffix.clone()
+        });
+        Ok((value, suffix))
+    })
+}
+
+/// Match an identifier parsed by `ident` against `keywords`, ignoring ASCII case
+///
+/// Returns the identifier as written, to echo the user's casing, along with the value for the
+/// matching keyword.  Parsing the whole identifier first means `SELECTED` won't match `select`.
+///
+/// Identifiers that aren't in `keywords` backtrack with [`ErrorKind::Tag`].
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::alpha1;
+/// use winnow::ascii::caseless_keyword;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq)]
+/// enum Keyword {
+///     Select,
+///     From,
+/// }
+///
+/// const KEYWORDS: &[(&str, Keyword)] = &[("select", Keyword::Select), ("from", Keyword::From)];
+///
+/// fn keyword<'i>(input: &mut &'i str) -> PResult<(&'i str, Keyword)> {
+///     caseless_keyword(alpha1, KEYWORDS).parse_next(input)
+/// }
+///
+/// assert_eq!(keyword.parse_peek("SeLeCt *"), Ok((" *", ("SeLeCt", Keyword::Select))));
+/// assert_eq!(keyword.parse_peek("FROM t"), Ok((" t", ("FROM", Keyword::From))));
+/// assert!(keyword.parse_peek("selected").is_err());
+/// ```
+pub fn caseless_keyword<Input, Output, Key, Value, Error, ParseNext, Table>(
+    mut ident: ParseNext,
+    keywords: Table,
+) -> impl Parser<Input, (Output, Value), Error>
+where
+    Input: Stream,
+    Output: AsBStr,
+    Key: AsRef<[u8]>,
+    Value: Clone,
+    ParseNext: Parser<Input, Output, Error>,
+    Table: AsRef<[(Key, Value)]>,
+    Error: ParserError<Input>,
+{
+    trace("caseless_keyword", move |input: &mut Input| {
+        let start = input.checkpoint();
+        let word = ident.parse_next(input)?;
+        let bytes = word.as_bstr();
+        match keywords
+            .as_ref()
+            .iter()
+            .find(|(keyword, _)| keyword.as_ref().eq_ignore_ascii_case(bytes))
+        {
+            Some((_, value)) => Ok((word, value.clone())),
+            None => {
+                input.reset(&start);
+                Err(ErrMode::from_error_kind(input, ErrorKind::Tag))
+            }
+        }
+    })
+}
+
+/// The framing of a line checked by [`line_with_checksum`]
+///
+/// The default is [NMEA 0183](https://en.wikipedia.org/wiki/NMEA_0183): `$`, the body, `*`, then
+/// the XOR of the body's bytes as 2 hex digits.
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::ascii::ChecksumSpec;
+///
+/// fn sum(bytes: &[u8]) -> u32 {
+///     bytes.iter().map(|b| u32::from(*b)).sum::<u32>() & 0xffff
+/// }
+///
+/// // `!body#HHHH` with a 16-bit sum
+/// let spec = ChecksumSpec::nmea()
+///     .start('!')
+///     .delimiter('#')
+///     .digits(4)
+///     .checksum(sum);
+/// # let _ = spec;
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct ChecksumSpec {
+    start: char,
+    delimiter: char,
+    digits: usize,
+    checksum: fn(&[u8]) -> u32,
+}
+
+impl ChecksumSpec {
+    /// `$body*HH`, with the XOR of the body's bytes
+    pub const fn nmea() -> Self {
+        Self {
+            start: '$',
+            delimiter: '*',
+            digits: 2,
+            checksum: xor_checksum,
+        }
+    }
+
+    /// The character that starts the line, before the body
+    pub const fn start(mut self, start: char) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// The character between the body and the checksum
+    pub const fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// The number of hex digits in the checksum, up to 8
+    ///
+    /// # Panics
+    ///
+    /// When `digits` is more than 8, as the checksum is a `u32`
+    pub const fn digits(mut self, digits: usize) -> Self {
+        assert!(digits <= 8, "a `u32` checksum has at most 8 hex digits");
+        self.digits = digits;
+        self
+    }
+
+    /// Calculate the checksum of the body's bytes
+    pub const fn checksum(mut self, checksum: fn(&[u8]) -> u32) -> Self {
+        self.checksum = checksum;
+        self
+    }
+}
+
+impl Default for ChecksumSpec {
+    fn default() -> Self {
+        Self::nmea()
+    }
+}
+
+fn xor_checksum(bytes: &[u8]) -> u32 {
+    u32::from(bytes.iter().fold(0, |sum, b| sum ^ b))
+}
+
+/// Parse a line framed by `spec`, like an [NMEA 0183](https://en.wikipedia.org/wiki/NMEA_0183)
+/// sentence, verifying its hex checksum
+///
+/// After the start character, `parser` parses the body.  The checksum of the bytes it consumed
+/// must equal the hex digits, in either case, after the delimiter.  A mismatch is an
+/// [`ErrorKind::Verify`] error positioned at the start of the line.  A trailing
+/// [`line_ending`] is consumed, if present.
+///
+/// For binary checksums, see [`checksummed`][crate::binary::checksummed].
+///
+/// *Complete version*: Returns an error if there is not enough input data.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there is not enough data.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::line_with_checksum;
+/// use winnow::ascii::ChecksumSpec;
+/// use winnow::combinator::separated;
+/// use winnow::token::take_till;
+///
+/// fn sentence<'i>(i: &mut &'i str) -> PResult<Vec<&'i str>> {
+///     line_with_checksum(
+///         separated(1.., take_till(0.., (',', '*')), ','),
+///         ChecksumSpec::nmea(),
+///     )
+///     .parse_next(i)
+/// }
+///
+/// assert_eq!(
+///     sentence.parse_peek("$GPGLL,5057.970,N,00146.110,E,142451,A*27\r\n$GPVTG"),
+///     Ok(("$GPVTG", vec!["GPGLL", "5057.970", "N", "00146.110", "E", "142451", "A"]))
+/// );
+/// assert!(sentence.parse_peek("$GPGLL,5057.970,N,00146.110,E,142451,A*28\r\n").is_err());
+/// ```
+pub fn line_with_checksum<Input, Output, Error, ParseNext>(
+    mut parser: ParseNext,
+    spec: ChecksumSpec,
+) -> impl Parser<Input, Output, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char> + Compare<&'static str>,
+    <Input as Stream>::Slice: AsBStr,
+    <Input as Stream>::Token: AsChar + Clone,
+    ParseNext: Parser<Input, Output, Error>,
+    Error: ParserError<Input>,
+{
+    trace("line_with_checksum", move |input: &mut Input| {
+        let start = input.checkpoint();
+        literal(spec.start).parse_next(input)?;
+        let (output, body) = parser.by_ref().with_taken().parse_next(input)?;
+        let actual = (spec.checksum)(body.as_bstr());
+        literal(spec.delimiter).parse_next(input)?;
+        let digits = take_while(spec.digits, AsChar::is_hex_digit).parse_next(input)?;
+        let expected = digits.as_bstr().iter().fold(0u32, |sum, digit| {
+            (sum << 4) | (*digit as char).to_digit(16).unwrap_or_default()
+        });
+        if actual != expected {
+            input.reset(&start);
+            return Err(ErrMode::from_error_kind(input, ErrorKind::Verify));
+        }
+        opt(line_ending).parse_next(input)?;
+        Ok(output)
+    })
+}
+
 mod sealed {
     pub struct SealedMarker;
 }
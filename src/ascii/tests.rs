@@ -7,6 +7,7 @@ mod complete {
     use crate::error::ErrMode;
     use crate::error::ErrorKind;
     use crate::error::InputError;
+    #[cfg(feature = "std")]
     use crate::stream::ParseSlice;
     use crate::token::none_of;
     use crate::token::one_of;
@@ -618,9 +619,120 @@ mod complete {
         }
     }
 
+    #[test]
     #[cfg(feature = "std")]
-    fn parse_f64(i: &str) -> IResult<&str, f64, ()> {
-        match take_float_or_exceptions.parse_peek(i) {
+    fn float_policy() {
+        fn strict(i: &str) -> IResult<&str, f64> {
+            let policy = FloatPolicy::new()
+                .leading_plus(false)
+                .leading_dot(false)
+                .trailing_dot(false)
+                .exponent_markers(&['e'])
+                .special_values(false);
+            float_with(policy).parse_peek(i)
+        }
+        assert_parse!(strict("-1.5e-3"), Ok(("", -0.0015)));
+        assert_parse!(strict("1.;"), Ok((".;", 1.0)));
+        assert_parse!(strict("1E5"), Ok(("E5", 1.0)));
+        assert_parse!(
+            strict("+1"),
+            Err(ErrMode::Backtrack(InputError::new("+1", ErrorKind::Slice)))
+        );
+        assert_parse!(
+            strict(".5"),
+            Err(ErrMode::Backtrack(InputError::new(".5", ErrorKind::Slice)))
+        );
+        assert_parse!(
+            strict("inf"),
+            Err(ErrMode::Backtrack(InputError::new("inf", ErrorKind::Slice)))
+        );
+
+        let no_exponent = FloatPolicy::new().exponent_markers(&[]);
+        assert_parse!(
+            float_with::<_, f64, _>(no_exponent).parse_peek(crate::Partial::new("1.5")),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+        assert_parse!(
+            float_with(no_exponent).parse_peek(crate::Partial::new("1.5e3")),
+            Ok((crate::Partial::new("e3"), 1.5))
+        );
+        assert_parse!(
+            float_with(FloatPolicy::default()).parse_peek("+.5E+1"),
+            Ok(("", 5.0))
+        );
+        assert_parse!(
+            float_with::<_, f64, _>(FloatPolicy::new().exponent_markers(&['p'])).parse_peek("1p3"),
+            Err(ErrMode::Backtrack(InputError::new("", ErrorKind::Verify)))
+        );
+    }
+
+    #[test]
+    fn hex_float_rounding() {
+        fn f64_bits(i: &str) -> u64 {
+            let res: IResult<&str, f64> = hex_float.parse_peek(i);
+            res.unwrap().1.to_bits()
+        }
+        fn f32_bits(i: &str) -> u32 {
+            let res: IResult<&str, f32> = hex_float.parse_peek(i);
+            res.unwrap().1.to_bits()
+        }
+
+        assert_eq!(f64_bits("0x1.8p3"), 12.0f64.to_bits());
+        assert_eq!(f64_bits("0x.1P+4"), 1.0f64.to_bits());
+        assert_eq!(f64_bits("0x10.p-4"), 1.0f64.to_bits());
+        assert_eq!(f64_bits("-0x0p0"), (-0.0f64).to_bits());
+        assert_eq!(f64_bits("0x1.fffffffffffffp1023"), f64::MAX.to_bits());
+        assert_eq!(f64_bits("0x1p1024"), f64::INFINITY.to_bits());
+        assert_eq!(f64_bits("0x1p-1022"), f64::MIN_POSITIVE.to_bits());
+        assert_eq!(f64_bits("0x1p-1074"), 1);
+        // Ties go to even, unless a digit past the mantissa breaks the tie
+        assert_eq!(f64_bits("0x1p-1075"), 0);
+        assert_eq!(f64_bits("0x1.8p-1074"), 2);
+        assert_eq!(f64_bits("0x1.00000000000008p0"), 1.0f64.to_bits());
+        assert_eq!(
+            f64_bits("0x1.0000000000000800000000001p0"),
+            1.0f64.to_bits() + 1
+        );
+        assert_eq!(f64_bits("0x1.00000000000018p0"), 1.0f64.to_bits() + 2);
+        assert_eq!(f64_bits("0x1.fffffffffffff8p0"), 2.0f64.to_bits());
+        assert_eq!(f64_bits("0x1.ffffffffffffffp1023"), f64::INFINITY.to_bits());
+        assert_eq!(
+            f64_bits("0x0.fffffffffffff8p-1022"),
+            f64::MIN_POSITIVE.to_bits()
+        );
+        assert_eq!(
+            f64_bits("0x123456789abcdef0123p-72"),
+            (0x123456789abcdef0123u128 as f64 / 2f64.powi(72)).to_bits()
+        );
+        assert_eq!(
+            f64_bits("0x1p99999999999999999999"),
+            f64::INFINITY.to_bits()
+        );
+        assert_eq!(f64_bits("0x1p-99999999999999999999"), 0);
+
+        assert_eq!(f32_bits("0x1.fffffep127"), f32::MAX.to_bits());
+        assert_eq!(f32_bits("0x1.ffffffp127"), f32::INFINITY.to_bits());
+        assert_eq!(f32_bits("0x1p-149"), 1);
+        assert_eq!(f32_bits("-0x1.000001p0"), (-1.0f32).to_bits());
+        assert_eq!(f32_bits("0x1.000003p0"), 1.0f32.to_bits() + 2);
+
+        assert_parse!(
+            hex_float::<_, f64, _>.parse_peek("0x1p"),
+            Err(ErrMode::Cut(InputError::new("", ErrorKind::Slice)))
+        );
+        assert_parse!(
+            hex_float::<_, f64, _>.parse_peek("0x.p1"),
+            Err(ErrMode::Backtrack(InputError::new("p1", ErrorKind::Slice)))
+        );
+        assert_parse!(
+            hex_float::<_, f64, _>.parse_peek("1.5p1"),
+            Err(ErrMode::Backtrack(InputError::new("1.5p1", ErrorKind::Tag)))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    fn parse_f64<'i>(i: &'i str) -> IResult<&'i str, f64, ()> {
+        match (|i: &mut &'i str| take_float_or_exceptions(i, FloatPolicy::new())).parse_peek(i) {
             Err(e) => Err(e),
             Ok((i, s)) => {
                 if s.is_empty() {
@@ -906,6 +1018,503 @@ mod complete {
 
         assert_eq!(esc_trans("abcd"), Ok(("abcd", String::new())));
     }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn number_policy() {
+        fn lit(policy: NumberPolicy, i: &str) -> IResult<&str, (&str, Number)> {
+            number(policy).parse_peek(i)
+        }
+
+        let default = NumberPolicy::new();
+        assert_eq!(
+            lit(default, "+12 "),
+            Ok((" ", ("+12", Number::Integer(12))))
+        );
+        assert_eq!(
+            lit(default, "-.5"),
+            Err(ErrMode::Backtrack(error_position!(&".5", ErrorKind::Slice)))
+        );
+        assert_eq!(lit(default, "1e"), Ok(("e", ("1", Number::Integer(1)))));
+        assert_eq!(
+            lit(default, "1E+2"),
+            Ok(("", ("1E+2", Number::Float(100.0))))
+        );
+        assert_eq!(lit(default, "0x10"), Ok(("x10", ("0", Number::Integer(0)))));
+        assert_eq!(
+            lit(default, "1_000"),
+            Ok(("_000", ("1", Number::Integer(1))))
+        );
+        assert_eq!(
+            lit(default, "-170141183460469231731687303715884105728"),
+            Ok((
+                "",
+                (
+                    "-170141183460469231731687303715884105728",
+                    Number::Integer(i128::MIN)
+                )
+            ))
+        );
+        assert_eq!(
+            lit(default, "170141183460469231731687303715884105728"),
+            Err(ErrMode::Backtrack(error_position!(
+                &"170141183460469231731687303715884105728",
+                ErrorKind::Verify
+            )))
+        );
+
+        let rust = NumberPolicy::new()
+            .sign(false)
+            .separators(&['_'])
+            .radix_prefixes(true);
+        assert_eq!(
+            lit(rust, "-1"),
+            Err(ErrMode::Backtrack(error_position!(&"-1", ErrorKind::Slice)))
+        );
+        assert_eq!(
+            lit(rust, "1_0_0_"),
+            Ok(("_", ("1_0_0", Number::Integer(100))))
+        );
+        assert_eq!(lit(rust, "1__0"), Ok(("__0", ("1", Number::Integer(1)))));
+        assert_eq!(lit(rust, "0O17"), Ok(("", ("0O17", Number::Integer(15)))));
+        assert_eq!(lit(rust, "0b12"), Ok(("2", ("0b1", Number::Integer(1)))));
+        assert_eq!(
+            lit(rust, "0b2"),
+            Err(ErrMode::Backtrack(error_position!(&"2", ErrorKind::Slice)))
+        );
+        assert_eq!(
+            lit(rust, "1_0.2_5"),
+            Ok(("", ("1_0.2_5", Number::Float(10.25))))
+        );
+        assert_eq!(lit(rust, "1.max"), Ok((".max", ("1", Number::Integer(1)))));
+
+        let integers = NumberPolicy::new().separators(&[',']).decimal_point(None);
+        assert_eq!(
+            lit(integers, "-1,234.5"),
+            Ok((".5", ("-1,234", Number::Integer(-1234))))
+        );
+        assert_eq!(lit(integers, "1e3"), Ok(("e3", ("1", Number::Integer(1)))));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn escaped_cow_borrows_without_escapes() {
+        use crate::lib::std::borrow::Cow;
+        use crate::lib::std::string::String;
+
+        const ESCAPES: &[(char, char)] = &[('n', '\n'), ('\\', '\\')];
+
+        fn esc(i: &str) -> IResult<&str, Cow<'_, str>> {
+            escaped_cow(alpha1, '\\', escape_table(ESCAPES)).parse_peek(i)
+        }
+
+        assert_eq!(esc("abc;"), Ok((";", Cow::Borrowed("abc"))));
+        assert_eq!(esc(""), Ok(("", Cow::Borrowed(""))));
+        assert_eq!(
+            esc("ab\\ncd\\\\;"),
+            Ok((";", Cow::Owned(String::from("ab\ncd\\"))))
+        );
+        assert_eq!(esc("\\nab"), Ok(("", Cow::Owned(String::from("\nab")))));
+        assert_eq!(
+            esc("ab\\q"),
+            Err(ErrMode::Backtrack(error_position!(&"q", ErrorKind::Verify)))
+        );
+
+        // Borrowing spans several runs of `normal`
+        fn word(i: &str) -> IResult<&str, Cow<'_, str>> {
+            escaped_cow(
+                crate::token::take_while(1..=1, AsChar::is_alpha),
+                '\\',
+                escape_table(ESCAPES),
+            )
+            .parse_peek(i)
+        }
+        assert_eq!(word("abc"), Ok(("", Cow::Borrowed("abc"))));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn utf8_policy() {
+        use crate::lib::std::borrow::Cow;
+
+        fn strict(i: &[u8]) -> IResult<&[u8], Cow<'_, str>> {
+            utf8(Utf8Policy::Strict, alphanumeric1).parse_peek(i)
+        }
+        fn lossy(i: &[u8]) -> IResult<&[u8], Cow<'_, str>> {
+            utf8(Utf8Policy::Lossy, till_line_ending).parse_peek(i)
+        }
+
+        assert_eq!(strict(&b"abc;"[..]), Ok((&b";"[..], Cow::Borrowed("abc"))));
+        assert_eq!(
+            lossy(&b"a\xffb\0c\n"[..]),
+            Ok((&b"\n"[..], Cow::Owned("a\u{fffd}b\0c".into())))
+        );
+        assert_eq!(lossy(&b"abc\n"[..]), Ok((&b"\n"[..], Cow::Borrowed("abc"))));
+
+        fn strict_line(i: &[u8]) -> IResult<&[u8], Cow<'_, str>> {
+            utf8(Utf8Policy::Strict, till_line_ending).parse_peek(i)
+        }
+        assert_eq!(
+            strict_line(&b"a\xffb\n"[..]),
+            Err(ErrMode::Backtrack(error_position!(
+                &&b"a\xffb\n"[..],
+                ErrorKind::Verify
+            )))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn decode_entities_table() {
+        use crate::lib::std::borrow::Cow;
+        use crate::lib::std::string::String;
+
+        const ENTITIES: &[(&str, &str)] = &[("amp", "&"), ("name", "{{ name }}")];
+
+        fn text(i: &str) -> IResult<&str, Cow<'_, str>> {
+            decode_entities(till_line_ending, ENTITIES).parse_peek(i)
+        }
+        assert_eq!(text("plain\n"), Ok(("\n", Cow::Borrowed("plain"))));
+        assert_eq!(
+            text("Hi &name;&amp;&#x1F600;&#65;\n"),
+            Ok(("\n", Cow::Owned(String::from("Hi {{ name }}&\u{1f600}A"))))
+        );
+        for kept in [
+            "&",
+            "&;",
+            "&amp",
+            "&#;",
+            "&#x;",
+            "&#0;",
+            "&#xD800;",
+            "&#99999999999;",
+            "& amp;",
+            "&lt;",
+        ] {
+            assert_eq!(
+                text(kept),
+                Ok(("", Cow::Owned(String::from(kept)))),
+                "{kept}"
+            );
+        }
+        assert_eq!(
+            text("&&amp;&#;&amp"),
+            Ok(("", Cow::Owned(String::from("&&&#;&amp"))))
+        );
+        assert_eq!(
+            decode_entities(till_line_ending::<_, InputError<_>>, &[("é", "e")][..])
+                .parse_peek("&é;"),
+            Ok(("", Cow::Owned(String::from("&é;"))))
+        );
+    }
+
+    #[test]
+    fn suffixed_longest_match() {
+        const UNITS: &[(&str, &str)] = &[("s", "s"), ("ms", "ms"), ("m", "m")];
+
+        fn duration(i: &str) -> IResult<&str, (u32, Option<&str>)> {
+            suffixed(dec_uint, UNITS).parse_peek(i)
+        }
+        assert_eq!(duration("10ms;"), Ok((";", (10, Some("ms")))));
+        assert_eq!(duration("10m;"), Ok((";", (10, Some("m")))));
+        assert_eq!(duration("10s"), Ok(("", (10, Some("s")))));
+        assert_eq!(duration("10;"), Ok((";", (10, None))));
+        assert_eq!(
+            duration("ms"),
+            Err(ErrMode::Backtrack(error_position!(
+                &"ms",
+                ErrorKind::Verify
+            )))
+        );
+    }
+
+    #[test]
+    fn caseless_keyword_echoes_input() {
+        #[derive(Copy, Clone, Debug, PartialEq)]
+        enum Kw {
+            Select,
+            Where,
+        }
+        const KEYWORDS: &[(&str, Kw)] = &[("select", Kw::Select), ("where", Kw::Where)];
+
+        fn keyword(i: &[u8]) -> IResult<&[u8], (&[u8], Kw)> {
+            caseless_keyword(alpha1, KEYWORDS).parse_peek(i)
+        }
+        assert_eq!(
+            keyword(&b"WHERE x"[..]),
+            Ok((&b" x"[..], (&b"WHERE"[..], Kw::Where)))
+        );
+        assert_eq!(
+            keyword(&b"sElEcT;"[..]),
+            Ok((&b";"[..], (&b"sElEcT"[..], Kw::Select)))
+        );
+        assert_eq!(
+            keyword(&b"selects"[..]),
+            Err(ErrMode::Backtrack(error_position!(
+                &&b"selects"[..],
+                ErrorKind::Tag
+            )))
+        );
+    }
+
+    #[test]
+    fn contextual_keyword_only_when_active() {
+        use crate::stream::Stateful;
+
+        type Stream<'i> = Stateful<&'i str, bool>;
+
+        fn active(i: &Stream<'_>) -> bool {
+            i.state
+        }
+        fn keyword<'i>(i: &mut Stream<'i>) -> PResult<&'i str, InputError<Stream<'i>>> {
+            contextual_keyword(alpha1, "yield", active).parse_next(i)
+        }
+        fn ident<'i>(i: &mut Stream<'i>) -> PResult<&'i str, InputError<Stream<'i>>> {
+            contextual_ident(alpha1, "yield", active).parse_next(i)
+        }
+
+        let input = |input, state| Stream { input, state };
+        assert_eq!(
+            keyword.parse_peek(input("yield x", true)).unwrap().1,
+            "yield"
+        );
+        assert_eq!(
+            keyword.parse_peek(input("yield x", false)).unwrap_err(),
+            ErrMode::Backtrack(InputError::new(input("yield x", false), ErrorKind::Tag))
+        );
+        assert_eq!(
+            keyword.parse_peek(input("yields x", true)).unwrap_err(),
+            ErrMode::Backtrack(InputError::new(input("yields x", true), ErrorKind::Tag))
+        );
+
+        assert_eq!(
+            ident.parse_peek(input("yield x", false)).unwrap().1,
+            "yield"
+        );
+        assert_eq!(
+            ident.parse_peek(input("yields x", true)).unwrap().1,
+            "yields"
+        );
+        assert_eq!(
+            ident.parse_peek(input("yield x", true)).unwrap_err(),
+            ErrMode::Backtrack(InputError::new(input("yield x", true), ErrorKind::Verify))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn ident_except_keywords() {
+        let keywords = Keywords::new(["if", "else", "while", "if"]);
+        assert_eq!(keywords.len(), 3);
+        assert!(keywords.contains(b"else"));
+        assert!(!keywords.contains(b"elsewhere"));
+        assert!(!keywords.contains(b""));
+
+        fn ident<'i>(i: &'i str, keywords: &Keywords) -> IResult<&'i str, &'i str> {
+            ident_except(alpha1, keywords).parse_peek(i)
+        }
+        assert_eq!(ident("iffy;", &keywords), Ok((";", "iffy")));
+        assert_eq!(ident("whil;", &keywords), Ok((";", "whil")));
+        assert_eq!(
+            ident("while;", &keywords),
+            Err(ErrMode::Backtrack(error_position!(
+                &"while;",
+                ErrorKind::Verify
+            )))
+        );
+
+        let err =
+            ident_except::<_, _, crate::error::ContextError, _, _>(alpha1, Keywords::new(["let"]))
+                .parse("let")
+                .unwrap_err();
+        assert_eq!(
+            err.inner().cause().map(|c| c.to_string()),
+            Some("`let` is a reserved word".into())
+        );
+    }
+
+    #[test]
+    fn line_with_checksum_test() {
+        use crate::token::take_till;
+
+        fn sentence(i: &str) -> IResult<&str, &str> {
+            line_with_checksum(take_till(0.., '*'), ChecksumSpec::nmea()).parse_peek(i)
+        }
+        assert_parse!(sentence("$GPAAM*5a\nrest"), Ok(("rest", "GPAAM")));
+        assert_parse!(sentence("$GPAAM*5A"), Ok(("", "GPAAM")));
+        assert_parse!(sentence("$*00\r\n"), Ok(("", "")));
+        assert_parse!(
+            sentence("$GPAAM*5C\n"),
+            Err(ErrMode::Backtrack(error_position!(
+                &"$GPAAM*5C\n",
+                ErrorKind::Verify
+            )))
+        );
+        assert_parse!(
+            sentence("$GPAAM*5"),
+            Err(ErrMode::Backtrack(error_position!(&"5", ErrorKind::Slice)))
+        );
+        assert_parse!(
+            sentence("GPAAM*5A"),
+            Err(ErrMode::Backtrack(error_position!(
+                &"GPAAM*5A",
+                ErrorKind::Tag
+            )))
+        );
+
+        fn sum(bytes: &[u8]) -> u32 {
+            bytes.iter().map(|b| u32::from(*b)).sum()
+        }
+        let spec = ChecksumSpec::nmea()
+            .start('!')
+            .delimiter('#')
+            .digits(4)
+            .checksum(sum);
+        assert_parse!(
+            line_with_checksum(take_till(0.., '#'), spec).parse_peek("!ab#00c3"),
+            Ok(("", "ab"))
+        );
+    }
+
+    #[test]
+    #[should_panic = "at most 8 hex digits"]
+    fn line_with_checksum_too_many_digits() {
+        let _ = ChecksumSpec::nmea().digits(9);
+    }
+
+    #[test]
+    fn datetime_test() {
+        fn date(i: &str) -> IResult<&str, Date> {
+            date_ymd.parse_peek(i)
+        }
+        fn time(i: &str) -> IResult<&str, Time> {
+            time_hms.parse_peek(i)
+        }
+
+        assert_parse!(
+            date("2000-02-29"),
+            Ok((
+                "",
+                Date {
+                    year: 2000,
+                    month: 2,
+                    day: 29
+                }
+            ))
+        );
+        assert_parse!(
+            date("1900-02-29"),
+            Err(ErrMode::Backtrack(error_position!(
+                &"1900-02-29",
+                ErrorKind::Verify
+            )))
+        );
+        assert_parse!(
+            date("2024-04-31"),
+            Err(ErrMode::Backtrack(error_position!(
+                &"2024-04-31",
+                ErrorKind::Verify
+            )))
+        );
+        assert_parse!(
+            date("2024-13-01"),
+            Err(ErrMode::Backtrack(error_position!(
+                &"13-01",
+                ErrorKind::Verify
+            )))
+        );
+        assert_parse!(
+            date("2024/01/01"),
+            Err(ErrMode::Backtrack(error_position!(
+                &"/01/01",
+                ErrorKind::Tag
+            )))
+        );
+
+        assert_parse!(
+            time("23:59:60.123456789123 "),
+            Ok((
+                " ",
+                Time {
+                    hour: 23,
+                    minute: 59,
+                    second: 60,
+                    nanosecond: 123_456_789
+                }
+            ))
+        );
+        assert_parse!(
+            time("12:00:00."),
+            Ok((
+                ".",
+                Time {
+                    hour: 12,
+                    minute: 0,
+                    second: 0,
+                    nanosecond: 0
+                }
+            ))
+        );
+        assert_parse!(
+            time("12:60:00"),
+            Err(ErrMode::Backtrack(error_position!(
+                &"60:00",
+                ErrorKind::Verify
+            )))
+        );
+
+        let stamp = |format, i| datetime::<_, InputError<_>>(format).parse_peek(i);
+        assert_eq!(
+            stamp("%d/%b/%Y:%H:%M:%S %z", "10/OCT/2000:13:55:36 +0730]"),
+            Ok((
+                "]",
+                DateTime {
+                    date: Date {
+                        year: 2000,
+                        month: 10,
+                        day: 10
+                    },
+                    time: Time {
+                        hour: 13,
+                        minute: 55,
+                        second: 36,
+                        nanosecond: 0
+                    },
+                    utc_offset: Some(7 * 3600 + 30 * 60),
+                }
+            ))
+        );
+        assert_eq!(
+            stamp("%Y%m%d %H%M%S%.f%z", "20240102 030405Z").map(|(_, s)| s.utc_offset),
+            Ok(Some(0))
+        );
+        assert_eq!(
+            stamp("%e %H:%M %%", "29 07:00 %").map(|(_, s)| s.date),
+            Ok(Date {
+                year: 1970,
+                month: 1,
+                day: 29
+            })
+        );
+        assert_eq!(stamp("%b %e", "Feb 29").map(|(_, s)| s.date.day), Ok(29));
+        assert!(stamp("%Y %b %e", "2023 Feb 29").is_err());
+        assert!(stamp("%b", "Foo").is_err());
+        assert_eq!(
+            stamp("%e", " 10").map(|(r, s)| (r, s.date.day)),
+            Ok(("0", 1))
+        );
+    }
+
+    #[test]
+    fn datetime_unknown_specifier() {
+        for format in ["%Y-%q", "%.x", "%"] {
+            assert_eq!(
+                datetime::<&str, InputError<_>>(format).parse_peek("2024-q"),
+                Err(ErrMode::Cut(error_position!(&"2024-q", ErrorKind::Assert))),
+                "{format}"
+            );
+        }
+    }
 }
 
 mod partial {
@@ -1531,4 +2140,104 @@ mod partial {
             Err(ErrMode::Incomplete(Needed::new(1)))
         );
     }
+
+    #[test]
+    fn line_with_checksum_test() {
+        use crate::token::take_till;
+
+        fn sentence(i: Partial<&str>) -> IResult<Partial<&str>, &str> {
+            line_with_checksum(take_till(0.., '*'), ChecksumSpec::nmea()).parse_peek(i)
+        }
+        assert_parse!(
+            sentence(Partial::new("$GPAAM*5A\r\n$")),
+            Ok((Partial::new("$"), "GPAAM"))
+        );
+        assert_parse!(
+            sentence(Partial::new("$GPAAM*5")),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+        assert_parse!(
+            sentence(Partial::new("$GPAAM*5A")),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+    }
+
+    #[test]
+    fn datetime_test() {
+        assert_parse!(
+            date_ymd::<_, InputError<_>>.parse_peek(Partial::new("2024-01-0")),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+        assert_parse!(
+            time_hms::<_, InputError<_>>.parse_peek(Partial::new("12:00:00.5")),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+        assert_parse!(
+            datetime::<_, InputError<_>>("%b %e").parse_peek(Partial::new("Ja")),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn escaped_cow_incomplete() {
+        use crate::lib::std::borrow::Cow;
+
+        fn esc(i: Partial<&str>) -> IResult<Partial<&str>, Cow<'_, str>> {
+            escaped_cow(alpha1, '\\', escape_table([('n', '\n')])).parse_peek(i)
+        }
+        assert_eq!(
+            esc(Partial::new("ab;")),
+            Ok((Partial::new(";"), Cow::Borrowed("ab")))
+        );
+        assert_eq!(
+            esc(Partial::new("ab\\n")),
+            Err(ErrMode::Incomplete(Needed::Unknown))
+        );
+        assert_eq!(
+            esc(Partial::new("ab\\")),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn number_incomplete() {
+        let policy = NumberPolicy::new().separators(&['_']).radix_prefixes(true);
+        fn lit(policy: NumberPolicy, i: Partial<&str>) -> IResult<Partial<&str>, (&str, Number)> {
+            number(policy).parse_peek(i)
+        }
+        assert_eq!(
+            lit(policy, Partial::new("12")),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+        assert_eq!(
+            lit(policy, Partial::new("0")),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+        assert_eq!(
+            lit(policy, Partial::new("1.5e")),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+        assert_eq!(
+            lit(policy, Partial::new("0xf_f;")),
+            Ok((Partial::new(";"), ("0xf_f", Number::Integer(255))))
+        );
+    }
+
+    #[test]
+    fn hex_float_incomplete() {
+        assert_parse!(
+            hex_float::<_, f64, _>.parse_peek(Partial::new("0x1.8")),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+        assert_parse!(
+            hex_float::<_, f64, _>.parse_peek(Partial::new("-0x1.8p")),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+        assert_parse!(
+            hex_float.parse_peek(Partial::new("0x1.8p-1,")),
+            Ok((Partial::new(","), 0.75f64))
+        );
+    }
 }
@@ -0,0 +1,271 @@
+//! Bit level parsers reading the least significant bit of each byte first
+//!
+//! These operate on the same `(Input, usize)` bit stream as the parsers in
+//! [`binary::bits`][super], so [`bits`][super::bits], [`bytes`][super::bytes], and
+//! [`align_to_byte`][super::align_to_byte] work the same way.  The difference is that the bit
+//! offset counts from the least significant bit of the current byte and the first bit read
+//! becomes the least significant bit of the output.
+//!
+//! Mixing these with the most-significant-bit-first parsers on the same byte is unlikely to do
+//! what you want.
+
+use crate::combinator::trace;
+use crate::error::{ErrMode, ErrorKind, Needed, ParserError};
+use crate::lib::std::ops::{AddAssign, Shl, Shr};
+use crate::stream::{Stream, StreamIsPartial, ToUsize};
+use crate::{unpeek, IResult, PResult, Parser};
+
+use super::BYTE;
+
+/// Parse taking `count` bits, least significant bit first
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `(&[u8], usize)` bit [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// # use winnow::error::ContextError;
+/// pub fn take<'i>(count: usize) -> impl Parser<(&'i [u8], usize), u8, ContextError>
+/// # {
+/// #     winnow::binary::bits::lsb0::take(count)
+/// # }
+/// ```
+///
+/// # Example
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::Bytes;
+/// # use winnow::error::{InputError, ErrorKind};
+/// use winnow::binary::bits::lsb0::take;
+///
+/// type Stream<'i> = &'i Bytes;
+///
+/// fn stream(b: &[u8]) -> Stream<'_> {
+///     Bytes::new(b)
+/// }
+///
+/// fn parser(input: (Stream<'_>, usize), count: usize)-> IResult<(Stream<'_>, usize), u8> {
+///   take(count).parse_peek(input)
+/// }
+///
+/// // Consumes 0 bits, returns 0
+/// assert_eq!(parser((stream(&[0b00010010]), 0), 0), Ok(((stream(&[0b00010010]), 0), 0)));
+///
+/// // Consumes the 4 lowest bits, returns their values and increase offset to 4
+/// assert_eq!(parser((stream(&[0b00010010]), 0), 4), Ok(((stream(&[0b00010010]), 4), 0b00000010)));
+///
+/// // Consumes the 4 highest bits, offset is 4, returns their values and increase offset to 0 of next byte
+/// assert_eq!(parser((stream(&[0b00010010]), 4), 4), Ok(((stream(&[]), 0), 0b00000001)));
+///
+/// // Tries to consume 12 bits but only 8 are available
+/// assert_eq!(parser((stream(&[0b00010010]), 0), 12), Err(winnow::error::ErrMode::Backtrack(InputError::new((stream(&[0b00010010]), 0), ErrorKind::Eof))));
+///
+/// // Tries to consume 12 bits but they don't fit in a `u8`
+/// assert_eq!(parser((stream(&[0b00010010, 0b00110100]), 0), 12), Err(winnow::error::ErrMode::Backtrack(InputError::new((stream(&[0b00010010, 0b00110100]), 0), ErrorKind::Verify))));
+/// ```
+#[inline(always)]
+pub fn take<Input, Output, Count, Error>(count: Count) -> impl Parser<(Input, usize), Output, Error>
+where
+    Input: Stream<Token = u8> + StreamIsPartial + Clone,
+    Output: From<u8> + AddAssign + Shl<usize, Output = Output> + Shr<usize, Output = Output>,
+    Count: ToUsize,
+    Error: ParserError<(Input, usize)>,
+{
+    let count = count.to_usize();
+    trace(
+        "take",
+        unpeek(move |input: (Input, usize)| {
+            if <Input as StreamIsPartial>::is_partial_supported() {
+                take_::<_, _, _, true>(input, count)
+            } else {
+                take_::<_, _, _, false>(input, count)
+            }
+        }),
+    )
+}
+
+fn take_<I, O, E: ParserError<(I, usize)>, const PARTIAL: bool>(
+    (input, bit_offset): (I, usize),
+    count: usize,
+) -> IResult<(I, usize), O, E>
+where
+    I: StreamIsPartial,
+    I: Stream<Token = u8> + Clone,
+    O: From<u8> + AddAssign + Shl<usize, Output = O> + Shr<usize, Output = O>,
+{
+    if count == 0 {
+        Ok(((input, bit_offset), 0u8.into()))
+    } else if input.eof_offset().saturating_mul(BYTE) < count.saturating_add(bit_offset) {
+        if PARTIAL && input.is_partial() {
+            Err(ErrMode::Incomplete(Needed::new(count)))
+        } else {
+            Err(ErrMode::from_error_kind(
+                &(input, bit_offset),
+                ErrorKind::Eof,
+            ))
+        }
+    } else if count > core::mem::size_of::<O>() * BYTE {
+        // The bits would be shifted out of `O`
+        Err(ErrMode::from_error_kind(
+            &(input, bit_offset),
+            ErrorKind::Verify,
+        ))
+    } else {
+        let cnt = (count + bit_offset) / BYTE;
+        let mut acc: O = 0_u8.into();
+        let mut offset: usize = bit_offset;
+        let mut shift: usize = 0;
+        let mut remaining: usize = count;
+
+        for (_, byte) in input.iter_offsets().take(cnt + 1) {
+            if remaining == 0 {
+                break;
+            }
+            if offset == BYTE {
+                offset = 0;
+                continue;
+            }
+            let available = BYTE - offset;
+            let taken = remaining.min(available);
+            let val = (byte >> offset) & (u8::MAX >> (BYTE - taken));
+            acc += O::from(val) << shift;
+
+            shift += taken;
+            remaining -= taken;
+            offset = (offset + taken) % BYTE;
+        }
+        let (input, _) = input.peek_slice(cnt);
+        Ok(((input, offset), acc))
+    }
+}
+
+/// Parse taking `count` bits, least significant bit first, and comparing them to `pattern`
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `(&[u8], usize)` bit [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// # use winnow::error::ContextError;
+/// pub fn pattern<'i>(pattern: u8, count: usize) -> impl Parser<(&'i [u8], usize), u8, ContextError>
+/// # {
+/// #     winnow::binary::bits::lsb0::pattern(pattern, count)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::Bytes;
+/// # use winnow::error::{InputError, ErrorKind};
+/// use winnow::binary::bits::lsb0::pattern;
+///
+/// type Stream<'i> = &'i Bytes;
+///
+/// fn stream(b: &[u8]) -> Stream<'_> {
+///     Bytes::new(b)
+/// }
+///
+/// fn parser(bits: u8, count: u8, input: (Stream<'_>, usize)) -> IResult<(Stream<'_>, usize), u8> {
+///     pattern(bits, count).parse_peek(input)
+/// }
+///
+/// // The lowest 3 bits of 0b1111_0101 are 0b101
+/// assert_eq!(
+///     parser(0b101, 3, (stream(&[0b1111_0101]), 0)),
+///     Ok(((stream(&[0b1111_0101]), 3), 0b101))
+/// );
+///
+/// // The lowest 3 bits of 0b1111_0101 are not 0b111
+/// assert_eq!(
+///     parser(0b111, 3, (stream(&[0b1111_0101]), 0)),
+///     Err(winnow::error::ErrMode::Backtrack(InputError::new(
+///         (stream(&[0b1111_0101]), 0),
+///         ErrorKind::Tag
+///     )))
+/// );
+/// ```
+#[inline(always)]
+#[doc(alias = "literal")]
+#[doc(alias = "just")]
+#[doc(alias = "tag")]
+pub fn pattern<Input, Output, Count, Error: ParserError<(Input, usize)>>(
+    pattern: Output,
+    count: Count,
+) -> impl Parser<(Input, usize), Output, Error>
+where
+    Input: Stream<Token = u8> + StreamIsPartial + Clone,
+    Count: ToUsize,
+    Output: From<u8>
+        + AddAssign
+        + Shl<usize, Output = Output>
+        + Shr<usize, Output = Output>
+        + PartialEq,
+{
+    let count = count.to_usize();
+    trace("pattern", move |input: &mut (Input, usize)| {
+        let start = input.checkpoint();
+
+        take(count).parse_next(input).and_then(|o| {
+            if pattern == o {
+                Ok(o)
+            } else {
+                input.reset(&start);
+                Err(ErrMode::Backtrack(Error::from_error_kind(
+                    input,
+                    ErrorKind::Tag,
+                )))
+            }
+        })
+    })
+}
+
+/// Parses one bit as a bool, least significant bit first
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `(&[u8], usize)` bit [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// # use winnow::error::ContextError;
+/// pub fn bool(input: &mut (&[u8], usize)) -> PResult<bool>
+/// # {
+/// #     winnow::binary::bits::lsb0::bool.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::Bytes;
+/// # use winnow::error::{InputError, ErrorKind};
+/// use winnow::binary::bits::lsb0::bool;
+///
+/// type Stream<'i> = &'i Bytes;
+///
+/// fn stream(b: &[u8]) -> Stream<'_> {
+///     Bytes::new(b)
+/// }
+///
+/// fn parse(input: (Stream<'_>, usize)) -> IResult<(Stream<'_>, usize), bool> {
+///     bool.parse_peek(input)
+/// }
+///
+/// assert_eq!(parse((stream(&[0b00000001]), 0)), Ok(((stream(&[0b00000001]), 1), true)));
+/// assert_eq!(parse((stream(&[0b00000001]), 1)), Ok(((stream(&[0b00000001]), 2), false)));
+/// ```
+#[doc(alias = "any")]
+pub fn bool<Input, Error: ParserError<(Input, usize)>>(
+    input: &mut (Input, usize),
+) -> PResult<bool, Error>
+where
+    Input: Stream<Token = u8> + StreamIsPartial + Clone,
+{
+    trace("bool", |input: &mut (Input, usize)| {
+        let bit: u32 = take(1usize).parse_next(input)?;
+        Ok(bit != 0)
+    })
+    .parse_next(input)
+}
@@ -1,5 +1,8 @@
 //! Bit level parsers
 //!
+//! Bits are read most significant bit first, see [`lsb0`] for least significant bit first.
+
+pub mod lsb0;
 
 #[cfg(test)]
 mod tests;
@@ -17,6 +20,13 @@ const BYTE: usize = u8::BITS as usize;
 ///
 /// See [`bytes`] to convert it back.
 ///
+/// [`Parser::take`] returns the bytes covered by a bit-level parse, like for computing a checksum:
+/// - `bits(parser).take()` returns the bytes consumed by `bits`, including a partially read last
+///   byte
+/// - Within `bits`, it returns `(bytes, start, end)`: the bytes holding the bits read, including
+///   a partially read last byte, the bit offset into the first byte, and the number of bits read
+///   from the last byte, with `0` when it was read in full
+///
 /// # Example
 /// ```
 /// use winnow::prelude::*;
@@ -194,6 +204,9 @@ where
 ///
 /// // Tries to consume 12 bits but only 8 are available
 /// assert_eq!(parser((stream(&[0b00010010]), 0), 12), Err(winnow::error::ErrMode::Backtrack(InputError::new((stream(&[0b00010010]), 0), ErrorKind::Eof))));
+///
+/// // Tries to consume 12 bits but they don't fit in a `u8`
+/// assert_eq!(parser((stream(&[0b00010010, 0b00110100]), 0), 12), Err(winnow::error::ErrMode::Backtrack(InputError::new((stream(&[0b00010010, 0b00110100]), 0), ErrorKind::Verify))));
 /// ```
 #[inline(always)]
 pub fn take<Input, Output, Count, Error>(count: Count) -> impl Parser<(Input, usize), Output, Error>
@@ -228,7 +241,7 @@ where
     if count == 0 {
         Ok(((input, bit_offset), 0u8.into()))
     } else {
-        if input.eof_offset() * BYTE < count + bit_offset {
+        if input.eof_offset().saturating_mul(BYTE) < count.saturating_add(bit_offset) {
             if PARTIAL && input.is_partial() {
                 Err(ErrMode::Incomplete(Needed::new(count)))
             } else {
@@ -237,6 +250,12 @@ where
                     ErrorKind::Eof,
                 ))
             }
+        } else if count > core::mem::size_of::<O>() * BYTE {
+            // The bits would be shifted out of `O`
+            Err(ErrMode::from_error_kind(
+                &(input, bit_offset),
+                ErrorKind::Verify,
+            ))
         } else {
             let cnt = (count + bit_offset).div(BYTE);
             let mut acc: O = 0_u8.into();
@@ -418,3 +437,124 @@ where
     })
     .parse_next(input)
 }
+
+/// Skip the remaining bits of a partially read byte
+///
+/// See [`aligned`] to require the stream already be at a byte boundary.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `(&[u8], usize)` bit [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// # use winnow::error::ContextError;
+/// pub fn align_to_byte(input: &mut (&[u8], usize)) -> PResult<()>
+/// # {
+/// #     winnow::binary::bits::align_to_byte.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::Bytes;
+/// use winnow::binary::bits::{align_to_byte, take};
+///
+/// type Stream<'i> = &'i Bytes;
+///
+/// fn stream(b: &[u8]) -> Stream<'_> {
+///     Bytes::new(b)
+/// }
+///
+/// fn parse(input: (Stream<'_>, usize)) -> IResult<(Stream<'_>, usize), (u8, u8)> {
+///     (take(3usize), align_to_byte, take(4usize))
+///         .map(|(flags, (), nibble)| (flags, nibble))
+///         .parse_peek(input)
+/// }
+///
+/// assert_eq!(
+///     parse((stream(&[0b1010_0000, 0b0110_0000]), 0)),
+///     Ok(((stream(&[0b0110_0000]), 4), (0b101, 0b0110)))
+/// );
+/// ```
+pub fn align_to_byte<Input, Error: ParserError<(Input, usize)>>(
+    input: &mut (Input, usize),
+) -> PResult<(), Error>
+where
+    Input: Stream<Token = u8> + StreamIsPartial + Clone,
+{
+    trace("align_to_byte", |input: &mut (Input, usize)| {
+        let (bytes, offset) = input;
+        if *offset != 0 {
+            let consumed =
+                (*offset / BYTE + usize::from(*offset % BYTE != 0)).min(bytes.eof_offset());
+            let _ = bytes.next_slice(consumed);
+            *offset = 0;
+        }
+        Ok(())
+    })
+    .parse_next(input)
+}
+
+/// Succeeds only when the stream is at a byte boundary, consuming nothing
+///
+/// See [`align_to_byte`] to skip to the next byte boundary.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `(&[u8], usize)` bit [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// # use winnow::error::ContextError;
+/// pub fn aligned(input: &mut (&[u8], usize)) -> PResult<()>
+/// # {
+/// #     winnow::binary::bits::aligned.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::Bytes;
+/// # use winnow::error::{InputError, ErrorKind};
+/// use winnow::binary::bits::{aligned, take};
+///
+/// type Stream<'i> = &'i Bytes;
+///
+/// fn stream(b: &[u8]) -> Stream<'_> {
+///     Bytes::new(b)
+/// }
+///
+/// fn parse(input: (Stream<'_>, usize)) -> IResult<(Stream<'_>, usize), u8> {
+///     (take(4usize), aligned).map(|(n, ())| n).parse_peek(input)
+/// }
+///
+/// assert_eq!(
+///     parse((stream(&[0b1010_0000]), 4)),
+///     Ok(((stream(&[]), 0), 0b0000))
+/// );
+/// assert_eq!(
+///     parse((stream(&[0b1010_0000]), 0)),
+///     Err(winnow::error::ErrMode::Backtrack(InputError::new(
+///         (stream(&[0b1010_0000]), 4),
+///         ErrorKind::Verify
+///     )))
+/// );
+/// ```
+pub fn aligned<Input, Error: ParserError<(Input, usize)>>(
+    input: &mut (Input, usize),
+) -> PResult<(), Error>
+where
+    Input: Stream<Token = u8> + StreamIsPartial + Clone,
+{
+    trace("aligned", |input: &mut (Input, usize)| {
+        if input.1 % BYTE == 0 {
+            Ok(())
+        } else {
+            Err(ErrMode::from_error_kind(input, ErrorKind::Verify))
+        }
+    })
+    .parse_next(input)
+}
@@ -62,6 +62,34 @@ fn test_incomplete_bits() {
     assert_eq!("Parsing requires 2 more data", error.to_string());
 }
 
+#[test]
+/// Take the bytes covered by a bit-level parse, with and without a partially read last byte
+fn test_take_bits_region() {
+    let input = &[0x12, 0x34, 0x56, 0x78][..];
+
+    // Within `bits`, a partially read last byte is part of the slice
+    let result: IResult<(&[u8], usize), (u8, (&[u8], usize, usize))> =
+        (take(4usize), (take::<_, u8, _, _>(8usize), bool).take()).parse_peek((input, 0));
+    assert_eq!(result, Ok(((&input[1..], 5), (0x01, (&input[..2], 4, 5)))));
+
+    let result: IResult<(&[u8], usize), (&[u8], usize, usize)> =
+        (take::<_, u16, _, _>(12usize), take::<_, u8, _, _>(4usize))
+            .take()
+            .parse_peek((input, 0));
+    assert_eq!(result, Ok(((&input[2..], 0), (&input[..2], 0, 0))));
+
+    let result: IResult<(&[u8], usize), (&[u8], usize, usize)> =
+        take::<_, u8, _, _>(0usize).take().parse_peek((input, 3));
+    assert_eq!(result, Ok(((input, 3), (&input[..0], 3, 3))));
+
+    // Across the boundary, all bytes consumed by `bits`, like for a checksum
+    let result: IResult<&[u8], ((u8, u8), &[u8])> =
+        bits::<_, _, InputError<(&[u8], usize)>, _, _>((take(4usize), take(8usize)))
+            .with_taken()
+            .parse_peek(input);
+    assert_eq!(result, Ok((&input[2..], ((0x01, 0x23), &input[..2]))));
+}
+
 #[test]
 fn test_take_complete_0() {
     let input = &[0b00010010][..];
@@ -189,3 +217,96 @@ fn test_bool_eof_partial() {
         Err(crate::error::ErrMode::Incomplete(Needed::new(1)))
     );
 }
+
+#[test]
+fn test_align_to_byte_partial_byte() {
+    let input = [0b10000000, 0b01000000].as_ref();
+
+    let result: IResult<(&[u8], usize), ()> = align_to_byte.parse_peek((input, 3));
+
+    assert_eq!(result, Ok(((&input[1..], 0), ())));
+}
+
+#[test]
+fn test_align_to_byte_aligned() {
+    let input = [0b10000000, 0b01000000].as_ref();
+
+    let result: IResult<(&[u8], usize), ()> = align_to_byte.parse_peek((input, 0));
+
+    assert_eq!(result, Ok(((input, 0), ())));
+}
+
+#[test]
+fn test_align_to_byte_eof() {
+    let input = [0b10000000].as_ref();
+
+    let result: IResult<(&[u8], usize), ()> = align_to_byte.parse_peek((input, 8));
+
+    assert_eq!(result, Ok(((&input[1..], 0), ())));
+}
+
+#[test]
+fn test_aligned() {
+    let input = [0b10000000].as_ref();
+
+    let result: IResult<(&[u8], usize), ()> = aligned.parse_peek((input, 0));
+    assert_eq!(result, Ok(((input, 0), ())));
+
+    let result: IResult<(&[u8], usize), ()> = aligned.parse_peek((input, 1));
+    assert_eq!(
+        result,
+        Err(crate::error::ErrMode::Backtrack(InputError::new(
+            (input, 1),
+            ErrorKind::Verify
+        )))
+    );
+}
+
+#[test]
+fn test_lsb0_take_span_over_multiple_bytes() {
+    let input = [0b1010_1100, 0b0000_0011].as_ref();
+
+    let result: IResult<(&[u8], usize), u16> = lsb0::take(10usize).parse_peek((input, 2));
+
+    assert_eq!(result, Ok(((&input[1..], 4), 0b0011_101011)));
+}
+
+#[test]
+fn test_lsb0_take_whole_bytes() {
+    let input = [0x34, 0x12, 0xff].as_ref();
+
+    let result: IResult<(&[u8], usize), u16> = lsb0::take(16usize).parse_peek((input, 0));
+
+    assert_eq!(result, Ok(((&input[2..], 0), 0x1234)));
+}
+
+#[test]
+fn test_lsb0_take_after_full_byte() {
+    let input = [0b1111_1111, 0b0000_0101].as_ref();
+
+    let result: IResult<(&[u8], usize), u8> = lsb0::take(3usize).parse_peek((input, 8));
+
+    assert_eq!(result, Ok(((&input[1..], 3), 0b101)));
+}
+
+#[test]
+fn test_lsb0_take_partial_eof() {
+    let input = Partial::new([0b0000_0001].as_ref());
+
+    let result: IResult<(Partial<&[u8]>, usize), u8> = lsb0::take(4usize).parse_peek((input, 6));
+
+    assert_eq!(
+        result,
+        Err(crate::error::ErrMode::Incomplete(Needed::new(4)))
+    );
+}
+
+#[test]
+fn test_lsb0_bool() {
+    let input = [0b0000_0010].as_ref();
+
+    let result: IResult<(&[u8], usize), (bool, bool)> =
+        (lsb0::bool, lsb0::bool).parse_peek((input, 0));
+
+    assert_eq!(result, Ok(((input, 2), (false, true))));
+}
@@ -3,18 +3,27 @@
 #![allow(clippy::match_same_arms)]
 
 pub mod bits;
+pub mod write;
 
 #[cfg(test)]
 mod tests;
 
+use crate::combinator::eof;
 use crate::combinator::repeat;
+use crate::combinator::terminated;
 use crate::combinator::trace;
+use crate::error::AddContext;
 use crate::error::ErrMode;
 use crate::error::ErrorKind;
+use crate::error::FromExternalError;
 use crate::error::Needed;
 use crate::error::ParserError;
+use crate::error::StrContext;
 use crate::lib::std::ops::{Add, Shl};
+use crate::stream::memrmem;
 use crate::stream::Accumulate;
+use crate::stream::AsBytes;
+use crate::stream::SeekableStream;
 use crate::stream::{Stream, StreamIsPartial};
 use crate::stream::{ToUsize, UpdateSlice};
 use crate::PResult;
@@ -1635,6 +1644,79 @@ where
     }(input)
 }
 
+/// Recognizes an unsigned integer `nbytes` wide, for `nbytes` in `1..=8`
+///
+/// For formats that give an integer's width at runtime, like in a preceding nibble.  An
+/// `nbytes` outside of `1..=8` fails with [`ErrorKind::Verify`].
+///
+/// If the parameter is `winnow::binary::Endianness::Big`, parse a big endian integer,
+/// otherwise if `winnow::binary::Endianness::Little` parse a little endian integer.
+///
+/// *Complete version*: returns an error if there is not enough input data
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there is not enough data.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::{error::ErrMode, error::ErrorKind, error::InputError, error::Needed};
+/// # use winnow::prelude::*;
+/// use winnow::binary::u8;
+/// use winnow::binary::uint_var;
+/// use winnow::binary::Endianness;
+///
+/// // The low nibble of the marker gives `log2` of the width, as in binary property lists
+/// fn int<'i>(input: &mut &'i [u8]) -> PResult<u64, InputError<&'i [u8]>> {
+///     let marker = u8.parse_next(input)?;
+///     uint_var(Endianness::Big, 1 << (marker & 0x0f)).parse_next(input)
+/// }
+///
+/// assert_eq!(int.parse_peek(&b"\x10\x2a"[..]), Ok((&b""[..], 0x2a)));
+/// assert_eq!(int.parse_peek(&b"\x12\x00\x01\x00\x00;"[..]), Ok((&b";"[..], 0x10000)));
+/// assert_eq!(
+///     int.parse_peek(&b"\x14\x00"[..]),
+///     Err(ErrMode::Backtrack(InputError::new(&b"\x00"[..], ErrorKind::Verify)))
+/// );
+/// ```
+///
+/// ```rust
+/// # use winnow::{error::ErrMode, error::ErrorKind, error::InputError, error::Needed};
+/// # use winnow::prelude::*;
+/// # use winnow::Partial;
+/// use winnow::binary::uint_var;
+/// use winnow::binary::Endianness;
+///
+/// let le_u24 = |s| {
+///     uint_var::<_, InputError<_>>(Endianness::Little, 3).parse_peek(s)
+/// };
+///
+/// assert_eq!(le_u24(Partial::new(&b"\x01\x02\x03abc"[..])), Ok((Partial::new(&b"abc"[..]), 0x030201)));
+/// assert_eq!(le_u24(Partial::new(&b"\x01"[..])), Err(ErrMode::Incomplete(Needed::new(2))));
+/// ```
+#[inline(always)]
+pub fn uint_var<Input, Error>(endian: Endianness, nbytes: usize) -> impl Parser<Input, u64, Error>
+where
+    Input: StreamIsPartial + Stream<Token = u8>,
+    Error: ParserError<Input>,
+{
+    trace("uint_var", move |input: &mut Input| {
+        if !(1..=8).contains(&nbytes) {
+            return Err(ErrMode::from_error_kind(input, ErrorKind::Verify));
+        }
+        if nbytes == 1 {
+            return be_u8.map(u64::from).parse_next(input);
+        }
+        match endian {
+            Endianness::Big => be_uint(input, nbytes),
+            Endianness::Little => le_uint(input, nbytes),
+            #[cfg(target_endian = "big")]
+            Endianness::Native => be_uint(input, nbytes),
+            #[cfg(target_endian = "little")]
+            Endianness::Native => le_uint(input, nbytes),
+        }
+    })
+}
+
 /// Recognizes a signed 1 byte integer
 ///
 /// <div class="warning">
@@ -2428,6 +2510,99 @@ where
     })
 }
 
+/// Get a length-prefixed slice ([TLV](https://en.wikipedia.org/wiki/Type-length-value)), up to
+/// `max` tokens long
+///
+/// Like [`length_take`] but a length over `max` is an error. This bounds how much a hostile
+/// length field can make the caller buffer, as a [partial][crate::_topic::partial] parser will
+/// report the length is invalid rather than request more data.
+///
+/// *Complete version*: Returns an error if there is not enough input data.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there is not enough data.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed, stream::Partial};
+/// # use winnow::prelude::*;
+/// use winnow::Bytes;
+/// use winnow::binary::be_u16;
+/// use winnow::binary::take_at_most;
+///
+/// type Stream<'i> = Partial<&'i Bytes>;
+///
+/// fn stream(b: &[u8]) -> Stream<'_> {
+///     Partial::new(Bytes::new(b))
+/// }
+///
+/// fn parser(s: Stream<'_>) -> IResult<Stream<'_>, &[u8]> {
+///   take_at_most(1024, be_u16).parse_peek(s)
+/// }
+///
+/// assert_eq!(parser(stream(b"\x00\x03abcefg")), Ok((stream(&b"efg"[..]), &b"abc"[..])));
+/// assert_eq!(parser(stream(b"\x00\x03a")), Err(ErrMode::Incomplete(Needed::new(2))));
+/// assert_eq!(parser(stream(b"\xff\xffa")), Err(ErrMode::Backtrack(InputError::new(stream(&b"\xff\xffa"[..]), ErrorKind::Verify))));
+/// ```
+pub fn take_at_most<Input, Count, Error, CountParser>(
+    max: usize,
+    count: CountParser,
+) -> impl Parser<Input, <Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream,
+    Count: ToUsize,
+    CountParser: Parser<Input, Count, Error>,
+    Error: ParserError<Input>,
+{
+    let mut count = count.verify_map(move |count: Count| {
+        let count = count.to_usize();
+        (count <= max).then_some(count)
+    });
+    trace("take_at_most", move |i: &mut Input| {
+        let length = count.parse_next(i)?;
+
+        crate::token::take(length).parse_next(i)
+    })
+}
+
+/// Add a parsed offset to `base`, failing rather than overflowing
+///
+/// This is for offset or length fields that are relative to another position, where a hostile
+/// value could overflow a `usize` computation and panic.
+///
+/// On overflow, the input is left at the start of the offset field.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}};
+/// # use winnow::prelude::*;
+/// use winnow::binary::be_u32;
+/// use winnow::binary::checked_add_offset;
+///
+/// fn parser(s: &[u8]) -> IResult<&[u8], usize> {
+///   checked_add_offset(usize::MAX - 10, be_u32).parse_peek(s)
+/// }
+///
+/// assert_eq!(parser(&b"\x00\x00\x00\x0a"[..]), Ok((&b""[..], usize::MAX)));
+/// assert_eq!(parser(&b"\x00\x00\x00\x0b"[..]), Err(ErrMode::Backtrack(InputError::new(&b"\x00\x00\x00\x0b"[..], ErrorKind::Verify))));
+/// ```
+pub fn checked_add_offset<Input, Offset, Error, OffsetParser>(
+    base: usize,
+    offset: OffsetParser,
+) -> impl Parser<Input, usize, Error>
+where
+    Input: Stream,
+    Offset: ToUsize,
+    OffsetParser: Parser<Input, Offset, Error>,
+    Error: ParserError<Input>,
+{
+    trace(
+        "checked_add_offset",
+        offset.verify_map(move |offset: Offset| base.checked_add(offset.to_usize())),
+    )
+}
+
 /// Parse a length-prefixed slice ([TLV](https://en.wikipedia.org/wiki/Type-length-value))
 ///
 /// *Complete version*: Returns an error if there is not enough input data.
@@ -2483,6 +2658,210 @@ where
     })
 }
 
+/// Parse a length-prefixed slice ([TLV](https://en.wikipedia.org/wiki/Type-length-value)),
+/// requiring `parser` to consume all of it
+///
+/// Like [`length_and_then`] but leftover data in the slice is an [`ErrorKind::Eof`] error.
+/// `parser` cannot read past the end of the slice, which it sees as complete, so an
+/// [`ErrMode::Incomplete`] only ever means the slice itself is not yet available.
+///
+/// For a fixed size, use a constant `count` like [`empty.value(4)`][crate::combinator::empty].
+///
+/// *Complete version*: Returns an error if there is not enough input data.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there is not enough data.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed, stream::{Partial, StreamIsPartial}};
+/// # use winnow::prelude::*;
+/// use winnow::Bytes;
+/// use winnow::binary::be_u16;
+/// use winnow::binary::length_and_then_exact;
+///
+/// type Stream<'i> = Partial<&'i Bytes>;
+///
+/// fn stream(b: &[u8]) -> Stream<'_> {
+///     Partial::new(Bytes::new(b))
+/// }
+///
+/// fn complete_stream(b: &[u8]) -> Stream<'_> {
+///     let mut p = Partial::new(Bytes::new(b));
+///     let _ = p.complete();
+///     p
+/// }
+///
+/// fn parser(s: Stream<'_>) -> IResult<Stream<'_>, &[u8]> {
+///   length_and_then_exact(be_u16, "abc").parse_peek(s)
+/// }
+///
+/// assert_eq!(parser(stream(b"\x00\x03abcefg")), Ok((stream(&b"efg"[..]), &b"abc"[..])));
+/// assert_eq!(parser(stream(b"\x00\x04abcdefg")), Err(ErrMode::Backtrack(InputError::new(complete_stream(&b"d"[..]), ErrorKind::Eof))));
+/// assert_eq!(parser(stream(b"\x00\x02abcdefg")), Err(ErrMode::Backtrack(InputError::new(complete_stream(&b"ab"[..]), ErrorKind::Tag))));
+/// assert_eq!(parser(stream(b"\x00\x03a")), Err(ErrMode::Incomplete(Needed::new(2))));
+/// ```
+///
+/// With a fixed size:
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::binary::be_u16;
+/// use winnow::binary::length_and_then_exact;
+/// use winnow::combinator::empty;
+///
+/// fn record(i: &mut &[u8]) -> PResult<(u16, u16)> {
+///     length_and_then_exact(empty.value(4usize), (be_u16, be_u16)).parse_next(i)
+/// }
+///
+/// assert_eq!(record.parse(b"\x00\x01\x00\x02"), Ok((1, 2)));
+/// ```
+pub fn length_and_then_exact<Input, Output, Count, Error, CountParser, ParseNext>(
+    mut count: CountParser,
+    mut parser: ParseNext,
+) -> impl Parser<Input, Output, Error>
+where
+    Input: StreamIsPartial + Stream + UpdateSlice + Clone,
+    Count: ToUsize,
+    CountParser: Parser<Input, Count, Error>,
+    ParseNext: Parser<Input, Output, Error>,
+    Error: ParserError<Input>,
+{
+    trace("length_and_then_exact", move |i: &mut Input| {
+        let data = length_take(count.by_ref()).parse_next(i)?;
+        let mut data = Input::update_slice(i.clone(), data);
+        let _ = data.complete();
+        let o = terminated(parser.by_ref(), eof)
+            .complete_err()
+            .parse_next(&mut data)?;
+        Ok(o)
+    })
+}
+
+/// Parse a type-length-value record ([TLV](https://en.wikipedia.org/wiki/Type-length-value)),
+/// choosing the value parser by the record's tag
+///
+/// After `tag` and `count`, `select` is given the tag:
+/// - `Some(parser)` parses the value, confined to the declared length like [`length_and_then`],
+///   producing [`Tlv::Known`]
+/// - `None` captures the value as a raw slice, producing [`Tlv::Unknown`].  To reject unknown
+///   tags instead, return a parser like [`fail`][crate::combinator::fail].
+///
+/// For a sequence of records, combine with [`repeat`][crate::combinator::repeat].
+///
+/// *Complete version*: Returns an error if there is not enough input data.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there is not enough data.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::{ErrMode, ErrorKind, InputError};
+/// use winnow::binary::be_u16;
+/// use winnow::binary::tlv;
+/// use winnow::binary::u8;
+/// use winnow::binary::Tlv;
+/// use winnow::combinator::rest;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Field<'i> {
+///     Name(&'i str),
+///     Port(u16),
+/// }
+///
+/// type Record<'i> = Tlv<u8, Field<'i>, &'i [u8]>;
+///
+/// fn record<'i>(i: &mut &'i [u8]) -> PResult<Record<'i>, InputError<&'i [u8]>> {
+///     fn name<'i>(i: &mut &'i [u8]) -> PResult<Field<'i>, InputError<&'i [u8]>> {
+///         rest.try_map(std::str::from_utf8).map(Field::Name).parse_next(i)
+///     }
+///     fn port<'i>(i: &mut &'i [u8]) -> PResult<Field<'i>, InputError<&'i [u8]>> {
+///         be_u16.map(Field::Port).parse_next(i)
+///     }
+///
+///     tlv(u8, u8, |tag: &u8| match tag {
+///         1 => Some(name as fn(&mut &'i [u8]) -> _),
+///         2 => Some(port),
+///         _ => None,
+///     })
+///     .parse_next(i)
+/// }
+///
+/// assert_eq!(
+///     record.parse_peek(&b"\x01\x03abc\x02"[..]),
+///     Ok((&b"\x02"[..], Tlv::Known { tag: 1, value: Field::Name("abc") }))
+/// );
+/// assert_eq!(
+///     record.parse_peek(&b"\x02\x02\x1f\x90"[..]),
+///     Ok((&b""[..], Tlv::Known { tag: 2, value: Field::Port(8080) }))
+/// );
+/// assert_eq!(
+///     record.parse_peek(&b"\x07\x02xy"[..]),
+///     Ok((&b""[..], Tlv::Unknown { tag: 7, data: &b"xy"[..] }))
+/// );
+/// assert_eq!(
+///     record.parse_peek(&b"\x02\x01\x1f"[..]),
+///     Err(ErrMode::Backtrack(InputError::new(&b"\x1f"[..], ErrorKind::Slice)))
+/// );
+/// ```
+pub fn tlv<Input, Tag, Count, Value, Error, TagParser, CountParser, Select, ParseNext>(
+    mut tag: TagParser,
+    mut count: CountParser,
+    mut select: Select,
+) -> impl Parser<Input, Tlv<Tag, Value, <Input as Stream>::Slice>, Error>
+where
+    Input: StreamIsPartial + Stream + UpdateSlice + Clone,
+    Count: ToUsize,
+    TagParser: Parser<Input, Tag, Error>,
+    CountParser: Parser<Input, Count, Error>,
+    Select: FnMut(&Tag) -> Option<ParseNext>,
+    ParseNext: Parser<Input, Value, Error>,
+    Error: ParserError<Input>,
+{
+    trace("tlv", move |i: &mut Input| {
+        let tag = tag.parse_next(i)?;
+        let data = length_take(count.by_ref()).parse_next(i)?;
+        match select(&tag) {
+            Some(mut parser) => {
+                let mut data = Input::update_slice(i.clone(), data);
+                let _ = data.complete();
+                let value = parser.by_ref().complete_err().parse_next(&mut data)?;
+                Ok(Tlv::Known { tag, value })
+            }
+            None => Ok(Tlv::Unknown { tag, data }),
+        }
+    })
+}
+
+/// A type-length-value record, see [`tlv`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Tlv<Tag, Value, Data> {
+    /// The tag had a value parser
+    Known {
+        /// The record's type
+        tag: Tag,
+        /// The parsed value
+        value: Value,
+    },
+    /// The tag had no value parser
+    Unknown {
+        /// The record's type
+        tag: Tag,
+        /// The raw value
+        data: Data,
+    },
+}
+
+impl<Tag, Value, Data> Tlv<Tag, Value, Data> {
+    /// The record's type
+    pub fn tag(&self) -> &Tag {
+        match self {
+            Self::Known { tag, .. } | Self::Unknown { tag, .. } => tag,
+        }
+    }
+}
+
 /// [`Accumulate`] a length-prefixed sequence of values ([TLV](https://en.wikipedia.org/wiki/Type-length-value))
 ///
 /// If the length represents token counts, see instead [`length_take`]
@@ -2533,3 +2912,429 @@ where
         repeat(n, parser.by_ref()).parse_next(i)
     })
 }
+
+/// Parse a region with `parser`, then verify it against a trailing checksum
+///
+/// The bytes consumed by `parser` are passed to `checksum`, whose result must equal the value
+/// parsed by `expected` right after the region.  A mismatch is an [`ErrorKind::Verify`] error
+/// positioned at the start of the region.
+///
+/// To include a header in the checksum, parse it as part of `parser`.  For text lines ending in
+/// a hex checksum, see [`line_with_checksum`][crate::ascii::line_with_checksum].
+///
+/// *Complete version*: Returns an error if there is not enough input data.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there is not enough data.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::{ErrMode, ErrorKind, InputError};
+/// use winnow::binary::be_u16;
+/// use winnow::binary::checksummed;
+/// use winnow::binary::length_take;
+/// use winnow::binary::u8;
+///
+/// fn sum(bytes: &[u8]) -> u16 {
+///     bytes.iter().fold(0u16, |sum, b| sum.wrapping_add(u16::from(*b)))
+/// }
+///
+/// fn packet<'i>(i: &mut &'i [u8]) -> PResult<&'i [u8], InputError<&'i [u8]>> {
+///     checksummed(length_take(u8), sum, be_u16).parse_next(i)
+/// }
+///
+/// assert_eq!(
+///     packet.parse_peek(&b"\x02ab\x00\xc5rest"[..]),
+///     Ok((&b"rest"[..], &b"ab"[..]))
+/// );
+/// assert_eq!(
+///     packet.parse_peek(&b"\x02ab\x00\xc6rest"[..]),
+///     Err(ErrMode::Backtrack(InputError::new(&b"\x02ab\x00\xc6rest"[..], ErrorKind::Verify)))
+/// );
+/// ```
+pub fn checksummed<Input, Output, Sum, Error, ParseNext, Checksum, SumParser>(
+    mut parser: ParseNext,
+    mut checksum: Checksum,
+    mut expected: SumParser,
+) -> impl Parser<Input, Output, Error>
+where
+    Input: Stream,
+    <Input as Stream>::Slice: AsBytes,
+    Sum: PartialEq,
+    ParseNext: Parser<Input, Output, Error>,
+    Checksum: FnMut(&[u8]) -> Sum,
+    SumParser: Parser<Input, Sum, Error>,
+    Error: ParserError<Input>,
+{
+    trace("checksummed", move |input: &mut Input| {
+        let start = input.checkpoint();
+        let (output, region) = parser.by_ref().with_taken().parse_next(input)?;
+        let actual = checksum(region.as_bytes());
+        let expected = expected.parse_next(input)?;
+        if actual != expected {
+            input.reset(&start);
+            return Err(ErrMode::from_error_kind(input, ErrorKind::Verify));
+        }
+        Ok(output)
+    })
+}
+
+/// Parse a value at an absolute offset from the start of parsing, like a pointer in a
+/// random-access format
+///
+/// `offset` is parsed from the input, then `parser` is run on a [fork][SeekableStream] of the
+/// input at that offset.  Afterwards, parsing continues after the offset field.
+///
+/// An offset past the end of the input is an [`ErrorKind::Eof`] error.  On error, the input is
+/// left at the start of the offset field.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::binary::at_offset;
+/// use winnow::binary::be_u8;
+/// use winnow::stream::Located;
+/// use winnow::token::take;
+///
+/// fn names<'i>(i: &mut Located<&'i [u8]>) -> PResult<(&'i [u8], &'i [u8])> {
+///     (at_offset(be_u8, take(3usize)), at_offset(be_u8, take(3usize))).parse_next(i)
+/// }
+///
+/// let mut input = Located::new(&b"\x05\x02abcdef"[..]);
+/// assert_eq!(names.parse_next(&mut input), Ok((&b"def"[..], &b"abc"[..])));
+///
+/// let mut input = Located::new(&b"\x0a\x02abcdef"[..]);
+/// assert!(names.parse_next(&mut input).is_err());
+/// ```
+#[doc(alias = "pointer")]
+#[doc(alias = "seek")]
+pub fn at_offset<Input, Output, Offset, Error, OffsetParser, ParseNext>(
+    mut offset: OffsetParser,
+    mut parser: ParseNext,
+) -> impl Parser<Input, Output, Error>
+where
+    Input: SeekableStream,
+    Offset: ToUsize,
+    OffsetParser: Parser<Input, Offset, Error>,
+    ParseNext: Parser<Input, Output, Error>,
+    Error: ParserError<Input>,
+{
+    trace("at_offset", move |input: &mut Input| {
+        let start = input.checkpoint();
+        let offset = offset.parse_next(input)?.to_usize();
+        let Some(mut target) = input.seek_to(offset) else {
+            input.reset(&start);
+            return Err(ErrMode::from_error_kind(input, ErrorKind::Eof));
+        };
+        parser.parse_next(&mut target).map_err(|e| {
+            input.reset(&start);
+            e
+        })
+    })
+}
+
+/// Resolve a parsed offset into a NUL-terminated string within a string pool
+///
+/// Many binary formats (e.g. ELF's `.strtab`) store strings once in a pool and refer to them by
+/// their offset into it. Parse the pool first, e.g. with [`length_take`], then use `pool_cstr`
+/// where an offset field is expected. The terminating NUL is not included in the output.
+///
+/// An offset past the end of the pool, a missing NUL, or invalid UTF-8 is reported as a
+/// [`PoolError`] through [`FromExternalError`], leaving the input at the offset field.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::binary::le_u16;
+/// use winnow::binary::length_take;
+/// use winnow::binary::pool_cstr;
+/// use winnow::combinator::repeat;
+///
+/// fn symbols<'i>(input: &mut &'i [u8]) -> PResult<Vec<&'i str>> {
+///     let pool = length_take(le_u16).parse_next(input)?;
+///     repeat(0.., pool_cstr(pool, le_u16)).parse_next(input)
+/// }
+///
+/// let data = b"\x0a\x00main\0exit\0\x05\x00\x00\x00";
+/// assert_eq!(symbols.parse(data), Ok(vec!["exit", "main"]));
+///
+/// // Offset 10 is past the last string
+/// let data = b"\x0a\x00main\0exit\0\x0a\x00";
+/// assert!(symbols.parse(data).is_err());
+/// ```
+pub fn pool_cstr<Input, Offset, Error, OffsetParser>(
+    pool: &[u8],
+    offset: OffsetParser,
+) -> impl Parser<Input, &str, Error>
+where
+    Input: Stream,
+    Offset: ToUsize,
+    OffsetParser: Parser<Input, Offset, Error>,
+    Error: ParserError<Input> + FromExternalError<Input, PoolError>,
+{
+    trace(
+        "pool_cstr",
+        offset.try_map(move |offset: Offset| {
+            let tail = pool
+                .get(offset.to_usize()..)
+                .ok_or(PoolError::OutOfBounds)?;
+            let len = tail
+                .iter()
+                .position(|b| *b == 0)
+                .ok_or(PoolError::Unterminated)?;
+            crate::lib::std::str::from_utf8(&tail[..len]).map_err(PoolError::Utf8)
+        }),
+    )
+}
+
+/// Resolve a parsed `(offset, length)` pair into a string within a string pool
+///
+/// Like [`pool_cstr`] but for pools whose strings are not NUL-terminated, with the length stored
+/// alongside the offset.
+///
+/// A range past the end of the pool or invalid UTF-8 is reported as a [`PoolError`] through
+/// [`FromExternalError`], leaving the input at the start of the `(offset, length)` fields.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::binary::be_u8;
+/// use winnow::binary::length_take;
+/// use winnow::binary::pool_str;
+///
+/// fn name<'i>(input: &mut &'i [u8]) -> PResult<(&'i str, &'i str)> {
+///     let pool = length_take(be_u8).parse_next(input)?;
+///     (pool_str(pool, (be_u8, be_u8)), pool_str(pool, (be_u8, be_u8))).parse_next(input)
+/// }
+///
+/// let data = b"\x0bAdaLovelace\x00\x03\x03\x08";
+/// assert_eq!(name.parse(data), Ok(("Ada", "Lovelace")));
+/// ```
+pub fn pool_str<Input, Offset, Len, Error, RangeParser>(
+    pool: &[u8],
+    range: RangeParser,
+) -> impl Parser<Input, &str, Error>
+where
+    Input: Stream,
+    Offset: ToUsize,
+    Len: ToUsize,
+    RangeParser: Parser<Input, (Offset, Len), Error>,
+    Error: ParserError<Input> + FromExternalError<Input, PoolError>,
+{
+    trace(
+        "pool_str",
+        range.try_map(move |(offset, len): (Offset, Len)| {
+            let offset = offset.to_usize();
+            let end = offset
+                .checked_add(len.to_usize())
+                .ok_or(PoolError::OutOfBounds)?;
+            let bytes = pool.get(offset..end).ok_or(PoolError::OutOfBounds)?;
+            crate::lib::std::str::from_utf8(bytes).map_err(PoolError::Utf8)
+        }),
+    )
+}
+
+/// Skip to the last occurrence of `pattern` within the final `window` bytes of the input
+///
+/// This is for formats with a trailer at the end of the input, like ZIP's "end of central
+/// directory" record, which can be followed by a variable-length comment.
+/// The skipped bytes are returned and the input is left at the start of `pattern`.
+///
+/// `pattern` must lie entirely within the last `window` bytes, bounding how much is scanned.
+///
+/// *[Partial version][crate::_topic::partial]*: will return `Err(winnow::error::ErrMode::Incomplete(Needed::Unknown))` as the end of the input is not known.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::binary::find_last;
+/// use winnow::binary::le_u16;
+/// use winnow::combinator::preceded;
+///
+/// // The comment length trailing a ZIP's "end of central directory" record
+/// fn comment_len(i: &mut &[u8]) -> PResult<u16> {
+///     find_last(b"PK\x05\x06", 22 + u16::MAX as usize).parse_next(i)?;
+///     preceded((b"PK\x05\x06", &[0; 16]), le_u16).parse_next(i)
+/// }
+///
+/// let mut data = b"PK\x05\x06 not the trailer ".to_vec();
+/// data.extend_from_slice(b"PK\x05\x06");
+/// data.extend_from_slice(&[0; 16]);
+/// data.extend_from_slice(b"\x03\x00abc");
+/// assert_eq!(comment_len.parse_peek(&data[..]), Ok((&b"abc"[..], 3)));
+/// assert!(comment_len.parse_peek(&data[..22]).is_err());
+/// ```
+pub fn find_last<Input, Pattern, Error>(
+    pattern: Pattern,
+    window: usize,
+) -> impl Parser<Input, <Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream<Token = u8> + AsBytes,
+    Pattern: AsRef<[u8]>,
+    Error: ParserError<Input>,
+{
+    trace("find_last", move |input: &mut Input| {
+        if input.is_partial() {
+            return Err(ErrMode::Incomplete(Needed::Unknown));
+        }
+        let bytes = input.as_bytes();
+        let tail = bytes.len().saturating_sub(window);
+        match memrmem(&bytes[tail..], pattern.as_ref()) {
+            Some(offset) => Ok(input.next_slice(tail + offset)),
+            None => Err(ErrMode::from_error_kind(input, ErrorKind::Slice)),
+        }
+    })
+}
+
+/// Parse a format version, then the body with a parser chosen by the version
+///
+/// This formalizes the common "header version selects layout" pattern.  `select` acts as the
+/// registry of layouts, like a `match` or a lookup table, returning `None` for versions with
+/// no layout.  Those fail with an [`UnsupportedVersion`] external error with
+/// [`ErrorKind::Fail`], after resetting the input to before the version.
+///
+/// When the body fails, the version is added to the error as a
+/// [`StrContext::Version`][crate::error::StrContext::Version] so reports say which layout was
+/// being parsed.
+///
+/// Returns the version and the body.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// # use winnow::error::StrContext;
+/// use winnow::binary::be_u16;
+/// use winnow::binary::be_u32;
+/// use winnow::binary::u8;
+/// use winnow::binary::versioned;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Header {
+///     len: u32,
+///     flags: u16,
+/// }
+///
+/// type Layout = fn(&mut &[u8]) -> PResult<Header>;
+///
+/// fn v1(i: &mut &[u8]) -> PResult<Header> {
+///     be_u16.map(|len| Header { len: len.into(), flags: 0 }).parse_next(i)
+/// }
+///
+/// fn v2(i: &mut &[u8]) -> PResult<Header> {
+///     (be_u32, be_u16).map(|(len, flags)| Header { len, flags }).parse_next(i)
+/// }
+///
+/// fn header(i: &mut &[u8]) -> PResult<(u8, Header)> {
+///     versioned(u8, |v: &u8| match v {
+///         1 => Some(v1 as Layout),
+///         2 => Some(v2),
+///         _ => None,
+///     })
+///     .parse_next(i)
+/// }
+///
+/// assert_eq!(
+///     header.parse_peek(&b"\x01\x00\x10"[..]),
+///     Ok((&b""[..], (1, Header { len: 16, flags: 0 })))
+/// );
+/// assert_eq!(
+///     header.parse_peek(&b"\x02\x00\x00\x00\x10\x00\x01"[..]),
+///     Ok((&b""[..], (2, Header { len: 16, flags: 1 })))
+/// );
+///
+/// let err = header.parse(&b"\x02\x00\x10"[..]).unwrap_err();
+/// assert!(err.inner().context().any(|c| *c == StrContext::Version(2)));
+///
+/// let err = header.parse(&b"\x03"[..]).unwrap_err();
+/// assert_eq!(err.offset(), 0);
+/// assert_eq!(err.inner().cause().unwrap().to_string(), "unsupported version 3");
+/// ```
+pub fn versioned<Input, Version, Output, Error, VersionParser, Select, ParseNext>(
+    mut version: VersionParser,
+    mut select: Select,
+) -> impl Parser<Input, (Version, Output), Error>
+where
+    Input: Stream,
+    Version: Copy + Into<u64>,
+    VersionParser: Parser<Input, Version, Error>,
+    Select: FnMut(&Version) -> Option<ParseNext>,
+    ParseNext: Parser<Input, Output, Error>,
+    Error: AddContext<Input, StrContext> + FromExternalError<Input, UnsupportedVersion>,
+{
+    trace("versioned", move |input: &mut Input| {
+        let start = input.checkpoint();
+        let v = version.parse_next(input)?;
+        match select(&v) {
+            Some(mut body) => {
+                let body_start = input.checkpoint();
+                let output = body.parse_next(input).map_err(|err| {
+                    err.add_context(input, &body_start, StrContext::Version(v.into()))
+                })?;
+                Ok((v, output))
+            }
+            None => {
+                input.reset(&start);
+                Err(ErrMode::from_external_error(
+                    input,
+                    ErrorKind::Fail,
+                    UnsupportedVersion(v.into()),
+                ))
+            }
+        }
+    })
+}
+
+/// A version that [`versioned`] has no layout for
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct UnsupportedVersion(pub u64);
+
+impl crate::lib::std::fmt::Display for UnsupportedVersion {
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        write!(f, "unsupported version {}", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnsupportedVersion {}
+
+/// Failure to resolve a string in a pool, see [`pool_cstr`] and [`pool_str`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PoolError {
+    /// The string starts or ends past the end of the pool
+    OutOfBounds,
+    /// No NUL was found between the offset and the end of the pool
+    Unterminated,
+    /// The string is not valid UTF-8
+    Utf8(crate::lib::std::str::Utf8Error),
+}
+
+impl crate::lib::std::fmt::Display for PoolError {
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        match self {
+            Self::OutOfBounds => "string is out of the pool's bounds".fmt(f),
+            Self::Unterminated => "string is missing a NUL terminator".fmt(f),
+            Self::Utf8(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PoolError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Utf8(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -431,6 +431,210 @@ mod complete {
             Ok((&b""[..], 36_028_874_334_732_032_i64))
         );
     }
+
+    #[test]
+    fn uint_var_test() {
+        use crate::binary::Endianness;
+
+        let bytes = &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09][..];
+        for nbytes in 1..=8 {
+            let be = bytes[..nbytes]
+                .iter()
+                .fold(0u64, |acc, b| (acc << 8) | u64::from(*b));
+            let le = bytes[..nbytes]
+                .iter()
+                .rev()
+                .fold(0u64, |acc, b| (acc << 8) | u64::from(*b));
+            assert_parse!(
+                uint_var(Endianness::Big, nbytes).parse_peek(bytes),
+                Ok((&bytes[nbytes..], be))
+            );
+            assert_parse!(
+                uint_var(Endianness::Little, nbytes).parse_peek(bytes),
+                Ok((&bytes[nbytes..], le))
+            );
+        }
+        assert_parse!(
+            uint_var(Endianness::Big, 8).parse_peek(&[0xff; 8][..]),
+            Ok((&b""[..], u64::MAX))
+        );
+        assert_parse!(
+            uint_var(Endianness::Big, 0).parse_peek(bytes),
+            Err(ErrMode::Backtrack(InputError::new(
+                bytes,
+                ErrorKind::Verify
+            )))
+        );
+        assert_parse!(
+            uint_var(Endianness::Big, 9).parse_peek(bytes),
+            Err(ErrMode::Backtrack(InputError::new(
+                bytes,
+                ErrorKind::Verify
+            )))
+        );
+        assert_parse!(
+            uint_var(Endianness::Little, 4).parse_peek(&bytes[..2]),
+            Err(ErrMode::Backtrack(InputError::new(
+                &bytes[..2],
+                ErrorKind::Slice
+            )))
+        );
+    }
+
+    #[test]
+    fn pool_cstr_test() {
+        const POOL: &[u8] = b"main\0exit\0\xff\0tail";
+        fn resolve(i: &[u8]) -> IResult<&[u8], &'static str> {
+            pool_cstr(POOL, be_u8).parse_peek(i)
+        }
+
+        assert_parse!(resolve(&[0, 1][..]), Ok((&[1][..], "main")));
+        assert_parse!(resolve(&[5][..]), Ok((&b""[..], "exit")));
+        assert_parse!(resolve(&[9][..]), Ok((&b""[..], "")));
+        assert_parse!(
+            resolve(&[30][..]),
+            Err(ErrMode::Backtrack(error_position!(
+                &&[30][..],
+                ErrorKind::Verify
+            )))
+        );
+        assert_parse!(
+            resolve(&[12][..]),
+            Err(ErrMode::Backtrack(error_position!(
+                &&[12][..],
+                ErrorKind::Verify
+            )))
+        );
+        assert_parse!(
+            resolve(&[10][..]),
+            Err(ErrMode::Backtrack(error_position!(
+                &&[10][..],
+                ErrorKind::Verify
+            )))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn pool_error_cause() {
+        use crate::error::ContextError;
+
+        const POOL: &[u8] = b"main\0\xff\0tail";
+        fn cause(offset: u8) -> Option<PoolError> {
+            let input = [offset];
+            let err = pool_cstr::<_, _, ContextError, _>(POOL, be_u8)
+                .parse(&input[..])
+                .unwrap_err();
+            err.inner()
+                .cause()
+                .and_then(|e| e.downcast_ref::<PoolError>())
+                .copied()
+        }
+
+        assert_eq!(cause(20), Some(PoolError::OutOfBounds));
+        assert_eq!(cause(7), Some(PoolError::Unterminated));
+        assert!(matches!(cause(5), Some(PoolError::Utf8(_))));
+    }
+
+    #[test]
+    fn pool_str_test() {
+        const POOL: &[u8] = b"AdaLovelace\xff";
+        fn resolve(i: &[u8]) -> IResult<&[u8], &'static str> {
+            pool_str(POOL, (be_u8, be_u8)).parse_peek(i)
+        }
+
+        assert_parse!(resolve(&[0, 3][..]), Ok((&b""[..], "Ada")));
+        assert_parse!(resolve(&[3, 8, 1][..]), Ok((&[1][..], "Lovelace")));
+        assert_parse!(resolve(&[11, 0][..]), Ok((&b""[..], "")));
+        assert_parse!(
+            resolve(&[3, 9][..]),
+            Err(ErrMode::Backtrack(error_position!(
+                &&[3, 9][..],
+                ErrorKind::Verify
+            )))
+        );
+        assert_parse!(
+            resolve(&[10, 5][..]),
+            Err(ErrMode::Backtrack(error_position!(
+                &&[10, 5][..],
+                ErrorKind::Verify
+            )))
+        );
+    }
+
+    #[test]
+    fn checked_add_offset_test() {
+        fn end(i: &[u8]) -> IResult<&[u8], usize> {
+            checked_add_offset(usize::MAX - 1, be_u8).parse_peek(i)
+        }
+
+        assert_parse!(end(&[0, 2][..]), Ok((&[2][..], usize::MAX - 1)));
+        assert_parse!(end(&[1][..]), Ok((&b""[..], usize::MAX)));
+        assert_parse!(
+            end(&[2][..]),
+            Err(ErrMode::Backtrack(error_position!(
+                &&[2][..],
+                ErrorKind::Verify
+            )))
+        );
+    }
+
+    #[test]
+    fn find_last_test() {
+        fn trailer(i: &[u8]) -> IResult<&[u8], &[u8]> {
+            find_last(b"PK", 6).parse_peek(i)
+        }
+
+        assert_parse!(trailer(&b"PKabPKcd"[..]), Ok((&b"PKcd"[..], &b"PKab"[..])));
+        assert_parse!(trailer(&b"PKabPK"[..]), Ok((&b"PK"[..], &b"PKab"[..])));
+        assert_parse!(
+            trailer(&b"PKabcdefg"[..]),
+            Err(ErrMode::Backtrack(error_position!(
+                &&b"PKabcdefg"[..],
+                ErrorKind::Slice
+            )))
+        );
+        assert_parse!(
+            trailer(&b"P"[..]),
+            Err(ErrMode::Backtrack(error_position!(
+                &&b"P"[..],
+                ErrorKind::Slice
+            )))
+        );
+    }
+
+    #[test]
+    fn at_offset_test() {
+        use crate::stream::Located;
+        use crate::token::take;
+
+        fn pointer(i: Located<&[u8]>) -> IResult<Located<&[u8]>, &[u8]> {
+            at_offset(be_u8, take(2usize)).parse_peek(i)
+        }
+
+        let input = Located::new(&b"\x03\x01abcd"[..]);
+        let (rest, value) = pointer(input).unwrap();
+        assert_eq!(value, &b"bc"[..]);
+        assert_eq!(*rest, &b"\x01abcd"[..]);
+
+        let (_, value) = pointer(rest).unwrap();
+        assert_eq!(value, &b"\x01a"[..]);
+
+        let input = Located::new(&b"\x06ab"[..]);
+        assert_parse!(
+            pointer(input),
+            Err(ErrMode::Backtrack(error_position!(&input, ErrorKind::Eof)))
+        );
+
+        let input = Located::new(&b"\x02ab"[..]);
+        assert_parse!(
+            pointer(input),
+            Err(ErrMode::Backtrack(error_position!(
+                &input.seek_to(2).unwrap(),
+                ErrorKind::Slice
+            )))
+        );
+    }
 }
 
 mod partial {
@@ -1345,4 +1549,287 @@ mod partial {
             Ok((Partial::new(&i4[4..]), (5, 6)))
         );
     }
+
+    #[test]
+    fn tlv_test() {
+        fn record(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, Tlv<u8, u16, &[u8]>> {
+            tlv(be_u8, be_u8, |tag: &u8| (*tag == 1).then_some(be_u16)).parse_peek(i)
+        }
+
+        let i = [1, 2, 5, 6, 9];
+        assert_eq!(
+            record(Partial::new(&i)),
+            Ok((
+                Partial::new(&i[4..]),
+                Tlv::Known {
+                    tag: 1,
+                    value: 1286
+                }
+            ))
+        );
+
+        let i = [4, 3, 5, 6, 7];
+        let res = record(Partial::new(&i));
+        assert_eq!(res.as_ref().map(|(_, r)| *r.tag()), Ok(4));
+        assert_eq!(
+            res,
+            Ok((
+                Partial::new(&i[5..]),
+                Tlv::Unknown {
+                    tag: 4,
+                    data: &i[2..5]
+                }
+            ))
+        );
+
+        let i = [1, 1, 5];
+        let mut short = Partial::new(&i[2..]);
+        let _ = short.complete();
+        assert_eq!(
+            record(Partial::new(&i)),
+            Err(ErrMode::Backtrack(error_position!(
+                &short,
+                ErrorKind::Slice
+            )))
+        );
+
+        let i = [4, 3, 5];
+        assert_eq!(
+            record(Partial::new(&i)),
+            Err(ErrMode::Incomplete(Needed::new(2)))
+        );
+        assert_eq!(
+            record(Partial::new(&i[..1])),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+    }
+
+    #[test]
+    fn checksummed_test() {
+        fn xor(bytes: &[u8]) -> u8 {
+            bytes.iter().fold(0, |sum, b| sum ^ b)
+        }
+
+        fn record(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, u16> {
+            checksummed(
+                (be_u8, be_u8).map(|(a, b)| u16::from(a) + u16::from(b)),
+                xor,
+                be_u8,
+            )
+            .parse_peek(i)
+        }
+
+        let i = [1, 2, 3, 4];
+        assert_eq!(record(Partial::new(&i)), Ok((Partial::new(&i[3..]), 3)));
+
+        let i = [1, 2, 4, 4];
+        assert_eq!(
+            record(Partial::new(&i)),
+            Err(ErrMode::Backtrack(error_position!(
+                &Partial::new(&i[..]),
+                ErrorKind::Verify
+            )))
+        );
+
+        let i = [1, 2];
+        assert_eq!(
+            record(Partial::new(&i)),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+    }
+
+    #[test]
+    fn versioned_test() {
+        use crate::error::ContextError;
+
+        fn header(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, (u8, u16)> {
+            versioned(be_u8, |v: &u8| (*v == 1).then_some(be_u16)).parse_peek(i)
+        }
+
+        let i = [1, 0, 2, 3];
+        assert_eq!(
+            header(Partial::new(&i)),
+            Ok((Partial::new(&i[3..]), (1, 2)))
+        );
+
+        let i = [2, 0, 2];
+        assert_eq!(
+            header(Partial::new(&i)),
+            Err(ErrMode::Backtrack(error_position!(
+                &Partial::new(&i[..]),
+                ErrorKind::Fail
+            )))
+        );
+
+        let i = [1, 0];
+        assert_eq!(
+            header(Partial::new(&i)),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+
+        let err: ContextError = versioned(be_u8, |v: &u8| (*v == 1).then_some(be_u16))
+            .parse(&[1, 0][..])
+            .unwrap_err()
+            .into_inner();
+        #[cfg(feature = "alloc")]
+        assert_eq!(
+            crate::lib::std::string::ToString::to_string(&err),
+            "in version 1"
+        );
+    }
+
+    #[test]
+    fn length_and_then_exact_test() {
+        use crate::stream::StreamIsPartial;
+
+        fn record(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, u16> {
+            length_and_then_exact(be_u8, be_u16).parse_peek(i)
+        }
+
+        let i = [2, 5, 6, 3];
+        assert_eq!(record(Partial::new(&i)), Ok((Partial::new(&i[3..]), 1286)));
+
+        let i = [3, 5, 6, 3, 4];
+        let mut leftover = Partial::new(&i[3..4]);
+        let _ = leftover.complete();
+        assert_eq!(
+            record(Partial::new(&i)),
+            Err(ErrMode::Backtrack(error_position!(
+                &leftover,
+                ErrorKind::Eof
+            )))
+        );
+
+        let i = [1, 5, 6];
+        let mut short = Partial::new(&i[1..2]);
+        let _ = short.complete();
+        assert_eq!(
+            record(Partial::new(&i)),
+            Err(ErrMode::Backtrack(error_position!(
+                &short,
+                ErrorKind::Slice
+            )))
+        );
+
+        let i = [3, 5, 6];
+        assert_eq!(
+            record(Partial::new(&i)),
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        );
+    }
+
+    #[test]
+    fn find_last_test() {
+        fn trailer(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, &[u8]> {
+            find_last(b"PK", 6).parse_peek(i)
+        }
+
+        assert_eq!(
+            trailer(Partial::new(&b"PKabPKcd"[..])),
+            Err(ErrMode::Incomplete(Needed::Unknown))
+        );
+    }
+
+    #[test]
+    fn take_at_most_test() {
+        fn field(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, &[u8]> {
+            take_at_most(3, be_u8).parse_peek(i)
+        }
+
+        assert_eq!(
+            field(Partial::new(&[3, b'a', b'b', b'c', b'd'][..])),
+            Ok((Partial::new(&b"d"[..]), &b"abc"[..]))
+        );
+        assert_eq!(
+            field(Partial::new(&[3, b'a'][..])),
+            Err(ErrMode::Incomplete(Needed::new(2)))
+        );
+        assert_eq!(
+            field(Partial::new(&[0][..])),
+            Ok((Partial::new(&b""[..]), &b""[..]))
+        );
+        assert_eq!(
+            field(Partial::new(&[4, b'a'][..])),
+            Err(ErrMode::Backtrack(error_position!(
+                &Partial::new(&[4, b'a'][..]),
+                ErrorKind::Verify
+            )))
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod round_trip {
+    use super::*;
+    use crate::error::InputError;
+    use crate::lib::std::vec::Vec;
+
+    macro_rules! assert_round_trip(
+        ($writer: expr, $parser: expr, $value: expr, $bytes: expr) => {
+            let mut output = Vec::new();
+            $writer(&mut output, $value);
+            assert_eq!(output, &$bytes[..]);
+            let res: IResult<_, _, InputError<_>> = $parser.parse_peek(&output[..]);
+            assert_eq!(res, Ok((&b""[..], $value)));
+        };
+    );
+
+    #[test]
+    fn be_le_round_trip() {
+        assert_round_trip!(write::be_u16, be_u16, 0x0102, [0x01, 0x02]);
+        assert_round_trip!(write::le_u16, le_u16, 0x0102, [0x02, 0x01]);
+        assert_round_trip!(write::be_u24, be_u24, 0x010203, [0x01, 0x02, 0x03]);
+        assert_round_trip!(write::le_u24, le_u24, 0x010203, [0x03, 0x02, 0x01]);
+        assert_round_trip!(write::be_i24, be_i24, -2, [0xff, 0xff, 0xfe]);
+        assert_round_trip!(write::le_i24, le_i24, -2, [0xfe, 0xff, 0xff]);
+        assert_round_trip!(
+            write::le_i32,
+            le_i32,
+            -0x01020304,
+            (-0x01020304_i32).to_le_bytes()
+        );
+        assert_round_trip!(write::be_u128, be_u128, u128::MAX - 1, {
+            let mut bytes = [0xff; 16];
+            bytes[15] = 0xfe;
+            bytes
+        });
+        assert_round_trip!(write::be_f64, be_f64, 1.5, 1.5_f64.to_be_bytes());
+        assert_round_trip!(write::u8, u8, 0x80, [0x80]);
+        assert_round_trip!(write::i8, i8, -1, [0xff]);
+    }
+
+    #[test]
+    fn configurable_endianness() {
+        assert_round_trip!(
+            |o: &mut Vec<u8>, v| write::u32(o, Endianness::Big, v),
+            u32(Endianness::Big),
+            0x01020304,
+            [0x01, 0x02, 0x03, 0x04]
+        );
+        assert_round_trip!(
+            |o: &mut Vec<u8>, v| write::i16(o, Endianness::Little, v),
+            i16(Endianness::Little),
+            -2,
+            [0xfe, 0xff]
+        );
+        assert_round_trip!(
+            |o: &mut Vec<u8>, v| write::f32(o, Endianness::Native, v),
+            f32(Endianness::Native),
+            0.25,
+            0.25_f32.to_ne_bytes()
+        );
+    }
+
+    #[test]
+    fn length_data_round_trip() {
+        let mut output = Vec::new();
+        write::length_data(&mut output, write::le_u16, b"abc").unwrap();
+        assert_eq!(output, b"\x03\x00abc");
+        let res: IResult<_, _, InputError<_>> = length_take(le_u16).parse_peek(&output[..]);
+        assert_eq!(res, Ok((&b""[..], &b"abc"[..])));
+
+        let mut output = Vec::new();
+        assert!(write::length_data(&mut output, write::u8, &[0; 256]).is_err());
+        assert!(output.is_empty());
+    }
 }
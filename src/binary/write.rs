@@ -0,0 +1,209 @@
+//! Writers mirroring the [`binary`][super] parsers
+//!
+//! Each writer appends the encoding of a value to an [`Extend<u8>`] output, like a `Vec<u8>`, so
+//! that an encoder can be written alongside its decoder:
+//!
+//! ```rust
+//! # use winnow::prelude::*;
+//! # use winnow::error::ContextError;
+//! use winnow::binary;
+//! use winnow::binary::write;
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Header {
+//!     version: u8,
+//!     len: u32,
+//! }
+//!
+//! fn parse(input: &mut &[u8]) -> PResult<Header> {
+//!     let version = binary::be_u8(input)?;
+//!     let len = binary::be_u32(input)?;
+//!     Ok(Header { version, len })
+//! }
+//!
+//! fn emit(output: &mut Vec<u8>, header: &Header) {
+//!     write::be_u8(output, header.version);
+//!     write::be_u32(output, header.len);
+//! }
+//!
+//! let header = Header { version: 1, len: 0x0102_0304 };
+//! let mut output = Vec::new();
+//! emit(&mut output, &header);
+//! assert_eq!(output, b"\x01\x01\x02\x03\x04");
+//! assert_eq!(parse.parse(&output[..]).unwrap(), header);
+//! ```
+
+use super::Endianness;
+
+macro_rules! write_be_le {
+    ($(($be:ident, $le:ident, $ty:ty, $parse_be:ident, $parse_le:ident)),* $(,)?) => {
+        $(
+            #[doc = concat!("Write a big endian `", stringify!($ty), "`, the dual of [`", stringify!($parse_be), "`][super::", stringify!($parse_be), "]")]
+            #[inline(always)]
+            pub fn $be<Output>(output: &mut Output, value: $ty)
+            where
+                Output: Extend<u8>,
+            {
+                output.extend(value.to_be_bytes());
+            }
+
+            #[doc = concat!("Write a little endian `", stringify!($ty), "`, the dual of [`", stringify!($parse_le), "`][super::", stringify!($parse_le), "]")]
+            #[inline(always)]
+            pub fn $le<Output>(output: &mut Output, value: $ty)
+            where
+                Output: Extend<u8>,
+            {
+                output.extend(value.to_le_bytes());
+            }
+        )*
+    };
+}
+
+write_be_le! {
+    (be_u8, le_u8, u8, be_u8, le_u8),
+    (be_u16, le_u16, u16, be_u16, le_u16),
+    (be_u32, le_u32, u32, be_u32, le_u32),
+    (be_u64, le_u64, u64, be_u64, le_u64),
+    (be_u128, le_u128, u128, be_u128, le_u128),
+    (be_i8, le_i8, i8, be_i8, le_i8),
+    (be_i16, le_i16, i16, be_i16, le_i16),
+    (be_i32, le_i32, i32, be_i32, le_i32),
+    (be_i64, le_i64, i64, be_i64, le_i64),
+    (be_i128, le_i128, i128, be_i128, le_i128),
+    (be_f32, le_f32, f32, be_f32, le_f32),
+    (be_f64, le_f64, f64, be_f64, le_f64),
+}
+
+/// Write a big endian 3 byte unsigned integer, the dual of [`be_u24`][super::be_u24]
+///
+/// The most significant byte of `value` is dropped.
+#[inline(always)]
+pub fn be_u24<Output>(output: &mut Output, value: u32)
+where
+    Output: Extend<u8>,
+{
+    output.extend(value.to_be_bytes()[1..].iter().copied());
+}
+
+/// Write a little endian 3 byte unsigned integer, the dual of [`le_u24`][super::le_u24]
+///
+/// The most significant byte of `value` is dropped.
+#[inline(always)]
+pub fn le_u24<Output>(output: &mut Output, value: u32)
+where
+    Output: Extend<u8>,
+{
+    output.extend(value.to_le_bytes()[..3].iter().copied());
+}
+
+/// Write a big endian 3 byte signed integer, the dual of [`be_i24`][super::be_i24]
+///
+/// The most significant byte of `value` is dropped.
+#[inline(always)]
+pub fn be_i24<Output>(output: &mut Output, value: i32)
+where
+    Output: Extend<u8>,
+{
+    be_u24(output, value as u32);
+}
+
+/// Write a little endian 3 byte signed integer, the dual of [`le_i24`][super::le_i24]
+///
+/// The most significant byte of `value` is dropped.
+#[inline(always)]
+pub fn le_i24<Output>(output: &mut Output, value: i32)
+where
+    Output: Extend<u8>,
+{
+    le_u24(output, value as u32);
+}
+
+/// Write an unsigned 1 byte integer, the dual of [`u8`][super::u8]
+#[inline(always)]
+pub fn u8<Output>(output: &mut Output, value: u8)
+where
+    Output: Extend<u8>,
+{
+    output.extend([value]);
+}
+
+/// Write a signed 1 byte integer, the dual of [`i8`][super::i8]
+#[inline(always)]
+pub fn i8<Output>(output: &mut Output, value: i8)
+where
+    Output: Extend<u8>,
+{
+    output.extend(value.to_be_bytes());
+}
+
+macro_rules! write_endian {
+    ($(($name:ident, $ty:ty, $be:ident, $le:ident)),* $(,)?) => {
+        $(
+            #[doc = concat!("Write a `", stringify!($ty), "` with the given [`Endianness`], the dual of [`", stringify!($name), "`][super::", stringify!($name), "]")]
+            #[inline(always)]
+            pub fn $name<Output>(output: &mut Output, endian: Endianness, value: $ty)
+            where
+                Output: Extend<u8>,
+            {
+                match endian {
+                    Endianness::Big => $be(output, value),
+                    Endianness::Little => $le(output, value),
+                    #[cfg(target_endian = "big")]
+                    Endianness::Native => $be(output, value),
+                    #[cfg(target_endian = "little")]
+                    Endianness::Native => $le(output, value),
+                }
+            }
+        )*
+    };
+}
+
+write_endian! {
+    (u16, u16, be_u16, le_u16),
+    (u24, u32, be_u24, le_u24),
+    (u32, u32, be_u32, le_u32),
+    (u64, u64, be_u64, le_u64),
+    (u128, u128, be_u128, le_u128),
+    (i16, i16, be_i16, le_i16),
+    (i24, i32, be_i24, le_i24),
+    (i32, i32, be_i32, le_i32),
+    (i64, i64, be_i64, le_i64),
+    (i128, i128, be_i128, le_i128),
+    (f32, f32, be_f32, le_f32),
+    (f64, f64, be_f64, le_f64),
+}
+
+/// Write a length-prefixed slice ([TLV](https://en.wikipedia.org/wiki/Type-length-value)), the
+/// dual of [`length_take`][super::length_take]
+///
+/// The length is written with `count`, failing if it does not fit in its type.
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::binary::write::be_u8;
+/// use winnow::binary::write::be_u16;
+/// use winnow::binary::write::length_data;
+///
+/// let mut output = Vec::new();
+/// length_data(&mut output, be_u16, b"abc").unwrap();
+/// assert_eq!(output, b"\x00\x03abc");
+///
+/// assert!(length_data(&mut output, be_u8, &[0; 256]).is_err());
+/// ```
+#[doc(alias = "length_take")]
+pub fn length_data<Output, Count, CountWriter>(
+    output: &mut Output,
+    count: CountWriter,
+    data: &[u8],
+) -> Result<(), <Count as TryFrom<usize>>::Error>
+where
+    Output: Extend<u8>,
+    Count: TryFrom<usize>,
+    CountWriter: FnOnce(&mut Output, Count),
+{
+    let len = Count::try_from(data.len())?;
+    count(output, len);
+    output.extend(data.iter().copied());
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use crate::combinator::trace;
-use crate::error::{ErrMode, ErrorKind, ParserError};
+use crate::error::{ErrMode, ErrorKind, FromExternalError, ParserError, UnmatchedKey};
 use crate::stream::Stream;
 use crate::*;
 
@@ -21,7 +21,8 @@ pub trait Alt<I, O, E> {
 ///
 /// For tight control over the error when no match is found, add a final case using [`fail`][crate::combinator::fail].
 /// Alternatively, with a [custom error type][crate::_topic::error], it is possible to track all
-/// errors or return the error of the parser that went the farthest in the input data.
+/// errors, or see [`alt_furthest`] to return the error of the parser that went the farthest in the
+/// input data.
 ///
 /// When the alternative cases have unique prefixes, [`dispatch`] can offer better performance.
 ///
@@ -47,7 +48,26 @@ pub trait Alt<I, O, E> {
 /// assert_eq!(parser(" "), Err(ErrMode::Backtrack(InputError::new(" ", ErrorKind::Slice))));
 /// # }
 /// ```
+///
+/// Besides tuples, `alt` accepts arrays and `&mut` slices of the same parser type.  For a list of
+/// alternatives only known at runtime, like from plugins, collect them as boxed parsers:
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::{alpha1, digit1};
+/// use winnow::combinator::alt;
+///
+/// type Alternative<'i> = Box<dyn Parser<&'i str, &'i str, ContextError> + 'i>;
+///
+/// let mut alternatives: Vec<Alternative<'_>> = vec![Box::new(alpha1)];
+/// alternatives.push(Box::new(digit1));
+///
+/// let mut parser = alt(&mut alternatives[..]);
+/// assert_eq!(parser.parse_peek("123;"), Ok((";", "123")));
+/// assert!(parser.parse_peek(";").is_err());
+/// ```
 #[doc(alias = "choice")]
+#[doc(alias = "alt_dyn")]
 pub fn alt<Input: Stream, Output, Error, Alternatives>(
     mut alternatives: Alternatives,
 ) -> impl Parser<Input, Output, Error>
@@ -58,6 +78,166 @@ where
     trace("alt", move |i: &mut Input| alternatives.choice(i))
 }
 
+/// Helper trait for the [`alt_lint()`] and [`alt_furthest()`] combinators.
+///
+/// This trait is implemented for tuples of up to 22 elements, arrays, and `&mut` slices
+pub trait AltLint<I, O, E> {
+    /// The number of alternatives
+    fn branch_count(&self) -> usize;
+    /// Parse with the alternative at `index`
+    fn parse_branch(&mut self, index: usize, input: &mut I) -> PResult<O, E>;
+}
+
+/// Like [`alt()`], but panic when an alternative is shadowed by an earlier one
+///
+/// Every alternative is tried on every input.  When a later alternative would have consumed
+/// more input than the alternative `alt` picks, like `"<="` after `"<"`, the later one can
+/// never match there and this panics, naming both.  Use this in place of `alt` while
+/// running tests to catch mis-ordered alternatives, then switch back to `alt`.
+///
+/// # Panic
+///
+/// Panics when an alternative is shadowed
+///
+/// # Example
+///
+/// ```rust,should_panic
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::combinator::alt_lint;
+///
+/// let mut op = alt_lint::<_, _, ContextError, _>(("<", "<=", ">"));
+/// assert_eq!(op.parse_peek("> 1"), Ok((" 1", ">")));
+/// // panics: `alt` branch 1 is shadowed by branch 0
+/// let _ = op.parse_peek("<= 1");
+/// ```
+pub fn alt_lint<Input: Stream, Output, Error, Alternatives>(
+    mut alternatives: Alternatives,
+) -> impl Parser<Input, Output, Error>
+where
+    Alternatives: AltLint<Input, Output, Error>,
+    Error: ParserError<Input>,
+{
+    trace("alt_lint", move |input: &mut Input| {
+        let start = input.checkpoint();
+        let mut winner: Option<(usize, Output, <Input as Stream>::Checkpoint, usize)> = None;
+        let mut error: Option<Error> = None;
+        for index in 0..alternatives.branch_count() {
+            input.reset(&start);
+            match alternatives.parse_branch(index, input) {
+                Ok(output) => match &winner {
+                    None => {
+                        winner = Some((index, output, input.checkpoint(), input.eof_offset()));
+                    }
+                    Some((first, _, _, remaining)) => {
+                        if input.eof_offset() < *remaining {
+                            input.reset(&start);
+                            panic!(
+                                "`alt` branch {index} is shadowed by branch {first}, which matches less of {input:?}"
+                            );
+                        }
+                    }
+                },
+                Err(ErrMode::Backtrack(e)) => {
+                    if winner.is_none() {
+                        error = match error {
+                            Some(error) => Some(error.or(e)),
+                            None => Some(e),
+                        };
+                    }
+                }
+                Err(e) => {
+                    if winner.is_none() {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+
+        match (winner, error) {
+            (Some((_, output, end, _)), _) => {
+                input.reset(&end);
+                Ok(output)
+            }
+            (None, Some(e)) => Err(ErrMode::Backtrack(e.append(input, &start, ErrorKind::Alt))),
+            (None, None) => Err(ErrMode::assert(input, "`alt` needs at least one parser")),
+        }
+    })
+}
+
+/// Like [`alt()`], but report the error from the alternative that got furthest into the input
+///
+/// `alt` reports the error of the last alternative, which is rarely the helpful one when an
+/// earlier alternative matched a prefix before failing deep inside.  Instead, the input is left
+/// where the furthest failure happened and its error is returned.  When several alternatives fail
+/// at that same point, their errors are combined with [`ParserError::or`], like every
+/// [`StrContext::Expected`][crate::error::StrContext::Expected] for a
+/// [`ContextError`][crate::error::ContextError].
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// use winnow::ascii::digit1;
+/// use winnow::combinator::alt;
+/// use winnow::combinator::alt_furthest;
+/// use winnow::combinator::preceded;
+///
+/// fn negative<'i>(input: &mut &'i str) -> PResult<&'i str> {
+///     preceded('-', digit1.expected("digits")).take().parse_next(input)
+/// }
+///
+/// fn number<'i>(input: &mut &'i str) -> PResult<&'i str> {
+///     digit1.expected("number").parse_next(input)
+/// }
+///
+/// let error = alt_furthest((negative, number)).parse("-?").unwrap_err();
+/// assert_eq!(error.offset(), 1);
+/// assert_eq!(error.inner().to_string(), "expected digits");
+///
+/// let error = alt((negative, number)).parse("-?").unwrap_err();
+/// assert_eq!(error.offset(), 0);
+/// assert_eq!(error.inner().to_string(), "expected number");
+/// ```
+pub fn alt_furthest<Input: Stream, Output, Error, Alternatives>(
+    mut alternatives: Alternatives,
+) -> impl Parser<Input, Output, Error>
+where
+    Alternatives: AltLint<Input, Output, Error>,
+    Error: ParserError<Input>,
+{
+    trace("alt_furthest", move |input: &mut Input| {
+        let start = input.checkpoint();
+        let mut furthest: Option<(Error, <Input as Stream>::Checkpoint, usize)> = None;
+        for index in 0..alternatives.branch_count() {
+            input.reset(&start);
+            match alternatives.parse_branch(index, input) {
+                Err(ErrMode::Backtrack(e)) => {
+                    let remaining = input.eof_offset();
+                    furthest = match furthest {
+                        Some((error, checkpoint, furthest)) if furthest < remaining => {
+                            Some((error, checkpoint, furthest))
+                        }
+                        Some((error, checkpoint, furthest)) if furthest == remaining => {
+                            Some((error.or(e), checkpoint, furthest))
+                        }
+                        _ => Some((e, input.checkpoint(), remaining)),
+                    };
+                }
+                res => return res,
+            }
+        }
+
+        match furthest {
+            Some((e, checkpoint, _)) => {
+                input.reset(&checkpoint);
+                Err(ErrMode::Backtrack(e.append(input, &start, ErrorKind::Alt)))
+            }
+            None => Err(ErrMode::assert(input, "`alt` needs at least one parser")),
+        }
+    })
+}
+
 /// Helper trait for the [`permutation()`] combinator.
 ///
 /// This trait is implemented for tuples of up to 21 elements
@@ -99,7 +279,9 @@ pub trait Permutation<I, O, E> {
 /// ```
 ///
 /// The parsers are applied greedily: if there are multiple unapplied parsers
-/// that could parse the next slice of input, the first one is used.
+/// that could parse the next slice of input, the first one is used.  This is guaranteed, so the
+/// assignment only depends on the order of the parsers in the tuple.  To instead fail when it
+/// matters, see [`permutation_unambiguous`].
 /// ```rust
 /// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}};
 /// # use winnow::prelude::*;
@@ -124,6 +306,140 @@ pub fn permutation<I: Stream, O, E: ParserError<I>, List: Permutation<I, O, E>>(
     trace("permutation", move |i: &mut I| l.permutation(i))
 }
 
+/// Helper trait for the [`permutation_unambiguous()`] combinator.
+///
+/// This trait is implemented for tuples of up to 21 elements
+pub trait UnambiguousPermutation<I, O, E> {
+    /// Tries to apply all parsers in the tuple in various orders until all of them succeed,
+    /// failing if more than one could be applied at the same point
+    fn permutation_unambiguous(&mut self, input: &mut I) -> PResult<O, E>;
+}
+
+/// Applies a list of parsers in any order, failing if the order is ambiguous
+///
+/// Like [`permutation`], except that every remaining parser is tried at each point of the input.
+/// If more than one succeeds, which one applies would depend on the order of the tuple, so this
+/// backtracks with [`ErrorKind::Verify`] at that point instead.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}};
+/// # use winnow::prelude::*;
+/// use winnow::ascii::{alpha1, digit1};
+/// use winnow::combinator::permutation_unambiguous;
+/// use winnow::token::any;
+///
+/// fn parser(input: &str) -> IResult<&str, (&str, &str)> {
+///   permutation_unambiguous((alpha1, digit1)).parse_peek(input)
+/// }
+///
+/// assert_eq!(parser("abc123"), Ok(("", ("abc", "123"))));
+/// assert_eq!(parser("123abc"), Ok(("", ("abc", "123"))));
+///
+/// fn ambiguous(input: &str) -> IResult<&str, (char, char)> {
+///   permutation_unambiguous((any, 'a')).parse_peek(input)
+/// }
+///
+/// // `any` parses 'b', then only char('a') remains
+/// assert_eq!(ambiguous("ba"), Ok(("", ('b', 'a'))));
+///
+/// // both `any` and char('a') could parse the first 'a'
+/// assert_eq!(ambiguous("ab"), Err(ErrMode::Backtrack(InputError::new("ab", ErrorKind::Verify))));
+/// ```
+pub fn permutation_unambiguous<
+    I: Stream,
+    O,
+    E: ParserError<I>,
+    List: UnambiguousPermutation<I, O, E>,
+>(
+    mut l: List,
+) -> impl Parser<I, O, E> {
+    trace("permutation_unambiguous", move |i: &mut I| {
+        l.permutation_unambiguous(i)
+    })
+}
+
+/// `match` for parsers, choosing the branch at runtime
+///
+/// Like [`dispatch!`][crate::combinator::dispatch] except `branch` is a closure, like over a
+/// lookup table, giving the parser for the `key` parsed by `selector`.  When `branch` returns
+/// `None`, the input is reset to before the key and the error is created from an
+/// [`UnmatchedKey`] with [`ErrorKind::Fail`], naming the key for error reports.
+///
+/// [`ContextError`][crate::error::ContextError] captures the [`UnmatchedKey`] as its
+/// [cause][crate::error::ContextError::cause] when the key is `'static`.  For keys borrowed from
+/// the input, like keywords, [`map`][Parser::map] them to an owned value.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use std::collections::HashMap;
+/// use winnow::ascii::alpha1;
+/// use winnow::ascii::dec_uint;
+/// use winnow::combinator::dispatch_with;
+/// use winnow::combinator::preceded;
+///
+/// type Command = fn(&mut &str) -> PResult<u32>;
+///
+/// fn command<'t>(
+///     table: &'t HashMap<String, Command>,
+/// ) -> impl Parser<&'t str, u32, ContextError> + 't {
+///     dispatch_with(
+///         alpha1.map(|k: &str| k.to_owned()),
+///         move |key: &String| table.get(key).copied(),
+///     )
+/// }
+///
+/// fn double(i: &mut &str) -> PResult<u32> {
+///     preceded(' ', dec_uint).map(|n: u32| 2 * n).parse_next(i)
+/// }
+///
+/// fn zero(_: &mut &str) -> PResult<u32> {
+///     Ok(0)
+/// }
+///
+/// let mut table = HashMap::new();
+/// table.insert("double".to_owned(), double as Command);
+/// table.insert("zero".to_owned(), zero as Command);
+///
+/// assert_eq!(command(&table).parse_peek("double 21"), Ok(("", 42)));
+/// assert_eq!(command(&table).parse_peek("zero"), Ok(("", 0)));
+///
+/// let err = command(&table).parse("triple 3").unwrap_err();
+/// assert_eq!(err.offset(), 0);
+/// assert_eq!(err.inner().cause().unwrap().to_string(), "no branch for `\"triple\"`");
+/// ```
+pub fn dispatch_with<Input, Key, Output, Error, Selector, Branch, ParseNext>(
+    mut selector: Selector,
+    mut branch: Branch,
+) -> impl Parser<Input, Output, Error>
+where
+    Input: Stream,
+    Selector: Parser<Input, Key, Error>,
+    Branch: FnMut(&Key) -> Option<ParseNext>,
+    ParseNext: Parser<Input, Output, Error>,
+    Error: FromExternalError<Input, UnmatchedKey<Key>>,
+{
+    trace("dispatch_with", move |input: &mut Input| {
+        let start = input.checkpoint();
+        let key = selector.parse_next(input)?;
+        match branch(&key) {
+            Some(mut parser) => parser.parse_next(input),
+            None => {
+                input.reset(&start);
+                Err(ErrMode::from_external_error(
+                    input,
+                    ErrorKind::Fail,
+                    UnmatchedKey(key),
+                ))
+            }
+        }
+    })
+}
+
 impl<const N: usize, I: Stream, O, E: ParserError<I>, P: Parser<I, O, E>> Alt<I, O, E> for [P; N] {
     fn choice(&mut self, input: &mut I) -> PResult<O, E> {
         let mut error: Option<E> = None;
@@ -256,6 +572,57 @@ impl<I: Stream, O, E: ParserError<I>, A: Parser<I, O, E>> Alt<I, O, E> for (A,)
     }
 }
 
+impl<const N: usize, I, O, E, P: Parser<I, O, E>> AltLint<I, O, E> for [P; N] {
+    fn branch_count(&self) -> usize {
+        N
+    }
+    fn parse_branch(&mut self, index: usize, input: &mut I) -> PResult<O, E> {
+        self[index].parse_next(input)
+    }
+}
+
+impl<I, O, E, P: Parser<I, O, E>> AltLint<I, O, E> for &mut [P] {
+    fn branch_count(&self) -> usize {
+        self.len()
+    }
+    fn parse_branch(&mut self, index: usize, input: &mut I) -> PResult<O, E> {
+        self[index].parse_next(input)
+    }
+}
+
+macro_rules! alt_lint_trait(
+  ($($index:tt $id:ident)+) => (
+    alt_lint_trait!(__impl []; $($index $id)+);
+  );
+  (__impl [$($cindex:tt $current:ident)*]; $index:tt $head:ident $($rindex:tt $rest:ident)*) => (
+    alt_lint_trait_impl!($($cindex $current)* $index $head);
+    alt_lint_trait!(__impl [$($cindex $current)* $index $head]; $($rindex $rest)*);
+  );
+  (__impl [$($cindex:tt $current:ident)*];) => ();
+);
+
+macro_rules! alt_lint_trait_impl(
+  ($($index:tt $id:ident)+) => (
+    impl<
+      I, Output, Error,
+      $($id: Parser<I, Output, Error>),+
+    > AltLint<I, Output, Error> for ( $($id,)+ ) {
+      fn branch_count(&self) -> usize {
+        [$($index),+].len()
+      }
+
+      fn parse_branch(&mut self, index: usize, input: &mut I) -> PResult<Output, Error> {
+        match index {
+          $($index => self.$index.parse_next(input),)+
+          _ => panic!("no `alt` branch {index}"),
+        }
+      }
+    }
+  );
+);
+
+alt_lint_trait!(0 Alt1 1 Alt2 2 Alt3 3 Alt4 4 Alt5 5 Alt6 6 Alt7 7 Alt8 8 Alt9 9 Alt10 10 Alt11 11 Alt12 12 Alt13 13 Alt14 14 Alt15 15 Alt16 16 Alt17 17 Alt18 18 Alt19 19 Alt20 20 Alt21 21 Alt22);
+
 macro_rules! permutation_trait(
   (
     $name1:ident $ty1:ident $item1:ident
@@ -307,6 +674,42 @@ macro_rules! permutation_trait_impl(
         }
       }
     }
+
+    impl<
+      I: Stream, $($ty),+ , Error: ParserError<I>,
+      $($name: Parser<I, $ty, Error>),+
+    > UnambiguousPermutation<I, ( $($ty),+ ), Error> for ( $($name),+ ) {
+
+      fn permutation_unambiguous(&mut self, input: &mut I) -> PResult<( $($ty),+ ), Error> {
+        let mut res = ($(Option::<$ty>::None),+);
+
+        loop {
+          let mut err: Option<Error> = None;
+          let mut end: Option<<I as Stream>::Checkpoint> = None;
+          let start = input.checkpoint();
+          permutation_unambiguous_inner!(0, self, input, start, res, err, end, $($name)+);
+
+          if let Some(end) = end {
+            input.reset(&end);
+            continue;
+          }
+
+          // If we reach here, every iterator has either been applied before,
+          // or errored on the remaining input
+          if let Some(err) = err {
+            // There are remaining parsers, and all errored on the remaining input
+            input.reset(&start);
+            return Err(ErrMode::Backtrack(err.append(input, &start, ErrorKind::Alt)));
+          }
+
+          // All parsers were applied
+          match res {
+            ($(Some($item)),+) => return Ok(($($item),+)),
+            _ => unreachable!(),
+          }
+        }
+      }
+    }
   );
 );
 
@@ -333,6 +736,34 @@ macro_rules! permutation_trait_inner(
   ($it:tt, $self:expr, $input:ident, $start:ident, $res:expr, $err:expr,) => ();
 );
 
+macro_rules! permutation_unambiguous_inner(
+  ($it:tt, $self:expr, $input:ident, $start:ident, $res:expr, $err:expr, $end:expr, $head:ident $($id:ident)*) => (
+    if $res.$it.is_none() {
+      $input.reset(&$start);
+      match $self.$it.parse_next($input) {
+        Ok(o) => {
+          if $end.is_some() {
+            // Another parser already matched here
+            $input.reset(&$start);
+            return Err(ErrMode::from_error_kind($input, ErrorKind::Verify));
+          }
+          $res.$it = Some(o);
+          $end = Some($input.checkpoint());
+        }
+        Err(ErrMode::Backtrack(e)) => {
+          $err = Some(match $err {
+            Some(err) => err.or(e),
+            None => e,
+          });
+        }
+        Err(e) => return Err(e),
+      };
+    }
+    succ!($it, permutation_unambiguous_inner!($self, $input, $start, $res, $err, $end, $($id)*));
+  );
+  ($it:tt, $self:expr, $input:ident, $start:ident, $res:expr, $err:expr, $end:expr,) => ();
+);
+
 permutation_trait!(
   P1 O1 o1
   P2 O2 o2
@@ -1,6 +1,8 @@
 use crate::combinator::trace;
 use crate::error::{ErrMode, ErrorKind, Needed, ParserError};
+use crate::stream::AsBStr;
 use crate::stream::Stream;
+use crate::stream::StreamIsPartial;
 use crate::*;
 
 /// Return the remaining input.
@@ -157,6 +159,122 @@ where
     })
 }
 
+/// Calls the parser with part of the input's state set to `value`, restoring it afterwards
+///
+/// `access` borrows the state, like a field of [`Stateful::state`][crate::stream::Stateful].
+/// This scopes context flags to part of a grammar, like whether parsing is inside of an `async`
+/// block, for [`contextual_keyword`][crate::ascii::contextual_keyword] to check.  The previous
+/// value is restored whether or not `parser` succeeds.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// use winnow::ascii::alpha1;
+/// use winnow::combinator::delimited;
+/// use winnow::combinator::scoped_state;
+/// use winnow::stream::Stateful;
+///
+/// type Stream<'i> = Stateful<&'i str, usize>;
+///
+/// fn depth(input: &mut Stream<'_>) -> PResult<usize> {
+///     let depth = input.state;
+///     alpha1.value(depth).parse_next(input)
+/// }
+///
+/// fn nested(input: &mut Stream<'_>) -> PResult<usize> {
+///     delimited('(', scoped_state(|i: &mut Stream<'_>| &mut i.state, 1, depth), ')')
+///         .parse_next(input)
+/// }
+///
+/// let mut input = Stream { input: "(a)", state: 0 };
+/// assert_eq!(nested.parse_next(&mut input), Ok(1));
+/// assert_eq!(input.state, 0);
+/// ```
+pub fn scoped_state<Input, Output, Error, ParseNext, Access, State>(
+    mut access: Access,
+    value: State,
+    mut parser: ParseNext,
+) -> impl Parser<Input, Output, Error>
+where
+    Input: Stream,
+    Access: FnMut(&mut Input) -> &mut State,
+    State: Clone,
+    ParseNext: Parser<Input, Output, Error>,
+    Error: ParserError<Input>,
+{
+    trace("scoped_state", move |input: &mut Input| {
+        let previous = crate::lib::std::mem::replace(access(input), value.clone());
+        let result = parser.parse_next(input);
+        *access(input) = previous;
+        result
+    })
+}
+
+/// Calls the parser, failing once more than `max` calls are nested, like for `((((...))))`
+///
+/// `access` borrows the current depth from the input's state, like a field of
+/// [`Stateful::state`][crate::stream::Stateful], which starts at `0`.  Wrap the recursive part
+/// of a grammar so untrusted input can't overflow the stack.
+///
+/// Going too deep is a [`Cut`][ErrMode::Cut] with [`ErrorKind::Depth`], so no alternatives are
+/// tried.  The depth is restored whether or not `parser` succeeds.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ErrMode;
+/// # use winnow::error::ErrorKind;
+/// # use winnow::error::InputError;
+/// use winnow::combinator::alt;
+/// use winnow::combinator::delimited;
+/// use winnow::combinator::depth_limited;
+/// use winnow::stream::Stateful;
+///
+/// type Stream<'i> = Stateful<&'i str, usize>;
+///
+/// fn parens<'i>(input: &mut Stream<'i>) -> PResult<(), InputError<Stream<'i>>> {
+///     depth_limited(
+///         |i: &mut Stream<'i>| &mut i.state,
+///         3,
+///         alt((delimited('(', parens, ')'), "x".void())),
+///     )
+///     .parse_next(input)
+/// }
+///
+/// assert!(parens.parse(Stream { input: "((x))", state: 0 }).is_ok());
+///
+/// let err = parens.parse(Stream { input: "(((x)))", state: 0 }).unwrap_err();
+/// assert_eq!(err.inner().kind, ErrorKind::Depth);
+/// assert_eq!(err.offset(), 3);
+/// ```
+pub fn depth_limited<Input, Output, Error, ParseNext, Access>(
+    mut access: Access,
+    max: usize,
+    mut parser: ParseNext,
+) -> impl Parser<Input, Output, Error>
+where
+    Input: Stream,
+    Access: FnMut(&mut Input) -> &mut usize,
+    ParseNext: Parser<Input, Output, Error>,
+    Error: ParserError<Input>,
+{
+    trace("depth_limited", move |input: &mut Input| {
+        let depth = *access(input);
+        if max <= depth {
+            return Err(ErrMode::Cut(Error::from_error_kind(
+                input,
+                ErrorKind::Depth,
+            )));
+        }
+        *access(input) = depth + 1;
+        let result = parser.parse_next(input);
+        *access(input) = depth;
+        result
+    })
+}
+
 /// Apply the parser without advancing the input.
 ///
 /// To lookahead and only advance on success, see [`opt`].
@@ -194,6 +312,57 @@ where
     })
 }
 
+/// Apply `inner` to the slice recognized by `slice`, reporting errors at their position in the
+/// original input
+///
+/// Like [`Parser::and_then`] but when `inner` fails, `input` is left where `inner` failed, rather
+/// than at the start of `slice`, so [`ParseError::offset`][crate::error::ParseError::offset]
+/// points into the original input.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::digit1;
+/// use winnow::combinator::within;
+/// use winnow::token::take;
+///
+/// fn field<'s>(input: &mut &'s str) -> PResult<&'s str> {
+///     let _ = "id=".parse_next(input)?;
+///     within(take(5u8), (digit1, "!").take()).parse_next(input)
+/// }
+///
+/// assert_eq!(field.parse("id=1234!"), Ok("1234!"));
+///
+/// let err = field.parse("id=12ab!").unwrap_err();
+/// assert_eq!(err.offset(), 5);
+/// ```
+pub fn within<Input, Output, Error, SliceParser, ParseNext>(
+    mut slice: SliceParser,
+    mut inner: ParseNext,
+) -> impl Parser<Input, Output, Error>
+where
+    Input: Stream + AsBStr,
+    <Input as Stream>::Slice: Stream + StreamIsPartial + AsBStr,
+    Error: ParserError<Input>,
+    SliceParser: Parser<Input, <Input as Stream>::Slice, Error>,
+    ParseNext: Parser<<Input as Stream>::Slice, Output, Error>,
+{
+    trace("within", move |input: &mut Input| {
+        let start = input.checkpoint();
+        let base = input.as_bstr().as_ptr() as usize;
+        let mut sub = slice.parse_next(input)?;
+        let _ = sub.complete();
+        inner.parse_next(&mut sub).map_err(|err| {
+            input.reset(&start);
+            let offset = (sub.as_bstr().as_ptr() as usize).wrapping_sub(base);
+            let _ = input.try_next_slice(offset);
+            err
+        })
+    })
+}
+
 /// Match the end of the [`Stream`]
 ///
 /// Otherwise, it will error.
@@ -505,6 +674,88 @@ enum State<E> {
     Incomplete(Needed),
 }
 
+/// Share a parser without `&mut` access, for immutable grammar tables used across threads
+///
+/// [`Parser::parse_next`] takes `&mut self` as combinators may carry state between calls.
+/// Instead, `make` is called to build a fresh parser for every parse.  Constructing
+/// combinators is cheap, typically free, so this is only as expensive as any state they capture.
+///
+/// [`Shared::parse_ref`] parses with `&self` and `&Shared` implements [`Parser`].
+/// `Shared` is [`Sync`] when `make` is, allowing it to be stored in a `static` or shared between
+/// threads.
+///
+/// Functions like `fn(&mut I) -> PResult<O, E>` and types deriving [`Parse`][crate::Parse] do
+/// not need this as they do not have state.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::dec_uint;
+/// use winnow::combinator::separated;
+/// use winnow::combinator::shared;
+///
+/// let list = shared(|| separated(1.., dec_uint::<_, u32, ContextError>, ','));
+///
+/// std::thread::scope(|s| {
+///     let a = s.spawn(|| list.parse_ref(&mut "1,2,3"));
+///     let b = s.spawn(|| (&list).parse_peek("4,5;"));
+///     assert_eq!(a.join().unwrap(), Ok(vec![1, 2, 3]));
+///     assert_eq!(b.join().unwrap(), Ok((";", vec![4, 5])));
+/// });
+/// ```
+#[inline(always)]
+pub fn shared<Make, ParseNext>(make: Make) -> Shared<Make>
+where
+    Make: Fn() -> ParseNext,
+{
+    Shared { make }
+}
+
+/// Implementation of [`shared`]
+#[derive(Copy, Clone, Debug)]
+pub struct Shared<Make> {
+    make: Make,
+}
+
+impl<Make> Shared<Make> {
+    /// Parse with a fresh parser from `make`, see [`Parser::parse_next`]
+    #[inline(always)]
+    pub fn parse_ref<Input, Output, Error, ParseNext>(
+        &self,
+        input: &mut Input,
+    ) -> PResult<Output, Error>
+    where
+        Make: Fn() -> ParseNext,
+        ParseNext: Parser<Input, Output, Error>,
+    {
+        (self.make)().parse_next(input)
+    }
+}
+
+impl<Input, Output, Error, Make, ParseNext> Parser<Input, Output, Error> for &Shared<Make>
+where
+    Make: Fn() -> ParseNext,
+    ParseNext: Parser<Input, Output, Error>,
+{
+    #[inline(always)]
+    fn parse_next(&mut self, input: &mut Input) -> PResult<Output, Error> {
+        self.parse_ref(input)
+    }
+}
+
+impl<Input, Output, Error, Make, ParseNext> Parser<Input, Output, Error> for Shared<Make>
+where
+    Make: Fn() -> ParseNext,
+    ParseNext: Parser<Input, Output, Error>,
+{
+    #[inline(always)]
+    fn parse_next(&mut self, input: &mut Input) -> PResult<Output, Error> {
+        self.parse_ref(input)
+    }
+}
+
 /// Succeed, consuming no input
 ///
 /// For example, it can be used as the last alternative in `alt` to
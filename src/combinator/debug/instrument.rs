@@ -0,0 +1,102 @@
+use crate::error::ErrMode;
+use crate::lib::std::fmt::Display;
+#[cfg(not(feature = "debug"))]
+use crate::stream::Stream;
+use crate::PResult;
+#[cfg(not(feature = "debug"))]
+use crate::Parser;
+
+use tracing::field::Empty;
+use tracing::span::EnteredSpan;
+
+/// Emit a `tracing` span for each call of the parser, for when `debug` is not enabled
+#[cfg(not(feature = "debug"))]
+pub(crate) struct Instrument<P, D, I, O, E>
+where
+    P: Parser<I, O, E>,
+    I: Stream,
+    D: Display,
+{
+    parser: P,
+    name: D,
+    call_count: usize,
+    i: core::marker::PhantomData<I>,
+    o: core::marker::PhantomData<O>,
+    e: core::marker::PhantomData<E>,
+}
+
+#[cfg(not(feature = "debug"))]
+impl<P, D, I, O, E> Instrument<P, D, I, O, E>
+where
+    P: Parser<I, O, E>,
+    I: Stream,
+    D: Display,
+{
+    #[inline(always)]
+    pub(crate) fn new(parser: P, name: D) -> Self {
+        Self {
+            parser,
+            name,
+            call_count: 0,
+            i: Default::default(),
+            o: Default::default(),
+            e: Default::default(),
+        }
+    }
+}
+
+#[cfg(not(feature = "debug"))]
+impl<P, D, I, O, E> Parser<I, O, E> for Instrument<P, D, I, O, E>
+where
+    P: Parser<I, O, E>,
+    I: Stream,
+    D: Display,
+{
+    #[inline]
+    fn parse_next(&mut self, i: &mut I) -> PResult<O, E> {
+        let original = i.checkpoint();
+        let span = enter(&self.name, self.call_count, i.eof_offset());
+
+        let res = self.parser.parse_next(i);
+
+        exit(span, i.offset_from(&original), &res);
+        self.call_count += 1;
+
+        res
+    }
+}
+
+/// Open the span for a call to a [`trace`][crate::combinator::trace]d parser
+pub(crate) fn enter(name: &dyn Display, call: usize, remaining: usize) -> EnteredSpan {
+    tracing::trace_span!(
+        target: "winnow",
+        "parse",
+        name = %name,
+        call,
+        remaining,
+        consumed = Empty,
+        result = Empty,
+    )
+    .entered()
+}
+
+/// Record how the call went and close its span
+pub(crate) fn exit<O, E>(span: EnteredSpan, consumed: usize, res: &PResult<O, E>) {
+    span.record("consumed", consumed);
+    span.record("result", outcome(res));
+}
+
+/// Report the outcome of a step that isn't a parser call, like a failed
+/// [`Parser::verify`][crate::Parser::verify]
+pub(crate) fn result<O, E>(name: &dyn Display, res: &Result<O, ErrMode<E>>) {
+    tracing::trace!(target: "winnow", name = %name, result = outcome(res));
+}
+
+fn outcome<O, E>(res: &Result<O, ErrMode<E>>) -> &'static str {
+    match res {
+        Ok(_) => "success",
+        Err(ErrMode::Backtrack(_)) => "backtrack",
+        Err(ErrMode::Cut(_)) => "cut",
+        Err(ErrMode::Incomplete(_)) => "incomplete",
+    }
+}
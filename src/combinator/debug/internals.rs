@@ -2,6 +2,7 @@
 
 use std::io::Write;
 
+use super::sink::{emit, TraceEvent, TraceOutcome};
 use crate::error::ErrMode;
 use crate::stream::Stream;
 use crate::*;
@@ -50,12 +51,23 @@ where
         let depth = Depth::new();
         let original = i.checkpoint();
         start(*depth, &self.name, self.call_count, i);
+        #[cfg(feature = "tracing")]
+        let span = super::instrument::enter(&self.name, self.call_count, i.eof_offset());
 
         let res = self.parser.parse_next(i);
 
         let consumed = i.offset_from(&original);
+        #[cfg(feature = "tracing")]
+        super::instrument::exit(span, consumed, &res);
         let severity = Severity::with_result(&res);
-        end(*depth, &self.name, self.call_count, consumed, severity);
+        end(
+            *depth,
+            &self.name,
+            self.call_count,
+            consumed,
+            i.eof_offset(),
+            severity,
+        );
         self.call_count += 1;
 
         res
@@ -115,6 +127,15 @@ pub(crate) enum Severity {
 }
 
 impl Severity {
+    fn outcome(&self) -> TraceOutcome {
+        match self {
+            Self::Success => TraceOutcome::Success,
+            Self::Backtrack => TraceOutcome::Backtrack,
+            Self::Cut => TraceOutcome::Cut,
+            Self::Incomplete => TraceOutcome::Incomplete,
+        }
+    }
+
     pub(crate) fn with_result<T, E>(result: &Result<T, ErrMode<E>>) -> Self {
         match result {
             Ok(_) => Self::Success,
@@ -131,6 +152,15 @@ pub(crate) fn start<I: Stream>(
     count: usize,
     input: &I,
 ) {
+    if emit(&TraceEvent::Enter {
+        name,
+        depth,
+        call: count,
+        remaining: input.eof_offset(),
+    }) {
+        return;
+    }
+
     let gutter_style = anstyle::Style::new().bold();
     let input_style = anstyle::Style::new().underline();
     let eof_style = anstyle::Style::new().fg_color(Some(anstyle::AnsiColor::Cyan.into()));
@@ -183,8 +213,20 @@ pub(crate) fn end(
     name: &dyn crate::lib::std::fmt::Display,
     count: usize,
     consumed: usize,
+    remaining: usize,
     severity: Severity,
 ) {
+    if emit(&TraceEvent::Exit {
+        name,
+        depth,
+        call: count,
+        remaining,
+        consumed,
+        outcome: severity.outcome(),
+    }) {
+        return;
+    }
+
     let gutter_style = anstyle::Style::new().bold();
 
     let (call_width, _) = column_widths();
@@ -229,6 +271,14 @@ pub(crate) fn end(
 }
 
 pub(crate) fn result(depth: usize, name: &dyn crate::lib::std::fmt::Display, severity: Severity) {
+    if emit(&TraceEvent::Result {
+        name,
+        depth,
+        outcome: severity.outcome(),
+    }) {
+        return;
+    }
+
     let gutter_style = anstyle::Style::new().bold();
 
     let (call_width, _) = column_widths();
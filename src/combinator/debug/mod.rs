@@ -1,7 +1,14 @@
 #![cfg_attr(feature = "debug", allow(clippy::std_instead_of_core))]
 
+#[cfg(feature = "tracing")]
+mod instrument;
 #[cfg(feature = "debug")]
 mod internals;
+#[cfg(feature = "debug")]
+mod sink;
+
+#[cfg(feature = "debug")]
+pub use sink::*;
 
 use crate::error::ErrMode;
 use crate::stream::Stream;
@@ -11,6 +18,18 @@ use crate::Parser;
 ///
 /// Note that [`Parser::context`] also provides high level trace information.
 ///
+/// With the `debug` feature, calls are printed to stderr or, for loading large parses into a
+/// visualizer, sent to a [`TraceSink`][crate::combinator::TraceSink] set with
+/// `set_trace_sink`.
+///
+/// With the `tracing` feature, each call is a [`tracing`](https://docs.rs/tracing) span at the
+/// `TRACE` level with a target of `winnow`.  The span is named `parse` and has the fields:
+/// - `name`: the name given to `trace`
+/// - `call`: how many times this parser was called before
+/// - `remaining`: the length of the input it was called on
+/// - `consumed`: how much input it consumed, recorded on return
+/// - `result`: `success`, `backtrack`, `cut`, or `incomplete`, recorded on return
+///
 /// See [tutorial][crate::_tutorial::chapter_8] for more details.
 ///
 /// # Example
@@ -34,9 +53,12 @@ use crate::Parser;
 /// assert_eq!(short_alpha.parse_peek(b"ed"), Err(ErrMode::Backtrack(InputError::new(&b"ed"[..], ErrorKind::Slice))));
 /// assert_eq!(short_alpha.parse_peek(b"12345"), Err(ErrMode::Backtrack(InputError::new(&b"12345"[..], ErrorKind::Slice))));
 /// ```
-#[cfg_attr(not(feature = "debug"), allow(unused_variables))]
-#[cfg_attr(not(feature = "debug"), allow(unused_mut))]
-#[cfg_attr(not(feature = "debug"), inline(always))]
+#[cfg_attr(
+    not(any(feature = "debug", feature = "tracing")),
+    allow(unused_variables)
+)]
+#[cfg_attr(not(any(feature = "debug", feature = "tracing")), allow(unused_mut))]
+#[cfg_attr(not(any(feature = "debug", feature = "tracing")), inline(always))]
 pub fn trace<I: Stream, O, E>(
     name: impl crate::lib::std::fmt::Display,
     parser: impl Parser<I, O, E>,
@@ -45,17 +67,26 @@ pub fn trace<I: Stream, O, E>(
     {
         internals::Trace::new(parser, name)
     }
-    #[cfg(not(feature = "debug"))]
+    #[cfg(all(feature = "tracing", not(feature = "debug")))]
+    {
+        instrument::Instrument::new(parser, name)
+    }
+    #[cfg(not(any(feature = "debug", feature = "tracing")))]
     {
         parser
     }
 }
 
-#[cfg_attr(not(feature = "debug"), allow(unused_variables))]
+#[cfg_attr(
+    not(any(feature = "debug", feature = "tracing")),
+    allow(unused_variables)
+)]
 pub(crate) fn trace_result<T, E>(
     name: impl crate::lib::std::fmt::Display,
     res: &Result<T, ErrMode<E>>,
 ) {
+    #[cfg(feature = "tracing")]
+    instrument::result(&name, res);
     #[cfg(feature = "debug")]
     {
         let depth = internals::Depth::existing();
@@ -94,3 +125,206 @@ fn example() {
     )
     .test("assets/trace.svg", [cmd.as_str()]);
 }
+
+/// Tests replacing the sink must not run at the same time
+#[cfg(test)]
+#[cfg(feature = "debug")]
+static SINK_TESTS: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[test]
+#[cfg(feature = "debug")]
+fn json_lines_sink() {
+    use crate::ascii::digit1;
+    use crate::error::ContextError;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let _guard = SINK_TESTS.lock().unwrap_or_else(|e| e.into_inner());
+    let buffer = Shared::default();
+    set_trace_sink(JsonLinesTrace::new(buffer.clone()));
+    let mut parser = trace(
+        "json_lines_outer",
+        (
+            trace("json_lines_\"digits\"", digit1::<_, ContextError>),
+            trace("json_lines_digits", digit1::<_, ContextError>),
+        ),
+    );
+    assert!(parser.parse_peek("12a").is_err());
+    take_trace_sink();
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    // Other tests may trace concurrently
+    let lines = output
+        .lines()
+        .filter(|line| line.contains("json_lines_"))
+        .collect::<Vec<_>>();
+    assert_eq!(lines.len(), 6, "{output}");
+    assert!(lines[0].starts_with(r#"{"event":"enter","name":"json_lines_outer","#));
+    assert!(lines[1].starts_with(r#"{"event":"enter","name":"json_lines_\"digits\"","#));
+    assert!(lines[2].ends_with(r#""consumed":2,"result":"success"}"#));
+    assert!(lines[3].starts_with(r#"{"event":"enter","name":"json_lines_digits","#));
+    assert!(lines[4].ends_with(r#""consumed":0,"result":"backtrack"}"#));
+    assert!(lines[5].starts_with(r#"{"event":"exit","name":"json_lines_outer","#));
+}
+
+#[test]
+#[cfg(feature = "debug")]
+fn profile_sink() {
+    use crate::ascii::digit1;
+    use crate::combinator::alt;
+    use crate::error::ContextError;
+
+    let _guard = SINK_TESTS.lock().unwrap_or_else(|e| e.into_inner());
+    let profile = ProfileTrace::new();
+    set_trace_sink(profile.clone());
+    let mut parser = crate::combinator::repeat::<_, _, (), _, _>(
+        1..,
+        alt((
+            trace("profile_hex", ("0x", digit1::<_, ContextError>)).void(),
+            trace("profile_dec", (digit1, ' ')).void(),
+        )),
+    );
+    assert!(parser.parse_peek("1 22 0x3").is_ok());
+    take_trace_sink();
+
+    let report = profile.report();
+    let hex = report.get("profile_hex").unwrap();
+    assert_eq!(
+        (
+            hex.calls(),
+            hex.successes(),
+            hex.backtracks(),
+            hex.consumed()
+        ),
+        (4, 1, 3, 3)
+    );
+    let dec = report.get("profile_dec").unwrap();
+    assert_eq!(
+        (
+            dec.calls(),
+            dec.successes(),
+            dec.backtracks(),
+            dec.consumed()
+        ),
+        (3, 2, 1, 5)
+    );
+    assert_eq!(dec.discarded(), 0);
+    assert_eq!(
+        dec.to_string(),
+        "`profile_dec`: 3 calls, 2 succeeded, 1 backtracked; consumed 5, discarded 0"
+    );
+
+    profile.clear();
+    assert!(profile.report().parsers().is_empty());
+}
+
+#[test]
+#[cfg(feature = "tracing")]
+fn tracing_spans() {
+    use crate::ascii::digit1;
+    use crate::error::ContextError;
+    use std::fmt::Write as _;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    #[derive(Default)]
+    struct Collect {
+        lines: Arc<Mutex<Vec<String>>>,
+        next_id: AtomicU64,
+    }
+
+    struct Fields(String);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+
+    impl Subscriber for Collect {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut fields = Fields(format!("{}:", span.metadata().target()));
+            span.record(&mut fields);
+            self.lines.lock().unwrap().push(fields.0);
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _: &Id, values: &Record<'_>) {
+            let mut fields = Fields("record:".to_owned());
+            values.record(&mut fields);
+            self.lines.lock().unwrap().push(fields.0);
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields("event:".to_owned());
+            event.record(&mut fields);
+            self.lines.lock().unwrap().push(fields.0);
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    let subscriber = Collect::default();
+    let lines = subscriber.lines.clone();
+    tracing::subscriber::with_default(subscriber, || {
+        let mut parser = trace(
+            "outer",
+            (
+                trace("digits", digit1::<_, ContextError>),
+                trace("digits", digit1::<_, ContextError>),
+            ),
+        );
+        assert!(parser.parse_peek("12a").is_err());
+    });
+
+    let lines = lines.lock().unwrap();
+    assert_eq!(
+        *lines,
+        [
+            "winnow: name=outer call=0 remaining=3",
+            "winnow: name=digits call=0 remaining=3",
+            "winnow: name=digit1 call=0 remaining=3",
+            "winnow: name=take_while call=0 remaining=3",
+            "record: consumed=2",
+            "record: result=\"success\"",
+            "record: consumed=2",
+            "record: result=\"success\"",
+            "record: consumed=2",
+            "record: result=\"success\"",
+            "winnow: name=digits call=0 remaining=1",
+            "winnow: name=digit1 call=0 remaining=1",
+            "winnow: name=take_while call=0 remaining=1",
+            "record: consumed=0",
+            "record: result=\"backtrack\"",
+            "record: consumed=0",
+            "record: result=\"backtrack\"",
+            "record: consumed=0",
+            "record: result=\"backtrack\"",
+            "record: consumed=2",
+            "record: result=\"backtrack\"",
+        ]
+    );
+}
@@ -0,0 +1,563 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Receive [`trace`][crate::combinator::trace] events, see [`set_trace_sink`]
+///
+/// Built-in sinks are [`JsonLinesTrace`], [`ChromeTrace`], and [`ProfileTrace`].
+pub trait TraceSink: Send {
+    /// Record a single event
+    fn event(&mut self, event: &TraceEvent<'_>);
+}
+
+/// What happened in a [`trace`][crate::combinator::trace]d parser, see [`TraceSink`]
+///
+/// Positions are reported as the [remaining length][crate::stream::Stream::eof_offset] of the
+/// input.  Subtracting them from the `remaining` of the outermost [`TraceEvent::Enter`] gives
+/// the offset into the input.
+#[derive(Copy, Clone)]
+#[non_exhaustive]
+pub enum TraceEvent<'a> {
+    /// A parser was called
+    Enter {
+        /// The name given to [`trace`][crate::combinator::trace]
+        name: &'a dyn Display,
+        /// How many traced parsers this is nested within
+        depth: usize,
+        /// How many times this parser was called before
+        call: usize,
+        /// The length of the input it was called on
+        remaining: usize,
+    },
+    /// A parser returned
+    Exit {
+        /// The name given to [`trace`][crate::combinator::trace]
+        name: &'a dyn Display,
+        /// How many traced parsers this is nested within
+        depth: usize,
+        /// How many times this parser was called before
+        call: usize,
+        /// The length of the input after it returned
+        remaining: usize,
+        /// How much input it consumed
+        consumed: usize,
+        /// What it returned
+        outcome: TraceOutcome,
+    },
+    /// A combinator reported the outcome of a step without calling a parser, like a failed
+    /// [`Parser::verify`][crate::Parser::verify]
+    Result {
+        /// The name of the step
+        name: &'a dyn Display,
+        /// How many traced parsers this is nested within
+        depth: usize,
+        /// What the step returned
+        outcome: TraceOutcome,
+    },
+}
+
+/// How a parser returned, see [`TraceEvent`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TraceOutcome {
+    /// `Ok`
+    Success,
+    /// [`ErrMode::Backtrack`][crate::error::ErrMode::Backtrack]
+    Backtrack,
+    /// [`ErrMode::Cut`][crate::error::ErrMode::Cut]
+    Cut,
+    /// [`ErrMode::Incomplete`][crate::error::ErrMode::Incomplete]
+    Incomplete,
+}
+
+impl TraceOutcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Backtrack => "backtrack",
+            Self::Cut => "cut",
+            Self::Incomplete => "incomplete",
+        }
+    }
+}
+
+static SINK: Mutex<Option<Box<dyn TraceSink>>> = Mutex::new(None);
+
+/// Send [`trace`][crate::combinator::trace] events to `sink` rather than printing them to stderr
+///
+/// The sink is shared by all threads.  Returns the sink it replaces, if any.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use winnow::prelude::*;
+/// use winnow::combinator::set_trace_sink;
+/// use winnow::combinator::take_trace_sink;
+/// use winnow::combinator::ChromeTrace;
+///
+/// # fn parse(_: &str) {}
+/// # let input = "";
+/// let file = std::fs::File::create("trace.json").unwrap();
+/// set_trace_sink(ChromeTrace::new(std::io::BufWriter::new(file)));
+/// parse(input);
+/// // Dropping the sink finishes the file
+/// drop(take_trace_sink());
+/// ```
+pub fn set_trace_sink(sink: impl TraceSink + 'static) -> Option<Box<dyn TraceSink>> {
+    lock().replace(Box::new(sink))
+}
+
+/// Go back to printing [`trace`][crate::combinator::trace] events to stderr, returning the
+/// sink set by [`set_trace_sink`]
+pub fn take_trace_sink() -> Option<Box<dyn TraceSink>> {
+    lock().take()
+}
+
+/// Send `event` to the sink, returning whether there was one
+pub(crate) fn emit(event: &TraceEvent<'_>) -> bool {
+    match lock().as_mut() {
+        Some(sink) => {
+            sink.event(event);
+            true
+        }
+        None => false,
+    }
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Option<Box<dyn TraceSink>>> {
+    SINK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Write [`TraceEvent`]s as one JSON object per line
+///
+/// For example:
+/// ```text
+/// {"event":"enter","name":"alpha1","depth":1,"call":0,"offset":0}
+/// {"event":"exit","name":"alpha1","depth":1,"call":0,"offset":5,"consumed":5,"result":"success"}
+/// ```
+///
+/// Offsets are relative to where the outermost traced parser started.
+#[derive(Debug)]
+pub struct JsonLinesTrace<W> {
+    writer: W,
+    base: Option<usize>,
+}
+
+impl<W: Write + Send> JsonLinesTrace<W> {
+    /// Write events to `writer`, like a [`BufWriter`][std::io::BufWriter]
+    pub fn new(writer: W) -> Self {
+        Self { writer, base: None }
+    }
+}
+
+impl<W: Write + Send> TraceSink for JsonLinesTrace<W> {
+    fn event(&mut self, event: &TraceEvent<'_>) {
+        let _ = write_json_line(&mut self.writer, &mut self.base, event);
+    }
+}
+
+fn write_json_line(
+    w: &mut impl Write,
+    base: &mut Option<usize>,
+    event: &TraceEvent<'_>,
+) -> std::io::Result<()> {
+    match *event {
+        TraceEvent::Enter {
+            name,
+            depth,
+            call,
+            remaining,
+        } => {
+            let offset = enter_offset(base, depth, remaining);
+            write!(w, r#"{{"event":"enter","name":"#)?;
+            write_json_str(w, name)?;
+            writeln!(w, r#","depth":{depth},"call":{call},"offset":{offset}}}"#)
+        }
+        TraceEvent::Exit {
+            name,
+            depth,
+            call,
+            remaining,
+            consumed,
+            outcome,
+        } => {
+            let offset = offset(*base, remaining);
+            write!(w, r#"{{"event":"exit","name":"#)?;
+            write_json_str(w, name)?;
+            writeln!(
+                w,
+                r#","depth":{depth},"call":{call},"offset":{offset},"consumed":{consumed},"result":"{}"}}"#,
+                outcome.as_str()
+            )
+        }
+        TraceEvent::Result {
+            name,
+            depth,
+            outcome,
+        } => {
+            write!(w, r#"{{"event":"result","name":"#)?;
+            write_json_str(w, name)?;
+            writeln!(w, r#","depth":{depth},"result":"{}"}}"#, outcome.as_str())
+        }
+    }
+}
+
+/// Write [`TraceEvent`]s in the [Chrome trace event
+/// format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU),
+/// for viewing in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev)
+///
+/// Each traced parser call is a slice on the timeline, nested by depth, with its input offset,
+/// consumed length, and result as arguments.  The closing `]` is written when the sink is
+/// dropped, though viewers also accept a trace without it.
+#[derive(Debug)]
+pub struct ChromeTrace<W: Write> {
+    writer: W,
+    start: Instant,
+    base: Option<usize>,
+    empty: bool,
+}
+
+impl<W: Write + Send> ChromeTrace<W> {
+    /// Write events to `writer`, like a [`BufWriter`][std::io::BufWriter]
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            start: Instant::now(),
+            base: None,
+            empty: true,
+        }
+    }
+
+    fn write_event(&mut self, event: &TraceEvent<'_>) -> std::io::Result<()> {
+        let ts = self.start.elapsed().as_micros();
+        let w = &mut self.writer;
+        if self.empty {
+            writeln!(w, "[")?;
+            self.empty = false;
+        } else {
+            writeln!(w, ",")?;
+        }
+        match *event {
+            TraceEvent::Enter {
+                name,
+                depth,
+                call,
+                remaining,
+            } => {
+                let offset = enter_offset(&mut self.base, depth, remaining);
+                write!(w, r#"{{"name":"#)?;
+                write_json_str(w, name)?;
+                write!(
+                    w,
+                    r#","ph":"B","ts":{ts},"pid":1,"tid":1,"args":{{"offset":{offset},"call":{call}}}}}"#
+                )
+            }
+            TraceEvent::Exit {
+                name,
+                remaining,
+                consumed,
+                outcome,
+                ..
+            } => {
+                let offset = offset(self.base, remaining);
+                write!(w, r#"{{"name":"#)?;
+                write_json_str(w, name)?;
+                write!(
+                    w,
+                    r#","ph":"E","ts":{ts},"pid":1,"tid":1,"args":{{"offset":{offset},"consumed":{consumed},"result":"{}"}}}}"#,
+                    outcome.as_str()
+                )
+            }
+            TraceEvent::Result { name, outcome, .. } => {
+                write!(w, r#"{{"name":"#)?;
+                write_json_str(w, name)?;
+                write!(
+                    w,
+                    r#","ph":"i","s":"t","ts":{ts},"pid":1,"tid":1,"args":{{"result":"{}"}}}}"#,
+                    outcome.as_str()
+                )
+            }
+        }
+    }
+}
+
+impl<W: Write + Send> TraceSink for ChromeTrace<W> {
+    fn event(&mut self, event: &TraceEvent<'_>) {
+        let _ = self.write_event(event);
+    }
+}
+
+impl<W: Write> Drop for ChromeTrace<W> {
+    fn drop(&mut self) {
+        if !self.empty {
+            let _ = writeln!(self.writer, "\n]");
+        }
+        let _ = self.writer.flush();
+    }
+}
+
+/// Count calls, results, and input consumed for each [`trace`][crate::combinator::trace]d
+/// parser, by name
+///
+/// This is a handle to shared counts: set a clone as the sink and then read the
+/// [`report`][ProfileTrace::report] from the original once parsing is done.  Parsers with the
+/// same name, like the built-in ones, are counted together.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::digit1;
+/// use winnow::combinator::alt;
+/// use winnow::combinator::set_trace_sink;
+/// use winnow::combinator::take_trace_sink;
+/// use winnow::combinator::trace;
+/// use winnow::combinator::ProfileTrace;
+///
+/// fn value<'s>(input: &mut &'s str) -> PResult<&'s str> {
+///     alt((
+///         trace("hex", ("0x", digit1).take()),
+///         trace("dec", digit1),
+///     ))
+///     .parse_next(input)
+/// }
+///
+/// let profile = ProfileTrace::new();
+/// set_trace_sink(profile.clone());
+/// value.parse("42").unwrap();
+/// take_trace_sink();
+///
+/// let report = profile.report();
+/// let hex = report.get("hex").unwrap();
+/// assert_eq!(hex.calls(), 1);
+/// assert_eq!(hex.backtracks(), 1);
+/// let dec = report.get("dec").unwrap();
+/// assert_eq!(dec.successes(), 1);
+/// assert_eq!(dec.consumed(), 2);
+/// println!("{report}");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ProfileTrace {
+    counts: Arc<Mutex<Profile>>,
+}
+
+#[derive(Debug, Default)]
+struct Profile {
+    parsers: HashMap<String, ParserCount>,
+    name: String,
+}
+
+impl ProfileTrace {
+    /// Create a sink with no counts
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget all counts
+    pub fn clear(&self) {
+        self.lock().parsers.clear();
+    }
+
+    /// Summarize the counts so far, most called parser first
+    pub fn report(&self) -> ProfileReport {
+        let mut parsers = self.lock().parsers.values().cloned().collect::<Vec<_>>();
+        parsers.sort_by(|a, b| b.calls.cmp(&a.calls).then_with(|| a.name.cmp(&b.name)));
+        ProfileReport { parsers }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Profile> {
+        self.counts
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl TraceSink for ProfileTrace {
+    fn event(&mut self, event: &TraceEvent<'_>) {
+        let TraceEvent::Exit {
+            name,
+            consumed,
+            outcome,
+            ..
+        } = *event
+        else {
+            return;
+        };
+        let mut profile = self.lock();
+        let Profile { parsers, name: key } = &mut *profile;
+        // Reuse the buffer to avoid allocating for names already seen
+        key.clear();
+        let _ = write!(key, "{name}");
+        if !parsers.contains_key(key.as_str()) {
+            parsers.insert(key.clone(), ParserCount::new(key.clone()));
+        }
+        let count = parsers.get_mut(key.as_str()).expect("just inserted");
+        count.calls += 1;
+        match outcome {
+            TraceOutcome::Success => {
+                count.successes += 1;
+                count.consumed += consumed;
+            }
+            TraceOutcome::Backtrack => {
+                count.backtracks += 1;
+                count.discarded += consumed;
+            }
+            TraceOutcome::Cut => {
+                count.cuts += 1;
+                count.discarded += consumed;
+            }
+            TraceOutcome::Incomplete => count.incompletes += 1,
+        }
+    }
+}
+
+/// The counts for every parser, see [`ProfileTrace::report`]
+///
+/// The [`Display`] implementation renders a human-readable report.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfileReport {
+    parsers: Vec<ParserCount>,
+}
+
+impl ProfileReport {
+    /// Each parser, most called first
+    pub fn parsers(&self) -> &[ParserCount] {
+        &self.parsers
+    }
+
+    /// The counts for the parser called `name`
+    pub fn get(&self, name: &str) -> Option<&ParserCount> {
+        self.parsers.iter().find(|p| p.name == name)
+    }
+}
+
+impl Display for ProfileReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for parser in &self.parsers {
+            writeln!(f, "{parser}")?;
+        }
+        Ok(())
+    }
+}
+
+/// How a single parser was called, see [`ProfileReport`]
+///
+/// Input consumed by nested parsers is also counted for the parsers containing them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParserCount {
+    name: String,
+    calls: usize,
+    successes: usize,
+    backtracks: usize,
+    cuts: usize,
+    incompletes: usize,
+    consumed: usize,
+    discarded: usize,
+}
+
+impl ParserCount {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            calls: 0,
+            successes: 0,
+            backtracks: 0,
+            cuts: 0,
+            incompletes: 0,
+            consumed: 0,
+            discarded: 0,
+        }
+    }
+
+    /// The name given to [`trace`][crate::combinator::trace]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// How many times the parser was called
+    pub fn calls(&self) -> usize {
+        self.calls
+    }
+
+    /// How many calls succeeded
+    pub fn successes(&self) -> usize {
+        self.successes
+    }
+
+    /// How many calls failed with [`ErrMode::Backtrack`][crate::error::ErrMode::Backtrack]
+    pub fn backtracks(&self) -> usize {
+        self.backtracks
+    }
+
+    /// How many calls failed with [`ErrMode::Cut`][crate::error::ErrMode::Cut]
+    pub fn cuts(&self) -> usize {
+        self.cuts
+    }
+
+    /// How many calls needed more input
+    pub fn incompletes(&self) -> usize {
+        self.incompletes
+    }
+
+    /// The input consumed by successful calls
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// The input consumed by failed calls before failing, which will likely be parsed again
+    pub fn discarded(&self) -> usize {
+        self.discarded
+    }
+}
+
+impl Display for ParserCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}`: {} calls, {} succeeded, {} backtracked",
+            self.name, self.calls, self.successes, self.backtracks
+        )?;
+        if self.cuts != 0 {
+            write!(f, ", {} cut", self.cuts)?;
+        }
+        if self.incompletes != 0 {
+            write!(f, ", {} incomplete", self.incompletes)?;
+        }
+        write!(
+            f,
+            "; consumed {}, discarded {}",
+            self.consumed, self.discarded
+        )
+    }
+}
+
+/// Start measuring offsets from the outermost traced parser
+fn enter_offset(base: &mut Option<usize>, depth: usize, remaining: usize) -> usize {
+    if depth == 0 || base.is_none() {
+        *base = Some(remaining);
+    }
+    offset(*base, remaining)
+}
+
+fn offset(base: Option<usize>, remaining: usize) -> usize {
+    base.unwrap_or(remaining).saturating_sub(remaining)
+}
+
+fn write_json_str(w: &mut impl Write, value: &dyn Display) -> std::io::Result<()> {
+    write!(w, "\"")?;
+    for c in value.to_string().chars() {
+        match c {
+            '"' => write!(w, "\\\"")?,
+            '\\' => write!(w, "\\\\")?,
+            '\n' => write!(w, "\\n")?,
+            '\r' => write!(w, "\\r")?,
+            '\t' => write!(w, "\\t")?,
+            c if c.is_control() => write!(w, "\\u{:04x}", c as u32)?,
+            c => write!(w, "{c}")?,
+        }
+    }
+    write!(w, "\"")
+}
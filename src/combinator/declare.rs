@@ -0,0 +1,161 @@
+//! Late-bound parsers for recursive grammars
+
+use core::cell::RefCell;
+
+use crate::combinator::trace;
+use crate::error::ParserError;
+use crate::lib::std::boxed::Box;
+use crate::lib::std::fmt;
+use crate::lib::std::rc::Rc;
+use crate::lib::std::vec::Vec;
+use crate::stream::Stream;
+use crate::PResult;
+use crate::Parser;
+
+/// Declare a parser now, to [define][Declared::define] later
+///
+/// Recursive grammars can then be built as values, like inside a builder or a test, rather than
+/// requiring every recursive rule to be a free function.  Clones of the returned handle all
+/// refer to the same parser, so they can be used within the definition itself.
+///
+/// <div class="warning">
+///
+/// **Note:** A definition holding a clone of its own handle is a reference cycle, so it is only
+/// freed by [`Declared::undefine`].
+///
+/// </div>
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::dec_uint;
+/// use winnow::combinator::alt;
+/// use winnow::combinator::declare;
+/// use winnow::combinator::delimited;
+/// use winnow::combinator::separated;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Tree {
+///     Leaf(u32),
+///     Node(Vec<Tree>),
+/// }
+///
+/// let tree = declare::<&str, Tree, ContextError>();
+/// let inner = tree.clone();
+/// tree.define(move || {
+///     alt((
+///         dec_uint.map(Tree::Leaf),
+///         delimited('[', separated(0.., inner.clone(), ','), ']').map(Tree::Node),
+///     ))
+/// });
+///
+/// assert_eq!(
+///     tree.clone().parse("[1,[2,3]]"),
+///     Ok(Tree::Node(vec![
+///         Tree::Leaf(1),
+///         Tree::Node(vec![Tree::Leaf(2), Tree::Leaf(3)]),
+///     ]))
+/// );
+/// tree.undefine();
+/// ```
+pub fn declare<'p, Input, Output, Error>() -> Declared<'p, Input, Output, Error> {
+    Declared {
+        inner: Rc::new(Inner {
+            make: RefCell::new(None),
+            idle: RefCell::new(Vec::new()),
+        }),
+    }
+}
+
+/// Implementation of [`declare`]
+pub struct Declared<'p, Input, Output, Error> {
+    inner: Rc<Inner<'p, Input, Output, Error>>,
+}
+
+type BoxedParser<'p, Input, Output, Error> = Box<dyn Parser<Input, Output, Error> + 'p>;
+type Make<'p, Input, Output, Error> = Rc<dyn Fn() -> BoxedParser<'p, Input, Output, Error> + 'p>;
+
+struct Inner<'p, Input, Output, Error> {
+    make: RefCell<Option<Make<'p, Input, Output, Error>>>,
+    /// Parsers from `make` not currently in use, reused to avoid rebuilding them
+    idle: RefCell<Vec<BoxedParser<'p, Input, Output, Error>>>,
+}
+
+impl<'p, Input, Output, Error> Declared<'p, Input, Output, Error> {
+    /// Define the parser, replacing any earlier definition
+    ///
+    /// A recursive rule is re-entered while it is still parsing, so `make` is called for a fresh
+    /// parser whenever every existing one is in use.
+    pub fn define<Make, ParseNext>(&self, make: Make)
+    where
+        Make: Fn() -> ParseNext + 'p,
+        ParseNext: Parser<Input, Output, Error> + 'p,
+    {
+        *self.inner.make.borrow_mut() = Some(Rc::new(move || {
+            Box::new(make()) as BoxedParser<'p, Input, Output, Error>
+        }));
+        self.inner.idle.borrow_mut().clear();
+    }
+
+    /// Remove the definition, breaking any reference cycle through it
+    pub fn undefine(&self) {
+        *self.inner.make.borrow_mut() = None;
+        self.inner.idle.borrow_mut().clear();
+    }
+
+    /// Whether [`Declared::define`] has been called
+    pub fn is_defined(&self) -> bool {
+        self.inner.make.borrow().is_some()
+    }
+}
+
+impl<Input, Output, Error> Clone for Declared<'_, Input, Output, Error> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+        }
+    }
+}
+
+impl<Input, Output, Error> fmt::Debug for Declared<'_, Input, Output, Error> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Declared")
+            .field("defined", &self.is_defined())
+            .finish()
+    }
+}
+
+impl<Input, Output, Error> Parser<Input, Output, Error> for Declared<'_, Input, Output, Error>
+where
+    Input: Stream,
+    Error: ParserError<Input>,
+{
+    fn parse_next(&mut self, input: &mut Input) -> PResult<Output, Error> {
+        trace("declared", |input: &mut Input| {
+            let idle = self.inner.idle.borrow_mut().pop();
+            let mut parser = match idle {
+                Some(parser) => parser,
+                None => {
+                    let make = self.inner.make.borrow().clone();
+                    match make {
+                        Some(make) => make(),
+                        None => {
+                            return Err(ParserError::assert(
+                                input,
+                                "`Declared::define` must be called before parsing",
+                            ))
+                        }
+                    }
+                }
+            };
+            let result = parser.parse_next(input);
+            if self.is_defined() {
+                self.inner.idle.borrow_mut().push(parser);
+            }
+            result
+        })
+        .parse_next(input)
+    }
+}
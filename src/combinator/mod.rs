@@ -17,16 +17,23 @@
 //! | [`literal`][crate::token::literal] | `"hello"` |  `"hello world"` |  `" world"` | `Ok("hello")` |Recognizes a specific suite of characters or bytes (see also [`Caseless`][crate::ascii::Caseless])|
 //! | [`take`][crate::token::take] | `take(4)` |  `"hello"` |  `"o"` | `Ok("hell")` |Takes a specific number of bytes or characters|
 //! | [`take_while`][crate::token::take_while] | `take_while(0.., is_alphabetic)` |  `"abc123"` |  `"123"` | `Ok("abc")` |Returns the longest slice of bytes or characters for which the provided [set of tokens][crate::stream::ContainsToken] matches.|
+//! | [`take_while_state`][crate::token::take_while_state] | `take_while_state(0.., false, in_quotes)` |  `"\"a,b\",c"` |  `",c"` | `Ok("\"a,b\"")` |Like `take_while` but the predicate carries state from one token to the next.|
 //! | [`take_till`][crate::token::take_till] | `take_till(0.., is_alphabetic)` |  `"123abc"` |  `"abc"` | `Ok("123")` |Returns a slice of bytes or characters until the provided [set of tokens][crate::stream::ContainsToken] matches. This is the reverse behaviour from `take_while`: `take_till(f)` is equivalent to `take_while(0.., \|c\| !f(c))`|
 //! | [`take_until`][crate::token::take_until] | `take_until(0.., "world")` |  `"Hello world"` |  `"world"` | `Ok("Hello ")` |Returns a slice of bytes or characters until the provided [literal][crate::token::literal] is found.|
+//! | [`take_balanced`][crate::token::take_balanced] | `take_balanced('(', ')')` |  `"(a(b))c"` |  `"c"` | `Ok("(a(b))")` |Returns the slice of a nested structure through its matching closing delimiter, without parsing its contents.|
 //!
 //! ## Choice combinators
 //!
 //! | combinator | usage | input | new input | output | comment |
 //! |---|---|---|---|---|---|
 //! | [`alt`] | `alt(("ab", "cd"))` |  `"cdef"` |  `"ef"` | `Ok("cd")` |Try a list of parsers and return the result of the first successful one|
+//! | [`alt_lint`] | `alt_lint(("<", "<="))` |  `"<="` |  | panics |Like `alt` but panics when a later parser would have matched more than the one picked, for catching mis-ordered alternatives in tests|
+//! | [`alt_furthest`] | `alt_furthest((("a", "b"), "c"))` |  `"ax"` |  | `Err` at `"x"` |Like `alt` but reports the error of the parser that got furthest into the input|
 //! | [`dispatch`] | \- | \- | \- | \- | `match` for parsers |
+//! | [`dispatch_with`] | \- | \- | \- | \- | `match` for parsers, choosing the branch at runtime |
+//! | [`byte_table`] | `byte_table().on(b'a', a).on(b'b', b)` | `"bc"` | `"c"` | `b`'s output |Jump to a parser by the first byte of the input, without trying each branch|
 //! | [`permutation`] | `permutation(("ab", "cd", "12"))` | `"cd12abc"` | `"c"` | `Ok(("ab", "cd", "12"))` |Succeeds when all its child parser have succeeded, whatever the order|
+//! | [`permutation_unambiguous`] | `permutation_unambiguous(("ab", alpha1))` | `"abcd"` | `"abcd"` | `Err(Backtrack(...))` |Like `permutation`, but fails when more than one remaining parser matches at the same point|
 //!
 //! ## Sequence combinators
 //!
@@ -44,18 +51,29 @@
 //! | combinator | usage | input | new input | output | comment |
 //! |---|---|---|---|---|---|
 //! | [`repeat`] | `repeat(1..=3, "ab")` | `"ababc"` | `"c"` | `Ok(vec!["ab", "ab"])` |Applies the parser between m and n times (n included) and returns the list of results in a Vec|
+//! | [`repeat_n`] | `repeat_n::<2, _, _, _, _>("ab")` | `"ababc"` | `"c"` | `Ok(["ab", "ab"])` |Applies the parser exactly N times and returns the results in an array|
+//! | [`repeat_bounded`] | `repeat_bounded::<2, _, _, _, _>(1.., "ab")` | `"ababab"` | `"ab"` | `Ok(BoundedVec(["ab", "ab"]))` |Like `repeat` but capped at MAX, returning the results in a [`BoundedVec`][crate::stream::BoundedVec]|
+//! | [`repeat_indexed`] | <code>repeat_indexed(0.., \|i\| take(i + 1))</code> | `"abbccc"` | `""` | `Ok(vec!["a", "bb", "ccc"])` |Like `repeat` but the parser is built from the index of each repetition|
 //! | [`repeat_till`] | `repeat_till(0.., "ab", "ef")` | `"ababefg"` | `"g"` | `Ok((vec!["ab", "ab"], "ef"))` |Applies the first parser until the second applies. Returns a tuple containing the list of results from the first in a Vec and the result of the second|
 //! | [`separated`] | `separated(1..=3, "ab", ",")` | `"ab,ab,ab."` | `"."` | `Ok(vec!["ab", "ab", "ab"])` |Applies the parser and separator between m and n times (n included) and returns the list of results in a Vec|
+//! | [`separated_trailing`] | `separated_trailing(1.., "ab", ",", TrailingSeparator::Allow)` | `"ab,ab,."` | `"."` | `Ok(vec!["ab", "ab"])` |Like `separated`, but with a [`TrailingSeparator`] policy to forbid, allow, or require a trailing separator|
+//! | [`separated_foldl1`] | <code>separated_foldl1(`dec_int`, '-', \|l, _, r\| l - r)</code> | `"9-3-5;"` | `";"` | `Ok(1)` |Applies the parser and separator one or more times, folding the results left associatively|
+//! | [`separated_foldr1`] | <code>separated_foldr1(`dec_uint`, '^', \|l, _, r\| l.pow(r))</code> | `"2^3^2;"` | `";"` | `Ok(512)` |Applies the parser and separator one or more times, folding the results right associatively|
+//! | [`key_value`] | `key_value(alpha1, '=', alpha1, ';')` | `"a=b; c=d;e"` | `";e"` | `Ok(vec![("a", "b"), ("c", "d")])` |Parses `key sep value` pairs separated by `pair_sep`, skipping spaces, with a configurable [`TrailingSeparator`] policy|
 //! | [`Repeat::fold`] | <code>repeat(1..=2, `be_u8`).fold(\|\| 0, \|acc, item\| acc + item)</code> | `[1, 2, 3]` | `[3]` | `Ok(3)` |Applies the parser between m and n times (n included) and folds the list of return value|
+//! | [`Repeat::fold_while`] | <code>repeat(0.., `be_u8`).fold_while(\|\| 0, \|acc, item\| if acc + item < 3 { Continue(acc + item) } else { Break(acc + item) })</code> | `[1, 2, 3]` | `[3]` | `Ok(3)` |Like `fold`, but the accumulator can stop the repetition early|
 //!
 //! ## Partial related
 //!
 //! - [`eof`]: Returns its input if it is at the end of input data
 //! - [`Parser::complete_err`]: Replaces an `Incomplete` returned by the child parser with an `Backtrack`
+//! - [`Parser::with_fuel`]: Bound the tokens consumed and backtracks performed by the child parser, see [`Fueled`][crate::stream::Fueled]
 //!
 //! ## Modifiers
 //!
 //! - [`cond`]: Conditional combinator. Wraps another parser and calls it if the condition is met
+//! - [`scoped_state`]: Calls the parser with part of the state set to a value, like a context flag, restoring it afterwards
+//! - [`depth_limited`]: Counts nested calls of the parser in part of the state, failing when there are too many
 //! - [`Parser::flat_map`]: method to map a new parser from the output of the first parser, then apply that parser over the rest of the input
 //! - [`Parser::value`]: method to replace the result of a parser
 //! - [`Parser::default_value`]: method to replace the result of a parser
@@ -68,10 +86,14 @@
 //! - [`not`]: Returns a result only if the embedded parser returns `Backtrack` or `Incomplete`. Does not consume the input
 //! - [`opt`]: Make the underlying parser optional
 //! - [`peek`]: Returns a result without consuming the input
+//! - [`within`]: Applies a second parser over the slice recognized by the first one, reporting errors at their position in the original input
 //! - [`Parser::take`]: If the child parser was successful, return the consumed input as the produced value
+//! - [`Parser::take_trimmed`]: Like [`Parser::take`], without leading and trailing tokens from a set
 //! - [`Parser::with_taken`]: If the child parser was successful, return a tuple of the consumed input and the produced output.
 //! - [`Parser::span`]: If the child parser was successful, return the location of the consumed input as the produced value
 //! - [`Parser::with_span`]: If the child parser was successful, return a tuple of the location of the consumed input and the produced output.
+//! - [`Parser::line_span`]: If the child parser was successful, return the line and column range of the consumed input as the produced value
+//! - [`Parser::with_line_span`]: If the child parser was successful, return a tuple of the line and column range of the consumed input and the produced output.
 //! - [`Parser::verify`]: Returns the result of the child parser if it satisfies a verification function
 //!
 //! ## Error management and debugging
@@ -88,6 +110,12 @@
 //! - [`empty`]: Returns a value without consuming any input, always succeeds
 //! - [`fail`]: Inversion of [`empty`]. Always fails.
 //! - [`Parser::by_ref`]: Allow moving `&mut impl Parser` into other parsers
+//! - [`shared`]: Allow using a parser through `&self`, e.g. from a grammar table shared between threads
+//! - [`declare`]: Declare a parser to define later, for building recursive grammars as values
+//! - [`BranchStats`]: Collect how often each `alt` branch succeeds, reporting a better order or when to use `dispatch!`
+//! - [`Parser::memoize`]: Cache the result of a parser for each input position (packrat parsing)
+//! - [`Parser::warn_if`]: Record a non-fatal warning about the output, with its span
+//! - [`precedence`]: Parse prefix, infix, and postfix operator expressions, using a runtime-mutable [`OperatorTable`]
 //!
 //! ## Text parsing
 //!
@@ -108,12 +136,19 @@
 //! - [`oct_digit0`][crate::ascii::oct_digit0]: Recognizes zero or more octal characters: `[0-7]`. [`oct_digit1`][crate::ascii::oct_digit1] does the same but returns at least one character
 //!
 //! - [`float`][crate::ascii::float]: Parse a floating point number in a byte string
+//! - [`float_with`][crate::ascii::float_with]: Parse a floating point number, only accepting the forms allowed by a [`FloatPolicy`][crate::ascii::FloatPolicy]
+//! - [`hex_float`][crate::ascii::hex_float]: Parse a hexadecimal floating point literal, like `0x1.8p3`, with correct rounding
 //! - [`dec_int`][crate::ascii::dec_int]: Decode a variable-width, decimal signed integer
 //! - [`dec_uint`][crate::ascii::dec_uint]: Decode a variable-width, decimal unsigned integer
 //! - [`hex_uint`][crate::ascii::hex_uint]: Decode a variable-width, hexadecimal integer
 //!
 //! - [`take_escaped`][crate::ascii::take_escaped]: Recognize the input slice with escaped characters
 //! - [`escaped_transform`][crate::ascii::escaped_transform]: Parse escaped characters, unescaping them
+//! - [`ident_except`][crate::ascii::ident_except]: Parse an identifier, rejecting reserved words
+//! - [`caseless_keyword`][crate::ascii::caseless_keyword]: Match an identifier against keywords, ignoring case
+//! - [`contextual_keyword`][crate::ascii::contextual_keyword]: Match an identifier as a keyword only where the keyword is active
+//! - [`contextual_ident`][crate::ascii::contextual_ident]: Parse an identifier, rejecting a keyword only where it is active
+//! - [`suffixed`][crate::ascii::suffixed]: Parse a number followed by an optional suffix, like `u8` or `px`
 //!
 //! ### Character test functions
 //!
@@ -132,6 +167,9 @@
 //! - [`length_repeat`][crate::binary::length_repeat] Gets a number from the first parser, then applies the second parser that many times
 //! - [`length_take`][crate::binary::length_take]: Gets a number from the first parser, then takes a subslice of the input of that size, and returns that subslice
 //! - [`length_and_then`][crate::binary::length_and_then]: Gets a number from the first parser, takes a subslice of the input of that size, then applies the second parser on that subslice. If the second parser returns `Incomplete`, `length_value` will return an error
+//! - [`tlv`][crate::binary::tlv]: Parses a tag and length, then the value with a parser chosen by the tag, capturing values of unknown tags as raw slices
+//! - [`versioned`][crate::binary::versioned]: Parses a format version, then the body with a parser chosen by the version, noting the version in errors
+//! - [`checksummed`][crate::binary::checksummed]: Applies a parser, then verifies a trailing checksum of the bytes it consumed
 //!
 //! ### Integers
 //!
@@ -142,7 +180,8 @@
 //!   [`u64`][crate::binary::u64] are combinators that take as argument a
 //!   [`winnow::binary::Endianness`][crate::binary::Endianness], like this: `i16(endianness)`. If the
 //!   parameter is `winnow::binary::Endianness::Big`, parse a big endian `i16` integer, otherwise a
-//!   little endian `i16` integer.
+//!   little endian `i16` integer. [`uint_var`][crate::binary::uint_var] also takes the width in
+//!   bytes, for integers whose width is only known at runtime.
 //! - **fixed endianness**: The functions are prefixed by `be_` for big endian numbers, and by `le_` for little endian numbers, and the suffix is the type they parse to. As an example, `be_u32` parses a big endian unsigned integer stored in 32 bits.
 //!   - [`be_f32`][crate::binary::be_f32], [`be_f64`][crate::binary::be_f64]: Big endian floating point numbers
 //!   - [`le_f32`][crate::binary::le_f32], [`le_f64`][crate::binary::le_f64]: Little endian floating point numbers
@@ -158,13 +197,21 @@
 //! - [`take`][crate::binary::bits::take]: Take a set number of bits
 //! - [`pattern`][crate::binary::bits::pattern]: Check if a set number of bits matches a pattern
 //! - [`bool`][crate::binary::bits::bool]: Match any one bit
+//! - [`lsb0`][crate::binary::bits::lsb0]: The above, reading the least significant bit first
 
 mod branch;
 mod core;
 mod debug;
+#[cfg(feature = "alloc")]
+mod declare;
 mod multi;
 mod parser;
+#[cfg(feature = "alloc")]
+mod precedence;
+#[cfg(feature = "alloc")]
+mod profile;
 mod sequence;
+mod table;
 
 #[cfg(test)]
 mod tests;
@@ -172,9 +219,16 @@ mod tests;
 pub use self::branch::*;
 pub use self::core::*;
 pub use self::debug::*;
+#[cfg(feature = "alloc")]
+pub use self::declare::*;
 pub use self::multi::*;
 pub use self::parser::*;
+#[cfg(feature = "alloc")]
+pub use self::precedence::*;
+#[cfg(feature = "alloc")]
+pub use self::profile::*;
 pub use self::sequence::*;
+pub use self::table::*;
 
 #[allow(unused_imports)]
 use crate::Parser;
@@ -1,12 +1,17 @@
 //! Combinators applying their child parser multiple times
 
+use crate::ascii::space0;
 use crate::combinator::trace;
 use crate::error::ErrMode;
 use crate::error::ErrorKind;
 use crate::error::ParserError;
+use crate::stream::accumulate;
 use crate::stream::Accumulate;
+use crate::stream::AsChar;
+use crate::stream::BoundedVec;
 use crate::stream::Range;
 use crate::stream::Stream;
+use crate::stream::StreamIsPartial;
 use crate::PResult;
 use crate::Parser;
 
@@ -19,11 +24,16 @@ use crate::Parser;
 /// (e.g. with [`.map(|()| ())`][Parser::map])
 /// and then [`Parser::take`].
 ///
+/// Without `alloc`, accumulate into a fixed-capacity container like
+/// [`BoundedVec`] or, with the `heapless` feature, `heapless::Vec`.  Running out of room is an
+/// [`ErrMode::Cut`] with [`ErrorKind::Many`].
+///
 /// <div class="warning">
 ///
 /// **Warning:** If the parser passed to `repeat` accepts empty inputs
-/// (like `alpha0` or `digit0`), `repeat` will return an error,
-/// to prevent going into an infinite loop.
+/// (like `alpha0` or `digit0`), `repeat` will return an [`ErrorKind::InfiniteLoop`] error,
+/// to prevent going into an infinite loop.  To intentionally accept empty matches, see
+/// [`Repeat::allow_empty`].
 ///
 /// </div>
 ///
@@ -125,6 +135,7 @@ where
     Repeat {
         occurrences: occurrences.into(),
         parser,
+        max_empty: 0,
         i: Default::default(),
         o: Default::default(),
         c: Default::default(),
@@ -142,12 +153,56 @@ where
 {
     occurrences: Range,
     parser: P,
+    max_empty: usize,
     i: core::marker::PhantomData<I>,
     o: core::marker::PhantomData<O>,
     c: core::marker::PhantomData<C>,
     e: core::marker::PhantomData<E>,
 }
 
+impl<P, I, O, C, E> Repeat<P, I, O, C, E>
+where
+    P: Parser<I, O, E>,
+    I: Stream,
+    C: Accumulate<O>,
+    E: ParserError<I>,
+{
+    /// Allow the parser to succeed without consuming input up to `max_empty` times in a row
+    ///
+    /// By default, a parser that succeeds without consuming input would repeat forever, so it
+    /// is reported as an [`ErrMode::Cut`] with [`ErrorKind::InfiniteLoop`].  With this, those
+    /// matches are accumulated like any other until `max_empty` of them happen in a row, at which
+    /// point the repetition stops as if the parser had backtracked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "std")] {
+    /// # use winnow::prelude::*;
+    /// use winnow::combinator::opt;
+    /// use winnow::combinator::repeat;
+    /// use winnow::combinator::terminated;
+    /// use winnow::ascii::digit1;
+    ///
+    /// // Fields may be empty, like in `1,,3,`
+    /// fn fields<'i>(s: &mut &'i str) -> PResult<Vec<Option<&'i str>>> {
+    ///   repeat(0.., terminated(opt(digit1), opt(',')))
+    ///     .allow_empty(1)
+    ///     .parse_next(s)
+    /// }
+    ///
+    /// assert_eq!(fields.parse_peek("1,,3,"), Ok(("", vec![Some("1"), None, Some("3"), None])));
+    /// // The second empty match in a row stops the repetition
+    /// assert_eq!(fields.parse_peek("a"), Ok(("a", vec![None])));
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn allow_empty(mut self, max_empty: usize) -> Self {
+        self.max_empty = max_empty;
+        self
+    }
+}
+
 impl<ParseNext, Input, Output, Error> Repeat<ParseNext, Input, Output, (), Error>
 where
     ParseNext: Parser<Input, Output, Error>,
@@ -282,6 +337,72 @@ where
             }
         })
     }
+
+    /// Repeats the embedded parser, calling `op` to gather the results until it breaks
+    ///
+    /// Like [`Repeat::fold`], except `op` returns a [`ControlFlow`][core::ops::ControlFlow]:
+    /// [`Continue`][core::ops::ControlFlow::Continue] keeps repeating while
+    /// [`Break`][core::ops::ControlFlow::Break] stops right after the value that was just
+    /// folded in, leaving the rest of the input unparsed.  This lets the accumulated state end the
+    /// repetition, like after a size budget is reached.
+    ///
+    /// Breaking before the minimum number of repetitions fails with [`ErrorKind::Many`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// use std::ops::ControlFlow;
+    /// use winnow::combinator::repeat;
+    /// use winnow::token::take;
+    ///
+    /// // Collect 4-byte chunks until at least 10 bytes have been read
+    /// fn payload<'i>(s: &mut &'i [u8]) -> PResult<Vec<&'i [u8]>> {
+    ///   repeat(0.., take(4usize))
+    ///     .fold_while(Vec::new, |mut acc: Vec<&[u8]>, chunk| {
+    ///       acc.push(chunk);
+    ///       if 10 <= acc.iter().map(|c| c.len()).sum::<usize>() {
+    ///         ControlFlow::Break(acc)
+    ///       } else {
+    ///         ControlFlow::Continue(acc)
+    ///       }
+    ///     })
+    ///     .parse_next(s)
+    /// }
+    ///
+    /// let input = &b"aaaabbbbccccdddd"[..];
+    /// assert_eq!(
+    ///   payload.parse_peek(input),
+    ///   Ok((&b"dddd"[..], vec![&b"aaaa"[..], &b"bbbb"[..], &b"cccc"[..]]))
+    /// );
+    /// assert_eq!(payload.parse_peek(&b"aaaab"[..]), Ok((&b"b"[..], vec![&b"aaaa"[..]])));
+    /// ```
+    #[doc(alias = "try_fold")]
+    #[inline(always)]
+    pub fn fold_while<Init, Op, Result>(
+        mut self,
+        mut init: Init,
+        mut op: Op,
+    ) -> impl Parser<Input, Result, Error>
+    where
+        Init: FnMut() -> Result,
+        Op: FnMut(Result, Output) -> core::ops::ControlFlow<Result, Result>,
+    {
+        let Range {
+            start_inclusive,
+            end_inclusive,
+        } = self.occurrences;
+        trace("repeat_fold_while", move |i: &mut Input| {
+            fold_while_m_n_(
+                start_inclusive,
+                end_inclusive.unwrap_or(usize::MAX),
+                &mut self.parser,
+                &mut init,
+                &mut op,
+                i,
+            )
+        })
+    }
 }
 
 impl<P, I, O, C, E> Parser<I, C, E> for Repeat<P, I, O, C, E>
@@ -297,8 +418,16 @@ where
             start_inclusive,
             end_inclusive,
         } = self.occurrences;
+        let max_empty = self.max_empty;
         trace("repeat", move |i: &mut I| {
             match (start_inclusive, end_inclusive) {
+                _ if 0 < max_empty => repeat_empty_(
+                    start_inclusive,
+                    end_inclusive.unwrap_or(usize::MAX),
+                    max_empty,
+                    &mut self.parser,
+                    i,
+                ),
                 (0, None) => repeat0_(&mut self.parser, i),
                 (1, None) => repeat1_(&mut self.parser, i),
                 (start, end) if Some(start) == end => repeat_n_(start, &mut self.parser, i),
@@ -329,10 +458,10 @@ where
             Ok(o) => {
                 // infinite loop check: the parser must always consume
                 if i.eof_offset() == len {
-                    return Err(ErrMode::assert(i, "`repeat` parsers must always consume"));
+                    return Err(infinite_loop(i));
                 }
 
-                acc.accumulate(o);
+                accumulate(&mut acc, o, i)?;
             }
         }
     }
@@ -350,7 +479,7 @@ where
         Err(e) => Err(e.append(i, &start, ErrorKind::Many)),
         Ok(o) => {
             let mut acc = C::initial(None);
-            acc.accumulate(o);
+            accumulate(&mut acc, o, i)?;
 
             loop {
                 let start = i.checkpoint();
@@ -364,10 +493,10 @@ where
                     Ok(o) => {
                         // infinite loop check: the parser must always consume
                         if i.eof_offset() == len {
-                            return Err(ErrMode::assert(i, "`repeat` parsers must always consume"));
+                            return Err(infinite_loop(i));
                         }
 
-                        acc.accumulate(o);
+                        accumulate(&mut acc, o, i)?;
                     }
                 }
             }
@@ -391,10 +520,10 @@ where
             Ok(o) => {
                 // infinite loop check: the parser must always consume
                 if i.eof_offset() == len {
-                    return Err(ErrMode::assert(i, "`repeat` parsers must always consume"));
+                    return Err(infinite_loop(i));
                 }
 
-                res.accumulate(o);
+                accumulate(&mut res, o, i)?;
             }
             Err(e) => {
                 return Err(e.append(i, &start, ErrorKind::Many));
@@ -427,13 +556,10 @@ where
             Ok(value) => {
                 // infinite loop check: the parser must always consume
                 if input.eof_offset() == len {
-                    return Err(ErrMode::assert(
-                        input,
-                        "`repeat` parsers must always consume",
-                    ));
+                    return Err(infinite_loop(input));
                 }
 
-                res.accumulate(value);
+                accumulate(&mut res, value, input)?;
             }
             Err(ErrMode::Backtrack(e)) => {
                 if count < min {
@@ -452,218 +578,556 @@ where
     Ok(res)
 }
 
-/// [`Accumulate`] the output of parser `f` into a container, like `Vec`, until the parser `g`
-/// produces a result.
-///
-/// Returns a tuple of the results of `f` in a `Vec` and the result of `g`.
-///
-/// `f` keeps going so long as `g` produces [`ErrMode::Backtrack`]. To instead chain an error up, see [`cut_err`][crate::combinator::cut_err].
-///
-/// To take a series of tokens, [`Accumulate`] into a `()`
-/// (e.g. with [`.map(|()| ())`][Parser::map])
-/// and then [`Parser::take`].
-///
-/// See also
-/// - [`take_till`][crate::token::take_till] for recognizing up-to a member of a [set of tokens][crate::stream::ContainsToken]
-/// - [`take_until`][crate::token::take_until] for recognizing up-to a [`literal`][crate::token::literal] (w/ optional simd optimizations)
-///
-/// # Example
-///
-/// ```rust
-/// # #[cfg(feature = "std")] {
-/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
-/// # use winnow::prelude::*;
-/// use winnow::combinator::repeat_till;
-///
-/// fn parser(s: &str) -> IResult<&str, (Vec<&str>, &str)> {
-///   repeat_till(0.., "abc", "end").parse_peek(s)
-/// };
-///
-/// assert_eq!(parser("abcabcend"), Ok(("", (vec!["abc", "abc"], "end"))));
-/// assert_eq!(parser("abc123end"), Err(ErrMode::Backtrack(InputError::new("123end", ErrorKind::Tag))));
-/// assert_eq!(parser("123123end"), Err(ErrMode::Backtrack(InputError::new("123123end", ErrorKind::Tag))));
-/// assert_eq!(parser(""), Err(ErrMode::Backtrack(InputError::new("", ErrorKind::Tag))));
-/// assert_eq!(parser("abcendefg"), Ok(("efg", (vec!["abc"], "end"))));
-/// # }
-/// ```
-#[doc(alias = "many_till0")]
-pub fn repeat_till<Input, Output, Accumulator, Terminator, Error, ParseNext, TerminatorParser>(
-    occurrences: impl Into<Range>,
-    mut parse: ParseNext,
-    mut terminator: TerminatorParser,
-) -> impl Parser<Input, (Accumulator, Terminator), Error>
-where
-    Input: Stream,
-    Accumulator: Accumulate<Output>,
-    ParseNext: Parser<Input, Output, Error>,
-    TerminatorParser: Parser<Input, Terminator, Error>,
-    Error: ParserError<Input>,
-{
-    let Range {
-        start_inclusive,
-        end_inclusive,
-    } = occurrences.into();
-    trace("repeat_till", move |i: &mut Input| {
-        match (start_inclusive, end_inclusive) {
-            (0, None) => repeat_till0_(&mut parse, &mut terminator, i),
-            (start, end) => repeat_till_m_n_(
-                start,
-                end.unwrap_or(usize::MAX),
-                &mut parse,
-                &mut terminator,
-                i,
-            ),
-        }
-    })
-}
-
-fn repeat_till0_<I, O, C, P, E, F, G>(f: &mut F, g: &mut G, i: &mut I) -> PResult<(C, P), E>
-where
-    I: Stream,
-    C: Accumulate<O>,
-    F: Parser<I, O, E>,
-    G: Parser<I, P, E>,
-    E: ParserError<I>,
-{
-    let mut res = C::initial(None);
-    loop {
-        let start = i.checkpoint();
-        let len = i.eof_offset();
-        match g.parse_next(i) {
-            Ok(o) => return Ok((res, o)),
-            Err(ErrMode::Backtrack(_)) => {
-                i.reset(&start);
-                match f.parse_next(i) {
-                    Err(e) => return Err(e.append(i, &start, ErrorKind::Many)),
-                    Ok(o) => {
-                        // infinite loop check: the parser must always consume
-                        if i.eof_offset() == len {
-                            return Err(ErrMode::assert(i, "`repeat` parsers must always consume"));
-                        }
-
-                        res.accumulate(o);
-                    }
-                }
-            }
-            Err(e) => return Err(e),
-        }
-    }
-}
-
-fn repeat_till_m_n_<I, O, C, P, E, F, G>(
+fn repeat_empty_<I, O, C, E, F>(
     min: usize,
     max: usize,
-    f: &mut F,
-    g: &mut G,
-    i: &mut I,
-) -> PResult<(C, P), E>
+    max_empty: usize,
+    parse: &mut F,
+    input: &mut I,
+) -> PResult<C, E>
 where
     I: Stream,
     C: Accumulate<O>,
     F: Parser<I, O, E>,
-    G: Parser<I, P, E>,
     E: ParserError<I>,
 {
     if min > max {
         return Err(ErrMode::assert(
-            i,
+            input,
             "range should be ascending, rather than descending",
         ));
     }
 
     let mut res = C::initial(Some(min));
+    let mut empty = 0;
+    for count in 0..max {
+        let start = input.checkpoint();
+        let len = input.eof_offset();
+        match parse.parse_next(input) {
+            Ok(value) => {
+                if input.eof_offset() == len {
+                    empty += 1;
+                    if max_empty < empty {
+                        if count < min {
+                            return Err(ErrMode::from_error_kind(input, ErrorKind::Many));
+                        } else {
+                            return Ok(res);
+                        }
+                    }
+                } else {
+                    empty = 0;
+                }
 
-    let start = i.checkpoint();
-    for _ in 0..min {
-        match f.parse_next(i) {
-            Ok(o) => {
-                res.accumulate(o);
+                accumulate(&mut res, value, input)?;
+            }
+            Err(ErrMode::Backtrack(e)) => {
+                if count < min {
+                    return Err(ErrMode::Backtrack(e.append(input, &start, ErrorKind::Many)));
+                } else {
+                    input.reset(&start);
+                    return Ok(res);
+                }
             }
             Err(e) => {
-                return Err(e.append(i, &start, ErrorKind::Many));
+                return Err(e);
             }
         }
     }
-    for count in min..=max {
-        let start = i.checkpoint();
-        let len = i.eof_offset();
-        match g.parse_next(i) {
-            Ok(o) => return Ok((res, o)),
-            Err(ErrMode::Backtrack(err)) => {
-                if count == max {
-                    return Err(ErrMode::Backtrack(err));
-                }
-                i.reset(&start);
-                match f.parse_next(i) {
-                    Err(e) => {
-                        return Err(e.append(i, &start, ErrorKind::Many));
-                    }
-                    Ok(o) => {
-                        // infinite loop check: the parser must always consume
-                        if i.eof_offset() == len {
-                            return Err(ErrMode::assert(i, "`repeat` parsers must always consume"));
-                        }
 
-                        res.accumulate(o);
-                    }
-                }
-            }
-            Err(e) => return Err(e),
-        }
-    }
-    unreachable!()
+    Ok(res)
 }
 
-/// [`Accumulate`] the output of a parser, interleaved with `sep`
-///
-/// This stops when either parser returns [`ErrMode::Backtrack`]. To instead chain an error up, see
-/// [`cut_err`][crate::combinator::cut_err].
-///
-/// To take a series of tokens, [`Accumulate`] into a `()`
-/// (e.g. with [`.map(|()| ())`][Parser::map])
-/// and then [`Parser::take`].
-///
-/// <div class="warning">
+/// A parser in a repetition succeeded without consuming input, so it would repeat forever
+#[inline]
+fn infinite_loop<I, E>(input: &I) -> ErrMode<E>
+where
+    I: Stream,
+    E: ParserError<I>,
+{
+    ErrMode::Cut(E::from_error_kind(input, ErrorKind::InfiniteLoop))
+}
+
+/// Run the parser exactly `N` times, collecting the results into an array
 ///
-/// **Warning:** If the separator parser accepts empty inputs
-/// (like `alpha0` or `digit0`), `separated` will return an error,
-/// to prevent going into an infinite loop.
+/// Unlike [`repeat`], this does not need an [`Accumulate`] container, making it available
+/// without the `alloc` feature.
 ///
-/// </div>
+/// See also [`repeat_bounded`] for a variable number of repetitions.
 ///
 /// # Example
 ///
-/// Zero or more repetitions:
 /// ```rust
-/// # #[cfg(feature = "std")] {
-/// # use winnow::{error::ErrMode, error::ErrorKind, error::Needed};
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
 /// # use winnow::prelude::*;
-/// use winnow::combinator::separated;
+/// use winnow::combinator::repeat_n;
 ///
-/// fn parser(s: &str) -> IResult<&str, Vec<&str>> {
-///   separated(0.., "abc", "|").parse_peek(s)
+/// fn parser(s: &str) -> IResult<&str, [&str; 2]> {
+///   repeat_n("abc").parse_peek(s)
 /// }
 ///
-/// assert_eq!(parser("abc|abc|abc"), Ok(("", vec!["abc", "abc", "abc"])));
-/// assert_eq!(parser("abc123abc"), Ok(("123abc", vec!["abc"])));
-/// assert_eq!(parser("abc|def"), Ok(("|def", vec!["abc"])));
-/// assert_eq!(parser(""), Ok(("", vec![])));
-/// assert_eq!(parser("def|abc"), Ok(("def|abc", vec![])));
-/// # }
+/// assert_eq!(parser("abcabc"), Ok(("", ["abc", "abc"])));
+/// assert_eq!(parser("abc123"), Err(ErrMode::Backtrack(InputError::new("123", ErrorKind::Tag))));
+/// assert_eq!(parser("123123"), Err(ErrMode::Backtrack(InputError::new("123123", ErrorKind::Tag))));
+/// assert_eq!(parser(""), Err(ErrMode::Backtrack(InputError::new("", ErrorKind::Tag))));
+/// assert_eq!(parser("abcabcabc"), Ok(("abc", ["abc", "abc"])));
 /// ```
 ///
-/// One or more repetitions:
+/// Fixed-size fields in binary formats:
+///
 /// ```rust
-/// # #[cfg(feature = "std")] {
-/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
 /// # use winnow::prelude::*;
-/// use winnow::combinator::separated;
+/// use winnow::binary::be_u16;
+/// use winnow::combinator::repeat_n;
 ///
-/// fn parser(s: &str) -> IResult<&str, Vec<&str>> {
-///   separated(1.., "abc", "|").parse_peek(s)
+/// fn ipv6(input: &mut &[u8]) -> PResult<[u16; 8]> {
+///     repeat_n(be_u16).parse_next(input)
 /// }
 ///
-/// assert_eq!(parser("abc|abc|abc"), Ok(("", vec!["abc", "abc", "abc"])));
+/// let mut addr = [0u8; 16];
+/// addr[0] = 0xfe;
+/// addr[1] = 0x80;
+/// addr[15] = 0x01;
+/// assert_eq!(ipv6.parse(&addr[..]), Ok([0xfe80, 0, 0, 0, 0, 0, 0, 1]));
+/// ```
+#[doc(alias = "count")]
+#[doc(alias = "array")]
+#[inline(always)]
+pub fn repeat_n<const N: usize, Input, Output, Error, ParseNext>(
+    mut parser: ParseNext,
+) -> impl Parser<Input, [Output; N], Error>
+where
+    Input: Stream,
+    ParseNext: Parser<Input, Output, Error>,
+    Error: ParserError<Input>,
+{
+    trace("repeat_n", move |i: &mut Input| {
+        let mut res: [Option<Output>; N] = core::array::from_fn(|_| None);
+
+        for elem in res.iter_mut() {
+            let start = i.checkpoint();
+            let len = i.eof_offset();
+            match parser.parse_next(i) {
+                Ok(o) => {
+                    // infinite loop check: the parser must always consume
+                    if i.eof_offset() == len {
+                        return Err(infinite_loop(i));
+                    }
+
+                    *elem = Some(o);
+                }
+                Err(e) => {
+                    return Err(e.append(i, &start, ErrorKind::Many));
+                }
+            }
+        }
+
+        Ok(res.map(|o| o.expect("every element is filled above")))
+    })
+}
+
+/// [`Accumulate`] the output of a parser into a [`BoundedVec`] holding at most `MAX` items
+///
+/// This behaves like [`repeat`] with `occurrences` capped at `MAX`, making it available without
+/// the `alloc` feature.  When `occurrences` starts above `MAX`, there is never room for enough
+/// items, so this fails with an [`ErrMode::Cut`] and [`ErrorKind::Many`] without parsing.
+///
+/// See also [`repeat_n`] for a fixed number of repetitions.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
+/// # use winnow::prelude::*;
+/// use winnow::combinator::repeat_bounded;
+/// use winnow::stream::BoundedVec;
+///
+/// fn parser(s: &str) -> IResult<&str, BoundedVec<&str, 2>> {
+///   repeat_bounded(1.., "abc").parse_peek(s)
+/// }
+///
+/// let (rest, items) = parser("abcabcabc").unwrap();
+/// assert_eq!(rest, "abc");
+/// assert_eq!(items.iter().collect::<Vec<_>>(), [&"abc", &"abc"]);
+///
+/// let (rest, items) = parser("abc123").unwrap();
+/// assert_eq!(rest, "123");
+/// assert_eq!(items.len(), 1);
+///
+/// assert_eq!(parser("123123"), Err(ErrMode::Backtrack(InputError::new("123123", ErrorKind::Tag))));
+///
+/// fn too_many(s: &str) -> IResult<&str, BoundedVec<&str, 2>> {
+///   repeat_bounded(3.., "abc").parse_peek(s)
+/// }
+///
+/// assert_eq!(too_many("abcabcabc"), Err(ErrMode::Cut(InputError::new("abcabcabc", ErrorKind::Many))));
+/// ```
+#[inline(always)]
+pub fn repeat_bounded<const MAX: usize, Input, Output, Error, ParseNext>(
+    occurrences: impl Into<Range>,
+    parser: ParseNext,
+) -> impl Parser<Input, BoundedVec<Output, MAX>, Error>
+where
+    Input: Stream,
+    ParseNext: Parser<Input, Output, Error>,
+    Error: ParserError<Input>,
+{
+    let Range {
+        start_inclusive,
+        end_inclusive,
+    } = occurrences.into();
+    let end_inclusive = end_inclusive.map(|end| end.min(MAX)).unwrap_or(MAX);
+    let mut parser = repeat(start_inclusive..=end_inclusive, parser);
+    trace("repeat_bounded", move |i: &mut Input| {
+        if MAX < start_inclusive {
+            return Err(ErrMode::Cut(Error::from_error_kind(i, ErrorKind::Many)));
+        }
+        parser.parse_next(i)
+    })
+}
+
+/// [`Accumulate`] the output of a parser built for each repetition from its index
+///
+/// This behaves like [`repeat`] except `make` is called with the zero-based index of every
+/// repetition, allowing positional logic, like a header row or per-column field widths, without
+/// external mutable state.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "std")] {
+/// # use winnow::prelude::*;
+/// use winnow::combinator::repeat_indexed;
+/// use winnow::token::take;
+///
+/// // Each field is one character wider than the one before it
+/// fn fields<'i>(s: &mut &'i str) -> PResult<Vec<&'i str>> {
+///   repeat_indexed(0..=3, |index| take(index + 1)).parse_next(s)
+/// }
+///
+/// assert_eq!(fields.parse_peek("abbccc!"), Ok(("!", vec!["a", "bb", "ccc"])));
+/// assert_eq!(fields.parse_peek("abb"), Ok(("", vec!["a", "bb"])));
+/// # }
+/// ```
+#[doc(alias = "enumerate")]
+#[inline(always)]
+pub fn repeat_indexed<Input, Output, Accumulator, Error, ParseNext, Make>(
+    occurrences: impl Into<Range>,
+    mut make: Make,
+) -> impl Parser<Input, Accumulator, Error>
+where
+    Input: Stream,
+    Accumulator: Accumulate<Output>,
+    Make: FnMut(usize) -> ParseNext,
+    ParseNext: Parser<Input, Output, Error>,
+    Error: ParserError<Input>,
+{
+    let Range {
+        start_inclusive,
+        end_inclusive,
+    } = occurrences.into();
+    trace("repeat_indexed", move |input: &mut Input| {
+        repeat_indexed_(
+            start_inclusive,
+            end_inclusive.unwrap_or(usize::MAX),
+            &mut make,
+            input,
+        )
+    })
+}
+
+fn repeat_indexed_<I, O, C, E, M, F>(
+    min: usize,
+    max: usize,
+    make: &mut M,
+    input: &mut I,
+) -> PResult<C, E>
+where
+    I: Stream,
+    C: Accumulate<O>,
+    M: FnMut(usize) -> F,
+    F: Parser<I, O, E>,
+    E: ParserError<I>,
+{
+    if min > max {
+        return Err(ErrMode::assert(
+            input,
+            "range should be ascending, rather than descending",
+        ));
+    }
+
+    let mut res = C::initial(Some(min));
+    for index in 0..max {
+        let start = input.checkpoint();
+        let len = input.eof_offset();
+        match make(index).parse_next(input) {
+            Ok(value) => {
+                // infinite loop check: the parser must always consume
+                if input.eof_offset() == len {
+                    return Err(infinite_loop(input));
+                }
+
+                accumulate(&mut res, value, input)?;
+            }
+            Err(ErrMode::Backtrack(e)) => {
+                if index < min {
+                    return Err(ErrMode::Backtrack(e.append(input, &start, ErrorKind::Many)));
+                } else {
+                    input.reset(&start);
+                    return Ok(res);
+                }
+            }
+            Err(e) => {
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(res)
+}
+
+/// [`Accumulate`] the output of parser `f` into a container, like `Vec`, until the parser `g`
+/// produces a result.
+///
+/// Returns a tuple of the results of `f` in a `Vec` and the result of `g`.
+///
+/// `f` keeps going so long as `g` produces [`ErrMode::Backtrack`]. To instead chain an error up, see [`cut_err`][crate::combinator::cut_err].
+///
+/// `occurrences` bounds how many times `f` applies, like with [`repeat`]: `g` is only tried once
+/// `f` has applied the minimum number of times, and if `g` still fails after the maximum, this
+/// fails with [`ErrorKind::Many`] rather than applying `f` again.
+///
+/// To take a series of tokens, [`Accumulate`] into a `()`
+/// (e.g. with [`.map(|()| ())`][Parser::map])
+/// and then [`Parser::take`].
+///
+/// See also
+/// - [`take_till`][crate::token::take_till] for recognizing up-to a member of a [set of tokens][crate::stream::ContainsToken]
+/// - [`take_until`][crate::token::take_until] for recognizing up-to a [`literal`][crate::token::literal] (w/ optional simd optimizations)
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "std")] {
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
+/// # use winnow::prelude::*;
+/// use winnow::combinator::repeat_till;
+///
+/// fn parser(s: &str) -> IResult<&str, (Vec<&str>, &str)> {
+///   repeat_till(0.., "abc", "end").parse_peek(s)
+/// };
+///
+/// assert_eq!(parser("abcabcend"), Ok(("", (vec!["abc", "abc"], "end"))));
+/// assert_eq!(parser("abc123end"), Err(ErrMode::Backtrack(InputError::new("123end", ErrorKind::Tag))));
+/// assert_eq!(parser("123123end"), Err(ErrMode::Backtrack(InputError::new("123123end", ErrorKind::Tag))));
+/// assert_eq!(parser(""), Err(ErrMode::Backtrack(InputError::new("", ErrorKind::Tag))));
+/// assert_eq!(parser("abcendefg"), Ok(("efg", (vec!["abc"], "end"))));
+/// # }
+/// ```
+///
+/// Bounded repetitions:
+/// ```rust
+/// # #[cfg(feature = "std")] {
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
+/// # use winnow::prelude::*;
+/// use winnow::combinator::repeat_till;
+///
+/// fn parser(s: &str) -> IResult<&str, (Vec<&str>, &str)> {
+///   repeat_till(0..=2, "abc", "end").parse_peek(s)
+/// };
+///
+/// assert_eq!(parser("end"), Ok(("", (vec![], "end"))));
+/// assert_eq!(parser("abcabcend"), Ok(("", (vec!["abc", "abc"], "end"))));
+/// assert_eq!(parser("abcabcabcend"), Err(ErrMode::Backtrack(InputError::new("abcend", ErrorKind::Tag))));
+/// # }
+/// ```
+#[doc(alias = "many_till0")]
+#[doc(alias = "many_till_m_n")]
+pub fn repeat_till<Input, Output, Accumulator, Terminator, Error, ParseNext, TerminatorParser>(
+    occurrences: impl Into<Range>,
+    mut parse: ParseNext,
+    mut terminator: TerminatorParser,
+) -> impl Parser<Input, (Accumulator, Terminator), Error>
+where
+    Input: Stream,
+    Accumulator: Accumulate<Output>,
+    ParseNext: Parser<Input, Output, Error>,
+    TerminatorParser: Parser<Input, Terminator, Error>,
+    Error: ParserError<Input>,
+{
+    let Range {
+        start_inclusive,
+        end_inclusive,
+    } = occurrences.into();
+    trace("repeat_till", move |i: &mut Input| {
+        match (start_inclusive, end_inclusive) {
+            (0, None) => repeat_till0_(&mut parse, &mut terminator, i),
+            (start, end) => repeat_till_m_n_(
+                start,
+                end.unwrap_or(usize::MAX),
+                &mut parse,
+                &mut terminator,
+                i,
+            ),
+        }
+    })
+}
+
+fn repeat_till0_<I, O, C, P, E, F, G>(f: &mut F, g: &mut G, i: &mut I) -> PResult<(C, P), E>
+where
+    I: Stream,
+    C: Accumulate<O>,
+    F: Parser<I, O, E>,
+    G: Parser<I, P, E>,
+    E: ParserError<I>,
+{
+    let mut res = C::initial(None);
+    loop {
+        let start = i.checkpoint();
+        let len = i.eof_offset();
+        match g.parse_next(i) {
+            Ok(o) => return Ok((res, o)),
+            Err(ErrMode::Backtrack(_)) => {
+                i.reset(&start);
+                match f.parse_next(i) {
+                    Err(e) => return Err(e.append(i, &start, ErrorKind::Many)),
+                    Ok(o) => {
+                        // infinite loop check: the parser must always consume
+                        if i.eof_offset() == len {
+                            return Err(infinite_loop(i));
+                        }
+
+                        accumulate(&mut res, o, i)?;
+                    }
+                }
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn repeat_till_m_n_<I, O, C, P, E, F, G>(
+    min: usize,
+    max: usize,
+    f: &mut F,
+    g: &mut G,
+    i: &mut I,
+) -> PResult<(C, P), E>
+where
+    I: Stream,
+    C: Accumulate<O>,
+    F: Parser<I, O, E>,
+    G: Parser<I, P, E>,
+    E: ParserError<I>,
+{
+    if min > max {
+        return Err(ErrMode::assert(
+            i,
+            "range should be ascending, rather than descending",
+        ));
+    }
+
+    let mut res = C::initial(Some(min));
+
+    for _ in 0..min {
+        let start = i.checkpoint();
+        let len = i.eof_offset();
+        match f.parse_next(i) {
+            Ok(o) => {
+                // infinite loop check: the parser must always consume
+                if i.eof_offset() == len {
+                    return Err(infinite_loop(i));
+                }
+
+                accumulate(&mut res, o, i)?;
+            }
+            Err(e) => {
+                return Err(e.append(i, &start, ErrorKind::Many));
+            }
+        }
+    }
+    for count in min..=max {
+        let start = i.checkpoint();
+        let len = i.eof_offset();
+        match g.parse_next(i) {
+            Ok(o) => return Ok((res, o)),
+            Err(ErrMode::Backtrack(err)) => {
+                if count == max {
+                    return Err(ErrMode::Backtrack(err.append(i, &start, ErrorKind::Many)));
+                }
+                i.reset(&start);
+                match f.parse_next(i) {
+                    Err(e) => {
+                        return Err(e.append(i, &start, ErrorKind::Many));
+                    }
+                    Ok(o) => {
+                        // infinite loop check: the parser must always consume
+                        if i.eof_offset() == len {
+                            return Err(infinite_loop(i));
+                        }
+
+                        accumulate(&mut res, o, i)?;
+                    }
+                }
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    unreachable!()
+}
+
+/// [`Accumulate`] the output of a parser, interleaved with `sep`
+///
+/// This stops when either parser returns [`ErrMode::Backtrack`]. To instead chain an error up, see
+/// [`cut_err`][crate::combinator::cut_err].
+///
+/// To take a series of tokens, [`Accumulate`] into a `()`
+/// (e.g. with [`.map(|()| ())`][Parser::map])
+/// and then [`Parser::take`].
+///
+/// A trailing separator is left unparsed; to allow or require one, see [`separated_trailing`].
+///
+/// <div class="warning">
+///
+/// **Warning:** If the separator parser accepts empty inputs
+/// (like `alpha0` or `digit0`), `separated` will return an [`ErrorKind::InfiniteLoop`] error,
+/// to prevent going into an infinite loop.  To intentionally accept empty matches, see
+/// [`Separated::allow_empty`].
+///
+/// </div>
+///
+/// # Example
+///
+/// Zero or more repetitions:
+/// ```rust
+/// # #[cfg(feature = "std")] {
+/// # use winnow::{error::ErrMode, error::ErrorKind, error::Needed};
+/// # use winnow::prelude::*;
+/// use winnow::combinator::separated;
+///
+/// fn parser(s: &str) -> IResult<&str, Vec<&str>> {
+///   separated(0.., "abc", "|").parse_peek(s)
+/// }
+///
+/// assert_eq!(parser("abc|abc|abc"), Ok(("", vec!["abc", "abc", "abc"])));
+/// assert_eq!(parser("abc123abc"), Ok(("123abc", vec!["abc"])));
+/// assert_eq!(parser("abc|def"), Ok(("|def", vec!["abc"])));
+/// assert_eq!(parser(""), Ok(("", vec![])));
+/// assert_eq!(parser("def|abc"), Ok(("def|abc", vec![])));
+/// # }
+/// ```
+///
+/// One or more repetitions:
+/// ```rust
+/// # #[cfg(feature = "std")] {
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
+/// # use winnow::prelude::*;
+/// use winnow::combinator::separated;
+///
+/// fn parser(s: &str) -> IResult<&str, Vec<&str>> {
+///   separated(1.., "abc", "|").parse_peek(s)
+/// }
+///
+/// assert_eq!(parser("abc|abc|abc"), Ok(("", vec!["abc", "abc", "abc"])));
 /// assert_eq!(parser("abc123abc"), Ok(("123abc", vec!["abc"])));
 /// assert_eq!(parser("abc|def"), Ok(("|def", vec!["abc"])));
 /// assert_eq!(parser(""), Err(ErrMode::Backtrack(InputError::new("", ErrorKind::Tag))));
@@ -716,9 +1180,9 @@ where
 #[inline(always)]
 pub fn separated<Input, Output, Accumulator, Sep, Error, ParseNext, SepParser>(
     occurrences: impl Into<Range>,
-    mut parser: ParseNext,
-    mut separator: SepParser,
-) -> impl Parser<Input, Accumulator, Error>
+    parser: ParseNext,
+    separator: SepParser,
+) -> Separated<ParseNext, SepParser, Input, Output, Sep, Accumulator, Error>
 where
     Input: Stream,
     Accumulator: Accumulate<Output>,
@@ -726,26 +1190,121 @@ where
     SepParser: Parser<Input, Sep, Error>,
     Error: ParserError<Input>,
 {
-    let Range {
-        start_inclusive,
-        end_inclusive,
-    } = occurrences.into();
-    trace("separated", move |input: &mut Input| {
-        match (start_inclusive, end_inclusive) {
-            (0, None) => separated0_(&mut parser, &mut separator, input),
-            (1, None) => separated1_(&mut parser, &mut separator, input),
-            (start, end) if Some(start) == end => {
-                separated_n_(start, &mut parser, &mut separator, input)
+    Separated {
+        occurrences: occurrences.into(),
+        parser,
+        separator,
+        max_empty: 0,
+        i: Default::default(),
+        o: Default::default(),
+        o2: Default::default(),
+        c: Default::default(),
+        e: Default::default(),
+    }
+}
+
+/// Implementation of [`separated`]
+pub struct Separated<P, S, I, O, O2, C, E>
+where
+    P: Parser<I, O, E>,
+    S: Parser<I, O2, E>,
+    I: Stream,
+    C: Accumulate<O>,
+    E: ParserError<I>,
+{
+    occurrences: Range,
+    parser: P,
+    separator: S,
+    max_empty: usize,
+    i: core::marker::PhantomData<I>,
+    o: core::marker::PhantomData<O>,
+    o2: core::marker::PhantomData<O2>,
+    c: core::marker::PhantomData<C>,
+    e: core::marker::PhantomData<E>,
+}
+
+impl<P, S, I, O, O2, C, E> Separated<P, S, I, O, O2, C, E>
+where
+    P: Parser<I, O, E>,
+    S: Parser<I, O2, E>,
+    I: Stream,
+    C: Accumulate<O>,
+    E: ParserError<I>,
+{
+    /// Allow a separator and value to succeed without consuming input up to `max_empty` times in
+    /// a row
+    ///
+    /// Like [`Repeat::allow_empty`], this accumulates values after an empty separator until
+    /// `max_empty` of them in a row consumed nothing, at which point the repetition stops as if
+    /// the separator had backtracked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "std")] {
+    /// # use winnow::prelude::*;
+    /// use winnow::ascii::alpha1;
+    /// use winnow::ascii::digit1;
+    /// use winnow::ascii::space0;
+    /// use winnow::combinator::alt;
+    /// use winnow::combinator::separated;
+    ///
+    /// // Tokens are separated by spaces, or by nothing between letters and digits
+    /// fn tokens<'i>(s: &mut &'i str) -> PResult<Vec<&'i str>> {
+    ///   separated(0.., alt((alpha1, digit1)), space0).allow_empty(1).parse_next(s)
+    /// }
+    ///
+    /// assert_eq!(tokens.parse_peek("ab12 cd!"), Ok(("!", vec!["ab", "12", "cd"])));
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn allow_empty(mut self, max_empty: usize) -> Self {
+        self.max_empty = max_empty;
+        self
+    }
+}
+
+impl<P, S, I, O, O2, C, E> Parser<I, C, E> for Separated<P, S, I, O, O2, C, E>
+where
+    P: Parser<I, O, E>,
+    S: Parser<I, O2, E>,
+    I: Stream,
+    C: Accumulate<O>,
+    E: ParserError<I>,
+{
+    #[inline(always)]
+    fn parse_next(&mut self, input: &mut I) -> PResult<C, E> {
+        let Range {
+            start_inclusive,
+            end_inclusive,
+        } = self.occurrences;
+        let max_empty = self.max_empty;
+        trace("separated", move |input: &mut I| {
+            match (start_inclusive, end_inclusive) {
+                _ if 0 < max_empty => separated_empty_(
+                    start_inclusive,
+                    end_inclusive.unwrap_or(usize::MAX),
+                    max_empty,
+                    &mut self.parser,
+                    &mut self.separator,
+                    input,
+                ),
+                (0, None) => separated0_(&mut self.parser, &mut self.separator, input),
+                (1, None) => separated1_(&mut self.parser, &mut self.separator, input),
+                (start, end) if Some(start) == end => {
+                    separated_n_(start, &mut self.parser, &mut self.separator, input)
+                }
+                (start, end) => separated_m_n_(
+                    start,
+                    end.unwrap_or(usize::MAX),
+                    &mut self.parser,
+                    &mut self.separator,
+                    input,
+                ),
             }
-            (start, end) => separated_m_n_(
-                start,
-                end.unwrap_or(usize::MAX),
-                &mut parser,
-                &mut separator,
-                input,
-            ),
-        }
-    })
+        })
+        .parse_next(input)
+    }
 }
 
 fn separated0_<I, O, C, O2, E, P, S>(
@@ -770,7 +1329,7 @@ where
         }
         Err(e) => return Err(e),
         Ok(o) => {
-            acc.accumulate(o);
+            accumulate(&mut acc, o, input)?;
         }
     }
 
@@ -786,10 +1345,7 @@ where
             Ok(_) => {
                 // infinite loop check
                 if input.eof_offset() == len {
-                    return Err(ErrMode::assert(
-                        input,
-                        "`separated` separator parser must always consume",
-                    ));
+                    return Err(infinite_loop(input));
                 }
 
                 match parser.parse_next(input) {
@@ -799,7 +1355,7 @@ where
                     }
                     Err(e) => return Err(e),
                     Ok(o) => {
-                        acc.accumulate(o);
+                        accumulate(&mut acc, o, input)?;
                     }
                 }
             }
@@ -825,7 +1381,7 @@ where
     match parser.parse_next(input) {
         Err(e) => return Err(e),
         Ok(o) => {
-            acc.accumulate(o);
+            accumulate(&mut acc, o, input)?;
         }
     }
 
@@ -841,10 +1397,7 @@ where
             Ok(_) => {
                 // infinite loop check
                 if input.eof_offset() == len {
-                    return Err(ErrMode::assert(
-                        input,
-                        "`separated` separator parser must always consume",
-                    ));
+                    return Err(infinite_loop(input));
                 }
 
                 match parser.parse_next(input) {
@@ -854,7 +1407,7 @@ where
                     }
                     Err(e) => return Err(e),
                     Ok(o) => {
-                        acc.accumulate(o);
+                        accumulate(&mut acc, o, input)?;
                     }
                 }
             }
@@ -887,7 +1440,7 @@ where
             return Err(e.append(input, &start, ErrorKind::Many));
         }
         Ok(o) => {
-            acc.accumulate(o);
+            accumulate(&mut acc, o, input)?;
         }
     }
 
@@ -901,18 +1454,102 @@ where
             Ok(_) => {
                 // infinite loop check
                 if input.eof_offset() == len {
-                    return Err(ErrMode::assert(
-                        input,
-                        "`separated` separator parser must always consume",
-                    ));
+                    return Err(infinite_loop(input));
+                }
+
+                match parser.parse_next(input) {
+                    Err(e) => {
+                        return Err(e.append(input, &start, ErrorKind::Many));
+                    }
+                    Ok(o) => {
+                        accumulate(&mut acc, o, input)?;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(acc)
+}
+
+fn separated_m_n_<I, O, C, O2, E, P, S>(
+    min: usize,
+    max: usize,
+    parser: &mut P,
+    separator: &mut S,
+    input: &mut I,
+) -> PResult<C, E>
+where
+    I: Stream,
+    C: Accumulate<O>,
+    P: Parser<I, O, E>,
+    S: Parser<I, O2, E>,
+    E: ParserError<I>,
+{
+    if min > max {
+        return Err(ErrMode::assert(
+            input,
+            "range should be ascending, rather than descending",
+        ));
+    }
+
+    let mut acc = C::initial(Some(min));
+
+    let start = input.checkpoint();
+    match parser.parse_next(input) {
+        Err(ErrMode::Backtrack(e)) => {
+            if min == 0 {
+                input.reset(&start);
+                return Ok(acc);
+            } else {
+                return Err(ErrMode::Backtrack(e.append(input, &start, ErrorKind::Many)));
+            }
+        }
+        Err(e) => return Err(e),
+        Ok(o) => {
+            accumulate(&mut acc, o, input)?;
+        }
+    }
+
+    for index in 1..max {
+        let start = input.checkpoint();
+        let len = input.eof_offset();
+        match separator.parse_next(input) {
+            Err(ErrMode::Backtrack(e)) => {
+                if index < min {
+                    return Err(ErrMode::Backtrack(e.append(input, &start, ErrorKind::Many)));
+                } else {
+                    input.reset(&start);
+                    return Ok(acc);
+                }
+            }
+            Err(e) => {
+                return Err(e);
+            }
+            Ok(_) => {
+                // infinite loop check
+                if input.eof_offset() == len {
+                    return Err(infinite_loop(input));
                 }
 
                 match parser.parse_next(input) {
+                    Err(ErrMode::Backtrack(e)) => {
+                        if index < min {
+                            return Err(ErrMode::Backtrack(e.append(
+                                input,
+                                &start,
+                                ErrorKind::Many,
+                            )));
+                        } else {
+                            input.reset(&start);
+                            return Ok(acc);
+                        }
+                    }
                     Err(e) => {
-                        return Err(e.append(input, &start, ErrorKind::Many));
+                        return Err(e);
                     }
                     Ok(o) => {
-                        acc.accumulate(o);
+                        accumulate(&mut acc, o, input)?;
                     }
                 }
             }
@@ -922,9 +1559,10 @@ where
     Ok(acc)
 }
 
-fn separated_m_n_<I, O, C, O2, E, P, S>(
+fn separated_empty_<I, O, C, O2, E, P, S>(
     min: usize,
     max: usize,
+    max_empty: usize,
     parser: &mut P,
     separator: &mut S,
     input: &mut I,
@@ -944,6 +1582,9 @@ where
     }
 
     let mut acc = C::initial(Some(min));
+    if max == 0 {
+        return Ok(acc);
+    }
 
     let start = input.checkpoint();
     match parser.parse_next(input) {
@@ -957,59 +1598,377 @@ where
         }
         Err(e) => return Err(e),
         Ok(o) => {
-            acc.accumulate(o);
+            accumulate(&mut acc, o, input)?;
         }
     }
 
-    for index in 1..max {
+    let mut empty = 0;
+    for count in 1..max {
         let start = input.checkpoint();
         let len = input.eof_offset();
-        match separator.parse_next(input) {
+        match (separator.by_ref(), parser.by_ref()).parse_next(input) {
             Err(ErrMode::Backtrack(e)) => {
-                if index < min {
+                if count < min {
                     return Err(ErrMode::Backtrack(e.append(input, &start, ErrorKind::Many)));
                 } else {
                     input.reset(&start);
                     return Ok(acc);
                 }
             }
-            Err(e) => {
-                return Err(e);
+            Err(e) => return Err(e),
+            Ok((_, o)) => {
+                if input.eof_offset() == len {
+                    empty += 1;
+                    if max_empty < empty {
+                        if count < min {
+                            return Err(ErrMode::from_error_kind(input, ErrorKind::Many));
+                        } else {
+                            return Ok(acc);
+                        }
+                    }
+                } else {
+                    empty = 0;
+                }
+
+                accumulate(&mut acc, o, input)?;
+            }
+        }
+    }
+
+    Ok(acc)
+}
+
+/// [`separated`], choosing how a separator after the last value is handled
+///
+/// Many formats, like Rust, JSON5, and TOML arrays, allow a trailing separator.  With
+/// [`TrailingSeparator::Forbid`], this acts like [`separated`], leaving a trailing separator
+/// unparsed.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "std")] {
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}};
+/// # use winnow::prelude::*;
+/// use winnow::ascii::digit1;
+/// use winnow::combinator::delimited;
+/// use winnow::combinator::separated_trailing;
+/// use winnow::combinator::TrailingSeparator;
+///
+/// fn array(s: &str) -> IResult<&str, Vec<&str>> {
+///   delimited('[', separated_trailing(0.., digit1, ',', TrailingSeparator::Allow), ']')
+///     .parse_peek(s)
+/// }
+///
+/// assert_eq!(array("[1,2,3]"), Ok(("", vec!["1", "2", "3"])));
+/// assert_eq!(array("[1,2,3,]"), Ok(("", vec!["1", "2", "3"])));
+/// assert_eq!(array("[]"), Ok(("", vec![])));
+/// assert_eq!(array("[,]"), Err(ErrMode::Backtrack(InputError::new(",]", ErrorKind::Tag))));
+///
+/// fn stmts(s: &str) -> IResult<&str, Vec<&str>> {
+///   separated_trailing(1.., digit1, ';', TrailingSeparator::Require).parse_peek(s)
+/// }
+///
+/// assert_eq!(stmts("1;2;"), Ok(("", vec!["1", "2"])));
+/// assert_eq!(stmts("1;2"), Err(ErrMode::Backtrack(InputError::new("", ErrorKind::Tag))));
+/// # }
+/// ```
+#[doc(alias = "sep_end_by")]
+#[doc(alias = "sep_end_by1")]
+#[doc(alias = "separated_list_trailing")]
+#[inline(always)]
+pub fn separated_trailing<Input, Output, Accumulator, Sep, Error, ParseNext, SepParser>(
+    occurrences: impl Into<Range>,
+    mut parser: ParseNext,
+    mut separator: SepParser,
+    trailing: TrailingSeparator,
+) -> impl Parser<Input, Accumulator, Error>
+where
+    Input: Stream,
+    Accumulator: Accumulate<Output>,
+    ParseNext: Parser<Input, Output, Error>,
+    SepParser: Parser<Input, Sep, Error>,
+    Error: ParserError<Input>,
+{
+    let Range {
+        start_inclusive,
+        end_inclusive,
+    } = occurrences.into();
+    trace("separated_trailing", move |input: &mut Input| {
+        separated_trailing_(
+            start_inclusive,
+            end_inclusive.unwrap_or(usize::MAX),
+            &mut parser,
+            &mut separator,
+            trailing,
+            input,
+        )
+    })
+}
+
+fn separated_trailing_<I, O, C, O2, E, P, S>(
+    min: usize,
+    max: usize,
+    parser: &mut P,
+    separator: &mut S,
+    trailing: TrailingSeparator,
+    input: &mut I,
+) -> PResult<C, E>
+where
+    I: Stream,
+    C: Accumulate<O>,
+    P: Parser<I, O, E>,
+    S: Parser<I, O2, E>,
+    E: ParserError<I>,
+{
+    if min > max {
+        return Err(ErrMode::assert(
+            input,
+            "range should be ascending, rather than descending",
+        ));
+    }
+
+    let mut acc = C::initial(Some(min));
+    // Where a trailing separator, if any, starts
+    let mut after_value = None;
+    let mut count = 0;
+    while count < max {
+        let start = input.checkpoint();
+        let len = input.eof_offset();
+        match parser.parse_next(input) {
+            Err(ErrMode::Backtrack(e)) => {
+                if count < min {
+                    return Err(ErrMode::Backtrack(e.append(input, &start, ErrorKind::Many)));
+                }
+                match (after_value, trailing) {
+                    (Some(after_value), TrailingSeparator::Forbid) => input.reset(&after_value),
+                    _ => input.reset(&start),
+                }
+                return Ok(acc);
+            }
+            Err(e) => return Err(e),
+            Ok(o) => {
+                accumulate(&mut acc, o, input)?;
+                count += 1;
+            }
+        }
+
+        let end = input.checkpoint();
+        if count == max && trailing == TrailingSeparator::Forbid {
+            break;
+        }
+        match separator.parse_next(input) {
+            Err(ErrMode::Backtrack(e)) => {
+                input.reset(&end);
+                if count < min || trailing == TrailingSeparator::Require {
+                    return Err(ErrMode::Backtrack(e.append(input, &end, ErrorKind::Many)));
+                }
+                return Ok(acc);
             }
+            Err(e) => return Err(e),
             Ok(_) => {
                 // infinite loop check
                 if input.eof_offset() == len {
-                    return Err(ErrMode::assert(
-                        input,
-                        "`separated` separator parser must always consume",
-                    ));
+                    return Err(infinite_loop(input));
                 }
+            }
+        }
+        after_value = Some(end);
+    }
 
-                match parser.parse_next(input) {
-                    Err(ErrMode::Backtrack(e)) => {
-                        if index < min {
-                            return Err(ErrMode::Backtrack(e.append(
-                                input,
-                                &start,
-                                ErrorKind::Many,
-                            )));
-                        } else {
-                            input.reset(&start);
-                            return Ok(acc);
+    Ok(acc)
+}
+
+/// [`Accumulate`] `key` `sep` `value` pairs, separated by `pair_sep`, into a container like a map
+///
+/// This covers lists like `k=v;k2=v2` in HTTP headers, connection strings, and config lines.
+/// Spaces and tabs are skipped around the keys, values, and separators.  This stops when a pair or
+/// `pair_sep` returns [`ErrMode::Backtrack`], accepting zero pairs.  To instead chain an error up,
+/// see [`cut_err`][crate::combinator::cut_err].
+///
+/// How a `pair_sep` after the last pair is handled is set with [`KeyValue::trailing`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "std")] {
+/// # use winnow::prelude::*;
+/// use std::collections::HashMap;
+/// use winnow::ascii::alphanumeric1;
+/// use winnow::combinator::key_value;
+/// use winnow::combinator::TrailingSeparator;
+///
+/// fn params(s: &str) -> IResult<&str, HashMap<&str, &str>> {
+///   key_value(alphanumeric1, '=', alphanumeric1, ';').parse_peek(s)
+/// }
+///
+/// let (rest, params) = params("host=db; port = 5432;").unwrap();
+/// assert_eq!(rest, ";");
+/// assert_eq!(params["host"], "db");
+/// assert_eq!(params["port"], "5432");
+///
+/// fn pairs(s: &str) -> IResult<&str, Vec<(&str, &str)>> {
+///   key_value(alphanumeric1, '=', alphanumeric1, ';')
+///     .trailing(TrailingSeparator::Allow)
+///     .parse_peek(s)
+/// }
+///
+/// assert_eq!(pairs("a=1;b=2;"), Ok(("", vec![("a", "1"), ("b", "2")])));
+/// assert_eq!(pairs("a=1;b=2"), Ok(("", vec![("a", "1"), ("b", "2")])));
+/// # }
+/// ```
+#[doc(alias = "key_value_list")]
+#[doc(alias = "pairs")]
+pub fn key_value<
+    Input,
+    Key,
+    Value,
+    Accumulator,
+    Sep,
+    PairSep,
+    Error,
+    KeyParser,
+    SepParser,
+    ValueParser,
+    PairSepParser,
+>(
+    key: KeyParser,
+    sep: SepParser,
+    value: ValueParser,
+    pair_sep: PairSepParser,
+) -> KeyValue<
+    KeyParser,
+    SepParser,
+    ValueParser,
+    PairSepParser,
+    Input,
+    Key,
+    Value,
+    Accumulator,
+    Sep,
+    PairSep,
+    Error,
+>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Accumulator: Accumulate<(Key, Value)>,
+    KeyParser: Parser<Input, Key, Error>,
+    SepParser: Parser<Input, Sep, Error>,
+    ValueParser: Parser<Input, Value, Error>,
+    PairSepParser: Parser<Input, PairSep, Error>,
+    Error: ParserError<Input>,
+{
+    KeyValue {
+        key,
+        sep,
+        value,
+        pair_sep,
+        trailing: TrailingSeparator::Forbid,
+        marker: Default::default(),
+    }
+}
+
+/// Implementation of [`key_value`]
+pub struct KeyValue<KP, SP, VP, PP, I, K, V, C, S, P, E> {
+    key: KP,
+    sep: SP,
+    value: VP,
+    pair_sep: PP,
+    trailing: TrailingSeparator,
+    marker: core::marker::PhantomData<(I, K, V, C, S, P, E)>,
+}
+
+/// How [`separated_trailing`] and [`key_value`] handle a separator after the last value
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TrailingSeparator {
+    /// Leave a trailing separator unparsed
+    #[default]
+    Forbid,
+    /// Consume a trailing separator, if present
+    Allow,
+    /// Backtrack unless every value, including the last, is followed by a separator
+    Require,
+}
+
+impl<KP, SP, VP, PP, I, K, V, C, S, P, E> KeyValue<KP, SP, VP, PP, I, K, V, C, S, P, E> {
+    /// Set how a separator after the last pair is handled, defaulting to
+    /// [`TrailingSeparator::Forbid`]
+    #[inline(always)]
+    pub fn trailing(mut self, trailing: TrailingSeparator) -> Self {
+        self.trailing = trailing;
+        self
+    }
+}
+
+impl<KP, SP, VP, PP, I, K, V, C, S, P, E> Parser<I, C, E>
+    for KeyValue<KP, SP, VP, PP, I, K, V, C, S, P, E>
+where
+    I: StreamIsPartial + Stream,
+    <I as Stream>::Token: AsChar + Clone,
+    C: Accumulate<(K, V)>,
+    KP: Parser<I, K, E>,
+    SP: Parser<I, S, E>,
+    VP: Parser<I, V, E>,
+    PP: Parser<I, P, E>,
+    E: ParserError<I>,
+{
+    #[inline(always)]
+    fn parse_next(&mut self, i: &mut I) -> PResult<C, E> {
+        trace("key_value", move |input: &mut I| {
+            let mut acc = C::initial(None);
+            // Where a trailing separator, if any, starts
+            let mut after_pair = None;
+            loop {
+                let start = input.checkpoint();
+                let len = input.eof_offset();
+                let pair = (
+                    space0,
+                    self.key.by_ref(),
+                    space0,
+                    self.sep.by_ref(),
+                    space0,
+                    self.value.by_ref(),
+                )
+                    .parse_next(input);
+                match pair {
+                    Err(ErrMode::Backtrack(_)) => {
+                        match (after_pair, self.trailing) {
+                            (Some(after_pair), TrailingSeparator::Forbid) => {
+                                input.reset(&after_pair);
+                            }
+                            _ => input.reset(&start),
                         }
+                        return Ok(acc);
                     }
-                    Err(e) => {
-                        return Err(e);
+                    Err(e) => return Err(e),
+                    Ok((_, k, _, _, _, v)) => {
+                        accumulate(&mut acc, (k, v), input)?;
                     }
-                    Ok(o) => {
-                        acc.accumulate(o);
+                }
+
+                let end = input.checkpoint();
+                match (space0, self.pair_sep.by_ref()).parse_next(input) {
+                    Err(ErrMode::Backtrack(e)) => {
+                        input.reset(&end);
+                        if self.trailing == TrailingSeparator::Require {
+                            return Err(ErrMode::Backtrack(e.append(input, &end, ErrorKind::Many)));
+                        }
+                        return Ok(acc);
+                    }
+                    Err(e) => return Err(e),
+                    Ok(_) => {
+                        // infinite loop check
+                        if input.eof_offset() == len {
+                            return Err(infinite_loop(input));
+                        }
                     }
                 }
+                after_pair = Some(end);
             }
-        }
+        })
+        .parse_next(i)
     }
-
-    Ok(acc)
 }
 
 /// Alternates between two parsers, merging the results (left associative)
@@ -1017,6 +1976,9 @@ where
 /// This stops when either parser returns [`ErrMode::Backtrack`]. To instead chain an error up, see
 /// [`cut_err`][crate::combinator::cut_err].
 ///
+/// Each operand is folded in as soon as it is parsed, so this never allocates, unlike collecting
+/// with [`separated`] and folding afterwards.
+///
 /// # Example
 ///
 /// ```rust
@@ -1033,6 +1995,7 @@ where
 /// assert_eq!(parser(""), Err(ErrMode::Backtrack(InputError::new("", ErrorKind::Token))));
 /// assert_eq!(parser("def|abc"), Err(ErrMode::Backtrack(InputError::new("def|abc", ErrorKind::Verify))));
 /// ```
+#[doc(alias = "foldl")]
 pub fn separated_foldl1<Input, Output, Sep, Error, ParseNext, SepParser, Op>(
     mut parser: ParseNext,
     mut sep: SepParser,
@@ -1060,7 +2023,7 @@ where
                 Ok(s) => {
                     // infinite loop check: the parser must always consume
                     if i.eof_offset() == len {
-                        return Err(ErrMode::assert(i, "`repeat` parsers must always consume"));
+                        return Err(infinite_loop(i));
                     }
 
                     match parser.parse_next(i) {
@@ -1084,6 +2047,10 @@ where
 /// This stops when either parser returns [`ErrMode::Backtrack`]. To instead chain an error up, see
 /// [`cut_err`][crate::combinator::cut_err].
 ///
+/// The rightmost operands have to be merged first, so the operands and separators are buffered
+/// until the end of the list is reached.  For left associative operators, prefer
+/// [`separated_foldl1`] which does not allocate.
+///
 /// # Example
 ///
 /// ```
@@ -1102,6 +2069,7 @@ where
 /// assert_eq!(parser("def|abc"), Err(ErrMode::Backtrack(InputError::new("def|abc", ErrorKind::Verify))));
 /// ```
 #[cfg(feature = "alloc")]
+#[doc(alias = "foldr")]
 pub fn separated_foldr1<Input, Output, Sep, Error, ParseNext, SepParser, Op>(
     mut parser: ParseNext,
     mut sep: SepParser,
@@ -1202,10 +2170,7 @@ where
             Ok(o) => {
                 // infinite loop check: the parser must always consume
                 if input.eof_offset() == len {
-                    return Err(ErrMode::assert(
-                        input,
-                        "`repeat` parsers must always consume",
-                    ));
+                    return Err(infinite_loop(input));
                 }
 
                 res = g(res, o);
@@ -1253,10 +2218,7 @@ where
                     Ok(o) => {
                         // infinite loop check: the parser must always consume
                         if input.eof_offset() == len {
-                            return Err(ErrMode::assert(
-                                input,
-                                "`repeat` parsers must always consume",
-                            ));
+                            return Err(infinite_loop(input));
                         }
 
                         acc = g(acc, o);
@@ -1299,10 +2261,7 @@ where
             Ok(value) => {
                 // infinite loop check: the parser must always consume
                 if input.eof_offset() == len {
-                    return Err(ErrMode::assert(
-                        input,
-                        "`repeat` parsers must always consume",
-                    ));
+                    return Err(infinite_loop(input));
                 }
 
                 acc = fold(acc, value);
@@ -1326,3 +2285,67 @@ where
 
     Ok(acc)
 }
+
+fn fold_while_m_n_<I, O, E, F, G, H, R>(
+    min: usize,
+    max: usize,
+    parse: &mut F,
+    init: &mut H,
+    fold: &mut G,
+    input: &mut I,
+) -> PResult<R, E>
+where
+    I: Stream,
+    F: Parser<I, O, E>,
+    G: FnMut(R, O) -> core::ops::ControlFlow<R, R>,
+    H: FnMut() -> R,
+    E: ParserError<I>,
+{
+    if min > max {
+        return Err(ErrMode::assert(
+            input,
+            "range should be ascending, rather than descending",
+        ));
+    }
+
+    let mut acc = init();
+    for count in 0..max {
+        let start = input.checkpoint();
+        let len = input.eof_offset();
+        match parse.parse_next(input) {
+            Ok(value) => {
+                // infinite loop check: the parser must always consume
+                if input.eof_offset() == len {
+                    return Err(infinite_loop(input));
+                }
+
+                match fold(acc, value) {
+                    core::ops::ControlFlow::Continue(next) => acc = next,
+                    core::ops::ControlFlow::Break(last) => {
+                        if count + 1 < min {
+                            return Err(ErrMode::from_error_kind(input, ErrorKind::Many));
+                        }
+                        return Ok(last);
+                    }
+                }
+            }
+            Err(ErrMode::Backtrack(err)) => {
+                if count < min {
+                    return Err(ErrMode::Backtrack(err.append(
+                        input,
+                        &start,
+                        ErrorKind::Many,
+                    )));
+                } else {
+                    input.reset(&start);
+                    break;
+                }
+            }
+            Err(e) => {
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(acc)
+}
@@ -4,14 +4,20 @@ use crate::combinator::DisplayDebug;
 #[cfg(feature = "unstable-recover")]
 #[cfg(feature = "std")]
 use crate::error::FromRecoverableError;
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+use crate::error::Repaired;
 use crate::error::{AddContext, ErrMode, ErrorKind, FromExternalError, ParserError};
 use crate::lib::std::borrow::Borrow;
 use crate::lib::std::ops::Range;
+#[cfg(feature = "alloc")]
+use crate::lib::std::vec::Vec;
+use crate::stream::Fueled;
 #[cfg(feature = "unstable-recover")]
 #[cfg(feature = "std")]
 use crate::stream::Recover;
 use crate::stream::StreamIsPartial;
-use crate::stream::{Location, Stream};
+use crate::stream::{ContainsToken, LineLocation, Location, Position, Stream};
 use crate::*;
 
 /// Implementation of [`Parser::by_ref`]
@@ -387,6 +393,42 @@ where
     }
 }
 
+/// Implementation of [`Parser::with_fuel`]
+pub struct WithFuel<F> {
+    f: F,
+    fuel: usize,
+}
+
+impl<F> WithFuel<F> {
+    #[inline(always)]
+    pub(crate) fn new(f: F, fuel: usize) -> Self {
+        Self { f, fuel }
+    }
+}
+
+impl<F, I, O, E> Parser<Fueled<I>, O, E> for WithFuel<F>
+where
+    I: Stream,
+    F: Parser<Fueled<I>, O, E>,
+    E: ParserError<Fueled<I>>,
+{
+    #[inline]
+    fn parse_next(&mut self, input: &mut Fueled<I>) -> PResult<O, E> {
+        trace("with_fuel", |input: &mut Fueled<I>| {
+            let outer = input.fuel();
+            let fuel = self.fuel.min(outer);
+            input.set_fuel(fuel);
+            let result = (self.f).parse_next(input);
+            if input.is_exhausted() {
+                return Err(ErrMode::Cut(E::from_error_kind(input, ErrorKind::Fuel)));
+            }
+            input.set_fuel(outer - (fuel - input.fuel()));
+            result
+        })
+        .parse_next(input)
+    }
+}
+
 /// Implementation of [`Parser::verify`]
 pub struct Verify<F, G, I, O, O2, E>
 where
@@ -619,6 +661,80 @@ where
     }
 }
 
+/// Implementation of [`Parser::take_trimmed`]
+pub struct TakeTrimmed<F, I, O, E, S>
+where
+    F: Parser<I, O, E>,
+    I: Stream,
+    S: ContainsToken<<I as Stream>::Token>,
+{
+    parser: F,
+    trim: S,
+    i: core::marker::PhantomData<I>,
+    o: core::marker::PhantomData<O>,
+    e: core::marker::PhantomData<E>,
+}
+
+impl<F, I, O, E, S> TakeTrimmed<F, I, O, E, S>
+where
+    F: Parser<I, O, E>,
+    I: Stream,
+    S: ContainsToken<<I as Stream>::Token>,
+{
+    #[inline(always)]
+    pub(crate) fn new(parser: F, trim: S) -> Self {
+        Self {
+            parser,
+            trim,
+            i: Default::default(),
+            o: Default::default(),
+            e: Default::default(),
+        }
+    }
+}
+
+impl<I, O, E, F, S> Parser<I, <I as Stream>::Slice, E> for TakeTrimmed<F, I, O, E, S>
+where
+    F: Parser<I, O, E>,
+    I: Stream,
+    S: ContainsToken<<I as Stream>::Token>,
+{
+    #[inline]
+    fn parse_next(&mut self, input: &mut I) -> PResult<<I as Stream>::Slice, E> {
+        let checkpoint = input.checkpoint();
+        let _ = self.parser.parse_next(input)?;
+        let offset = input.offset_from(&checkpoint);
+        input.reset(&checkpoint);
+
+        let mut start = None;
+        let mut end = 0;
+        let mut kept = false;
+        for (o, token) in input.iter_offsets() {
+            if kept {
+                end = o;
+                kept = false;
+            }
+            if offset <= o {
+                break;
+            }
+            if !self.trim.contains_token(token) {
+                start.get_or_insert(o);
+                kept = true;
+            }
+        }
+        if kept {
+            end = offset;
+        }
+        let start = start.unwrap_or(offset);
+        let end = end.max(start);
+
+        let _ = input.next_slice(start);
+        let trimmed = input.next_slice(end - start);
+        let _ = input.next_slice(offset - end);
+        Ok(trimmed)
+    }
+}
+
 /// Replaced with [`WithTaken`]
 #[deprecated(since = "0.6.14", note = "Replaced with `WithTaken`")]
 pub type WithRecognized<F, I, O, E> = WithTaken<F, I, O, E>;
@@ -757,6 +873,92 @@ where
     }
 }
 
+/// Implementation of [`Parser::line_span`]
+pub struct LineSpan<F, I, O, E>
+where
+    F: Parser<I, O, E>,
+    I: Stream + LineLocation,
+{
+    parser: F,
+    i: core::marker::PhantomData<I>,
+    o: core::marker::PhantomData<O>,
+    e: core::marker::PhantomData<E>,
+}
+
+impl<F, I, O, E> LineSpan<F, I, O, E>
+where
+    F: Parser<I, O, E>,
+    I: Stream + LineLocation,
+{
+    #[inline(always)]
+    pub(crate) fn new(parser: F) -> Self {
+        Self {
+            parser,
+            i: Default::default(),
+            o: Default::default(),
+            e: Default::default(),
+        }
+    }
+}
+
+impl<I, O, E, F> Parser<I, Range<Position>, E> for LineSpan<F, I, O, E>
+where
+    F: Parser<I, O, E>,
+    I: Stream + LineLocation,
+{
+    #[inline]
+    fn parse_next(&mut self, input: &mut I) -> PResult<Range<Position>, E> {
+        let start = input.line_location();
+        self.parser.parse_next(input).map(move |_| {
+            let end = input.line_location();
+            start..end
+        })
+    }
+}
+
+/// Implementation of [`Parser::with_line_span`]
+pub struct WithLineSpan<F, I, O, E>
+where
+    F: Parser<I, O, E>,
+    I: Stream + LineLocation,
+{
+    parser: F,
+    i: core::marker::PhantomData<I>,
+    o: core::marker::PhantomData<O>,
+    e: core::marker::PhantomData<E>,
+}
+
+impl<F, I, O, E> WithLineSpan<F, I, O, E>
+where
+    F: Parser<I, O, E>,
+    I: Stream + LineLocation,
+{
+    #[inline(always)]
+    pub(crate) fn new(parser: F) -> Self {
+        Self {
+            parser,
+            i: Default::default(),
+            o: Default::default(),
+            e: Default::default(),
+        }
+    }
+}
+
+impl<F, I, O, E> Parser<I, (O, Range<Position>), E> for WithLineSpan<F, I, O, E>
+where
+    F: Parser<I, O, E>,
+    I: Stream + LineLocation,
+{
+    #[inline]
+    fn parse_next(&mut self, input: &mut I) -> PResult<(O, Range<Position>), E> {
+        let start = input.line_location();
+        self.parser.parse_next(input).map(move |output| {
+            let end = input.line_location();
+            (output, (start..end))
+        })
+    }
+}
+
 /// Implementation of [`Parser::output_into`]
 pub struct OutputInto<F, I, O, O2, E>
 where
@@ -898,6 +1100,308 @@ where
     }
 }
 
+/// Implementation of [`Parser::memoize`]
+#[cfg(feature = "std")]
+pub struct Memoize<F, T, I, O, E>
+where
+    F: Parser<I, O, E>,
+    T: FnMut(&mut I) -> &mut MemoTable<O, E>,
+    I: Stream,
+    O: Clone,
+    E: Clone,
+{
+    parser: F,
+    table: T,
+    i: core::marker::PhantomData<I>,
+    o: core::marker::PhantomData<O>,
+    e: core::marker::PhantomData<E>,
+}
+
+#[cfg(feature = "std")]
+impl<F, T, I, O, E> Memoize<F, T, I, O, E>
+where
+    F: Parser<I, O, E>,
+    T: FnMut(&mut I) -> &mut MemoTable<O, E>,
+    I: Stream,
+    O: Clone,
+    E: Clone,
+{
+    #[inline(always)]
+    pub(crate) fn new(parser: F, table: T) -> Self {
+        Self {
+            parser,
+            table,
+            i: Default::default(),
+            o: Default::default(),
+            e: Default::default(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F, T, I, O, E> Parser<I, O, E> for Memoize<F, T, I, O, E>
+where
+    F: Parser<I, O, E>,
+    T: FnMut(&mut I) -> &mut MemoTable<O, E>,
+    I: Stream,
+    O: Clone,
+    E: Clone,
+{
+    #[inline]
+    fn parse_next(&mut self, i: &mut I) -> PResult<O, E> {
+        let start = i.eof_offset();
+        if let Some((consumed, result)) = (self.table)(i).entries.get(&start).cloned() {
+            let _ = i.next_slice(consumed);
+            return result;
+        }
+
+        let result = self.parser.parse_next(i);
+        // More data may change the result
+        if !matches!(result, Err(ErrMode::Incomplete(_))) {
+            let consumed = start.saturating_sub(i.eof_offset());
+            (self.table)(i).insert(start, consumed, result.clone());
+        }
+        result
+    }
+}
+
+/// Results of a [`Parser::memoize`]d parser, keyed by input position
+///
+/// A table only holds results for one parser and one input, as positions from different inputs
+/// can't be told apart. Store it alongside the input (e.g. in
+/// [`Stateful`][crate::stream::Stateful]) so each call to [`Parser::parse`] starts with a fresh
+/// cache.
+///
+/// By default, every result is kept until the table is dropped or [cleared][MemoTable::clear].
+/// For very large inputs, [`MemoTable::with_window`] bounds memory to the results near the
+/// furthest position parsed.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct MemoTable<O, E> {
+    entries: crate::lib::std::collections::HashMap<usize, (usize, PResult<O, E>)>,
+    window: Option<usize>,
+    /// The furthest position parsed, as an [`eof_offset`][Stream::eof_offset]
+    frontier: usize,
+    /// The `frontier` when results outside of the window were last evicted
+    evicted_at: usize,
+}
+
+#[cfg(feature = "std")]
+impl<O, E> MemoTable<O, E> {
+    /// Create an empty table
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            entries: Default::default(),
+            window: None,
+            frontier: usize::MAX,
+            evicted_at: usize::MAX,
+        }
+    }
+
+    /// Create an empty table that evicts results from more than `window` tokens behind the
+    /// furthest position parsed
+    ///
+    /// Evicted results are recomputed if backtracking reaches them again, so pick a window
+    /// larger than how far the grammar typically backtracks.  Eviction is batched, so results
+    /// from up to twice `window` tokens behind may be held at once.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// # use winnow::error::ContextError;
+    /// use winnow::ascii::dec_uint;
+    /// use winnow::combinator::separated;
+    /// use winnow::combinator::MemoTable;
+    /// use winnow::stream::Stateful;
+    ///
+    /// type Stream<'i> = Stateful<&'i str, MemoTable<u32, ContextError>>;
+    ///
+    /// fn number(i: &mut Stream<'_>) -> PResult<u32> {
+    ///     dec_uint.memoize(|i: &mut Stream<'_>| &mut i.state).parse_next(i)
+    /// }
+    ///
+    /// let numbers = vec!["1"; 1_000].join(",");
+    /// let mut input = Stream { input: &numbers, state: MemoTable::with_window(20) };
+    /// let parsed: Vec<u32> = separated(0.., number, ',').parse_next(&mut input).unwrap();
+    /// assert_eq!(parsed.len(), 1_000);
+    /// // Results start every 2 tokens, so 2 * 20 tokens hold at most 20 of them
+    /// assert!(input.state.len() <= 20);
+    /// ```
+    #[inline]
+    pub fn with_window(window: usize) -> Self {
+        Self {
+            window: Some(window),
+            ..Self::new()
+        }
+    }
+
+    /// The number of input positions with a cached result
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no results have been cached
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Forget all cached results, e.g. before reusing the table for another input
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.frontier = usize::MAX;
+        self.evicted_at = usize::MAX;
+    }
+
+    fn insert(&mut self, start: usize, consumed: usize, result: PResult<O, E>) {
+        self.entries.insert(start, (consumed, result));
+        self.frontier = self.frontier.min(start - consumed);
+        if let Some(window) = self.window {
+            if window < self.evicted_at - self.frontier {
+                let oldest = self.frontier.saturating_add(window);
+                self.entries.retain(|start, _| *start <= oldest);
+                self.evicted_at = self.frontier;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<O, E> Default for MemoTable<O, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implementation of [`Parser::warn_if`]
+#[cfg(feature = "alloc")]
+pub struct WarnIf<F, C, S, I, O, E, W>
+where
+    F: Parser<I, O, E>,
+    C: FnMut(&O) -> Option<W>,
+    S: FnMut(&mut I) -> &mut Warnings<W>,
+    I: Stream + Location,
+{
+    parser: F,
+    check: C,
+    warnings: S,
+    i: core::marker::PhantomData<I>,
+    o: core::marker::PhantomData<O>,
+    e: core::marker::PhantomData<E>,
+    w: core::marker::PhantomData<W>,
+}
+
+#[cfg(feature = "alloc")]
+impl<F, C, S, I, O, E, W> WarnIf<F, C, S, I, O, E, W>
+where
+    F: Parser<I, O, E>,
+    C: FnMut(&O) -> Option<W>,
+    S: FnMut(&mut I) -> &mut Warnings<W>,
+    I: Stream + Location,
+{
+    #[inline(always)]
+    pub(crate) fn new(parser: F, check: C, warnings: S) -> Self {
+        Self {
+            parser,
+            check,
+            warnings,
+            i: Default::default(),
+            o: Default::default(),
+            e: Default::default(),
+            w: Default::default(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F, C, S, I, O, E, W> Parser<I, O, E> for WarnIf<F, C, S, I, O, E, W>
+where
+    F: Parser<I, O, E>,
+    C: FnMut(&O) -> Option<W>,
+    S: FnMut(&mut I) -> &mut Warnings<W>,
+    I: Stream + Location,
+{
+    #[inline]
+    fn parse_next(&mut self, input: &mut I) -> PResult<O, E> {
+        let start = input.location();
+        let output = self.parser.parse_next(input)?;
+        if let Some(warning) = (self.check)(&output) {
+            let end = input.location();
+            (self.warnings)(input).entries.push((start..end, warning));
+        }
+        Ok(output)
+    }
+}
+
+/// Non-fatal diagnostics recorded by [`Parser::warn_if`], with the span they apply to
+///
+/// Store it alongside the input (e.g. in [`Stateful`][crate::stream::Stateful]) and read it
+/// after parsing succeeds.  Warnings are kept in the order they were recorded; see
+/// [`sort_by_span`][crate::error::sort_by_span] for ordering them by position.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warnings<W> {
+    entries: Vec<(Range<usize>, W)>,
+}
+
+#[cfg(feature = "alloc")]
+impl<W> Warnings<W> {
+    /// Create an empty collection
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Record a warning for `span` directly, like from within a parser function
+    #[inline]
+    pub fn push(&mut self, span: Range<usize>, warning: W) {
+        self.entries.push((span, warning));
+    }
+
+    /// The number of warnings recorded
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no warnings were recorded
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The warnings recorded, with their spans
+    #[inline]
+    pub fn as_slice(&self) -> &[(Range<usize>, W)] {
+        &self.entries
+    }
+
+    /// Take the warnings recorded, with their spans
+    #[inline]
+    pub fn into_vec(self) -> Vec<(Range<usize>, W)> {
+        self.entries
+    }
+
+    /// Forget all recorded warnings, e.g. before reusing the collection for another input
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<W> Default for Warnings<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Implementation of [`Parser::retry_after`]
 #[cfg(feature = "unstable-recover")]
 #[cfg(feature = "std")]
@@ -1091,3 +1595,139 @@ where
     err = err.map(|err| E::from_recoverable_error(&token_start, &err_start, i, err));
     Err(err)
 }
+
+/// Implementation of [`Parser::repair`]
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+pub struct Repair<P, F, I, O, E>
+where
+    P: Parser<I, O, E>,
+    F: FnMut() -> O,
+    I: Stream,
+    I: Recover<E>,
+    E: FromRecoverableError<I, E>,
+    E: FromExternalError<I, Repaired>,
+{
+    parser: P,
+    label: &'static str,
+    missing: F,
+    max_deletions: usize,
+    i: core::marker::PhantomData<I>,
+    o: core::marker::PhantomData<O>,
+    e: core::marker::PhantomData<E>,
+}
+
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+impl<P, F, I, O, E> Repair<P, F, I, O, E>
+where
+    P: Parser<I, O, E>,
+    F: FnMut() -> O,
+    I: Stream,
+    I: Recover<E>,
+    E: FromRecoverableError<I, E>,
+    E: FromExternalError<I, Repaired>,
+{
+    #[inline(always)]
+    pub(crate) fn new(parser: P, label: &'static str, missing: F) -> Self {
+        Self {
+            parser,
+            label,
+            missing,
+            max_deletions: 1,
+            i: Default::default(),
+            o: Default::default(),
+            e: Default::default(),
+        }
+    }
+
+    /// The most tokens to delete before treating the parser's input as missing
+    ///
+    /// Each extra deletion re-runs the parser, so keep this small.  `0` only inserts.
+    #[inline(always)]
+    pub fn max_deletions(mut self, max_deletions: usize) -> Self {
+        self.max_deletions = max_deletions;
+        self
+    }
+}
+
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+impl<P, F, I, O, E> Parser<I, O, E> for Repair<P, F, I, O, E>
+where
+    P: Parser<I, O, E>,
+    F: FnMut() -> O,
+    I: Stream,
+    I: Recover<E>,
+    E: FromRecoverableError<I, E>,
+    E: FromExternalError<I, Repaired>,
+{
+    #[inline(always)]
+    fn parse_next(&mut self, i: &mut I) -> PResult<O, E> {
+        if I::is_recovery_supported() {
+            repair_inner(self, i)
+        } else {
+            self.parser.parse_next(i)
+        }
+    }
+}
+
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+fn repair_inner<P, F, I, O, E>(repair: &mut Repair<P, F, I, O, E>, i: &mut I) -> PResult<O, E>
+where
+    P: Parser<I, O, E>,
+    F: FnMut() -> O,
+    I: Stream,
+    I: Recover<E>,
+    E: FromRecoverableError<I, E>,
+    E: FromExternalError<I, Repaired>,
+{
+    let token_start = i.checkpoint();
+    let mut err = match repair.parser.parse_next(i) {
+        Ok(o) => {
+            return Ok(o);
+        }
+        Err(ErrMode::Incomplete(e)) => return Err(ErrMode::Incomplete(e)),
+        Err(err) => err,
+    };
+    let err_start = i.checkpoint();
+
+    i.reset(&token_start);
+    let mut repaired = None;
+    for tokens in 1..=repair.max_deletions {
+        if i.next_token().is_none() {
+            break;
+        }
+        let repair_start = i.checkpoint();
+        match repair.parser.parse_next(i) {
+            Ok(o) => {
+                let end = i.checkpoint();
+                i.reset(&repair_start);
+                let label = repair.label;
+                repaired = Some((o, Repaired::Deleted { label, tokens }, end));
+                break;
+            }
+            Err(ErrMode::Incomplete(e)) => return Err(ErrMode::Incomplete(e)),
+            Err(_) => i.reset(&repair_start),
+        }
+    }
+    let (o, repaired, end) = repaired.unwrap_or_else(|| {
+        i.reset(&token_start);
+        let o = (repair.missing)();
+        (o, Repaired::Inserted(repair.label), token_start.clone())
+    });
+
+    let repair_start = i.checkpoint();
+    let repair_err = E::from_external_error(i, ErrorKind::Verify, repaired);
+    if i.record_err(&token_start, &repair_start, ErrMode::Backtrack(repair_err))
+        .is_ok()
+    {
+        i.reset(&end);
+        return Ok(o);
+    }
+
+    i.reset(&err_start);
+    err = err.map(|err| E::from_recoverable_error(&token_start, &err_start, i, err));
+    Err(err)
+}
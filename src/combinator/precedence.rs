@@ -0,0 +1,324 @@
+//! Operator-precedence parsing of expressions
+
+use crate::combinator::trace;
+use crate::error::ErrMode;
+use crate::error::ErrorKind;
+use crate::error::ParserError;
+use crate::lib::std::borrow::Borrow;
+use crate::lib::std::collections::BTreeMap;
+use crate::stream::Stream;
+use crate::PResult;
+use crate::Parser;
+
+/// Parse an expression of `operand`s combined with prefix, infix, and postfix operators
+///
+/// Unlike a fixed grammar, how each operator binds is decided while parsing:
+/// - `operator` recognizes an operator token, like `"+"` or a user-defined symbol
+/// - `lookup` reports how that token behaves as an [`Operator`]. As it is given the current
+///   input, the table can live in [`Stateful`][crate::stream::Stateful] state and be updated
+///   mid-parse, e.g. by `infixl 6 <+>` declarations in a Haskell-like language.
+/// - `fold` combines the operands of an [`Operation`] into a single output
+///
+/// Operators with a higher precedence bind tighter. An operator token that isn't valid where it
+/// is found (e.g. `lookup` returns `None` or it lacks the needed fixity) ends the expression,
+/// leaving the token unconsumed.
+///
+/// <div class="warning">
+///
+/// **Warning:** `operator` must consume input when it succeeds, otherwise `precedence` will
+/// return an error to prevent going into an infinite loop.
+///
+/// </div>
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::dec_int;
+/// use winnow::combinator::precedence;
+/// use winnow::combinator::Assoc;
+/// use winnow::combinator::Operation;
+/// use winnow::combinator::OperatorTable;
+/// use winnow::stream::Stateful;
+/// use winnow::token::one_of;
+///
+/// type Stream<'i> = Stateful<&'i str, OperatorTable<&'i str>>;
+///
+/// fn expr(i: &mut Stream<'_>) -> PResult<i64> {
+///     precedence(
+///         dec_int,
+///         one_of(['+', '-', '*', '!', '^']).take(),
+///         |i: &Stream<'_>, op: &&str| i.state.get(*op),
+///         |op: Operation<&str, i64>| match op {
+///             Operation::Prefix { op: "-", operand } => -operand,
+///             Operation::Postfix { operand, op: "!" } => (1..=operand).product(),
+///             Operation::Infix { lhs, op: "+", rhs } => lhs + rhs,
+///             Operation::Infix { lhs, op: "-", rhs } => lhs - rhs,
+///             Operation::Infix { lhs, op: "*", rhs } => lhs * rhs,
+///             Operation::Infix { lhs, op: "^", rhs } => lhs.pow(rhs as u32),
+///             _ => unreachable!(),
+///         },
+///     )
+///     .parse_next(i)
+/// }
+///
+/// let mut table = OperatorTable::new();
+/// table
+///     .insert_infix("+", 1, Assoc::Left)
+///     .insert_infix("-", 1, Assoc::Left)
+///     .insert_infix("*", 2, Assoc::Left)
+///     .insert_prefix("-", 3);
+///
+/// let input = Stream { input: "2*-3+4", state: table.clone() };
+/// assert_eq!(expr.parse(input), Ok(-2));
+///
+/// // `^` and `!` are unknown until declared
+/// let input = Stream { input: "2^3", state: table.clone() };
+/// assert!(expr.parse(input).is_err());
+///
+/// table.insert_infix("^", 4, Assoc::Right).insert_postfix("!", 5);
+/// let input = Stream { input: "2^3!", state: table };
+/// assert_eq!(expr.parse(input), Ok(64));
+/// ```
+#[doc(alias = "pratt")]
+#[doc(alias = "expression")]
+#[doc(alias = "operator_precedence")]
+#[inline(always)]
+pub fn precedence<Input, Output, Token, Error, ParseOperand, ParseOperator, Lookup, Fold>(
+    mut operand: ParseOperand,
+    mut operator: ParseOperator,
+    mut lookup: Lookup,
+    mut fold: Fold,
+) -> impl Parser<Input, Output, Error>
+where
+    Input: Stream,
+    ParseOperand: Parser<Input, Output, Error>,
+    ParseOperator: Parser<Input, Token, Error>,
+    Lookup: FnMut(&Input, &Token) -> Option<Operator>,
+    Fold: FnMut(Operation<Token, Output>) -> Output,
+    Error: ParserError<Input>,
+{
+    trace("precedence", move |i: &mut Input| {
+        precedence_(&mut operand, &mut operator, &mut lookup, &mut fold, 0, i)
+    })
+}
+
+fn precedence_<I, O, K, E, P, Op, L, F>(
+    operand: &mut P,
+    operator: &mut Op,
+    lookup: &mut L,
+    fold: &mut F,
+    min_precedence: u32,
+    i: &mut I,
+) -> PResult<O, E>
+where
+    I: Stream,
+    P: Parser<I, O, E>,
+    Op: Parser<I, K, E>,
+    L: FnMut(&I, &K) -> Option<Operator>,
+    F: FnMut(Operation<K, O>) -> O,
+    E: ParserError<I>,
+{
+    let start = i.checkpoint();
+    let mut lhs = match next_operator(operator, lookup, i)? {
+        Some((
+            op,
+            Operator {
+                prefix: Some(precedence),
+                ..
+            },
+        )) => {
+            let operand = precedence_(operand, operator, lookup, fold, precedence, i)?;
+            fold(Operation::Prefix { op, operand })
+        }
+        _ => {
+            i.reset(&start);
+            operand.parse_next(i)?
+        }
+    };
+
+    let mut nonassoc = None;
+    loop {
+        let start = i.checkpoint();
+        let Some((op, info)) = next_operator(operator, lookup, i)? else {
+            break;
+        };
+
+        match info {
+            Operator {
+                postfix: Some(precedence),
+                ..
+            } if min_precedence <= precedence => {
+                lhs = fold(Operation::Postfix { operand: lhs, op });
+            }
+            Operator {
+                infix: Some((precedence, assoc)),
+                ..
+            } if min_precedence <= precedence && nonassoc != Some(precedence) => {
+                let next_precedence = match assoc {
+                    Assoc::Left | Assoc::Neither => precedence + 1,
+                    Assoc::Right => precedence,
+                };
+                let rhs = precedence_(operand, operator, lookup, fold, next_precedence, i)?;
+                lhs = fold(Operation::Infix { lhs, op, rhs });
+                nonassoc = (assoc == Assoc::Neither).then_some(precedence);
+            }
+            _ => {
+                i.reset(&start);
+                break;
+            }
+        }
+    }
+
+    Ok(lhs)
+}
+
+fn next_operator<I, K, E, Op, L>(
+    operator: &mut Op,
+    lookup: &mut L,
+    i: &mut I,
+) -> PResult<Option<(K, Operator)>, E>
+where
+    I: Stream,
+    Op: Parser<I, K, E>,
+    L: FnMut(&I, &K) -> Option<Operator>,
+    E: ParserError<I>,
+{
+    let start = i.checkpoint();
+    let len = i.eof_offset();
+    match operator.parse_next(i) {
+        Ok(op) => {
+            // infinite loop check: the parser must always consume
+            if i.eof_offset() == len {
+                return Err(ErrMode::Cut(E::from_error_kind(i, ErrorKind::InfiniteLoop)));
+            }
+            match lookup(i, &op) {
+                Some(info) => Ok(Some((op, info))),
+                None => {
+                    i.reset(&start);
+                    Ok(None)
+                }
+            }
+        }
+        Err(ErrMode::Backtrack(_)) => {
+            i.reset(&start);
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// An operator application for [`precedence`] to [`fold`][precedence] into an output
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Operation<K, O> {
+    /// An operator before its operand, like `-x`
+    Prefix {
+        /// The operator token
+        op: K,
+        /// The operand the operator applies to
+        operand: O,
+    },
+    /// An operator between its operands, like `x + y`
+    Infix {
+        /// The left-hand operand
+        lhs: O,
+        /// The operator token
+        op: K,
+        /// The right-hand operand
+        rhs: O,
+    },
+    /// An operator after its operand, like `x!`
+    Postfix {
+        /// The operand the operator applies to
+        operand: O,
+        /// The operator token
+        op: K,
+    },
+}
+
+/// How a chain of infix operators with the same precedence groups
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Assoc {
+    /// `a - b - c` is `(a - b) - c`
+    Left,
+    /// `a ^ b ^ c` is `a ^ (b ^ c)`
+    Right,
+    /// `a == b == c` is not allowed; the expression ends before the second `==`
+    Neither,
+}
+
+/// How a token behaves as an operator in [`precedence`]
+///
+/// A token may have several fixities, like `-` being both prefix and infix. Each fixity has its
+/// own precedence, with higher values binding tighter.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Operator {
+    /// Precedence when the token comes before an operand
+    pub prefix: Option<u32>,
+    /// Precedence and associativity when the token is between operands
+    pub infix: Option<(u32, Assoc)>,
+    /// Precedence when the token comes after an operand
+    pub postfix: Option<u32>,
+}
+
+/// A runtime-mutable table of [`Operator`]s, keyed by token
+///
+/// See [`precedence`] for an example.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OperatorTable<K> {
+    operators: BTreeMap<K, Operator>,
+}
+
+impl<K: Ord> OperatorTable<K> {
+    /// Create an empty table
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            operators: BTreeMap::new(),
+        }
+    }
+
+    /// Declare `token` as a prefix operator
+    pub fn insert_prefix(&mut self, token: K, precedence: u32) -> &mut Self {
+        self.operators.entry(token).or_default().prefix = Some(precedence);
+        self
+    }
+
+    /// Declare `token` as an infix operator
+    pub fn insert_infix(&mut self, token: K, precedence: u32, assoc: Assoc) -> &mut Self {
+        self.operators.entry(token).or_default().infix = Some((precedence, assoc));
+        self
+    }
+
+    /// Declare `token` as a postfix operator
+    pub fn insert_postfix(&mut self, token: K, precedence: u32) -> &mut Self {
+        self.operators.entry(token).or_default().postfix = Some(precedence);
+        self
+    }
+
+    /// Look up how `token` behaves as an operator
+    #[inline]
+    pub fn get<Q>(&self, token: &Q) -> Option<Operator>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.operators.get(token).copied()
+    }
+
+    /// Remove all fixities of `token`, returning what it was
+    pub fn remove<Q>(&mut self, token: &Q) -> Option<Operator>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.operators.remove(token)
+    }
+}
+
+impl<K: Ord> Default for OperatorTable<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
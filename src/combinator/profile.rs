@@ -0,0 +1,328 @@
+//! Branch statistics for tuning [`alt`][crate::combinator::alt]
+
+use core::cell::RefCell;
+
+use crate::combinator::trace;
+use crate::lib::std::cmp::Reverse;
+use crate::lib::std::fmt;
+use crate::lib::std::vec::Vec;
+use crate::stream::Stream;
+use crate::PResult;
+use crate::Parser;
+
+/// Collect how often each branch of an [`alt`][crate::combinator::alt] is tried and succeeds
+///
+/// Wrap each alternative with [`BranchStats::branch`], run the grammar over representative
+/// input, and then read the suggestions from [`BranchStats::report`].
+///
+/// `alt` tries branches in order, so putting the most frequently matched branches first avoids
+/// failed attempts. If many attempts remain even after reordering, a
+/// [`dispatch!`][crate::combinator::dispatch] on the leading token is likely faster.
+///
+/// To first find which parsers backtrack the most, the `debug` feature's `ProfileTrace` counts
+/// calls to every [`trace`]d parser.
+///
+/// <div class="warning">
+///
+/// **Warning:** Reordering is only valid when no input is accepted by more than one branch, as
+/// `alt` returns the first branch that succeeds.
+///
+/// </div>
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::dec_uint;
+/// use winnow::combinator::alt;
+/// use winnow::combinator::separated;
+/// use winnow::combinator::BranchStats;
+///
+/// fn value(stats: &BranchStats) -> impl Parser<&str, (), ContextError> + '_ {
+///     alt((
+///         stats.branch("value", "null", "null".void()),
+///         stats.branch("value", "bool", alt(("true", "false")).void()),
+///         stats.branch("value", "number", dec_uint::<_, u32, _>.void()),
+///     ))
+/// }
+///
+/// let stats = BranchStats::new();
+/// let () = separated(0.., value(&stats), ',')
+///     .parse("1,2,true,3,null,4")
+///     .unwrap();
+///
+/// let report = stats.report();
+/// let alt = &report.alts()[0];
+/// assert_eq!(alt.suggested_order(), ["number", "null", "bool"]);
+/// assert!(!alt.suggest_dispatch());
+/// println!("{report}");
+/// ```
+#[derive(Debug, Default)]
+pub struct BranchStats {
+    alts: RefCell<Vec<AltStats>>,
+}
+
+impl BranchStats {
+    /// Create an empty collection of statistics
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Instrument `parser` as the `label` branch of the `alt` called `name`
+    ///
+    /// Branches are reported in the order they are first tried, which is their declared order
+    /// within the `alt`.
+    pub fn branch<ParseNext>(
+        &self,
+        name: &'static str,
+        label: &'static str,
+        parser: ParseNext,
+    ) -> Branch<'_, ParseNext> {
+        Branch {
+            stats: self,
+            name,
+            label,
+            parser,
+        }
+    }
+
+    /// Forget all collected statistics
+    pub fn clear(&self) {
+        self.alts.borrow_mut().clear();
+    }
+
+    /// Summarize the statistics, suggesting how to reorder each `alt`
+    pub fn report(&self) -> BranchReport {
+        let alts = self.alts.borrow();
+        BranchReport {
+            alts: alts.iter().map(AltReport::new).collect(),
+        }
+    }
+
+    fn record(&self, name: &'static str, label: &'static str, success: bool) {
+        let mut alts = self.alts.borrow_mut();
+        let alt = match alts.iter().position(|a| a.name == name) {
+            Some(i) => &mut alts[i],
+            None => {
+                alts.push(AltStats {
+                    name,
+                    branches: Vec::new(),
+                });
+                alts.last_mut().expect("just pushed")
+            }
+        };
+        let branch = match alt.branches.iter().position(|b| b.label == label) {
+            Some(i) => &mut alt.branches[i],
+            None => {
+                alt.branches.push(BranchCount {
+                    label,
+                    attempts: 0,
+                    successes: 0,
+                });
+                alt.branches.last_mut().expect("just pushed")
+            }
+        };
+        branch.attempts += 1;
+        if success {
+            branch.successes += 1;
+        }
+    }
+}
+
+/// Implementation of [`BranchStats::branch`]
+pub struct Branch<'s, ParseNext> {
+    stats: &'s BranchStats,
+    name: &'static str,
+    label: &'static str,
+    parser: ParseNext,
+}
+
+impl<Input, Output, Error, ParseNext> Parser<Input, Output, Error> for Branch<'_, ParseNext>
+where
+    Input: Stream,
+    ParseNext: Parser<Input, Output, Error>,
+{
+    #[inline]
+    fn parse_next(&mut self, input: &mut Input) -> PResult<Output, Error> {
+        trace(self.label, |input: &mut Input| {
+            let result = self.parser.parse_next(input);
+            self.stats.record(self.name, self.label, result.is_ok());
+            result
+        })
+        .parse_next(input)
+    }
+}
+
+#[derive(Debug)]
+struct AltStats {
+    name: &'static str,
+    branches: Vec<BranchCount>,
+}
+
+/// How often a single branch was tried and succeeded, see [`BranchStats`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BranchCount {
+    label: &'static str,
+    attempts: usize,
+    successes: usize,
+}
+
+impl BranchCount {
+    /// The label given to [`BranchStats::branch`]
+    pub fn label(&self) -> &'static str {
+        self.label
+    }
+
+    /// How many times the branch was tried
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// How many times the branch succeeded
+    pub fn successes(&self) -> usize {
+        self.successes
+    }
+}
+
+/// Suggestions for every instrumented `alt`, see [`BranchStats::report`]
+///
+/// The [`Display`][fmt::Display] implementation renders a human-readable report.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BranchReport {
+    alts: Vec<AltReport>,
+}
+
+impl BranchReport {
+    /// Each `alt`, in the order they were first tried
+    pub fn alts(&self) -> &[AltReport] {
+        &self.alts
+    }
+}
+
+impl fmt::Display for BranchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, alt) in self.alts.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{alt}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Suggestions for a single `alt`, see [`BranchReport`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AltReport {
+    name: &'static str,
+    branches: Vec<BranchCount>,
+    suggested: Vec<&'static str>,
+    attempts_per_success: f64,
+    suggested_attempts_per_success: f64,
+}
+
+impl AltReport {
+    /// When the expected attempts per success stay at or above this after reordering,
+    /// [`dispatch!`][crate::combinator::dispatch] is suggested instead
+    pub const DISPATCH_THRESHOLD: f64 = 2.0;
+
+    fn new(alt: &AltStats) -> Self {
+        let mut suggested = alt.branches.clone();
+        // Stable, so ties keep their declared order
+        suggested.sort_by_key(|b| Reverse(b.successes));
+        Self {
+            name: alt.name,
+            branches: alt.branches.clone(),
+            suggested: suggested.iter().map(|b| b.label).collect(),
+            attempts_per_success: expected_attempts(&alt.branches),
+            suggested_attempts_per_success: expected_attempts(&suggested),
+        }
+    }
+
+    /// The name given to [`BranchStats::branch`]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The statistics for each branch, in declared order
+    pub fn branches(&self) -> &[BranchCount] {
+        &self.branches
+    }
+
+    /// Branch labels, most frequently successful first
+    pub fn suggested_order(&self) -> &[&'static str] {
+        &self.suggested
+    }
+
+    /// Whether the suggested order differs from the declared order
+    pub fn suggest_reorder(&self) -> bool {
+        self.branches
+            .iter()
+            .map(|b| b.label)
+            .ne(self.suggested.iter().copied())
+    }
+
+    /// Whether the `alt` is better replaced by a [`dispatch!`][crate::combinator::dispatch]
+    pub fn suggest_dispatch(&self) -> bool {
+        Self::DISPATCH_THRESHOLD <= self.suggested_attempts_per_success
+    }
+
+    /// Average branches tried for each success, in the declared order
+    pub fn attempts_per_success(&self) -> f64 {
+        self.attempts_per_success
+    }
+
+    /// Average branches tried for each success, in the suggested order
+    pub fn suggested_attempts_per_success(&self) -> f64 {
+        self.suggested_attempts_per_success
+    }
+}
+
+impl fmt::Display for AltReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let successes: usize = self.branches.iter().map(|b| b.successes).sum();
+        writeln!(
+            f,
+            "alt `{}`: {} successes, {:.2} attempts per success",
+            self.name, successes, self.attempts_per_success
+        )?;
+        for branch in &self.branches {
+            writeln!(
+                f,
+                "  {}: {} of {} attempts succeeded",
+                branch.label, branch.successes, branch.attempts
+            )?;
+        }
+        if self.suggest_reorder() {
+            writeln!(
+                f,
+                "  suggestion: reorder as {} ({:.2} attempts per success)",
+                self.suggested.join(", "),
+                self.suggested_attempts_per_success
+            )?;
+        }
+        if self.suggest_dispatch() {
+            writeln!(
+                f,
+                "  suggestion: convert to `dispatch!` on the leading token"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Branches tried per success if each success had to fail every branch before it
+fn expected_attempts(branches: &[BranchCount]) -> f64 {
+    let mut successes = 0;
+    let mut attempts = 0;
+    for (i, branch) in branches.iter().enumerate() {
+        successes += branch.successes;
+        attempts += branch.successes * (i + 1);
+    }
+    if successes == 0 {
+        0.0
+    } else {
+        attempts as f64 / successes as f64
+    }
+}
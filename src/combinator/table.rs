@@ -0,0 +1,185 @@
+//! Jump tables for grammars dispatched on their first byte
+
+use crate::combinator::trace;
+use crate::error::ErrMode;
+use crate::error::ErrorKind;
+use crate::error::FromExternalError;
+use crate::error::Needed;
+use crate::error::ParserError;
+use crate::error::UnmatchedKey;
+use crate::lib::std::fmt;
+use crate::stream::AsChar;
+use crate::stream::ContainsToken;
+use crate::stream::Stream;
+use crate::stream::StreamIsPartial;
+use crate::PResult;
+use crate::Parser;
+
+/// Build a 256-entry jump table choosing a parser from the first byte of the input
+///
+/// Unlike [`alt`][crate::combinator::alt], which tries each branch in turn, and
+/// [`dispatch!`][crate::combinator::dispatch], which compiles to a `match`, the branch is found
+/// with a single index into the table.  This suits hot loops of binary and text protocol
+/// parsers where every rule is distinguished by its leading byte.
+///
+/// The first byte is not consumed, so each parser sees the complete input.  For `&str` input,
+/// tokens at or above `U+0100` have no entry.
+///
+/// Entries are plain `fn` pointers, keeping the table [`Copy`] and free of allocation, so
+/// closures must not capture anything.
+///
+/// When there is no entry for the next byte, this fails with
+/// [`ErrorKind::Fail`] and an [`UnmatchedKey`] holding the token, like
+/// [`dispatch_with`][crate::combinator::dispatch_with].
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::dec_int;
+/// use winnow::combinator::byte_table;
+/// use winnow::combinator::delimited;
+/// use winnow::token::take_till;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Value<'s> {
+///     Null,
+///     Int(i64),
+///     Str(&'s str),
+/// }
+///
+/// fn null<'s>(input: &mut &'s str) -> PResult<Value<'s>> {
+///     "null".value(Value::Null).parse_next(input)
+/// }
+///
+/// fn int<'s>(input: &mut &'s str) -> PResult<Value<'s>> {
+///     dec_int.map(Value::Int).parse_next(input)
+/// }
+///
+/// fn string<'s>(input: &mut &'s str) -> PResult<Value<'s>> {
+///     delimited('"', take_till(0.., '"'), '"')
+///         .map(Value::Str)
+///         .parse_next(input)
+/// }
+///
+/// let mut value = byte_table()
+///     .on(b'n', null)
+///     .on((b'0'..=b'9', b'-'), int)
+///     .on(b'"', string);
+///
+/// assert_eq!(value.parse_peek("null,"), Ok((",", Value::Null)));
+/// assert_eq!(value.parse_peek("-42,"), Ok((",", Value::Int(-42))));
+/// assert_eq!(value.parse_peek("\"hi\","), Ok((",", Value::Str("hi"))));
+///
+/// let err = value.parse("true").unwrap_err();
+/// assert_eq!(err.offset(), 0);
+/// assert_eq!(err.inner().cause().unwrap().to_string(), "no branch for `'t'`");
+/// ```
+pub fn byte_table<Input, Output, Error>() -> ByteTable<Input, Output, Error> {
+    ByteTable {
+        entries: [None; 256],
+    }
+}
+
+/// Implementation of [`byte_table`]
+pub struct ByteTable<Input, Output, Error> {
+    entries: [Option<Entry<Input, Output, Error>>; 256],
+}
+
+type Entry<Input, Output, Error> = fn(&mut Input) -> PResult<Output, Error>;
+
+impl<Input, Output, Error> ByteTable<Input, Output, Error> {
+    /// Parse with `parser` when the first byte is in `bytes`
+    ///
+    /// `bytes` is anything [`one_of`][crate::token::one_of] accepts for bytes, like a `u8`, a
+    /// range, or a tuple of them.  Later entries replace earlier ones for the same byte, so a
+    /// fallback can be set first with `..` and then narrowed.
+    pub fn on<Bytes>(
+        mut self,
+        bytes: Bytes,
+        parser: fn(&mut Input) -> PResult<Output, Error>,
+    ) -> Self
+    where
+        Bytes: ContainsToken<u8>,
+    {
+        for byte in 0..=u8::MAX {
+            if bytes.contains_token(byte) {
+                self.entries[byte as usize] = Some(parser);
+            }
+        }
+        self
+    }
+
+    /// Remove the entries for `bytes`
+    pub fn remove<Bytes>(mut self, bytes: Bytes) -> Self
+    where
+        Bytes: ContainsToken<u8>,
+    {
+        for byte in 0..=u8::MAX {
+            if bytes.contains_token(byte) {
+                self.entries[byte as usize] = None;
+            }
+        }
+        self
+    }
+
+    /// The parser used when the first byte is `byte`
+    #[inline(always)]
+    pub fn get(&self, byte: u8) -> Option<Entry<Input, Output, Error>> {
+        self.entries[byte as usize]
+    }
+}
+
+impl<Input, Output, Error> Clone for ByteTable<Input, Output, Error> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Input, Output, Error> Copy for ByteTable<Input, Output, Error> {}
+
+impl<Input, Output, Error> fmt::Debug for ByteTable<Input, Output, Error> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries((0..=u8::MAX).filter(|b| self.entries[*b as usize].is_some()))
+            .finish()
+    }
+}
+
+impl<Input, Output, Error> Parser<Input, Output, Error> for ByteTable<Input, Output, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input> + FromExternalError<Input, UnmatchedKey<<Input as Stream>::Token>>,
+{
+    #[inline]
+    fn parse_next(&mut self, input: &mut Input) -> PResult<Output, Error> {
+        trace("byte_table", |input: &mut Input| {
+            let start = input.checkpoint();
+            let token = match input.next_token() {
+                Some(token) => token,
+                None => {
+                    return if input.is_partial() {
+                        Err(ErrMode::Incomplete(Needed::new(1)))
+                    } else {
+                        Err(ErrMode::from_error_kind(input, ErrorKind::Token))
+                    };
+                }
+            };
+            input.reset(&start);
+            let entry = u8::try_from(token.clone().as_char() as u32)
+                .ok()
+                .and_then(|byte| self.entries[byte as usize]);
+            match entry {
+                Some(parser) => parser(input),
+                None => Err(ErrMode::from_external_error(
+                    input,
+                    ErrorKind::Fail,
+                    UnmatchedKey(token),
+                )),
+            }
+        })
+        .parse_next(input)
+    }
+}
@@ -11,6 +11,8 @@ use crate::error::Needed;
 use crate::error::ParserError;
 #[cfg(feature = "alloc")]
 use crate::lib::std::borrow::ToOwned;
+#[cfg(feature = "alloc")]
+use crate::lib::std::string::ToString;
 use crate::stream::Stream;
 use crate::token::take;
 use crate::unpeek;
@@ -669,6 +671,63 @@ fn alt_array() {
     );
 }
 
+#[test]
+#[cfg(feature = "alloc")]
+fn alt_boxed_runtime_list() {
+    use crate::lib::std::boxed::Box;
+
+    type Alternative<'i> = Box<dyn Parser<&'i str, usize, InputError<&'i str>> + 'i>;
+
+    let keywords = ["let", "fn", "if"];
+    let mut alternatives = keywords
+        .iter()
+        .enumerate()
+        .map(|(i, k)| Box::new(k.value(i)) as Alternative<'_>)
+        .collect::<Vec<_>>();
+    let mut parser = alt(&mut alternatives[..]);
+    assert_eq!(parser.parse_peek("fn main"), Ok((" main", 1)));
+    assert_eq!(parser.parse_peek("if x"), Ok((" x", 2)));
+    assert_eq!(
+        parser.parse_peek("while"),
+        Err(ErrMode::Backtrack(InputError::new("while", ErrorKind::Tag)))
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn boxed_send_parser_and_error() {
+    use crate::error::ContextError;
+    use crate::error::StrContext;
+    use crate::error::StrContextValue;
+    use crate::lib::std::boxed::Box;
+
+    type Error = Box<ContextError>;
+    type Alternative<'i> = Box<dyn Parser<&'i str, &'i str, Error> + Send + 'i>;
+
+    let keywords = ["let", "fn"];
+    let mut alternatives = keywords
+        .iter()
+        .map(|k| {
+            Box::new(
+                k.context(StrContext::Expected(StrContextValue::from(format!(
+                    "`{k}`"
+                )))),
+            ) as Alternative<'_>
+        })
+        .collect::<Vec<_>>();
+    let mut parser = alt(&mut alternatives[..]);
+    assert_eq!(parser.parse_peek("fn main"), Ok((" main", "fn")));
+
+    let err = parser.parse_peek("if").unwrap_err().into_inner().unwrap();
+    assert_eq!(
+        err.context().cloned().collect::<Vec<_>>(),
+        [
+            StrContext::Expected(StrContextValue::OwnedDescription("`let`".into())),
+            StrContext::Expected(StrContextValue::OwnedDescription("`fn`".into())),
+        ]
+    );
+}
+
 #[test]
 fn alt_dynamic_array() {
     fn alt1<'i>(i: &mut &'i [u8]) -> PResult<&'i [u8]> {
@@ -685,6 +744,65 @@ fn alt_dynamic_array() {
     assert_eq!(alt1.parse_peek(defg), Ok((&b"g"[..], (&b"def"[..]))));
 }
 
+#[test]
+fn alt_lint_unshadowed() {
+    fn op(i: &str) -> IResult<&str, &str> {
+        alt_lint(("<=", "<", ">")).parse_peek(i)
+    }
+    assert_eq!(op("<= 1"), Ok((" 1", "<=")));
+    assert_eq!(op("< 1"), Ok((" 1", "<")));
+    assert_eq!(op("> 1"), Ok((" 1", ">")));
+    assert_eq!(
+        op("= 1"),
+        Err(ErrMode::Backtrack(error_position!(&"= 1", ErrorKind::Tag)))
+    );
+
+    fn keyword(i: &str) -> IResult<&str, &str> {
+        alt_lint(["in", "if"]).parse_peek(i)
+    }
+    assert_eq!(keyword("if x"), Ok((" x", "if")));
+}
+
+#[test]
+#[should_panic(expected = "`alt` branch 2 is shadowed by branch 0")]
+fn alt_lint_shadowed() {
+    fn op(i: &str) -> IResult<&str, &str> {
+        alt_lint(("<", ">", "<=")).parse_peek(i)
+    }
+    assert_eq!(op("< 1"), Ok((" 1", "<")));
+    let _ = op("<= 1");
+}
+
+#[test]
+fn alt_furthest_test() {
+    use crate::ascii::{alpha1, digit1};
+
+    fn value(i: &str) -> IResult<&str, &str> {
+        alt_furthest((("let ", alpha1, ";").take(), ("let", digit1).take(), "lex")).parse_peek(i)
+    }
+    assert_eq!(value("let a;"), Ok(("", "let a;")));
+    assert_eq!(value("let1"), Ok(("", "let1")));
+    // The first alternative got furthest
+    assert_eq!(
+        value("let a?"),
+        Err(ErrMode::Backtrack(error_position!(&"?", ErrorKind::Tag)))
+    );
+    // Only the second alternative got past `let`
+    assert_eq!(
+        value("letx"),
+        Err(ErrMode::Backtrack(error_position!(&"x", ErrorKind::Slice)))
+    );
+
+    fn array(i: &str) -> IResult<&str, &str> {
+        alt_furthest(["ab", "abc", "b"]).parse_peek(i)
+    }
+    // Every alternative failed at `ax`; ties are combined with `ParserError::or`
+    assert_eq!(
+        array("ax"),
+        Err(ErrMode::Backtrack(error_position!(&"ax", ErrorKind::Tag)))
+    );
+}
+
 #[test]
 fn permutation_test() {
     #[allow(clippy::type_complexity)]
@@ -727,6 +845,58 @@ fn permutation_test() {
     );
 }
 
+#[test]
+fn permutation_order_is_tuple_order() {
+    use crate::ascii::alpha1;
+
+    fn literal_first(i: &str) -> IResult<&str, (&str, &str)> {
+        permutation(("ab", alpha1)).parse_peek(i)
+    }
+    fn alpha_first(i: &str) -> IResult<&str, (&str, &str)> {
+        permutation((alpha1, "ab")).parse_peek(i)
+    }
+    // Where both could match, the earlier parser in the tuple is applied
+    assert_eq!(literal_first("abcd"), Ok(("", ("ab", "cd"))));
+    assert_eq!(
+        alpha_first("abcd"),
+        Err(ErrMode::Backtrack(error_node_position!(
+            &"",
+            ErrorKind::Alt,
+            error_position!(&"", ErrorKind::Tag)
+        )))
+    );
+}
+
+#[test]
+fn permutation_unambiguous_test() {
+    use crate::ascii::alpha1;
+
+    fn perm(i: &str) -> IResult<&str, (&str, &str, char)> {
+        permutation_unambiguous((alpha1, digit, ';')).parse_peek(i)
+    }
+    assert_eq!(perm("ab;12"), Ok(("", ("ab", "12", ';'))));
+    assert_eq!(perm("12ab;x"), Ok(("x", ("ab", "12", ';'))));
+    assert_eq!(
+        perm("ab;x"),
+        Err(ErrMode::Backtrack(error_node_position!(
+            &"x",
+            ErrorKind::Alt,
+            error_position!(&"x", ErrorKind::Slice)
+        )))
+    );
+
+    fn overlapping(i: &str) -> IResult<&str, (&str, &str)> {
+        permutation_unambiguous(("ab", alpha1)).parse_peek(i)
+    }
+    assert_eq!(
+        overlapping("ab12"),
+        Err(ErrMode::Backtrack(error_position!(
+            &"ab12",
+            ErrorKind::Verify
+        )))
+    );
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn separated0_test() {
@@ -784,7 +954,6 @@ fn separated0_test() {
 
 #[test]
 #[cfg(feature = "alloc")]
-#[cfg_attr(debug_assertions, should_panic)]
 fn separated0_empty_sep_test() {
     fn empty_sep(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, Vec<&[u8]>> {
         separated(0.., "abc", "").parse_peek(i)
@@ -797,7 +966,7 @@ fn separated0_empty_sep_test() {
         empty_sep(Partial::new(i)),
         Err(ErrMode::Cut(error_position!(
             &Partial::new(i_err_pos),
-            ErrorKind::Assert
+            ErrorKind::InfiniteLoop
         )))
     );
 }
@@ -893,6 +1062,103 @@ fn separated_test() {
     );
 }
 
+#[test]
+fn separated_foldl1_test() {
+    use crate::ascii::dec_int;
+
+    fn sub(i: &str) -> IResult<&str, i32> {
+        separated_foldl1(dec_int, '-', |l, _, r| l - r).parse_peek(i)
+    }
+    assert_eq!(sub("9-3-5;"), Ok((";", 1)));
+    assert_eq!(sub("9"), Ok(("", 9)));
+    // A trailing separator without an operand is left unparsed
+    assert_eq!(sub("9-3-;"), Ok(("-;", 6)));
+    assert_eq!(
+        sub(";"),
+        Err(ErrMode::Backtrack(error_position!(&";", ErrorKind::Verify)))
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn separated_foldr1_test() {
+    use crate::ascii::dec_uint;
+
+    fn pow(i: &str) -> IResult<&str, u32> {
+        separated_foldr1(dec_uint, '^', |l: u32, _, r: u32| l.pow(r)).parse_peek(i)
+    }
+    assert_eq!(pow("2^3^2;"), Ok((";", 512)));
+    assert_eq!(pow("2"), Ok(("", 2)));
+    assert_eq!(pow("2^3^;"), Ok(("^;", 8)));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn separated_trailing_test() {
+    fn list(
+        occurrences: impl Into<crate::stream::Range>,
+        trailing: TrailingSeparator,
+        i: &str,
+    ) -> IResult<&str, Vec<&str>> {
+        separated_trailing(occurrences, "ab", ",", trailing).parse_peek(i)
+    }
+    use TrailingSeparator::*;
+
+    assert_eq!(list(0.., Forbid, "ab,ab,"), Ok((",", vec!["ab", "ab"])));
+    assert_eq!(list(0.., Allow, "ab,ab,"), Ok(("", vec!["ab", "ab"])));
+    assert_eq!(list(0.., Allow, "ab,ab"), Ok(("", vec!["ab", "ab"])));
+    assert_eq!(list(0.., Require, "ab,ab,"), Ok(("", vec!["ab", "ab"])));
+    assert_eq!(
+        list(0.., Require, "ab,ab"),
+        Err(ErrMode::Backtrack(error_position!(&"", ErrorKind::Tag)))
+    );
+    assert_eq!(list(0.., Require, "cd"), Ok(("cd", vec![])));
+
+    assert_eq!(list(0..=1, Forbid, "ab,ab"), Ok((",ab", vec!["ab"])));
+    assert_eq!(list(0..=1, Allow, "ab,ab"), Ok(("ab", vec!["ab"])));
+    assert_eq!(list(0..=1, Require, "ab,ab"), Ok(("ab", vec!["ab"])));
+    assert_eq!(
+        list(2, Allow, "ab,"),
+        Err(ErrMode::Backtrack(error_position!(&"", ErrorKind::Tag)))
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn key_value_trailing() {
+    use crate::ascii::alphanumeric1;
+
+    fn pairs(trailing: TrailingSeparator, i: &str) -> IResult<&str, Vec<(&str, &str)>> {
+        key_value(alphanumeric1, '=', alphanumeric1, ';')
+            .trailing(trailing)
+            .parse_peek(i)
+    }
+    let ab = vec![("a", "1"), ("b", "2")];
+
+    assert_eq!(
+        pairs(TrailingSeparator::Forbid, "a=1 ; b =\t2;"),
+        Ok((";", ab.clone()))
+    );
+    assert_eq!(
+        pairs(TrailingSeparator::Forbid, "a=1;b=2 ;c"),
+        Ok((" ;c", ab.clone()))
+    );
+    assert_eq!(
+        pairs(TrailingSeparator::Allow, "a=1;b=2 ; c"),
+        Ok((" c", ab.clone()))
+    );
+    assert_eq!(
+        pairs(TrailingSeparator::Allow, "a=1;b=2"),
+        Ok(("", ab.clone()))
+    );
+    assert_eq!(pairs(TrailingSeparator::Require, "a=1;b=2;"), Ok(("", ab)));
+    assert_eq!(
+        pairs(TrailingSeparator::Require, "a=1;b=2"),
+        Err(ErrMode::Backtrack(error_position!(&"", ErrorKind::Tag)))
+    );
+    assert_eq!(pairs(TrailingSeparator::Forbid, "=1"), Ok(("=1", vec![])));
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn repeat0_test() {
@@ -928,7 +1194,6 @@ fn repeat0_test() {
 
 #[test]
 #[cfg(feature = "alloc")]
-#[cfg_attr(debug_assertions, should_panic)]
 fn repeat0_empty_test() {
     fn multi_empty(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, Vec<&[u8]>> {
         repeat(0.., "").parse_peek(i)
@@ -938,11 +1203,54 @@ fn repeat0_empty_test() {
         multi_empty(Partial::new(&b"abcdef"[..])),
         Err(ErrMode::Cut(error_position!(
             &Partial::new(&b"abcdef"[..]),
-            ErrorKind::Assert
+            ErrorKind::InfiniteLoop
         )))
     );
 }
 
+#[test]
+#[cfg(feature = "alloc")]
+fn repeat_allow_empty_test() {
+    fn multi(i: &str) -> IResult<&str, Vec<&str>> {
+        repeat(0.., alt(("ab", ""))).allow_empty(2).parse_peek(i)
+    }
+    fn multi_min(i: &str) -> IResult<&str, Vec<&str>> {
+        repeat(3..=4, alt(("ab", ""))).allow_empty(1).parse_peek(i)
+    }
+
+    assert_eq!(multi("ababc"), Ok(("c", vec!["ab", "ab", "", ""])));
+    assert_eq!(multi(""), Ok(("", vec!["", ""])));
+    assert_eq!(multi_min("abab"), Ok(("", vec!["ab", "ab", ""])));
+    assert_eq!(multi_min("ababab"), Ok(("", vec!["ab", "ab", "ab", ""])));
+    assert_eq!(
+        multi_min("ab"),
+        Err(ErrMode::Backtrack(error_position!(&"", ErrorKind::Many)))
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn separated_allow_empty_test() {
+    fn list(i: &str) -> IResult<&str, Vec<&str>> {
+        separated(0.., alt(("ab", "")), opt(','))
+            .allow_empty(1)
+            .parse_peek(i)
+    }
+    fn list_min(i: &str) -> IResult<&str, Vec<&str>> {
+        separated(3.., alt(("ab", "")), opt(','))
+            .allow_empty(1)
+            .parse_peek(i)
+    }
+
+    assert_eq!(list("ab,abab!"), Ok(("!", vec!["ab", "ab", "ab", ""])));
+    assert_eq!(list("ab,,ab"), Ok(("", vec!["ab", "", "ab", ""])));
+    assert_eq!(list("!"), Ok(("!", vec!["", ""])));
+    assert_eq!(
+        list_min("ab"),
+        Err(ErrMode::Backtrack(error_position!(&"", ErrorKind::Many)))
+    );
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn repeat1_test() {
@@ -1039,6 +1347,20 @@ fn repeat_till_range_test() {
             error_position!(&"abcd", ErrorKind::Tag)
         )))
     );
+
+    #[allow(clippy::type_complexity)]
+    fn exact(i: &str) -> IResult<&str, (Vec<&str>, &str)> {
+        repeat_till(1, "ab", "cd").parse_peek(i)
+    }
+    assert_eq!(exact("abcd"), Ok(("", (vec!["ab"], "cd"))));
+    assert_eq!(
+        exact("ababcd"),
+        Err(ErrMode::Backtrack(error_node_position!(
+            &"abcd",
+            ErrorKind::Many,
+            error_position!(&"abcd", ErrorKind::Tag)
+        )))
+    );
 }
 
 #[test]
@@ -1189,6 +1511,156 @@ fn count_zero() {
     assert_eq!(counter_2(error_2), Ok((error_2_remain, parsed_err_2)));
 }
 
+#[test]
+fn repeat_n_test() {
+    fn cnt_2(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, [&[u8]; 2]> {
+        repeat_n("abc").parse_peek(i)
+    }
+
+    assert_eq!(
+        cnt_2(Partial::new(&b"abcabcabcdef"[..])),
+        Ok((Partial::new(&b"abcdef"[..]), [&b"abc"[..], &b"abc"[..]]))
+    );
+    assert_eq!(
+        cnt_2(Partial::new(&b"abcab"[..])),
+        Err(ErrMode::Incomplete(Needed::new(1)))
+    );
+    assert_eq!(
+        cnt_2(Partial::new(&b"abcxxxabcdef"[..])),
+        Err(ErrMode::Backtrack(error_position!(
+            &Partial::new(&b"xxxabcdef"[..]),
+            ErrorKind::Tag
+        )))
+    );
+}
+
+#[test]
+fn repeat_n_zero() {
+    fn cnt_0(i: &[u8]) -> IResult<&[u8], [&[u8]; 0]> {
+        repeat_n("abc").parse_peek(i)
+    }
+
+    assert_eq!(cnt_0(&b"abcabc"[..]), Ok((&b"abcabc"[..], [])));
+}
+
+#[test]
+fn repeat_n_empty_test() {
+    fn cnt_2(i: &str) -> IResult<&str, [&str; 2]> {
+        repeat_n("").parse_peek(i)
+    }
+
+    assert_eq!(
+        cnt_2("abc"),
+        Err(ErrMode::Cut(error_position!(
+            &"abc",
+            ErrorKind::InfiniteLoop
+        )))
+    );
+}
+
+#[test]
+fn repeat_bounded_test() {
+    use crate::stream::BoundedVec;
+
+    fn multi(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, BoundedVec<&[u8], 3>> {
+        repeat_bounded(2.., "Abcd").parse_peek(i)
+    }
+
+    let mut expected = BoundedVec::new();
+    expected.push(&b"Abcd"[..]).unwrap();
+    expected.push(&b"Abcd"[..]).unwrap();
+    assert_eq!(
+        multi(Partial::new(&b"AbcdAbcdefgh"[..])),
+        Ok((Partial::new(&b"efgh"[..]), expected.clone()))
+    );
+    expected.push(&b"Abcd"[..]).unwrap();
+    assert_eq!(
+        multi(Partial::new(&b"AbcdAbcdAbcdAbcdefgh"[..])),
+        Ok((Partial::new(&b"Abcdefgh"[..]), expected))
+    );
+    assert_eq!(
+        multi(Partial::new(&b"Abcdef"[..])),
+        Err(ErrMode::Backtrack(error_position!(
+            &Partial::new(&b"ef"[..]),
+            ErrorKind::Tag
+        )))
+    );
+    assert_eq!(
+        multi(Partial::new(&b"AbcdAb"[..])),
+        Err(ErrMode::Incomplete(Needed::new(2)))
+    );
+
+    fn too_many(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, BoundedVec<&[u8], 1>> {
+        repeat_bounded(2..=3, "Abcd").parse_peek(i)
+    }
+    assert_eq!(
+        too_many(Partial::new(&b"AbcdAbcd"[..])),
+        Err(ErrMode::Cut(error_position!(
+            &Partial::new(&b"AbcdAbcd"[..]),
+            ErrorKind::Many
+        )))
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn repeat_indexed_test() {
+    use crate::token::take;
+
+    // A 3-wide header field followed by 2-wide fields
+    fn rows(i: &str) -> IResult<&str, Vec<&str>> {
+        repeat_indexed(2..=3, |index| {
+            let width = if index == 0 { 3usize } else { 2 };
+            terminated(take(width), ';')
+        })
+        .parse_peek(i)
+    }
+
+    assert_eq!(rows("abc;12;34;"), Ok(("", vec!["abc", "12", "34"])));
+    assert_eq!(rows("abc;12;34;56;"), Ok(("56;", vec!["abc", "12", "34"])));
+    assert_eq!(rows("abc;12;3"), Ok(("3", vec!["abc", "12"])));
+    assert_eq!(
+        rows("12;34;"),
+        Err(ErrMode::Backtrack(error_position!(&"34;", ErrorKind::Tag)))
+    );
+    assert_eq!(
+        rows("abc;1"),
+        Err(ErrMode::Backtrack(error_position!(&"1", ErrorKind::Slice)))
+    );
+}
+
+#[test]
+fn within_error_position() {
+    use crate::error::ContextError;
+    use crate::stream::Located;
+    use crate::token::take_until;
+
+    fn quoted<'i>(i: &mut Located<&'i str>) -> PResult<&'i str, ContextError> {
+        within(
+            delimited('"', take_until(0.., '"'), '"'),
+            (digit, ';').take(),
+        )
+        .parse_next(i)
+    }
+
+    assert_eq!(quoted.parse(Located::new("\"12;\"")), Ok("12;"));
+    let err = quoted.parse(Located::new("\"12x;\"")).unwrap_err();
+    assert_eq!(err.offset(), 3);
+
+    fn fixed<'i>(i: &mut &'i [u8]) -> PResult<(&'i [u8], &'i [u8]), InputError<&'i [u8]>> {
+        within(take(3usize), ("ab", "c")).parse_next(i)
+    }
+    let mut input = &b"abxabc"[..];
+    assert_eq!(
+        fixed.parse_next(&mut input),
+        Err(ErrMode::Backtrack(InputError::new(
+            &b"x"[..],
+            ErrorKind::Tag
+        )))
+    );
+    assert_eq!(input, &b"xabc"[..]);
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 struct NilError;
 
@@ -1248,7 +1720,6 @@ fn fold_repeat0_test() {
 
 #[test]
 #[cfg(feature = "alloc")]
-#[cfg_attr(debug_assertions, should_panic)]
 fn fold_repeat0_empty_test() {
     fn fold_into_vec<T>(mut acc: Vec<T>, item: T) -> Vec<T> {
         acc.push(item);
@@ -1262,7 +1733,7 @@ fn fold_repeat0_empty_test() {
         multi_empty(Partial::new(&b"abcdef"[..])),
         Err(ErrMode::Cut(error_position!(
             &Partial::new(&b"abcdef"[..]),
-            ErrorKind::Assert
+            ErrorKind::InfiniteLoop
         )))
     );
 }
@@ -1352,6 +1823,48 @@ fn fold_repeat_test() {
     );
 }
 
+#[test]
+fn fold_while_test() {
+    use crate::ascii::digit1;
+    use crate::lib::std::ops::ControlFlow;
+
+    fn sum_until(limit: usize) -> impl FnMut(&str) -> IResult<&str, usize> {
+        move |i| {
+            repeat(2..=4, terminated(digit1, ','))
+                .fold_while(
+                    || 0,
+                    |acc, d: &str| {
+                        let acc = acc + d.len();
+                        if limit <= acc {
+                            ControlFlow::Break(acc)
+                        } else {
+                            ControlFlow::Continue(acc)
+                        }
+                    },
+                )
+                .parse_peek(i)
+        }
+    }
+
+    // Breaks once the limit is reached, leaving the rest of the input
+    assert_eq!(sum_until(4)("12,345,6,x"), Ok(("6,x", 5)));
+    // Continues to the maximum without breaking
+    assert_eq!(sum_until(100)("1,2,3,4,5,x"), Ok(("5,x", 4)));
+    assert_eq!(sum_until(100)("1,2,x"), Ok(("x", 2)));
+    // Breaking before the minimum is an error
+    assert_eq!(
+        sum_until(1)("12,345,x"),
+        Err(ErrMode::Backtrack(error_position!(
+            &"345,x",
+            ErrorKind::Many
+        )))
+    );
+    assert_eq!(
+        sum_until(100)("1,x"),
+        Err(ErrMode::Backtrack(error_position!(&"x", ErrorKind::Slice)))
+    );
+}
+
 #[test]
 fn repeat0_count_test() {
     fn count0_nums(i: &[u8]) -> IResult<&[u8], usize> {
@@ -1391,3 +1904,797 @@ fn repeat1_count_test() {
         )))
     );
 }
+
+#[test]
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+fn resume_after_collects_errors() {
+    use crate::error::ContextError;
+    use crate::stream::Recoverable;
+    use crate::token::take_till;
+    use crate::RecoverableParser;
+
+    type Stream<'i> = Recoverable<&'i str, ContextError>;
+
+    fn number(i: &mut Stream<'_>) -> PResult<Option<u32>> {
+        digit
+            .parse_to()
+            .resume_after(take_till(1.., ',').void())
+            .parse_next(i)
+    }
+
+    fn list(i: &mut Stream<'_>) -> PResult<Vec<Option<u32>>> {
+        separated(0.., number, ',').parse_next(i)
+    }
+
+    let (_, output, errors) = list.recoverable_parse("1,x,3,y");
+    assert_eq!(output, Some(vec![Some(1), None, Some(3), None]));
+    assert_eq!(errors.len(), 2);
+
+    let (_, output, errors) = list.recoverable_parse("1,2,3");
+    assert_eq!(output, Some(vec![Some(1), Some(2), Some(3)]));
+    assert!(errors.is_empty());
+}
+
+#[test]
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+fn repair_records_insertion_and_deletion() {
+    use crate::ascii::alpha1;
+    use crate::error::ContextError;
+    use crate::error::Repaired;
+    use crate::stream::Recoverable;
+    use crate::RecoverableParser;
+
+    type Stream<'i> = Recoverable<&'i str, ContextError>;
+
+    fn call<'i>(i: &mut Stream<'i>) -> PResult<&'i str> {
+        delimited('(', alpha1, ')'.repair("`)`", || ')')).parse_next(i)
+    }
+    fn repairs(errors: &[ContextError]) -> Vec<String> {
+        errors
+            .iter()
+            .map(|e| {
+                e.cause()
+                    .and_then(|c| c.downcast_ref::<Repaired>())
+                    .unwrap()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    let (_, output, errors) = call.recoverable_parse("(x)");
+    assert_eq!(output, Some("x"));
+    assert!(errors.is_empty());
+
+    let (_, output, errors) = call.recoverable_parse("(x");
+    assert_eq!(output, Some("x"));
+    assert_eq!(repairs(&errors), ["missing `)` inserted"]);
+
+    let (_, output, errors) = call.recoverable_parse("(x])");
+    assert_eq!(output, Some("x"));
+    assert_eq!(repairs(&errors), ["unexpected token before `)` deleted"]);
+
+    // Only one token is deleted by default, leaving `;)` for `eof`
+    let (_, output, errors) = call.recoverable_parse("(x;;)");
+    assert_eq!(output, Some("x"));
+    assert_eq!(errors.len(), 2);
+    assert_eq!(repairs(&errors[..1]), ["missing `)` inserted"]);
+
+    fn call2<'i>(i: &mut Stream<'i>) -> PResult<&'i str> {
+        delimited('(', alpha1, ')'.repair("`)`", || ')').max_deletions(2)).parse_next(i)
+    }
+    let (_, output, errors) = call2.recoverable_parse("(x;;)");
+    assert_eq!(output, Some("x"));
+    assert_eq!(repairs(&errors), ["2 unexpected tokens before `)` deleted"]);
+}
+
+#[cfg(feature = "alloc")]
+mod precedence {
+    use super::*;
+    use crate::ascii::dec_uint;
+    use crate::ascii::space0;
+    use crate::error::ContextError;
+    use crate::lib::std::boxed::Box;
+    use crate::lib::std::string::String;
+    use crate::stream::Stateful;
+    use crate::token::one_of;
+    use crate::token::take_while;
+
+    type Stream<'i> = Stateful<&'i str, OperatorTable<&'i str>>;
+
+    const OPERATOR_CHARS: [char; 8] = ['+', '-', '*', '/', '^', '!', '=', '<'];
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    enum Expr<'i> {
+        Num(u32),
+        Prefix(&'i str, Box<Expr<'i>>),
+        Infix(Box<Expr<'i>>, &'i str, Box<Expr<'i>>),
+        Postfix(Box<Expr<'i>>, &'i str),
+    }
+
+    impl crate::lib::std::fmt::Display for Expr<'_> {
+        fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+            match self {
+                Self::Num(n) => write!(f, "{n}"),
+                Self::Prefix(op, e) => write!(f, "({op}{e})"),
+                Self::Infix(l, op, r) => write!(f, "({l} {op} {r})"),
+                Self::Postfix(e, op) => write!(f, "({e}{op})"),
+            }
+        }
+    }
+
+    fn expr<'i>(i: &mut Stream<'i>) -> PResult<Expr<'i>> {
+        precedence(
+            delimited(space0, dec_uint, space0).map(Expr::Num),
+            delimited(space0, take_while(1.., OPERATOR_CHARS), space0),
+            |i: &Stream<'i>, op: &&'i str| i.state.get(*op),
+            |op: Operation<&'i str, Expr<'i>>| match op {
+                Operation::Prefix { op, operand } => Expr::Prefix(op, Box::new(operand)),
+                Operation::Infix { lhs, op, rhs } => Expr::Infix(Box::new(lhs), op, Box::new(rhs)),
+                Operation::Postfix { operand, op } => Expr::Postfix(Box::new(operand), op),
+            },
+        )
+        .parse_next(i)
+    }
+
+    /// `infixl 6 <+>;` style declarations, updating the table for later statements
+    fn declaration(i: &mut Stream<'_>) -> PResult<()> {
+        let (fixity, precedence, op) = (
+            alt(("infixl", "infixr", "infix", "prefix", "postfix")),
+            preceded(' ', dec_uint),
+            preceded(' ', take_while(1.., OPERATOR_CHARS)),
+        )
+            .parse_next(i)?;
+        match fixity {
+            "infixl" => i.state.insert_infix(op, precedence, Assoc::Left),
+            "infixr" => i.state.insert_infix(op, precedence, Assoc::Right),
+            "infix" => i.state.insert_infix(op, precedence, Assoc::Neither),
+            "prefix" => i.state.insert_prefix(op, precedence),
+            _ => i.state.insert_postfix(op, precedence),
+        };
+        Ok(())
+    }
+
+    fn program(i: &mut Stream<'_>) -> PResult<Vec<String>> {
+        let statements: Vec<Option<Expr<'_>>> = separated(
+            0..,
+            alt((declaration.value(None), expr.map(Some))),
+            one_of(';'),
+        )
+        .parse_next(i)?;
+        Ok(statements
+            .into_iter()
+            .flatten()
+            .map(|e| e.to_string())
+            .collect())
+    }
+
+    fn parse(input: &str) -> Result<Vec<String>, ()> {
+        program
+            .parse(Stream {
+                input,
+                state: OperatorTable::new(),
+            })
+            .map_err(|_: crate::error::ParseError<_, ContextError>| ())
+    }
+
+    #[test]
+    fn operand_only() {
+        assert_eq!(parse("1"), Ok(vec!["1".to_owned()]));
+    }
+
+    #[test]
+    fn undeclared_operator_stops_expression() {
+        assert_eq!(parse("1 + 2"), Err(()));
+    }
+
+    #[test]
+    fn associativity() {
+        assert_eq!(
+            parse("infixl 6 -;infixr 8 ^;1 - 2 - 3;1 ^ 2 ^ 3"),
+            Ok(vec!["((1 - 2) - 3)".to_owned(), "(1 ^ (2 ^ 3))".to_owned()])
+        );
+    }
+
+    #[test]
+    fn non_associative() {
+        assert_eq!(
+            parse("infix 4 ==;infixl 6 +;1 + 2 == 3"),
+            Ok(vec!["((1 + 2) == 3)".to_owned()])
+        );
+        assert_eq!(parse("infix 4 ==;1 == 2 == 3"), Err(()));
+    }
+
+    #[test]
+    fn precedence_levels() {
+        assert_eq!(
+            parse("infixl 6 +;infixl 7 *;1 + 2 * 3 + 4;1 * 2 + 3 * 4"),
+            Ok(vec![
+                "((1 + (2 * 3)) + 4)".to_owned(),
+                "((1 * 2) + (3 * 4))".to_owned()
+            ])
+        );
+    }
+
+    #[test]
+    fn prefix_and_postfix() {
+        assert_eq!(
+            parse("infixl 6 -;infixl 7 *;prefix 9 -;postfix 10 !;-1 - -2 * 3!;- - 1"),
+            Ok(vec![
+                "((-1) - ((-2) * (3!)))".to_owned(),
+                "(-(-1))".to_owned()
+            ])
+        );
+    }
+
+    #[test]
+    fn low_precedence_prefix() {
+        assert_eq!(
+            parse("infixl 6 +;prefix 1 -;-1 + 2"),
+            Ok(vec!["(-(1 + 2))".to_owned()])
+        );
+    }
+
+    #[test]
+    fn redeclared_operator() {
+        assert_eq!(
+            parse("infixl 6 +;infixl 7 *;1 + 2 * 3;infixl 5 *;1 + 2 * 3"),
+            Ok(vec!["(1 + (2 * 3))".to_owned(), "((1 + 2) * 3)".to_owned()])
+        );
+    }
+
+    #[test]
+    fn operator_table() {
+        let mut table = OperatorTable::new();
+        table
+            .insert_prefix("-", 3)
+            .insert_infix("-", 1, Assoc::Left);
+        assert_eq!(
+            table.get("-"),
+            Some(Operator {
+                prefix: Some(3),
+                infix: Some((1, Assoc::Left)),
+                postfix: None,
+            })
+        );
+        assert_eq!(table.get("+"), None);
+        assert!(table.remove("-").is_some());
+        assert_eq!(table.get("-"), None);
+    }
+
+    #[test]
+    fn empty_operator() {
+        fn expr<'i>(i: &mut &'i str) -> PResult<u32, InputError<&'i str>> {
+            precedence(
+                dec_uint,
+                "",
+                |_: &&str, _: &&str| {
+                    Some(Operator {
+                        prefix: None,
+                        infix: Some((1, Assoc::Left)),
+                        postfix: None,
+                    })
+                },
+                |_: Operation<&str, u32>| 0,
+            )
+            .parse_next(i)
+        }
+        assert_eq!(
+            expr.parse_peek("1+2"),
+            Err(ErrMode::Cut(InputError::new(
+                "1+2",
+                ErrorKind::InfiniteLoop
+            )))
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+mod memoize {
+    use super::*;
+    use crate::ascii::alpha1;
+    use crate::error::ContextError;
+    use crate::stream::Stateful;
+
+    #[derive(Debug, Default)]
+    struct State {
+        calls: usize,
+        word: MemoTable<String, ContextError>,
+    }
+
+    type Stream<'i> = Stateful<&'i str, State>;
+
+    fn word(i: &mut Stream<'_>) -> PResult<String> {
+        (|i: &mut Stream<'_>| {
+            i.state.calls += 1;
+            alpha1.map(ToOwned::to_owned).parse_next(i)
+        })
+        .memoize(|i: &mut Stream<'_>| &mut i.state.word)
+        .parse_next(i)
+    }
+
+    fn stmt(i: &mut Stream<'_>) -> PResult<(String, String)> {
+        alt((
+            (word, ';').map(|(w, _)| (w, String::new())),
+            (word, '=', word).map(|(l, _, r)| (l, r)),
+            (word, '(', word, ')').map(|(l, _, r, _)| (l, r)),
+        ))
+        .parse_next(i)
+    }
+
+    #[test]
+    fn caches_success() {
+        let mut input = Stream {
+            input: "f(x)",
+            state: State::default(),
+        };
+        assert_eq!(
+            stmt.parse_next(&mut input),
+            Ok(("f".to_owned(), "x".to_owned()))
+        );
+        assert_eq!(input.input, "");
+        assert_eq!(input.state.calls, 2);
+        assert_eq!(input.state.word.len(), 2);
+    }
+
+    #[test]
+    fn caches_failure() {
+        let mut input = Stream {
+            input: "1",
+            state: State::default(),
+        };
+        assert!(stmt.parse_next(&mut input).is_err());
+        assert_eq!(input.state.calls, 1);
+        assert_eq!(input.state.word.len(), 1);
+    }
+
+    #[test]
+    fn scoped_to_input() {
+        let first = stmt.parse(Stream {
+            input: "a=b",
+            state: State::default(),
+        });
+        assert_eq!(first.ok(), Some(("a".to_owned(), "b".to_owned())));
+        let second = stmt.parse(Stream {
+            input: "c=d",
+            state: State::default(),
+        });
+        assert_eq!(second.ok(), Some(("c".to_owned(), "d".to_owned())));
+    }
+
+    #[test]
+    fn window_evicts_behind_frontier() {
+        let source = "a;".repeat(100);
+        let mut input = Stream {
+            input: &source,
+            state: State {
+                calls: 0,
+                word: MemoTable::with_window(4),
+            },
+        };
+        let stmts: Vec<_> = repeat(0.., stmt).parse_next(&mut input).unwrap();
+        assert_eq!(stmts.len(), 100);
+        // One more for the failed attempt at the end of input
+        assert_eq!(input.state.calls, 101);
+        assert!(input.state.word.len() <= 4);
+    }
+
+    #[test]
+    fn window_keeps_recent() {
+        let mut input = Stream {
+            input: "f(x)",
+            state: State {
+                calls: 0,
+                word: MemoTable::with_window(2),
+            },
+        };
+        assert_eq!(
+            stmt.parse_next(&mut input),
+            Ok(("f".to_owned(), "x".to_owned()))
+        );
+        assert_eq!(input.state.calls, 2);
+    }
+
+    #[test]
+    fn incomplete_is_not_cached() {
+        type Stream<'i> = Stateful<Partial<&'i str>, MemoTable<String, ContextError>>;
+
+        fn word(i: &mut Stream<'_>) -> PResult<String> {
+            alpha1
+                .map(ToOwned::to_owned)
+                .memoize(|i: &mut Stream<'_>| &mut i.state)
+                .parse_next(i)
+        }
+
+        let mut input = Stream {
+            input: Partial::new("abc"),
+            state: MemoTable::new(),
+        };
+        assert!(matches!(
+            word.parse_next(&mut input),
+            Err(ErrMode::Incomplete(_))
+        ));
+        assert!(input.state.is_empty());
+    }
+}
+
+#[test]
+fn scoped_state_restores_on_error() {
+    use crate::stream::Stateful;
+
+    type Stream<'i> = Stateful<&'i str, u8>;
+
+    fn flagged<'i>(i: &mut Stream<'i>) -> PResult<u8, InputError<Stream<'i>>> {
+        let flag = i.state;
+        "x".value(flag).parse_next(i)
+    }
+    let mut parser = scoped_state(|i: &mut Stream<'_>| &mut i.state, 2, flagged);
+
+    let mut input = Stream {
+        input: "x",
+        state: 1,
+    };
+    assert_eq!(parser.parse_next(&mut input), Ok(2));
+    assert_eq!(input.state, 1);
+
+    let mut input = Stream {
+        input: "y",
+        state: 1,
+    };
+    assert!(parser.parse_next(&mut input).is_err());
+    assert_eq!(input.state, 1);
+}
+
+#[test]
+fn depth_limited_nesting() {
+    use crate::stream::Stateful;
+
+    type Stream<'i> = Stateful<&'i str, usize>;
+
+    fn list<'i>(i: &mut Stream<'i>) -> PResult<usize, InputError<Stream<'i>>> {
+        depth_limited(
+            |i: &mut Stream<'i>| &mut i.state,
+            4,
+            alt((
+                delimited('[', list, ']').map(|n| n + 1),
+                "[]".value(1),
+                "x".value(0),
+            )),
+        )
+        .parse_next(i)
+    }
+
+    let mut input = Stream {
+        input: "[[[x]]];",
+        state: 0,
+    };
+    assert_eq!(list.parse_next(&mut input), Ok(3));
+    assert_eq!(input.input, ";");
+    assert_eq!(input.state, 0);
+
+    let mut input = Stream {
+        input: "[[[[x]]]]",
+        state: 0,
+    };
+    assert_eq!(
+        list.parse_next(&mut input),
+        Err(ErrMode::Cut(InputError::new(
+            Stream {
+                input: "x]]]]",
+                state: 4,
+            },
+            ErrorKind::Depth
+        )))
+    );
+    assert_eq!(input.state, 0);
+
+    #[cfg(feature = "alloc")]
+    {
+        let deep = format!("{}x{}", "[".repeat(100_000), "]".repeat(100_000));
+        let mut input = Stream {
+            input: &deep,
+            state: 0,
+        };
+        assert!(list.parse_next(&mut input).is_err());
+    }
+}
+
+#[test]
+fn shared_parse_ref() {
+    use crate::ascii::dec_uint;
+
+    let pair = shared(|| (dec_uint::<_, u8, InputError<_>>, ',', dec_uint::<_, u8, _>));
+    let mut input = "1,2;";
+    assert_eq!(pair.parse_ref(&mut input), Ok((1, ',', 2)));
+    assert_eq!(input, ";");
+    assert_eq!((&pair).parse_peek("3,4"), Ok(("", (3, ',', 4))));
+    assert_eq!(
+        (&pair).parse_peek("3;4"),
+        Err(ErrMode::Backtrack(InputError::new(";4", ErrorKind::Tag)))
+    );
+
+    let counter = shared(|| repeat(0.., 'a').fold(|| 0usize, |acc, _: char| acc + 1));
+    let mut parser = &counter;
+    assert_eq!(
+        Parser::<_, _, InputError<_>>::parse_peek(&mut parser, "aab"),
+        Ok(("b", 2))
+    );
+    assert_eq!(
+        counter.parse_ref::<_, _, InputError<_>, _>(&mut "aaa"),
+        Ok(3)
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn declare_mutually_recursive() {
+    // expr = term ('+' term)* ; term = digit | '(' expr ')'
+    let expr = declare::<&str, u32, InputError<&str>>();
+    let term = declare::<&str, u32, InputError<&str>>();
+    assert!(!expr.is_defined());
+
+    let t = term.clone();
+    expr.define(move || {
+        (t.clone(), repeat(0.., preceded('+', t.clone())))
+            .map(|(first, rest): (u32, Vec<u32>)| first + rest.iter().sum::<u32>())
+    });
+    let e = expr.clone();
+    term.define(move || {
+        alt((
+            crate::token::one_of('0'..='9').map(|c: char| c as u32 - '0' as u32),
+            delimited('(', e.clone(), ')'),
+        ))
+    });
+
+    assert_eq!(expr.clone().parse_peek("1+(2+(3+4))+5;"), Ok((";", 15)));
+    assert_eq!(
+        expr.clone().parse_peek("(1+2"),
+        Err(ErrMode::Backtrack(InputError::new("", ErrorKind::Tag)))
+    );
+
+    expr.undefine();
+    term.undefine();
+    assert!(!expr.is_defined());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn branch_stats_report() {
+    use crate::lib::std::string::ToString;
+
+    let stats = BranchStats::new();
+    let mut token = alt((
+        stats.branch("token", "a", 'a'),
+        stats.branch("token", "b", 'b'),
+        stats.branch("token", "c", 'c'),
+        stats.branch("token", "d", 'd'),
+    ));
+    let mut input = "dddcdcda";
+    while !input.is_empty() {
+        let _: char = Parser::<_, _, InputError<_>>::parse_next(&mut token, &mut input).unwrap();
+    }
+
+    let report = stats.report();
+    let alt = &report.alts()[0];
+    assert_eq!(alt.name(), "token");
+    let counts = alt
+        .branches()
+        .iter()
+        .map(|b| (b.label(), b.attempts(), b.successes()))
+        .collect::<Vec<_>>();
+    assert_eq!(counts, [("a", 8, 1), ("b", 7, 0), ("c", 7, 2), ("d", 5, 5)]);
+    assert_eq!(alt.suggested_order(), ["d", "c", "a", "b"]);
+    assert!(alt.suggest_reorder());
+    assert_eq!(alt.attempts_per_success(), 3.375);
+    assert_eq!(alt.suggested_attempts_per_success(), 1.5);
+    assert!(!alt.suggest_dispatch());
+    assert_eq!(
+        report.to_string(),
+        "\
+alt `token`: 8 successes, 3.38 attempts per success
+  a: 1 of 8 attempts succeeded
+  b: 0 of 7 attempts succeeded
+  c: 2 of 7 attempts succeeded
+  d: 5 of 5 attempts succeeded
+  suggestion: reorder as d, c, a, b (1.50 attempts per success)
+"
+    );
+
+    stats.clear();
+    let mut input = "abcdabcd";
+    while !input.is_empty() {
+        let _: char = Parser::<_, _, InputError<_>>::parse_next(&mut token, &mut input).unwrap();
+    }
+    let report = stats.report();
+    let alt = &report.alts()[0];
+    assert!(!alt.suggest_reorder());
+    assert!(alt.suggest_dispatch());
+    assert!(report
+        .to_string()
+        .ends_with("  suggestion: convert to `dispatch!` on the leading token\n"));
+}
+
+#[test]
+fn dispatch_with_lookup() {
+    use crate::token::any;
+
+    type Branch<'i> = fn(&mut &'i [u8]) -> PResult<u16, InputError<&'i [u8]>>;
+
+    fn branch<'i>(key: &u8) -> Option<Branch<'i>> {
+        match key {
+            1 => Some(|i| u8.map(u16::from).parse_next(i)),
+            2 => Some(|i| u16(Endianness::Big).parse_next(i)),
+            _ => None,
+        }
+    }
+    fn parser<'i>(i: &mut &'i [u8]) -> PResult<u16, InputError<&'i [u8]>> {
+        dispatch_with(any, branch).parse_next(i)
+    }
+
+    assert_eq!(parser.parse_peek(&[1, 7, 9][..]), Ok((&[9][..], 7)));
+    assert_eq!(parser.parse_peek(&[2, 1, 0][..]), Ok((&[][..], 256)));
+    assert_eq!(
+        parser.parse_peek(&[3, 1][..]),
+        Err(ErrMode::Backtrack(InputError::new(
+            &[3, 1][..],
+            ErrorKind::Fail
+        )))
+    );
+    assert_eq!(
+        parser.parse_peek(&[2, 1][..]),
+        Err(ErrMode::Backtrack(InputError::new(
+            &[1][..],
+            ErrorKind::Slice
+        )))
+    );
+    #[cfg(feature = "alloc")]
+    assert_eq!(
+        crate::error::UnmatchedKey(b'x').to_string(),
+        "no branch for `120`"
+    );
+}
+
+#[test]
+fn take_trimmed_test() {
+    use crate::token::take_till;
+
+    fn field(i: &str) -> IResult<&str, &str> {
+        terminated(take_till(0.., ';'), ';')
+            .take_trimmed([' ', '\t', ';'])
+            .parse_peek(i)
+    }
+    assert_eq!(field(" \tπ x ;rest"), Ok(("rest", "π x")));
+    assert_eq!(field("ab;"), Ok(("", "ab")));
+    assert_eq!(field("  ;"), Ok(("", "")));
+    assert_eq!(field(";"), Ok(("", "")));
+    assert_eq!(
+        field("ab"),
+        Err(ErrMode::Backtrack(error_position!(&"", ErrorKind::Tag)))
+    );
+
+    fn bytes(i: &[u8]) -> IResult<&[u8], &[u8]> {
+        take(4usize).take_trimmed(b'0').parse_peek(i)
+    }
+    assert_eq!(bytes(b"0120!"), Ok((&b"!"[..], &b"12"[..])));
+    assert_eq!(bytes(b"0000"), Ok((&b""[..], &b""[..])));
+}
+
+#[test]
+fn byte_table_test() {
+    use crate::error::UnmatchedKey;
+    use crate::token::literal;
+
+    fn open<'i>(i: &mut &'i [u8]) -> PResult<u8, InputError<&'i [u8]>> {
+        literal(b"[").value(0).parse_next(i)
+    }
+    fn close<'i>(i: &mut &'i [u8]) -> PResult<u8, InputError<&'i [u8]>> {
+        literal(b"]").value(1).parse_next(i)
+    }
+    fn digits<'i>(i: &mut &'i [u8]) -> PResult<u8, InputError<&'i [u8]>> {
+        digit.value(2).parse_next(i)
+    }
+    fn other<'i>(i: &mut &'i [u8]) -> PResult<u8, InputError<&'i [u8]>> {
+        take(1usize).value(3).parse_next(i)
+    }
+
+    let mut parser = byte_table()
+        .on(.., other)
+        .on(b'[', open)
+        .on(b']', close)
+        .on(b'0'..=b'9', digits)
+        .remove(b'x');
+    assert_eq!(
+        parser.get(b'5').unwrap().parse_peek(&b"5"[..]),
+        Ok((&b""[..], 2))
+    );
+    assert!(parser.get(b'x').is_none());
+
+    assert_eq!(parser.parse_peek(&b"[1"[..]), Ok((&b"1"[..], 0)));
+    assert_eq!(parser.parse_peek(&b"]"[..]), Ok((&b""[..], 1)));
+    assert_eq!(parser.parse_peek(&b"123]"[..]), Ok((&b"]"[..], 2)));
+    assert_eq!(parser.parse_peek(&b"\xff"[..]), Ok((&b""[..], 3)));
+    assert_eq!(
+        parser.parse_peek(&b"x"[..]),
+        Err(ErrMode::Backtrack(InputError::new(
+            &b"x"[..],
+            ErrorKind::Fail
+        )))
+    );
+    assert_eq!(
+        parser.parse_peek(&b""[..]),
+        Err(ErrMode::Backtrack(InputError::new(
+            &b""[..],
+            ErrorKind::Token
+        )))
+    );
+
+    fn word<'i>(i: &mut Partial<&'i str>) -> PResult<char, InputError<Partial<&'i str>>> {
+        crate::token::any.parse_next(i)
+    }
+    let mut partial = byte_table().on(b'a'..=b'z', word);
+    assert_eq!(
+        partial.parse_peek(Partial::new("")),
+        Err(ErrMode::Incomplete(Needed::new(1)))
+    );
+    assert_eq!(
+        partial.parse_peek(Partial::new("q!")),
+        Ok((Partial::new("!"), 'q'))
+    );
+    assert_eq!(
+        partial.parse_peek(Partial::new("π")),
+        Err(ErrMode::Backtrack(InputError::new(
+            Partial::new("π"),
+            ErrorKind::Fail
+        )))
+    );
+    #[cfg(feature = "alloc")]
+    assert_eq!(UnmatchedKey('π').to_string(), "no branch for `'π'`");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn warn_if_test() {
+    use crate::ascii::dec_uint;
+    use crate::error::ContextError;
+    use crate::stream::Located;
+    use crate::stream::Location;
+    use crate::stream::Stateful;
+
+    type Stream<'i> = Stateful<Located<&'i str>, Warnings<&'static str>>;
+
+    fn port(i: &mut Stream<'_>) -> PResult<u32, ContextError> {
+        dec_uint
+            .verify(|p: &u32| *p <= 65535)
+            .warn_if(
+                |p: &u32| (*p < 1024).then_some("privileged port"),
+                |i: &mut Stream<'_>| &mut i.state,
+            )
+            .parse_next(i)
+    }
+
+    let mut input = Stream {
+        input: Located::new("80,8080,443,99999"),
+        state: Warnings::new(),
+    };
+    let ports: Vec<u32> = separated(1.., port, ',').parse_next(&mut input).unwrap();
+    assert_eq!(ports, [80, 8080, 443]);
+    assert_eq!(input.location(), 11);
+    assert_eq!(
+        input.state.as_slice(),
+        [(0..2, "privileged port"), (8..11, "privileged port")]
+    );
+
+    let mut input = Stream {
+        input: Located::new("99999"),
+        state: Warnings::new(),
+    };
+    assert!(port(&mut input).is_err());
+    assert!(input.state.is_empty());
+
+    input.state.push(0..5, "pushed");
+    assert_eq!(input.state.len(), 1);
+    assert_eq!(input.state.clone().into_vec(), [(0..5, "pushed")]);
+    input.state.clear();
+    assert!(input.state.is_empty());
+}
@@ -0,0 +1,264 @@
+//! Email address parsers, following [RFC 5322](https://www.rfc-editor.org/rfc/rfc5322#section-3.4.1)
+//!
+//! # Example
+//!
+//! ```rust
+//! # use winnow::prelude::*;
+//! # use winnow::error::ContextError;
+//! use winnow::contrib::email::addr_spec;
+//! use winnow::contrib::email::AddrSpecSyntax;
+//!
+//! let addr = addr_spec::<_, ContextError>(AddrSpecSyntax::Practical)
+//!     .parse("jane.doe+news@example.com")
+//!     .unwrap();
+//! assert_eq!(addr.local_part, "jane.doe+news");
+//! assert_eq!(addr.domain, "example.com");
+//! ```
+
+use crate::combinator::alt;
+use crate::combinator::delimited;
+use crate::combinator::repeat;
+use crate::combinator::trace;
+use crate::error::{ErrMode, ErrorKind, ParserError};
+use crate::stream::{AsChar, Compare, Stream, StreamIsPartial};
+use crate::token::one_of;
+use crate::token::take_while;
+use crate::PResult;
+use crate::Parser;
+
+/// An `addr-spec`, like `local@domain`, see [`addr_spec`]
+///
+/// Each part is a slice of the input.  A quoted local part keeps its quotes and escapes and a
+/// domain literal keeps its brackets.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AddrSpec<S> {
+    /// What precedes the `@`
+    pub local_part: S,
+    /// What follows the `@`
+    pub domain: S,
+}
+
+/// Which addresses [`addr_spec`] accepts
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum AddrSpecSyntax {
+    /// Everything RFC 5322 allows, except comments, folding whitespace, and the obsolete
+    /// syntax
+    ///
+    /// This includes quoted local parts, like `"john doe"@example.com`, and domain literals,
+    /// like `user@[192.0.2.1]`.
+    Rfc5322,
+    /// What mail systems deliver in practice
+    ///
+    /// The local part is dot-separated atoms of at most 64 characters and the domain is a
+    /// host name of at most 253 characters, with labels of at most 63 letters, digits, and
+    /// inner hyphens.
+    #[default]
+    Practical,
+}
+
+/// Parse an email address, without a display name or angle brackets
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if the address may continue.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// # use winnow::error::ContextError;
+/// # use winnow::contrib::email::{AddrSpec, AddrSpecSyntax};
+/// pub fn addr_spec<'i>(syntax: AddrSpecSyntax) -> impl Parser<&'i str, AddrSpec<&'i str>, ContextError>
+/// # {
+/// #     winnow::contrib::email::addr_spec(syntax)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::contrib::email::addr_spec;
+/// use winnow::contrib::email::AddrSpecSyntax;
+///
+/// let mut rfc = addr_spec::<_, ContextError>(AddrSpecSyntax::Rfc5322);
+/// let addr = rfc.parse(r#""john \"jd\" doe"@[192.0.2.1]"#).unwrap();
+/// assert_eq!(addr.local_part, r#""john \"jd\" doe""#);
+/// assert_eq!(addr.domain, "[192.0.2.1]");
+///
+/// let mut practical = addr_spec::<_, ContextError>(AddrSpecSyntax::Practical);
+/// assert!(practical.parse(r#""john doe"@example.com"#).is_err());
+/// assert!(practical.parse("user@-example.com").is_err());
+/// assert!(practical.parse("first..last@example.com").is_err());
+/// ```
+pub fn addr_spec<Input, Error>(
+    syntax: AddrSpecSyntax,
+) -> impl Parser<Input, AddrSpec<<Input as Stream>::Slice>, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    trace("addr_spec", move |input: &mut Input| {
+        let (local_part, domain) = match syntax {
+            AddrSpecSyntax::Rfc5322 => {
+                let local_part = alt((dot_atom, quoted_string)).parse_next(input)?;
+                '@'.parse_next(input)?;
+                let domain = alt((dot_atom, domain_literal)).parse_next(input)?;
+                (local_part, domain)
+            }
+            AddrSpecSyntax::Practical => {
+                let local_part = at_most(64, dot_atom).parse_next(input)?;
+                '@'.parse_next(input)?;
+                let domain = at_most(253, host_name).parse_next(input)?;
+                (local_part, domain)
+            }
+        };
+        Ok(AddrSpec { local_part, domain })
+    })
+}
+
+/// `1*atext *("." 1*atext)`
+fn dot_atom<Input, Error>(input: &mut Input) -> PResult<<Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    (
+        take_while(1.., is_atext),
+        repeat::<_, _, (), _, _>(0.., ('.', take_while(1.., is_atext))),
+    )
+        .take()
+        .parse_next(input)
+}
+
+/// `DQUOTE *(qtext / quoted-pair) DQUOTE`
+fn quoted_string<Input, Error>(input: &mut Input) -> PResult<<Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    delimited(
+        '"',
+        repeat::<_, _, (), _, _>(
+            0..,
+            alt((
+                take_while(1.., |t: <Input as Stream>::Token| {
+                    let c = t.as_char();
+                    c == ' ' || (is_vchar(c) && c != '"' && c != '\\')
+                })
+                .void(),
+                (
+                    '\\',
+                    one_of(|t: <Input as Stream>::Token| {
+                        let c = t.as_char();
+                        c == ' ' || c == '\t' || is_vchar(c)
+                    }),
+                )
+                    .void(),
+            )),
+        ),
+        '"',
+    )
+    .take()
+    .parse_next(input)
+}
+
+/// `"[" *dtext "]"`
+fn domain_literal<Input, Error>(input: &mut Input) -> PResult<<Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    delimited(
+        '[',
+        take_while(0.., |t: <Input as Stream>::Token| {
+            let c = t.as_char();
+            is_vchar(c) && !matches!(c, '[' | ']' | '\\')
+        }),
+        ']',
+    )
+    .take()
+    .parse_next(input)
+}
+
+/// Dot-separated labels of letters, digits, and inner hyphens
+fn host_name<Input, Error>(input: &mut Input) -> PResult<<Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    let label = || {
+        at_most(
+            63,
+            (
+                take_while(1.., AsChar::is_alphanum),
+                repeat::<_, _, (), _, _>(
+                    0..,
+                    (take_while(1.., '-'), take_while(1.., AsChar::is_alphanum)),
+                ),
+            )
+                .take(),
+        )
+    };
+    (label(), repeat::<_, _, (), _, _>(0.., ('.', label())))
+        .take()
+        .parse_next(input)
+}
+
+/// Fail when `parser` consumes more than `max` characters
+fn at_most<Input, Output, Error, ParseNext>(
+    max: usize,
+    mut parser: ParseNext,
+) -> impl Parser<Input, Output, Error>
+where
+    Input: Stream,
+    Error: ParserError<Input>,
+    ParseNext: Parser<Input, Output, Error>,
+{
+    move |input: &mut Input| {
+        let start = input.checkpoint();
+        let output = parser.parse_next(input)?;
+        // Only ASCII is accepted, so offsets count characters
+        let len = input.offset_from(&start);
+        if max < len {
+            input.reset(&start);
+            return Err(ErrMode::from_error_kind(input, ErrorKind::Verify));
+        }
+        Ok(output)
+    }
+}
+
+fn is_atext<T: AsChar>(t: T) -> bool {
+    let c = t.as_char();
+    c.is_ascii_alphanumeric()
+        || matches!(
+            c,
+            '!' | '#'
+                | '$'
+                | '%'
+                | '&'
+                | '\''
+                | '*'
+                | '+'
+                | '-'
+                | '/'
+                | '='
+                | '?'
+                | '^'
+                | '_'
+                | '`'
+                | '{'
+                | '|'
+                | '}'
+                | '~'
+        )
+}
+
+fn is_vchar(c: char) -> bool {
+    c.is_ascii_graphic()
+}
@@ -0,0 +1,225 @@
+//! Parsers for `application/x-www-form-urlencoded` bodies and URI query strings
+//!
+//! # Example
+//!
+//! ```rust
+//! # use winnow::prelude::*;
+//! # use winnow::error::ContextError;
+//! use winnow::contrib::form::form_urlencoded;
+//! use winnow::contrib::form::FormPolicy;
+//!
+//! let pairs = form_urlencoded::<_, ContextError>(FormPolicy::new())
+//!     .parse("name=J%C3%BCrgen+M&lang=rust")
+//!     .unwrap();
+//! assert_eq!(pairs, [("name".into(), "Jürgen M".into()), ("lang".into(), "rust".into())]);
+//! ```
+
+use crate::combinator::opt;
+use crate::combinator::preceded;
+use crate::combinator::trace;
+use crate::contrib::uri::percent_decode;
+use crate::error::DuplicateKey;
+use crate::error::{ErrMode, ErrorKind, FromExternalError, ParserError};
+use crate::lib::std::borrow::Cow;
+use crate::lib::std::collections::BTreeMap;
+use crate::lib::std::string::String;
+use crate::lib::std::vec::Vec;
+use crate::stream::{AsChar, Compare, Stream, StreamIsPartial};
+use crate::token::take_while;
+use crate::Parser;
+
+/// How [`form_urlencoded`] decodes pairs
+///
+/// The default follows the [WHATWG URL standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded):
+/// `+` is a space and every pair is kept, even with a repeated key.
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::contrib::form::DuplicateKeys;
+/// use winnow::contrib::form::FormPolicy;
+///
+/// // A generic URI query, where `+` is literal and keys must be unique
+/// let query = FormPolicy::new()
+///     .plus_as_space(false)
+///     .duplicate_keys(DuplicateKeys::Reject);
+/// # let _ = query;
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FormPolicy {
+    plus_as_space: bool,
+    duplicate_keys: DuplicateKeys,
+}
+
+impl FormPolicy {
+    /// Decode like a form submitted by a browser
+    pub const fn new() -> Self {
+        Self {
+            plus_as_space: true,
+            duplicate_keys: DuplicateKeys::Keep,
+        }
+    }
+
+    /// Decode `+` as a space
+    ///
+    /// An encoded `%2B` is always decoded as `+`.
+    pub const fn plus_as_space(mut self, yes: bool) -> Self {
+        self.plus_as_space = yes;
+        self
+    }
+
+    /// What to do when a key repeats
+    pub const fn duplicate_keys(mut self, duplicate_keys: DuplicateKeys) -> Self {
+        self.duplicate_keys = duplicate_keys;
+        self
+    }
+}
+
+impl Default for FormPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What [`form_urlencoded`] does when a key repeats, see [`FormPolicy::duplicate_keys`]
+///
+/// Keys are compared after decoding.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    /// Keep every pair, like for `color=red&color=blue` from a multi-select
+    #[default]
+    Keep,
+    /// Keep the first pair with a key
+    First,
+    /// Keep the value of the last pair with a key, in the position of the first
+    Last,
+    /// Fail with a [`DuplicateKey`]
+    Reject,
+}
+
+/// Parse `application/x-www-form-urlencoded` pairs, like `a=1&b=2`, decoding keys and values
+///
+/// Keys and values are borrowed from the input unless decoding changed them.  A pair without
+/// `=` has an empty value and empty pairs, like in `a=1&&b=2`, are skipped.  Malformed escapes,
+/// like `%G0`, are left as they are and escapes that aren't UTF-8 are replaced with `U+FFFD`.
+///
+/// This stops at `#` or ASCII whitespace, so it can parse the query of a URI in place.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if the input may continue.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use std::borrow::Cow;
+/// # use winnow::prelude::*;;
+/// # use winnow::error::ContextError;
+/// # use winnow::contrib::form::FormPolicy;
+/// pub fn form_urlencoded<'i>(policy: FormPolicy) -> impl Parser<&'i str, Vec<(Cow<'i, str>, Cow<'i, str>)>, ContextError>
+/// # {
+/// #     winnow::contrib::form::form_urlencoded(policy)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use std::borrow::Cow;
+/// use winnow::contrib::form::form_urlencoded;
+/// use winnow::contrib::form::DuplicateKeys;
+/// use winnow::contrib::form::FormPolicy;
+///
+/// let mut last = form_urlencoded::<_, ContextError>(
+///     FormPolicy::new().duplicate_keys(DuplicateKeys::Last),
+/// );
+/// let pairs = last.parse("a=1&b&a=2").unwrap();
+/// assert_eq!(pairs, [("a".into(), "2".into()), ("b".into(), "".into())]);
+/// assert!(matches!(pairs[0].1, Cow::Borrowed(_)));
+///
+/// let mut unique = form_urlencoded::<_, ContextError>(
+///     FormPolicy::new().duplicate_keys(DuplicateKeys::Reject),
+/// );
+/// let err = unique.parse("a=1&b&a=2").unwrap_err();
+/// assert_eq!(err.offset(), 6);
+/// assert_eq!(err.inner().cause().unwrap().to_string(), r#"duplicate key `"a"`"#);
+/// ```
+pub fn form_urlencoded<'i, Input, Error>(
+    policy: FormPolicy,
+) -> impl Parser<Input, Vec<(Cow<'i, str>, Cow<'i, str>)>, Error>
+where
+    Input: StreamIsPartial + Stream<Slice = &'i str> + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input> + FromExternalError<Input, DuplicateKey>,
+{
+    trace("form_urlencoded", move |input: &mut Input| {
+        let mut pairs: Vec<(Cow<'i, str>, Cow<'i, str>)> = Vec::new();
+        // Index of each key in `pairs`, when they are deduplicated
+        let mut seen: BTreeMap<Cow<'i, str>, usize> = BTreeMap::new();
+        loop {
+            let start = input.checkpoint();
+            let key = take_while(0.., |t: <Input as Stream>::Token| {
+                let c = t.as_char();
+                c != '=' && is_form_char(c)
+            })
+            .parse_next(input)?;
+            let value = opt(preceded(
+                '=',
+                take_while(0.., |t: <Input as Stream>::Token| is_form_char(t.as_char())),
+            ))
+            .parse_next(input)?;
+
+            if !key.is_empty() || value.is_some() {
+                let key = decode(key, policy.plus_as_space);
+                let value = decode(value.unwrap_or_default(), policy.plus_as_space);
+                let existing = match policy.duplicate_keys {
+                    DuplicateKeys::Keep => None,
+                    _ => seen.get(&key).copied(),
+                };
+                match (existing, policy.duplicate_keys) {
+                    (None, DuplicateKeys::Keep) => pairs.push((key, value)),
+                    (None, _) => {
+                        seen.insert(key.clone(), pairs.len());
+                        pairs.push((key, value));
+                    }
+                    (Some(_), DuplicateKeys::Keep | DuplicateKeys::First) => {}
+                    (Some(i), DuplicateKeys::Last) => pairs[i].1 = value,
+                    (Some(_), DuplicateKeys::Reject) => {
+                        input.reset(&start);
+                        return Err(ErrMode::from_external_error(
+                            input,
+                            ErrorKind::Verify,
+                            DuplicateKey(alloc::format!("{key:?}")),
+                        ));
+                    }
+                }
+            }
+
+            if opt('&').parse_next(input)?.is_none() {
+                return Ok(pairs);
+            }
+        }
+    })
+}
+
+fn is_form_char(c: char) -> bool {
+    !matches!(c, '&' | '#') && !c.is_ascii_whitespace() && !c.is_ascii_control()
+}
+
+fn decode(encoded: &str, plus_as_space: bool) -> Cow<'_, str> {
+    let plus = plus_as_space && encoded.contains('+');
+    if !plus && !encoded.contains('%') {
+        return Cow::Borrowed(encoded);
+    }
+    let decoded = if plus {
+        let spaced = encoded.replace('+', " ");
+        percent_decode(spaced.as_bytes()).into_owned()
+    } else {
+        percent_decode(encoded.as_bytes()).into_owned()
+    };
+    Cow::Owned(match String::from_utf8(decoded) {
+        Ok(decoded) => decoded,
+        Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+    })
+}
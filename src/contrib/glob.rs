@@ -0,0 +1,286 @@
+//! Glob patterns, like `src/**/*.{rs,toml}`
+//!
+//! The pattern is parsed into a list of [`GlobToken`]s which [`Glob::matches`] then walks,
+//! showing how a grammar can produce an intermediate representation to evaluate later.
+//!
+//! | Syntax      | Matches                                                         |
+//! |-------------|-----------------------------------------------------------------|
+//! | `?`         | Any character except `/`                                        |
+//! | `*`         | Any characters except `/`                                       |
+//! | `**`        | Any characters                                                  |
+//! | `**/`       | Nothing or any characters ending in `/`, like `a/b/`            |
+//! | `[abc]`     | Any of the characters, with ranges like `[a-z]`                 |
+//! | `[!abc]`    | Any character but `/` and those listed, also written `[^abc]`   |
+//! | `{a,b}`     | Any of the comma-separated patterns                             |
+//! | `\*`        | The character after the `\`                                     |
+//!
+//! # Example
+//!
+//! ```rust
+//! use winnow::contrib::glob::Glob;
+//!
+//! let glob = Glob::new("src/**/*.{rs,toml}").unwrap();
+//! assert!(glob.matches("src/lib.rs"));
+//! assert!(glob.matches("src/contrib/glob.rs"));
+//! assert!(!glob.matches("src/lib.rs.bak"));
+//! assert!(!glob.matches("tests/lib.rs"));
+//! ```
+
+use crate::combinator::alt;
+use crate::combinator::delimited;
+use crate::combinator::opt;
+use crate::combinator::preceded;
+use crate::combinator::repeat;
+use crate::combinator::separated;
+use crate::combinator::trace;
+use crate::error::{ContextError, ParseError, ParserError};
+use crate::lib::std::ops::RangeInclusive;
+use crate::lib::std::string::String;
+use crate::lib::std::vec::Vec;
+use crate::stream::{AsChar, Compare, Stream, StreamIsPartial};
+use crate::token::any;
+use crate::token::one_of;
+use crate::PResult;
+use crate::Parser;
+
+/// A compiled glob pattern, see the [module docs][self]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Glob {
+    tokens: Vec<GlobToken>,
+}
+
+impl Glob {
+    /// Parse all of `pattern`
+    pub fn new(pattern: &str) -> Result<Self, ParseError<&str, ContextError>> {
+        glob.parse(pattern)
+    }
+
+    /// The parsed pattern
+    pub fn tokens(&self) -> &[GlobToken] {
+        &self.tokens
+    }
+
+    /// Whether all of `path` matches the pattern
+    ///
+    /// Matching backtracks, so patterns with many `*`s can be slow on long paths.
+    pub fn matches(&self, path: &str) -> bool {
+        match_tokens(&self.tokens, None, path)
+    }
+}
+
+/// A part of a [`Glob`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GlobToken {
+    /// Characters to match exactly
+    Literal(String),
+    /// `?`
+    AnyChar,
+    /// `*`
+    AnySequence,
+    /// `**`
+    AnyPath,
+    /// `**/`
+    AnyDirectories,
+    /// `[...]`
+    Class {
+        /// Whether this is `[!...]`
+        negated: bool,
+        /// The characters listed, with single characters as one-character ranges
+        ranges: Vec<RangeInclusive<char>>,
+    },
+    /// `{...,...}`
+    Alternatives(Vec<Vec<GlobToken>>),
+}
+
+/// Parse a glob pattern
+///
+/// Outside of `{...}`, `,` and `}` are literal.  This stops at a `[` or `{` that isn't closed
+/// and at a trailing `\`.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// # use winnow::contrib::glob::Glob;
+/// pub fn glob(input: &mut &str) -> PResult<Glob>
+/// # {
+/// #     winnow::contrib::glob::glob.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::contrib::glob::glob;
+/// use winnow::contrib::glob::GlobToken;
+///
+/// let pattern = glob::<_, ContextError>.parse("*.[ch]").unwrap();
+/// assert_eq!(
+///     pattern.tokens(),
+///     [
+///         GlobToken::AnySequence,
+///         GlobToken::Literal(".".into()),
+///         GlobToken::Class { negated: false, ranges: vec!['c'..='c', 'h'..='h'] },
+///     ]
+/// );
+/// ```
+pub fn glob<Input, Error>(input: &mut Input) -> PResult<Glob, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char> + Compare<&'static str>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    trace("glob", |input: &mut Input| {
+        let tokens = sequence(input, false)?;
+        Ok(Glob { tokens })
+    })
+    .parse_next(input)
+}
+
+fn sequence<Input, Error>(input: &mut Input, in_braces: bool) -> PResult<Vec<GlobToken>, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char> + Compare<&'static str>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    repeat(0.., |input: &mut Input| token(input, in_braces)).parse_next(input)
+}
+
+fn token<Input, Error>(input: &mut Input, in_braces: bool) -> PResult<GlobToken, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char> + Compare<&'static str>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    alt((
+        "**/".value(GlobToken::AnyDirectories),
+        "**".value(GlobToken::AnyPath),
+        '*'.value(GlobToken::AnySequence),
+        '?'.value(GlobToken::AnyChar),
+        class,
+        alternatives,
+        repeat(
+            1..,
+            alt((
+                preceded('\\', any).map(AsChar::as_char),
+                one_of(move |t: <Input as Stream>::Token| !is_special(t.as_char(), in_braces))
+                    .map(AsChar::as_char),
+            )),
+        )
+        .map(GlobToken::Literal),
+    ))
+    .parse_next(input)
+}
+
+/// `[` `!`? `]`? (char (`-` char)?)* `]`
+fn class<Input, Error>(input: &mut Input) -> PResult<GlobToken, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    '['.parse_next(input)?;
+    let negated = opt(one_of(['!', '^'])).parse_next(input)?.is_some();
+    let mut ranges = Vec::new();
+    if opt(']').parse_next(input)?.is_some() {
+        ranges.push(']'..=']');
+    }
+    let member = || one_of(|t: <Input as Stream>::Token| t.as_char() != ']').map(AsChar::as_char);
+    loop {
+        if opt(']').parse_next(input)?.is_some() {
+            return Ok(GlobToken::Class { negated, ranges });
+        }
+        let start = member().parse_next(input)?;
+        let end = opt(preceded('-', member())).parse_next(input)?;
+        ranges.push(start..=end.unwrap_or(start));
+    }
+}
+
+/// `{` pattern (`,` pattern)* `}`
+fn alternatives<Input, Error>(input: &mut Input) -> PResult<GlobToken, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char> + Compare<&'static str>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    delimited(
+        '{',
+        separated(1.., |input: &mut Input| sequence(input, true), ','),
+        '}',
+    )
+    .map(GlobToken::Alternatives)
+    .parse_next(input)
+}
+
+fn is_special(c: char, in_braces: bool) -> bool {
+    match c {
+        '*' | '?' | '[' | '{' | '\\' => true,
+        ',' | '}' => in_braces,
+        _ => false,
+    }
+}
+
+/// What to match after the current tokens
+struct Continuation<'t> {
+    tokens: &'t [GlobToken],
+    next: Option<&'t Continuation<'t>>,
+}
+
+fn match_tokens(tokens: &[GlobToken], next: Option<&Continuation<'_>>, path: &str) -> bool {
+    let Some((first, rest)) = tokens.split_first() else {
+        return match next {
+            Some(next) => match_tokens(next.tokens, next.next, path),
+            None => path.is_empty(),
+        };
+    };
+    match first {
+        GlobToken::Literal(literal) => match path.strip_prefix(literal.as_str()) {
+            Some(path) => match_tokens(rest, next, path),
+            None => false,
+        },
+        GlobToken::AnyChar => {
+            let mut chars = path.chars();
+            match chars.next() {
+                Some(c) if c != '/' => match_tokens(rest, next, chars.as_str()),
+                _ => false,
+            }
+        }
+        GlobToken::AnySequence => {
+            let end = path.find('/').unwrap_or(path.len());
+            path[..end]
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain(Some(end))
+                .any(|i| match_tokens(rest, next, &path[i..]))
+        }
+        GlobToken::AnyPath => path
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(Some(path.len()))
+            .any(|i| match_tokens(rest, next, &path[i..])),
+        GlobToken::AnyDirectories => {
+            match_tokens(rest, next, path)
+                || path
+                    .match_indices('/')
+                    .any(|(i, _)| match_tokens(rest, next, &path[i + 1..]))
+        }
+        GlobToken::Class { negated, ranges } => {
+            let mut chars = path.chars();
+            match chars.next() {
+                Some(c) if c != '/' && ranges.iter().any(|r| r.contains(&c)) != *negated => {
+                    match_tokens(rest, next, chars.as_str())
+                }
+                _ => false,
+            }
+        }
+        GlobToken::Alternatives(alternatives) => {
+            let next = Continuation { tokens: rest, next };
+            alternatives
+                .iter()
+                .any(|alternative| match_tokens(alternative, Some(&next), path))
+        }
+    }
+}
@@ -0,0 +1,384 @@
+//! Scanners for angle-bracket markup, like XML and HTML
+//!
+//! These recognize the pieces of a document, not the document itself: there is no tree, no
+//! namespaces, and no DTD.  This is enough for XML-like config formats and for pulling data out
+//! of HTML, where a full XML parser would reject the input.
+//!
+//! Attribute values are returned as slices of the input, still escaped.  Decode them with
+//! [`unescape`] or, while parsing, with [`entity`].
+//!
+//! # Example
+//!
+//! ```rust
+//! # use winnow::prelude::*;
+//! # use winnow::error::ContextError;
+//! use winnow::ascii::multispace0;
+//! use winnow::ascii::multispace1;
+//! use winnow::combinator::alt;
+//! use winnow::combinator::preceded;
+//! use winnow::combinator::repeat;
+//! use winnow::contrib::markup::attribute;
+//! use winnow::contrib::markup::tag_name;
+//! use winnow::contrib::markup::Attribute;
+//!
+//! fn start_tag<'i>(input: &mut &'i str) -> PResult<(&'i str, Vec<Attribute<&'i str>>, bool)> {
+//!     (
+//!         preceded('<', tag_name),
+//!         repeat(0.., preceded(multispace1, attribute)),
+//!         preceded(multispace0, alt((">".value(false), "/>".value(true)))),
+//!     )
+//!         .parse_next(input)
+//! }
+//!
+//! let (name, attributes, empty) = start_tag
+//!     .parse(r#"<a href="/?q=1&amp;r=2" class='nav'>"#)
+//!     .unwrap();
+//! assert_eq!(name, "a");
+//! assert_eq!(attributes[0], Attribute { name: "href", value: "/?q=1&amp;r=2" });
+//! assert_eq!(attributes[1], Attribute { name: "class", value: "nav" });
+//! assert!(!empty);
+//! ```
+
+use crate::ascii::char_ref;
+#[cfg(feature = "alloc")]
+use crate::ascii::decode_entity_refs;
+use crate::ascii::multispace0;
+use crate::combinator::alt;
+use crate::combinator::delimited;
+use crate::combinator::preceded;
+use crate::combinator::repeat;
+use crate::combinator::separated_pair;
+use crate::combinator::trace;
+use crate::error::ParserError;
+#[cfg(feature = "alloc")]
+use crate::lib::std::borrow::Cow;
+use crate::stream::{AsBStr, AsChar, Compare, FindSlice, Stream, StreamIsPartial};
+use crate::token::one_of;
+use crate::token::take_until;
+use crate::token::take_while;
+use crate::PResult;
+use crate::Parser;
+
+/// A `name="value"` pair in a tag, see [`attribute`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Attribute<S> {
+    /// Like `href`
+    pub name: S,
+    /// What is between the quotes, still escaped
+    pub value: S,
+}
+
+/// The entities predefined by XML, for [`decode_entities`][crate::ascii::decode_entities]
+pub const XML_ENTITIES: &[(&str, &str)] = &[
+    ("amp", "&"),
+    ("lt", "<"),
+    ("gt", ">"),
+    ("quot", "\""),
+    ("apos", "'"),
+];
+
+/// Parse the name of a tag or attribute, like `svg:rect` or `data-id`
+///
+/// This follows the [XML `Name`](https://www.w3.org/TR/xml/#NT-Name) production, treating any
+/// non-ASCII letter as a name character.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if the name may continue.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// pub fn tag_name<'i>(input: &mut &'i str) -> PResult<&'i str>
+/// # {
+/// #     winnow::contrib::markup::tag_name.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::InputError;
+/// use winnow::contrib::markup::tag_name;
+///
+/// assert_eq!(tag_name::<_, InputError<_>>.parse_peek("svg:rect x='1'"), Ok((" x='1'", "svg:rect")));
+/// assert!(tag_name::<_, InputError<_>>.parse_peek("1st").is_err());
+/// ```
+pub fn tag_name<Input, Error>(input: &mut Input) -> PResult<<Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    trace(
+        "tag_name",
+        (
+            one_of(|t: <Input as Stream>::Token| is_name_start_char(t.as_char())),
+            take_while(0.., |t: <Input as Stream>::Token| is_name_char(t.as_char())),
+        )
+            .take(),
+    )
+    .parse_next(input)
+}
+
+/// Parse a `name="value"` or `name='value'` pair
+///
+/// Whitespace is allowed around `=`.  The value may not contain `<`.  Like browsers, an `&` that
+/// doesn't start an [`entity`], as in `R&D` or `&nbsp;`, is kept as it is.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if the value isn't closed yet.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// # use winnow::contrib::markup::Attribute;
+/// pub fn attribute<'i>(input: &mut &'i str) -> PResult<Attribute<&'i str>>
+/// # {
+/// #     winnow::contrib::markup::attribute.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::InputError;
+/// use winnow::contrib::markup::attribute;
+/// use winnow::contrib::markup::Attribute;
+///
+/// assert_eq!(
+///     attribute::<_, InputError<_>>.parse_peek(r#"title = 'Say "hi" &amp; go'/>"#),
+///     Ok(("/>", Attribute { name: "title", value: r#"Say "hi" &amp; go"# }))
+/// );
+/// assert_eq!(
+///     attribute::<_, InputError<_>>.parse_peek(r#"title="R&D""#),
+///     Ok(("", Attribute { name: "title", value: "R&D" }))
+/// );
+/// assert!(attribute::<_, InputError<_>>.parse_peek(r#"title="a<b""#).is_err());
+/// ```
+pub fn attribute<Input, Error>(
+    input: &mut Input,
+) -> PResult<Attribute<<Input as Stream>::Slice>, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char> + Compare<&'static str>,
+    <Input as Stream>::Token: AsChar + Clone,
+    <Input as Stream>::Slice: AsBStr,
+    Error: ParserError<Input>,
+{
+    trace(
+        "attribute",
+        separated_pair(
+            tag_name,
+            (multispace0, '=', multispace0),
+            alt((quoted_value('"'), quoted_value('\''))),
+        )
+        .map(|(name, value)| Attribute { name, value }),
+    )
+    .parse_next(input)
+}
+
+/// Parse an entity or character reference, like `&amp;` or `&#x263A;`, returning its value
+///
+/// Only the entities in [`XML_ENTITIES`] are recognized: `&amp;`, `&lt;`, `&gt;`, `&quot;`, and
+/// `&apos;`.  For HTML's, put a parser for the names you need in an
+/// [`alt`][crate::combinator::alt] after this.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if the reference may continue.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// pub fn entity(input: &mut &str) -> PResult<char>
+/// # {
+/// #     winnow::contrib::markup::entity.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::InputError;
+/// use winnow::contrib::markup::entity;
+///
+/// assert_eq!(entity::<_, InputError<_>>.parse_peek("&lt;b>"), Ok(("b>", '<')));
+/// assert_eq!(entity::<_, InputError<_>>.parse_peek("&#233;t"), Ok(("t", 'é')));
+/// assert_eq!(entity::<_, InputError<_>>.parse_peek("&#x263a;"), Ok(("", '☺')));
+/// assert!(entity::<_, InputError<_>>.parse_peek("&nbsp;").is_err());
+/// assert!(entity::<_, InputError<_>>.parse_peek("&#xD800;").is_err());
+/// ```
+pub fn entity<Input, Error>(input: &mut Input) -> PResult<char, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char> + Compare<&'static str>,
+    <Input as Stream>::Token: AsChar + Clone,
+    <Input as Stream>::Slice: AsBStr,
+    Error: ParserError<Input>,
+{
+    trace(
+        "entity",
+        delimited(
+            '&',
+            alt((
+                preceded(
+                    ('#', one_of(['x', 'X'])),
+                    take_while(1.., AsChar::is_hex_digit),
+                )
+                .verify_map(|digits: <Input as Stream>::Slice| char_ref(digits.as_bstr(), 16)),
+                preceded('#', take_while(1.., AsChar::is_dec_digit))
+                    .verify_map(|digits: <Input as Stream>::Slice| char_ref(digits.as_bstr(), 10)),
+                take_while(1.., AsChar::is_alphanum).verify_map(
+                    |name: <Input as Stream>::Slice| {
+                        let name = name.as_bstr();
+                        XML_ENTITIES
+                            .iter()
+                            .find(|(n, _)| n.as_bytes() == name)
+                            .and_then(|(_, value)| value.chars().next())
+                    },
+                ),
+            )),
+            ';',
+        ),
+    )
+    .parse_next(input)
+}
+
+/// Parse a `<![CDATA[...]]>` section, returning its content
+///
+/// The content is returned as it is, as nothing is escaped in a CDATA section.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if the section isn't closed yet.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// pub fn cdata<'i>(input: &mut &'i str) -> PResult<&'i str>
+/// # {
+/// #     winnow::contrib::markup::cdata.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::InputError;
+/// use winnow::contrib::markup::cdata;
+///
+/// assert_eq!(
+///     cdata::<_, InputError<_>>.parse_peek("<![CDATA[if (a < b) {}]]></script>"),
+///     Ok(("</script>", "if (a < b) {}"))
+/// );
+/// ```
+pub fn cdata<Input, Error>(input: &mut Input) -> PResult<<Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<&'static str> + FindSlice<&'static str>,
+    Error: ParserError<Input>,
+{
+    trace(
+        "cdata",
+        delimited("<![CDATA[", take_until(0.., "]]>"), "]]>"),
+    )
+    .parse_next(input)
+}
+
+/// Parse a `<!-- ... -->` comment, returning its content
+///
+/// Unlike XML, `--` is allowed inside, like browsers do.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if the comment isn't closed yet.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// pub fn comment<'i>(input: &mut &'i str) -> PResult<&'i str>
+/// # {
+/// #     winnow::contrib::markup::comment.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::InputError;
+/// use winnow::contrib::markup::comment;
+///
+/// assert_eq!(
+///     comment::<_, InputError<_>>.parse_peek("<!-- <b>old</b> --><i>"),
+///     Ok(("<i>", " <b>old</b> "))
+/// );
+/// ```
+pub fn comment<Input, Error>(input: &mut Input) -> PResult<<Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<&'static str> + FindSlice<&'static str>,
+    Error: ParserError<Input>,
+{
+    trace("comment", delimited("<!--", take_until(0.., "-->"), "-->")).parse_next(input)
+}
+
+/// Decode the [`entity`] references in an [`Attribute`] value or in text
+///
+/// References that aren't recognized, like `&nbsp;`, are left as they are.  This is
+/// [`decode_entities`][crate::ascii::decode_entities] with [`XML_ENTITIES`], for a value that
+/// was already parsed.
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::contrib::markup::unescape;
+///
+/// assert_eq!(unescape("Tom &amp; Jerry"), "Tom & Jerry");
+/// assert_eq!(unescape("&#x3C;p&#62;"), "<p>");
+/// assert_eq!(unescape("a&nbsp;b &"), "a&nbsp;b &");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn unescape(escaped: &str) -> Cow<'_, str> {
+    decode_entity_refs(escaped, XML_ENTITIES)
+}
+
+/// An attribute value between `quote`s, without them
+fn quoted_value<Input, Error>(quote: char) -> impl Parser<Input, <Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char> + Compare<&'static str>,
+    <Input as Stream>::Token: AsChar + Clone,
+    <Input as Stream>::Slice: AsBStr,
+    Error: ParserError<Input>,
+{
+    delimited(
+        quote,
+        repeat::<_, _, (), _, _>(
+            0..,
+            alt((
+                take_while(1.., move |t: <Input as Stream>::Token| {
+                    let c = t.as_char();
+                    !matches!(c, '&' | '<') && c != quote
+                })
+                .void(),
+                entity.void(),
+                '&'.void(),
+            )),
+        )
+        .take(),
+        quote,
+    )
+}
+
+fn is_name_start_char(c: char) -> bool {
+    c.is_ascii_alphabetic() || matches!(c, ':' | '_') || (!c.is_ascii() && c.is_alphabetic())
+}
+
+fn is_name_char(c: char) -> bool {
+    is_name_start_char(c)
+        || c.is_ascii_digit()
+        || matches!(c, '-' | '.' | '\u{b7}')
+        || (!c.is_ascii() && c.is_alphanumeric())
+}
@@ -0,0 +1,16 @@
+//! Parsers for common formats
+//!
+//! These are building blocks shared by many protocols and file formats, written with the same
+//! combinators as the rest of the crate so they work with any suitable [`Stream`][crate::stream::Stream].
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "email")]
+pub mod email;
+#[cfg(feature = "alloc")]
+pub mod form;
+#[cfg(feature = "alloc")]
+pub mod glob;
+pub mod markup;
+pub mod uri;
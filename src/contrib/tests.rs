@@ -0,0 +1,403 @@
+use crate::contrib::uri::*;
+use crate::error::ErrMode;
+use crate::error::InputError;
+use crate::error::Needed;
+use crate::stream::Partial;
+use crate::Parser;
+
+#[test]
+fn uri_components() {
+    let parsed = uri::<_, InputError<_>>
+        .parse("foo://example.com:8042/over/there?name=ferret#nose")
+        .unwrap();
+    assert_eq!(
+        parsed,
+        Uri {
+            scheme: Some("foo"),
+            authority: Some(Authority {
+                userinfo: None,
+                host: "example.com",
+                port: Some("8042"),
+            }),
+            path: "/over/there",
+            query: Some("name=ferret"),
+            fragment: Some("nose"),
+        }
+    );
+
+    let parsed = uri::<_, InputError<_>>
+        .parse("urn:example:animal:ferret:nose")
+        .unwrap();
+    assert_eq!(parsed.scheme, Some("urn"));
+    assert_eq!(parsed.authority, None);
+    assert_eq!(parsed.path, "example:animal:ferret:nose");
+
+    let parsed = uri::<_, InputError<_>>.parse("file:///etc/hosts").unwrap();
+    assert_eq!(parsed.authority.unwrap().host, "");
+    assert_eq!(parsed.path, "/etc/hosts");
+
+    let parsed = uri::<_, InputError<_>>
+        .parse("ftp://a%40b:pw@[v1.fe80::1]:/x%2Fy")
+        .unwrap();
+    let authority = parsed.authority.unwrap();
+    assert_eq!(authority.userinfo, Some("a%40b:pw"));
+    assert_eq!(authority.host, "[v1.fe80::1]");
+    assert_eq!(authority.port, Some(""));
+    assert_eq!(parsed.path, "/x%2Fy");
+    #[cfg(feature = "alloc")]
+    assert_eq!(percent_decode(parsed.path.as_bytes()), &b"/x/y"[..]);
+
+    // Stops at characters that can't be in a URI
+    assert_eq!(
+        uri::<_, InputError<_>>
+            .parse_peek("http://a/b c")
+            .map(|(rest, uri)| (rest, uri.path)),
+        Ok((" c", "/b"))
+    );
+    // Malformed escapes end the component
+    assert_eq!(
+        uri::<_, InputError<_>>
+            .parse_peek("http://a/b%zz")
+            .map(|(rest, uri)| (rest, uri.path)),
+        Ok(("%zz", "/b"))
+    );
+    assert!(uri::<_, InputError<_>>.parse("//a/b").is_err());
+    assert!(uri::<_, InputError<_>>.parse("").is_err());
+}
+
+#[test]
+fn uri_reference_components() {
+    let parsed = uri_reference::<_, InputError<_>>.parse("").unwrap();
+    assert_eq!(parsed.scheme, None);
+    assert_eq!(parsed.path, "");
+
+    let parsed = uri_reference::<_, InputError<_>>.parse("#frag").unwrap();
+    assert_eq!(parsed.path, "");
+    assert_eq!(parsed.fragment, Some("frag"));
+
+    let parsed = uri_reference::<_, InputError<_>>
+        .parse("/a/b:c?d/e?f")
+        .unwrap();
+    assert_eq!(parsed.path, "/a/b:c");
+    assert_eq!(parsed.query, Some("d/e?f"));
+
+    // A colon in the first segment makes it a scheme
+    let parsed = uri_reference::<_, InputError<_>>.parse("a:b").unwrap();
+    assert_eq!(parsed.scheme, Some("a"));
+    assert_eq!(parsed.path, "b");
+    assert_eq!(
+        uri_reference::<_, InputError<_>>
+            .parse_peek("1a:b")
+            .map(|(rest, uri)| (rest, uri.path)),
+        Ok((":b", "1a"))
+    );
+}
+
+#[test]
+fn uri_bytes() {
+    let parsed = uri::<_, InputError<_>>
+        .parse(&b"http://example.com/"[..])
+        .unwrap();
+    assert_eq!(parsed.scheme, Some(&b"http"[..]));
+    assert_eq!(parsed.path, &b"/"[..]);
+}
+
+#[test]
+fn uri_partial() {
+    assert_eq!(
+        uri::<_, InputError<_>>.parse_peek(Partial::new("http://exam")),
+        Err(ErrMode::Incomplete(Needed::new(1)))
+    );
+    assert_eq!(
+        uri::<_, InputError<_>>
+            .parse_peek(Partial::new("http://example.com/ "))
+            .map(|(_, uri)| uri.path),
+        Ok("/")
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn form_pairs() {
+    use crate::contrib::form::*;
+    use crate::error::ContextError;
+    use crate::lib::std::borrow::Cow;
+
+    let mut form = form_urlencoded::<_, ContextError>(FormPolicy::new());
+    let pairs = form.parse("a=1&&=x&b&c=d=e&sp=a+b%2Bc&bad=%G0%FF").unwrap();
+    assert_eq!(
+        pairs,
+        [
+            ("a".into(), "1".into()),
+            ("".into(), "x".into()),
+            ("b".into(), "".into()),
+            ("c".into(), "d=e".into()),
+            ("sp".into(), "a b+c".into()),
+            ("bad".into(), "%G0\u{FFFD}".into()),
+        ]
+    );
+    assert!(matches!(pairs[3].1, Cow::Borrowed(_)));
+    assert!(matches!(pairs[4].1, Cow::Owned(_)));
+    assert_eq!(form.parse("").unwrap(), []);
+
+    let pairs = form_urlencoded::<_, ContextError>(FormPolicy::new().plus_as_space(false))
+        .parse("q=a+b")
+        .unwrap();
+    assert_eq!(pairs, [("q".into(), "a+b".into())]);
+
+    let mut first =
+        form_urlencoded::<_, ContextError>(FormPolicy::new().duplicate_keys(DuplicateKeys::First));
+    assert_eq!(
+        first.parse("k=1&k%3D=2&k=3").unwrap(),
+        [("k".into(), "1".into()), ("k=".into(), "2".into())]
+    );
+
+    // Stops at a fragment
+    assert_eq!(
+        form_urlencoded::<_, InputError<_>>(FormPolicy::new()).parse_peek("a=1#frag"),
+        Ok(("#frag", vec![("a".into(), "1".into())]))
+    );
+    assert_eq!(
+        form_urlencoded::<_, InputError<_>>(FormPolicy::new()).parse_peek(Partial::new("a=1")),
+        Err(ErrMode::Incomplete(Needed::new(1)))
+    );
+}
+
+#[cfg(feature = "email")]
+#[cfg(feature = "alloc")]
+#[test]
+fn email_addr_spec() {
+    use crate::contrib::email::*;
+
+    let long_local = format!("{}@example.com", "a".repeat(65));
+    let long_label = format!("user@{}.com", "a".repeat(64));
+    let long_domain = format!("user@{}com", "a.".repeat(126));
+    let mut rfc = addr_spec::<_, InputError<_>>(AddrSpecSyntax::Rfc5322);
+    let mut practical = addr_spec::<_, InputError<_>>(AddrSpecSyntax::Practical);
+
+    for valid in [
+        "simple@example.com",
+        "very.common@example.com",
+        "x@example.com",
+        "long.email-address-with-hyphens@and.subdomains.example.com",
+        "user.name+tag+sorting@example.com",
+        "name/surname@example.com",
+        "admin@example",
+        "mailhost!username@example.org",
+        "user%example.com@example.org",
+        "user-@example.org",
+    ] {
+        assert_eq!(rfc.parse(valid).map(|_| ()), Ok(()), "{valid}");
+        assert_eq!(practical.parse(valid).map(|_| ()), Ok(()), "{valid}");
+    }
+
+    for rfc_only in [
+        r#""very.(),:;<>[]\".VERY.\"very@\\ \"very\".unusual"@strange.example.com"#,
+        r#"" "@example.org"#,
+        r#""john..doe"@example.org"#,
+        "postmaster@[123.123.123.123]",
+        "postmaster@[IPv6:2001:0db8:85a3:0000:0000:8a2e:0370:7334]",
+        "user@example-.com",
+    ] {
+        assert_eq!(rfc.parse(rfc_only).map(|_| ()), Ok(()), "{rfc_only}");
+        assert!(practical.parse(rfc_only).is_err(), "{rfc_only}");
+    }
+
+    for invalid in [
+        "abc.example.com",
+        "a@b@c@example.com",
+        "just\"not\"right@example.com",
+        "this is\"not\\allowed@example.com",
+        ".dot@example.com",
+        "dot.@example.com",
+        "two..dots@example.com",
+        "user@",
+        "@example.com",
+    ] {
+        assert!(rfc.parse(invalid).is_err(), "{invalid}");
+        assert!(practical.parse(invalid).is_err(), "{invalid}");
+    }
+
+    assert!(rfc.parse(long_local.as_str()).is_ok());
+    assert!(practical.parse(long_local.as_str()).is_err());
+    assert!(practical.parse(long_label.as_str()).is_err());
+    assert!(practical.parse(long_domain.as_str()).is_err());
+
+    assert_eq!(
+        rfc.parse_peek(r#""a@b"@c.d>"#),
+        Ok((
+            ">",
+            AddrSpec {
+                local_part: r#""a@b""#,
+                domain: "c.d"
+            }
+        ))
+    );
+    assert_eq!(
+        addr_spec::<_, InputError<_>>(AddrSpecSyntax::Practical)
+            .parse_peek(Partial::new("user@example.co")),
+        Err(ErrMode::Incomplete(Needed::new(1)))
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn glob_matches() {
+    use crate::contrib::glob::*;
+
+    let cases = [
+        ("*.rs", "lib.rs", true),
+        ("*.rs", ".rs", true),
+        ("*.rs", "src/lib.rs", false),
+        ("?.txt", "a.txt", true),
+        ("?.txt", "ab.txt", false),
+        ("a?b", "a/b", false),
+        ("**", "a/b/c", true),
+        ("**.rs", "src/a/lib.rs", true),
+        ("src/**/*.rs", "src/lib.rs", true),
+        ("src/**/*.rs", "src/a/b/lib.rs", true),
+        ("src/**/*.rs", "srca/lib.rs", false),
+        ("[a-c]x", "bx", true),
+        ("[a-c]x", "dx", false),
+        ("[!a-c]x", "dx", true),
+        ("[^a-c]x", "ax", false),
+        ("[!a]", "/", false),
+        ("[]]", "]", true),
+        ("[a-]", "-", true),
+        ("{foo,bar}.rs", "bar.rs", true),
+        ("{foo,bar}.rs", "baz.rs", false),
+        ("{*.rs,src/{a,b}}", "src/b", true),
+        ("{a,}b", "b", true),
+        ("\\*", "*", true),
+        ("\\*", "a", false),
+        ("a,b}", "a,b}", true),
+        ("日本*", "日本語", true),
+    ];
+    for (pattern, path, expected) in cases {
+        let glob = Glob::new(pattern).unwrap();
+        assert_eq!(glob.matches(path), expected, "{pattern} {path}");
+    }
+
+    assert_eq!(
+        Glob::new("src/**/{a,[!b]}").unwrap().tokens(),
+        [
+            GlobToken::Literal("src/".into()),
+            GlobToken::AnyDirectories,
+            GlobToken::Alternatives(vec![
+                vec![GlobToken::Literal("a".into())],
+                vec![GlobToken::Class {
+                    negated: true,
+                    ranges: vec!['b'..='b'],
+                }],
+            ]),
+        ]
+    );
+
+    for invalid in ["[ab", "{a,b", "a\\", "a]b}c{"] {
+        assert!(Glob::new(invalid).is_err(), "{invalid}");
+    }
+    assert_eq!(
+        glob::<_, InputError<_>>.parse_peek("*.rs[a"),
+        Ok(("[a", Glob::new("*.rs").unwrap()))
+    );
+}
+
+#[test]
+fn markup_scanners() {
+    use crate::contrib::markup::*;
+
+    assert_eq!(
+        tag_name::<_, InputError<_>>.parse_peek("data-id.x_1>"),
+        Ok((">", "data-id.x_1"))
+    );
+    assert_eq!(
+        tag_name::<_, InputError<_>>.parse_peek("über/>"),
+        Ok(("/>", "über"))
+    );
+    assert!(tag_name::<_, InputError<_>>.parse_peek("-a").is_err());
+
+    assert_eq!(
+        attribute::<_, InputError<_>>.parse_peek(r#"a="x &lt; &#x79;" b"#),
+        Ok((
+            " b",
+            Attribute {
+                name: "a",
+                value: "x &lt; &#x79;"
+            }
+        ))
+    );
+    assert_eq!(
+        attribute::<_, InputError<_>>.parse_peek(r#"a=''"#),
+        Ok((
+            "",
+            Attribute {
+                name: "a",
+                value: ""
+            }
+        ))
+    );
+    for bare in [r#"a="&""#, r#"a="&bogus;""#, r#"a="R&D &amp""#] {
+        let value = &bare[3..bare.len() - 1];
+        assert_eq!(
+            attribute::<_, InputError<_>>.parse_peek(bare),
+            Ok(("", Attribute { name: "a", value })),
+            "{bare}"
+        );
+    }
+    for invalid in [r#"a="<""#, "a=x", r#"a="x'"#] {
+        assert!(
+            attribute::<_, InputError<_>>.parse_peek(invalid).is_err(),
+            "{invalid}"
+        );
+    }
+
+    assert_eq!(
+        entity::<_, InputError<_>>.parse_peek("&apos;"),
+        Ok(("", '\''))
+    );
+    assert_eq!(
+        entity::<_, InputError<_>>.parse_peek("&#X41;"),
+        Ok(("", 'A'))
+    );
+    for invalid in [
+        "&#0;",
+        "&#x110000;",
+        "&#99999999999;",
+        "&amp",
+        "&#;",
+        "&nbsp;",
+    ] {
+        assert!(
+            entity::<_, InputError<_>>.parse_peek(invalid).is_err(),
+            "{invalid}"
+        );
+    }
+
+    assert_eq!(
+        cdata::<_, InputError<_>>.parse_peek("<![CDATA[]]]]>"),
+        Ok(("", "]]"))
+    );
+    assert_eq!(
+        comment::<_, InputError<_>>.parse_peek("<!-- a -- b -->"),
+        Ok(("", " a -- b "))
+    );
+    assert_eq!(
+        comment::<_, InputError<_>>.parse_peek(Partial::new("<!-- a --")),
+        Err(ErrMode::Incomplete(Needed::Unknown))
+    );
+    assert_eq!(
+        attribute::<_, InputError<_>>
+            .parse_peek(&b"lang='en'"[..])
+            .map(|(_, a)| a.value),
+        Ok(&b"en"[..])
+    );
+
+    #[cfg(feature = "alloc")]
+    {
+        use crate::lib::std::borrow::Cow;
+
+        assert!(matches!(unescape("plain"), Cow::Borrowed("plain")));
+        assert_eq!(unescape("&lt;&lt;&amp;&#9731;"), "<<&☃");
+        assert_eq!(unescape("&&amp;;"), "&&;");
+    }
+}
@@ -0,0 +1,459 @@
+//! URI parsers, following [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986)
+//!
+//! Components are returned as slices of the input, still percent-encoded.  Decode them with
+//! [`percent_decode`] or, while parsing, with [`pct_encoded`].
+//!
+//! # Example
+//!
+//! ```rust
+//! # use winnow::prelude::*;
+//! # use winnow::error::ContextError;
+//! use winnow::contrib::uri::uri;
+//!
+//! let parsed = uri::<_, ContextError>
+//!     .parse("https://user@example.com:8080/a%20b?q=1#top")
+//!     .unwrap();
+//! assert_eq!(parsed.scheme, Some("https"));
+//! let authority = parsed.authority.unwrap();
+//! assert_eq!(authority.userinfo, Some("user"));
+//! assert_eq!(authority.host, "example.com");
+//! assert_eq!(authority.port, Some("8080"));
+//! assert_eq!(parsed.path, "/a%20b");
+//! assert_eq!(parsed.query, Some("q=1"));
+//! assert_eq!(parsed.fragment, Some("top"));
+//! ```
+
+use crate::combinator::alt;
+use crate::combinator::delimited;
+use crate::combinator::opt;
+use crate::combinator::preceded;
+use crate::combinator::repeat;
+use crate::combinator::terminated;
+use crate::combinator::trace;
+use crate::error::ParserError;
+#[cfg(feature = "alloc")]
+use crate::lib::std::borrow::Cow;
+#[cfg(feature = "alloc")]
+use crate::lib::std::vec::Vec;
+use crate::stream::{AsChar, Compare, ContainsToken, Stream, StreamIsPartial};
+use crate::token::one_of;
+use crate::token::take_while;
+use crate::PResult;
+use crate::Parser;
+
+/// The components of a URI reference, see [`uri`] and [`uri_reference`]
+///
+/// Each component is a slice of the input, without its delimiters and still percent-encoded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Uri<S> {
+    /// Like `https`, only missing for a relative reference
+    pub scheme: Option<S>,
+    /// What follows `//`
+    pub authority: Option<Authority<S>>,
+    /// The path, which may be empty
+    pub path: S,
+    /// What follows `?`
+    pub query: Option<S>,
+    /// What follows `#`
+    pub fragment: Option<S>,
+}
+
+/// The `userinfo@host:port` part of a [`Uri`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Authority<S> {
+    /// What precedes `@`
+    pub userinfo: Option<S>,
+    /// A registered name, an IPv4 address, or an IP literal like `[::1]`, brackets included
+    ///
+    /// This may be empty, like in `file:///etc/hosts`.
+    pub host: S,
+    /// The digits after `:`, which may be empty
+    pub port: Option<S>,
+}
+
+/// Parse an absolute URI, with a scheme
+///
+/// This stops at the first character that cannot be part of a URI, like whitespace, leaving it
+/// for the caller.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if the URI may continue.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// # use winnow::contrib::uri::Uri;
+/// pub fn uri<'i>(input: &mut &'i str) -> PResult<Uri<&'i str>>
+/// # {
+/// #     winnow::contrib::uri::uri.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::contrib::uri::uri;
+///
+/// let parsed = uri::<_, ContextError>.parse("mailto:someone@example.com").unwrap();
+/// assert_eq!(parsed.scheme, Some("mailto"));
+/// assert_eq!(parsed.authority, None);
+/// assert_eq!(parsed.path, "someone@example.com");
+///
+/// assert!(uri::<_, ContextError>.parse("/relative/path").is_err());
+/// ```
+pub fn uri<Input, Error>(input: &mut Input) -> PResult<Uri<<Input as Stream>::Slice>, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char> + Compare<&'static str>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    trace("uri", |input: &mut Input| {
+        let scheme = terminated(scheme, ':').parse_next(input)?;
+        let (authority, path) = alt((
+            (preceded("//", authority).map(Some), path_abempty),
+            (empty_authority, path_rootless),
+        ))
+        .parse_next(input)?;
+        let mut uri = Uri {
+            scheme: Some(scheme),
+            authority,
+            path,
+            query: None,
+            fragment: None,
+        };
+        query_fragment(input, &mut uri)?;
+        Ok(uri)
+    })
+    .parse_next(input)
+}
+
+/// Parse a URI or a relative reference, like `../index.html` or `//example.com/`
+///
+/// A reference that could be either is parsed as a [`uri`], like with links in HTML.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if the reference may continue.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// # use winnow::contrib::uri::Uri;
+/// pub fn uri_reference<'i>(input: &mut &'i str) -> PResult<Uri<&'i str>>
+/// # {
+/// #     winnow::contrib::uri::uri_reference.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::contrib::uri::uri_reference;
+///
+/// let parsed = uri_reference::<_, ContextError>.parse("../a/b?x#y").unwrap();
+/// assert_eq!(parsed.scheme, None);
+/// assert_eq!(parsed.path, "../a/b");
+/// assert_eq!(parsed.query, Some("x"));
+/// assert_eq!(parsed.fragment, Some("y"));
+///
+/// let parsed = uri_reference::<_, ContextError>.parse("//example.com").unwrap();
+/// assert_eq!(parsed.authority.unwrap().host, "example.com");
+/// ```
+pub fn uri_reference<Input, Error>(
+    input: &mut Input,
+) -> PResult<Uri<<Input as Stream>::Slice>, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char> + Compare<&'static str>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    trace("uri_reference", alt((uri, relative_ref))).parse_next(input)
+}
+
+fn relative_ref<Input, Error>(input: &mut Input) -> PResult<Uri<<Input as Stream>::Slice>, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char> + Compare<&'static str>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    let (authority, path) = alt((
+        (preceded("//", authority).map(Some), path_abempty),
+        (empty_authority, path_noscheme),
+    ))
+    .parse_next(input)?;
+    let mut uri = Uri {
+        scheme: None,
+        authority,
+        path,
+        query: None,
+        fragment: None,
+    };
+    query_fragment(input, &mut uri)?;
+    Ok(uri)
+}
+
+/// Parse a URI scheme, like `https`
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::InputError;
+/// use winnow::contrib::uri::scheme;
+///
+/// assert_eq!(scheme::<_, InputError<_>>.parse_peek("svn+ssh://host"), Ok(("://host", "svn+ssh")));
+/// assert!(scheme::<_, InputError<_>>.parse_peek("1up:").is_err());
+/// ```
+pub fn scheme<Input, Error>(input: &mut Input) -> PResult<<Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    trace(
+        "scheme",
+        (
+            one_of(AsChar::is_alpha),
+            take_while(0.., (AsChar::is_alphanum, '+', '-', '.')),
+        )
+            .take(),
+    )
+    .parse_next(input)
+}
+
+/// Parse the `userinfo@host:port` that follows `//` in a URI
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::InputError;
+/// use winnow::contrib::uri::authority;
+/// use winnow::contrib::uri::Authority;
+///
+/// assert_eq!(
+///     authority::<_, InputError<_>>.parse_peek("[::1]:80/index.html"),
+///     Ok(("/index.html", Authority { userinfo: None, host: "[::1]", port: Some("80") }))
+/// );
+/// ```
+pub fn authority<Input, Error>(
+    input: &mut Input,
+) -> PResult<Authority<<Input as Stream>::Slice>, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    trace("authority", |input: &mut Input| {
+        let userinfo = opt(terminated(
+            encoded(|t: <Input as Stream>::Token| {
+                let c = t.as_char();
+                is_unreserved(c) || is_sub_delim(c) || c == ':'
+            }),
+            '@',
+        ))
+        .parse_next(input)?;
+        let host = alt((
+            delimited(
+                '[',
+                take_while(1.., |t: <Input as Stream>::Token| {
+                    let c = t.as_char();
+                    is_unreserved(c) || is_sub_delim(c) || c == ':'
+                }),
+                ']',
+            )
+            .take(),
+            encoded(|t: <Input as Stream>::Token| {
+                let c = t.as_char();
+                is_unreserved(c) || is_sub_delim(c)
+            }),
+        ))
+        .parse_next(input)?;
+        let port = opt(preceded(':', take_while(0.., AsChar::is_dec_digit))).parse_next(input)?;
+        Ok(Authority {
+            userinfo,
+            host,
+            port,
+        })
+    })
+    .parse_next(input)
+}
+
+/// Parse a percent-encoded octet, like `%20`, returning its value
+///
+/// This is the hook for decoding components while parsing them, like with
+/// [`repeat`][crate::combinator::repeat] and [`alt`][crate::combinator::alt] alongside a parser
+/// for unencoded characters.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::InputError;
+/// use winnow::contrib::uri::pct_encoded;
+///
+/// assert_eq!(pct_encoded::<_, InputError<_>>.parse_peek("%2Fx"), Ok(("x", b'/')));
+/// assert!(pct_encoded::<_, InputError<_>>.parse_peek("%2G").is_err());
+/// ```
+pub fn pct_encoded<Input, Error>(input: &mut Input) -> PResult<u8, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    trace(
+        "pct_encoded",
+        preceded(
+            '%',
+            (one_of(AsChar::is_hex_digit), one_of(AsChar::is_hex_digit)),
+        )
+        .map(
+            |(high, low): (<Input as Stream>::Token, <Input as Stream>::Token)| {
+                (hex_value(high.as_char()) << 4) | hex_value(low.as_char())
+            },
+        ),
+    )
+    .parse_next(input)
+}
+
+/// Decode the percent-encoded octets in a component of a [`Uri`]
+///
+/// Malformed escapes, like `%G0`, are left as they are.  The result is bytes as an escape may
+/// encode part of a UTF-8 character or not be UTF-8 at all.
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::contrib::uri::percent_decode;
+///
+/// assert_eq!(percent_decode(b"a%20b"), &b"a b"[..]);
+/// assert_eq!(percent_decode(b"caf%C3%A9"), "café".as_bytes());
+/// assert_eq!(percent_decode(b"100%"), &b"100%"[..]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn percent_decode(encoded: &[u8]) -> Cow<'_, [u8]> {
+    if !encoded.contains(&b'%') {
+        return Cow::Borrowed(encoded);
+    }
+    let mut decoded = Vec::with_capacity(encoded.len());
+    let mut i = 0;
+    while i < encoded.len() {
+        match encoded[i..] {
+            [b'%', high, low, ..] if high.is_ascii_hexdigit() && low.is_ascii_hexdigit() => {
+                decoded.push((hex_value(high as char) << 4) | hex_value(low as char));
+                i += 3;
+            }
+            _ => {
+                decoded.push(encoded[i]);
+                i += 1;
+            }
+        }
+    }
+    Cow::Owned(decoded)
+}
+
+/// `[ "?" query ] [ "#" fragment ]`
+fn query_fragment<Input, Error>(
+    input: &mut Input,
+    uri: &mut Uri<<Input as Stream>::Slice>,
+) -> PResult<(), Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    uri.query = opt(preceded('?', encoded(is_query_char))).parse_next(input)?;
+    uri.fragment = opt(preceded('#', encoded(is_query_char))).parse_next(input)?;
+    Ok(())
+}
+
+fn empty_authority<Input, Error>(
+    _input: &mut Input,
+) -> PResult<Option<Authority<<Input as Stream>::Slice>>, Error>
+where
+    Input: Stream,
+{
+    Ok(None)
+}
+
+/// `*( "/" segment )`
+fn path_abempty<Input, Error>(input: &mut Input) -> PResult<<Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    repeat::<_, _, (), _, _>(0.., ('/', encoded(is_pchar)))
+        .take()
+        .parse_next(input)
+}
+
+/// A path not following an authority, which cannot start with `//`
+fn path_rootless<Input, Error>(input: &mut Input) -> PResult<<Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    (encoded(is_pchar), path_abempty).take().parse_next(input)
+}
+
+/// A path in a relative reference, whose first segment cannot contain `:` so it isn't mistaken for
+/// a scheme
+fn path_noscheme<Input, Error>(input: &mut Input) -> PResult<<Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    (
+        encoded(|t: <Input as Stream>::Token| {
+            let c = t.as_char();
+            c != ':' && is_pchar(c)
+        }),
+        path_abempty,
+    )
+        .take()
+        .parse_next(input)
+}
+
+/// Any number of characters from `set` or [`pct_encoded`] octets
+fn encoded<Input, Error, Set>(set: Set) -> impl Parser<Input, <Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream + Compare<char>,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+    Set: ContainsToken<<Input as Stream>::Token>,
+{
+    repeat::<_, _, (), _, _>(0.., alt((take_while(1.., set).void(), pct_encoded.void()))).take()
+}
+
+fn is_unreserved(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~')
+}
+
+fn is_sub_delim(c: char) -> bool {
+    matches!(
+        c,
+        '!' | '$' | '&' | '\'' | '(' | ')' | '*' | '+' | ',' | ';' | '='
+    )
+}
+
+fn is_pchar<T: AsChar>(t: T) -> bool {
+    let c = t.as_char();
+    is_unreserved(c) || is_sub_delim(c) || matches!(c, ':' | '@')
+}
+
+fn is_query_char<T: AsChar>(t: T) -> bool {
+    let c = t.as_char();
+    is_pchar(c) || matches!(c, '/' | '?')
+}
+
+fn hex_value(c: char) -> u8 {
+    c.to_digit(16).unwrap_or(0) as u8
+}
@@ -17,12 +17,26 @@
 //! - [`ErrorKind`]
 //! - [`InputError`] (mostly for testing)
 //! - [`ContextError`]
+//!   - [`Report`] for rendering with a snippet of the input
+//!   - [`ParseDiagnostic`] for reporting through [`miette`](https://docs.rs/miette)
+//! - [`ArrayContextError`] (for `no_std` without `alloc`)
 //! - [`TreeError`] (mostly for testing)
 //! - [Custom errors][crate::_topic::error]
+//!
+//! When reporting many recovered errors, [`sort_by_span`], [`group_by_span`], and
+//! [`remove_overlapping`] can put them into a clean, document-ordered list.
 
 #[cfg(feature = "alloc")]
 use crate::lib::std::borrow::ToOwned;
+#[cfg(feature = "alloc")]
+use crate::lib::std::boxed::Box;
 use crate::lib::std::fmt;
+#[cfg(feature = "alloc")]
+use crate::lib::std::ops::Range;
+#[cfg(feature = "alloc")]
+use crate::lib::std::string::{String, ToString};
+#[cfg(feature = "alloc")]
+use crate::lib::std::vec::Vec;
 use core::num::NonZeroUsize;
 
 use crate::stream::AsBStr;
@@ -188,6 +202,11 @@ impl<I: Stream, E: ParserError<I>> ParserError<I> for ErrMode<E> {
         ErrMode::Backtrack(E::from_error_kind(input, kind))
     }
 
+    #[inline(always)]
+    fn from_literal_mismatch(input: &I, expected: &[u8], found: &[u8]) -> Self {
+        ErrMode::Backtrack(E::from_literal_mismatch(input, expected, found))
+    }
+
     #[cfg_attr(debug_assertions, track_caller)]
     #[inline(always)]
     fn assert(input: &I, message: &'static str) -> Self
@@ -267,6 +286,21 @@ where
     }
 }
 
+/// The underlying error is the [source][std::error::Error::source], so its own source chain is
+/// reachable from the `ErrMode`
+#[cfg(feature = "std")]
+impl<E> std::error::Error for ErrMode<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ErrMode::Backtrack(e) | ErrMode::Cut(e) => Some(e),
+            ErrMode::Incomplete(_) => None,
+        }
+    }
+}
+
 /// The basic [`Parser`] trait for errors
 ///
 /// It provides methods to create an error from some combinators,
@@ -276,6 +310,14 @@ pub trait ParserError<I: Stream>: Sized {
     fn from_error_kind(input: &I, kind: ErrorKind) -> Self;
 
     /// Process a parser assertion
+    ///
+    /// Built-in parsers do not panic, regardless of the input.  Malformed input is reported as an
+    /// error, and bugs in how parsers are composed, like a [`repeat`][crate::combinator::repeat]
+    /// parser that doesn't consume, are reported through this function.  In debug builds, this
+    /// panics to help find the bug; in release builds, this returns an [`ErrorKind::Assert`].
+    ///
+    /// When slicing a [`Stream`] with an offset derived from the input, use
+    /// [`Stream::try_next_slice`] to uphold the same guarantee.
     #[cfg_attr(debug_assertions, track_caller)]
     fn assert(input: &I, _message: &'static str) -> Self
     where
@@ -287,6 +329,17 @@ pub trait ParserError<I: Stream>: Sized {
         Self::from_error_kind(input, ErrorKind::Assert)
     }
 
+    /// Creates an error for a [`literal`][crate::token::literal] that did not match the input
+    ///
+    /// `expected` is the literal and `found` is the input at the error location, truncated to the
+    /// length of `expected`.  This is only called for byte streams and literals, see
+    /// [`Compare::mismatch_bytes`][crate::stream::Compare::mismatch_bytes]; otherwise,
+    /// [`ParserError::from_error_kind`] is called with [`ErrorKind::Tag`].
+    #[inline]
+    fn from_literal_mismatch(input: &I, _expected: &[u8], _found: &[u8]) -> Self {
+        Self::from_error_kind(input, ErrorKind::Tag)
+    }
+
     /// Like [`ParserError::from_error_kind`] but merges it with the existing error.
     ///
     /// This is useful when backtracking through a parse tree, accumulating error context on the
@@ -343,6 +396,88 @@ pub trait FromExternalError<I, E> {
     fn from_external_error(input: &I, kind: ErrorKind, e: E) -> Self;
 }
 
+/// The key a [`dispatch_with`][crate::combinator::dispatch_with] had no branch for
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct UnmatchedKey<K>(pub K);
+
+impl<K: fmt::Debug> fmt::Display for UnmatchedKey<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no branch for `{:?}`", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: fmt::Debug> std::error::Error for UnmatchedKey<K> {}
+
+/// The first repeated key found by [`UniqueKeys`][crate::stream::UniqueKeys], rendered with
+/// [`Debug`][fmt::Debug]
+///
+/// The key is rendered so the error doesn't borrow from the input.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateKey(pub crate::lib::std::string::String);
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for DuplicateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "duplicate key `{}`", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DuplicateKey {}
+
+/// The identifier an [`ident_except`][crate::ascii::ident_except] rejected
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReservedWord(pub crate::lib::std::string::String);
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for ReservedWord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is a reserved word", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReservedWord {}
+
+/// The repair a [`Parser::repair`] made to continue parsing, recorded as an error
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Repaired {
+    /// The expected `label` was missing, so it was treated as if it were inserted
+    Inserted(&'static str),
+    /// Unexpected tokens were deleted before the expected `label`
+    Deleted {
+        /// What the repaired parser expected
+        label: &'static str,
+        /// How many tokens were deleted
+        tokens: usize,
+    },
+}
+
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+impl fmt::Display for Repaired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Inserted(label) => write!(f, "missing {label} inserted"),
+            Self::Deleted { label, tokens: 1 } => {
+                write!(f, "unexpected token before {label} deleted")
+            }
+            Self::Deleted { label, tokens } => {
+                write!(f, "{tokens} unexpected tokens before {label} deleted")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+impl std::error::Error for Repaired {}
+
 /// Equivalent of `From` implementation to avoid orphan rules in bits parsers
 pub trait ErrorConvert<E> {
     /// Transform to another error type
@@ -386,7 +521,7 @@ impl<I: Clone> InputError<I> {
 }
 
 #[cfg(feature = "alloc")]
-impl<'i, I: ToOwned> InputError<&'i I>
+impl<I: ?Sized + ToOwned> InputError<&I>
 where
     <I as ToOwned>::Owned: Clone,
 {
@@ -520,13 +655,84 @@ impl ErrorConvert<()> for () {
     fn convert(self) {}
 }
 
+/// Keep large errors off the stack, making [`PResult`] cheaper to return
+#[cfg(feature = "alloc")]
+impl<I: Stream, E: ParserError<I>> ParserError<I> for Box<E> {
+    #[inline]
+    fn from_error_kind(input: &I, kind: ErrorKind) -> Self {
+        Box::new(E::from_error_kind(input, kind))
+    }
+
+    #[cfg_attr(debug_assertions, track_caller)]
+    #[inline]
+    fn assert(input: &I, message: &'static str) -> Self
+    where
+        I: crate::lib::std::fmt::Debug,
+    {
+        Box::new(E::assert(input, message))
+    }
+
+    #[inline]
+    fn from_literal_mismatch(input: &I, expected: &[u8], found: &[u8]) -> Self {
+        Box::new(E::from_literal_mismatch(input, expected, found))
+    }
+
+    #[inline]
+    fn append(
+        mut self,
+        input: &I,
+        token_start: &<I as Stream>::Checkpoint,
+        kind: ErrorKind,
+    ) -> Self {
+        *self = (*self).append(input, token_start, kind);
+        self
+    }
+
+    #[inline]
+    fn or(mut self, other: Self) -> Self {
+        *self = (*self).or(*other);
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Stream, C, E: AddContext<I, C>> AddContext<I, C> for Box<E> {
+    #[inline]
+    fn add_context(
+        mut self,
+        input: &I,
+        token_start: &<I as Stream>::Checkpoint,
+        context: C,
+    ) -> Self {
+        *self = (*self).add_context(input, token_start, context);
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, EXT, E: FromExternalError<I, EXT>> FromExternalError<I, EXT> for Box<E> {
+    #[inline]
+    fn from_external_error(input: &I, kind: ErrorKind, e: EXT) -> Self {
+        Box::new(E::from_external_error(input, kind, e))
+    }
+}
+
 /// Accumulate context while backtracking errors
+///
+/// This does not hold on to the input, so it can outlive it.  The location of the error is
+/// reported separately, like through [`ParseError::offset`] or a span.
 #[derive(Debug)]
 pub struct ContextError<C = StrContext> {
     #[cfg(feature = "alloc")]
     context: crate::lib::std::vec::Vec<C>,
     #[cfg(not(feature = "alloc"))]
     context: core::marker::PhantomData<C>,
+    /// Remaining input where the error started, if known
+    #[cfg(feature = "alloc")]
+    eof_offset: Option<usize>,
+    /// Number of leading `context` entries from earlier `alt` branches
+    #[cfg(feature = "alloc")]
+    earlier: usize,
     #[cfg(feature = "std")]
     cause: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
 }
@@ -537,11 +743,28 @@ impl<C> ContextError<C> {
     pub fn new() -> Self {
         Self {
             context: Default::default(),
+            #[cfg(feature = "alloc")]
+            eof_offset: None,
+            #[cfg(feature = "alloc")]
+            earlier: 0,
             #[cfg(feature = "std")]
             cause: None,
         }
     }
 
+    #[inline]
+    fn at<I: Stream>(input: &I) -> Self {
+        #[allow(unused_mut)]
+        let mut err = Self::new();
+        #[cfg(feature = "alloc")]
+        {
+            err.eof_offset = Some(input.eof_offset());
+        }
+        #[cfg(not(feature = "alloc"))]
+        let _ = input;
+        err
+    }
+
     /// Access context from [`Parser::context`]
     #[inline]
     #[cfg(feature = "alloc")]
@@ -561,6 +784,10 @@ impl<C: Clone> Clone for ContextError<C> {
     fn clone(&self) -> Self {
         Self {
             context: self.context.clone(),
+            #[cfg(feature = "alloc")]
+            eof_offset: self.eof_offset,
+            #[cfg(feature = "alloc")]
+            earlier: self.earlier,
             #[cfg(feature = "std")]
             cause: self.cause.as_ref().map(|e| e.to_string().into()),
         }
@@ -576,8 +803,8 @@ impl<C> Default for ContextError<C> {
 
 impl<I: Stream, C> ParserError<I> for ContextError<C> {
     #[inline]
-    fn from_error_kind(_input: &I, _kind: ErrorKind) -> Self {
-        Self::new()
+    fn from_error_kind(input: &I, _kind: ErrorKind) -> Self {
+        Self::at(input)
     }
 
     #[inline]
@@ -590,8 +817,35 @@ impl<I: Stream, C> ParserError<I> for ContextError<C> {
         self
     }
 
+    /// Keep the context of both alternatives, like every [`StrContext::Expected`] of a failed
+    /// [`alt`][crate::combinator::alt], when they failed at the same position
+    ///
+    /// Otherwise, only `other` is kept.  Context from `self` comes first, so it is reported in
+    /// the order the alternatives were tried, though a [`StrContext::Label`] is taken from
+    /// `other`.  The [cause][ContextError::cause] of `other` takes precedence.
     #[inline]
     fn or(self, other: Self) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            if self.eof_offset.is_none() || self.eof_offset != other.eof_offset {
+                return other;
+            }
+        }
+        #[cfg(feature = "alloc")]
+        let mut other = other;
+        #[cfg(feature = "alloc")]
+        {
+            let mut context = self.context;
+            other.earlier += context.len();
+            context.append(&mut other.context);
+            other.context = context;
+        }
+        #[cfg(feature = "std")]
+        {
+            if other.cause.is_none() {
+                other.cause = self.cause;
+            }
+        }
         other
     }
 }
@@ -670,14 +924,59 @@ impl<C: core::cmp::PartialEq> core::cmp::PartialEq for ContextError<C> {
     }
 }
 
+impl ContextError<StrContext> {
+    /// How serious the failure is, from the innermost [`StrContext::Severity`]
+    ///
+    /// Without a [`StrContext::Severity`], this is [`Severity::Error`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// use winnow::ascii::dec_uint;
+    /// use winnow::error::Severity;
+    /// use winnow::error::StrContext;
+    ///
+    /// fn port(input: &mut &str) -> PResult<u16> {
+    ///     dec_uint
+    ///         .verify(|p| *p != 0)
+    ///         .context(StrContext::Label("port"))
+    ///         .context(StrContext::Severity(Severity::Warning))
+    ///         .parse_next(input)
+    /// }
+    ///
+    /// let error = port.parse("0").unwrap_err();
+    /// assert_eq!(error.inner().severity(), Severity::Warning);
+    ///
+    /// let error = ("port=", port).parse("host=0").unwrap_err();
+    /// assert_eq!(error.inner().severity(), Severity::Error);
+    /// ```
+    #[inline]
+    #[cfg(feature = "alloc")]
+    pub fn severity(&self) -> Severity {
+        self.context()
+            .find_map(|c| match c {
+                StrContext::Severity(severity) => Some(*severity),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+}
+
 impl crate::lib::std::fmt::Display for ContextError<StrContext> {
+    #[cfg_attr(not(feature = "std"), allow(unused_assignments))]
     fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
         #[cfg(feature = "alloc")]
         {
-            let expression = self.context().find_map(|c| match c {
-                StrContext::Label(c) => Some(c),
+            let label = |c: &StrContext| match c {
+                StrContext::Label(c) => Some(*c),
                 _ => None,
-            });
+            };
+            // Prefer the label of the last `alt` branch
+            let expression = self.context[self.earlier..]
+                .iter()
+                .find_map(label)
+                .or_else(|| self.context.iter().find_map(label));
             let expected = self
                 .context()
                 .filter_map(|c| match c {
@@ -701,12 +1000,19 @@ impl crate::lib::std::fmt::Display for ContextError<StrContext> {
                 newline = true;
 
                 write!(f, "expected ")?;
-                for (i, expected) in expected.iter().enumerate() {
-                    if i != 0 {
-                        write!(f, ", ")?;
-                    }
-                    write!(f, "{expected}")?;
+                write_one_of(f, expected.len(), expected.iter())?;
+            }
+
+            if let Some(version) = self.context().find_map(|c| match c {
+                StrContext::Version(v) => Some(v),
+                _ => None,
+            }) {
+                if newline {
+                    writeln!(f)?;
                 }
+                newline = true;
+
+                write!(f, "in version {version}")?;
             }
             #[cfg(feature = "std")]
             {
@@ -730,70 +1036,471 @@ impl<C> ErrorConvert<ContextError<C>> for ContextError<C> {
     }
 }
 
-/// Additional parse context for [`ContextError`] added via [`Parser::context`]
+/// Accumulate up to `N` frames of context while backtracking errors, without allocating
+///
+/// Like [`ContextError`] but usable without the `alloc` feature.  Context beyond the `N`
+/// innermost frames is dropped, see [`ArrayContextError::is_truncated`].
+///
+/// The position of the error is tracked as its distance from the end of the input, see
+/// [`ArrayContextError::offset_in`].
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ArrayContextError;
+/// # use winnow::error::StrContext;
+/// # use winnow::error::StrContextValue;
+/// # use winnow::ascii::digit1;
+/// fn number<'s>(input: &mut &'s str) -> PResult<&'s str, ArrayContextError> {
+///     digit1
+///         .context(StrContext::Label("number"))
+///         .context(StrContext::Expected(StrContextValue::Description("digits")))
+///         .parse_next(input)
+/// }
+///
+/// let original = "12 + x";
+/// let mut input = &original[5..];
+/// let err = number(&mut input).unwrap_err().into_inner().unwrap();
+/// assert_eq!(err.offset_in(&original), 5);
+/// assert_eq!(err.to_string(), "invalid number\nexpected digits");
+/// ```
 #[derive(Clone, Debug, PartialEq, Eq)]
-#[non_exhaustive]
-pub enum StrContext {
-    /// Description of what is currently being parsed
-    Label(&'static str),
-    /// Grammar item that was expected
-    Expected(StrContextValue),
+pub struct ArrayContextError<C = StrContext, const N: usize = 4> {
+    context: [Option<C>; N],
+    len: usize,
+    truncated: bool,
+    eof_offset: usize,
 }
 
-impl crate::lib::std::fmt::Display for StrContext {
-    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
-        match self {
-            Self::Label(name) => write!(f, "invalid {name}"),
-            Self::Expected(value) => write!(f, "expected {value}"),
+impl<C, const N: usize> ArrayContextError<C, N> {
+    /// Create an empty error
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            context: core::array::from_fn(|_| None),
+            len: 0,
+            truncated: false,
+            eof_offset: 0,
         }
     }
-}
 
-/// See [`StrContext`]
-#[derive(Clone, Debug, PartialEq, Eq)]
-#[non_exhaustive]
-pub enum StrContextValue {
-    /// A [`char`] token
-    CharLiteral(char),
-    /// A [`&str`] token
-    StringLiteral(&'static str),
-    /// A description of what was being parsed
-    Description(&'static str),
+    /// Access context from [`Parser::context`], innermost first
+    #[inline]
+    pub fn context(&self) -> impl Iterator<Item = &C> {
+        self.context[..self.len].iter().filter_map(Option::as_ref)
+    }
+
+    /// Whether context was dropped for lack of capacity
+    #[inline]
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Length of the input remaining where the error occurred
+    #[inline]
+    pub fn eof_offset(&self) -> usize {
+        self.eof_offset
+    }
+
+    /// Offset of the error within `original`, the input that was originally passed to the parser
+    #[inline]
+    pub fn offset_in<I: Stream>(&self, original: &I) -> usize {
+        original.eof_offset().saturating_sub(self.eof_offset)
+    }
 }
 
-impl From<char> for StrContextValue {
+impl<C, const N: usize> Default for ArrayContextError<C, N> {
     #[inline]
-    fn from(inner: char) -> Self {
-        Self::CharLiteral(inner)
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-impl From<&'static str> for StrContextValue {
+impl<I: Stream, C, const N: usize> ParserError<I> for ArrayContextError<C, N> {
     #[inline]
-    fn from(inner: &'static str) -> Self {
-        Self::StringLiteral(inner)
+    fn from_error_kind(input: &I, _kind: ErrorKind) -> Self {
+        let mut err = Self::new();
+        err.eof_offset = input.eof_offset();
+        err
+    }
+
+    #[inline]
+    fn append(
+        self,
+        _input: &I,
+        _token_start: &<I as Stream>::Checkpoint,
+        _kind: ErrorKind,
+    ) -> Self {
+        self
+    }
+
+    #[inline]
+    fn or(self, other: Self) -> Self {
+        other
     }
 }
 
-impl crate::lib::std::fmt::Display for StrContextValue {
-    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
-        match self {
-            Self::CharLiteral('\n') => "newline".fmt(f),
-            Self::CharLiteral('`') => "'`'".fmt(f),
-            Self::CharLiteral(c) if c.is_ascii_control() => {
-                write!(f, "`{}`", c.escape_debug())
-            }
-            Self::CharLiteral(c) => write!(f, "`{c}`"),
-            Self::StringLiteral(c) => write!(f, "`{c}`"),
-            Self::Description(c) => write!(f, "{c}"),
+impl<C, I: Stream, const N: usize> AddContext<I, C> for ArrayContextError<C, N> {
+    #[inline]
+    fn add_context(
+        mut self,
+        _input: &I,
+        _token_start: &<I as Stream>::Checkpoint,
+        context: C,
+    ) -> Self {
+        if self.len < N {
+            self.context[self.len] = Some(context);
+            self.len += 1;
+        } else {
+            self.truncated = true;
         }
+        self
     }
 }
 
-/// Trace all error paths, particularly for tests
-#[derive(Debug)]
+#[cfg(feature = "unstable-recover")]
 #[cfg(feature = "std")]
-pub enum TreeError<I, C = StrContext> {
+impl<I: Stream, C, const N: usize> FromRecoverableError<I, Self> for ArrayContextError<C, N> {
+    #[inline]
+    fn from_recoverable_error(
+        _token_start: &<I as Stream>::Checkpoint,
+        _err_start: &<I as Stream>::Checkpoint,
+        _input: &I,
+        e: Self,
+    ) -> Self {
+        e
+    }
+}
+
+impl<C, I: Stream, E, const N: usize> FromExternalError<I, E> for ArrayContextError<C, N> {
+    #[inline]
+    fn from_external_error(input: &I, kind: ErrorKind, _e: E) -> Self {
+        Self::from_error_kind(input, kind)
+    }
+}
+
+impl<const N: usize> crate::lib::std::fmt::Display for ArrayContextError<StrContext, N> {
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        let expression = self.context().find_map(|c| match c {
+            StrContext::Label(c) => Some(c),
+            _ => None,
+        });
+        let mut expected = self
+            .context()
+            .filter_map(|c| match c {
+                StrContext::Expected(c) => Some(c),
+                _ => None,
+            })
+            .peekable();
+        let count = self
+            .context()
+            .filter(|c| matches!(c, StrContext::Expected(_)))
+            .count();
+
+        let mut newline = false;
+
+        if let Some(expression) = expression {
+            newline = true;
+
+            write!(f, "invalid {expression}")?;
+        }
+
+        if expected.peek().is_some() {
+            if newline {
+                writeln!(f)?;
+            }
+
+            write!(f, "expected ")?;
+            write_one_of(f, count, expected)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<C, const N: usize> ErrorConvert<ArrayContextError<C, N>> for ArrayContextError<C, N> {
+    #[inline]
+    fn convert(self) -> ArrayContextError<C, N> {
+        self
+    }
+}
+
+/// Write `items` as a list of alternatives: `a`, `a or b`, or `a, b, or c`
+fn write_one_of<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    count: usize,
+    items: impl Iterator<Item = T>,
+) -> fmt::Result {
+    for (i, item) in items.enumerate() {
+        if i != 0 {
+            if count == 2 {
+                write!(f, " or ")?;
+            } else if i + 1 == count {
+                write!(f, ", or ")?;
+            } else {
+                write!(f, ", ")?;
+            }
+        }
+        write!(f, "{item}")?;
+    }
+    Ok(())
+}
+
+/// [`write_one_of`] for a list of alternatives collected up front
+#[cfg(feature = "std")]
+struct OneOf<'a, T>(&'a [T]);
+
+#[cfg(feature = "std")]
+impl<T: fmt::Display> fmt::Display for OneOf<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_one_of(f, self.0.len(), self.0.iter())
+    }
+}
+
+/// Additional parse context for [`ContextError`] added via [`Parser::context`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StrContext {
+    /// Description of what is currently being parsed
+    Label(&'static str),
+    /// Grammar item that was expected
+    Expected(StrContextValue),
+    /// How serious a failure is, see [`ContextError::severity`]
+    Severity(Severity),
+    /// The format version whose layout was being parsed, see
+    /// [`versioned`][crate::binary::versioned]
+    Version(u64),
+}
+
+impl crate::lib::std::fmt::Display for StrContext {
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        match self {
+            Self::Label(name) => write!(f, "invalid {name}"),
+            Self::Expected(value) => write!(f, "expected {value}"),
+            Self::Severity(severity) => write!(f, "{severity}"),
+            Self::Version(version) => write!(f, "in version {version}"),
+        }
+    }
+}
+
+/// How serious a failure is, see [`StrContext::Severity`]
+///
+/// This allows one grammar to serve both strict and lenient modes: mark the failures that can be
+/// tolerated, recover from them with [`Parser::resume_after`], and let the top-level caller decide
+/// which severities are fatal, like with [`partition_by_severity`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// A suggestion that doesn't affect the result
+    Hint,
+    /// Questionable input that could still be parsed
+    Warning,
+    /// Invalid input
+    #[default]
+    Error,
+}
+
+impl crate::lib::std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        match self {
+            Self::Hint => "hint".fmt(f),
+            Self::Warning => "warning".fmt(f),
+            Self::Error => "error".fmt(f),
+        }
+    }
+}
+
+/// See [`StrContext`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StrContextValue {
+    /// A [`char`] token
+    CharLiteral(char),
+    /// A [`&str`] token
+    StringLiteral(&'static str),
+    /// A description of what was being parsed
+    Description(&'static str),
+    /// A description of what was being parsed, built while parsing
+    #[cfg(feature = "alloc")]
+    OwnedDescription(crate::lib::std::string::String),
+}
+
+impl From<char> for StrContextValue {
+    #[inline]
+    fn from(inner: char) -> Self {
+        Self::CharLiteral(inner)
+    }
+}
+
+impl From<&'static str> for StrContextValue {
+    #[inline]
+    fn from(inner: &'static str) -> Self {
+        Self::StringLiteral(inner)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<crate::lib::std::string::String> for StrContextValue {
+    #[inline]
+    fn from(inner: crate::lib::std::string::String) -> Self {
+        Self::OwnedDescription(inner)
+    }
+}
+
+impl crate::lib::std::fmt::Display for StrContextValue {
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        match self {
+            Self::CharLiteral('\n') => "newline".fmt(f),
+            Self::CharLiteral('`') => "'`'".fmt(f),
+            Self::CharLiteral(c) if c.is_ascii_control() => {
+                write!(f, "`{}`", c.escape_debug())
+            }
+            Self::CharLiteral(c) => write!(f, "`{c}`"),
+            Self::StringLiteral(c) => write!(f, "`{c}`"),
+            Self::Description(c) => write!(f, "{c}"),
+            #[cfg(feature = "alloc")]
+            Self::OwnedDescription(c) => write!(f, "{c}"),
+        }
+    }
+}
+
+/// A [`literal`][crate::token::literal] that did not match the input
+///
+/// This is the [cause][TreeErrorBase::cause] of [`TreeError`]s from
+/// [`ParserError::from_literal_mismatch`], showing where the input diverged from the literal.
+/// [`ContextError`] doesn't record it, so failed alternatives don't allocate.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// use winnow::error::LiteralMismatch;
+/// use winnow::error::TreeError;
+///
+/// fn magic<'i>(input: &mut &'i [u8]) -> PResult<&'i [u8], TreeError<&'i [u8]>> {
+///     b"\x7fELF".parse_next(input)
+/// }
+///
+/// let error = magic.parse(b"\x7fEL\x00").unwrap_err();
+/// let TreeError::Base(base) = error.inner() else {
+///     unreachable!("`literal` fails without adding context");
+/// };
+/// let cause = base.cause.as_ref().unwrap();
+/// let mismatch = cause.downcast_ref::<LiteralMismatch>().unwrap();
+/// assert_eq!(mismatch.offset(), 3);
+/// assert_eq!(
+///     mismatch.to_string(),
+///     r#"expected b"\x7fELF", found b"\x7fEL\x00" (differs at byte 3)"#
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg(feature = "std")]
+pub struct LiteralMismatch {
+    expected: Vec<u8>,
+    found: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl LiteralMismatch {
+    /// `found` is the input compared against `expected`
+    #[inline]
+    pub fn new(expected: &[u8], found: &[u8]) -> Self {
+        Self {
+            expected: expected.to_owned(),
+            found: found.to_owned(),
+        }
+    }
+
+    /// The literal
+    #[inline]
+    pub fn expected(&self) -> &[u8] {
+        &self.expected
+    }
+
+    /// The input at the error location, at most as long as [`LiteralMismatch::expected`]
+    #[inline]
+    pub fn found(&self) -> &[u8] {
+        &self.found
+    }
+
+    /// The offset of the first byte that differs
+    ///
+    /// When the input ended before the literal did, this is the length of
+    /// [`LiteralMismatch::found`].
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.expected
+            .iter()
+            .zip(&self.found)
+            .position(|(e, f)| e != f)
+            .unwrap_or(self.found.len())
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for LiteralMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected b\"{}\", found b\"{}\"",
+            self.expected.escape_ascii(),
+            self.found.escape_ascii()
+        )?;
+        let offset = self.offset();
+        if offset < self.found.len() {
+            write!(f, " (differs at byte {offset})")
+        } else {
+            write!(f, " (input ends at byte {offset})")
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LiteralMismatch {}
+
+/// Trace all error paths, particularly for tests
+///
+/// Unlike [`ContextError`]'s flat list of context, this keeps the structure of the failure: each
+/// failed branch of an [`alt`][crate::combinator::alt] and the
+/// [context][AddContext] and [kinds][ParserError::append] added on the way back up from it.
+/// [`Display`][fmt::Display] renders it as an indented tree for debugging grammars.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// use winnow::ascii::digit1;
+/// use winnow::combinator::alt;
+/// use winnow::combinator::delimited;
+/// use winnow::error::ErrMode;
+/// use winnow::error::StrContext;
+/// use winnow::error::TreeError;
+///
+/// fn value<'i>(input: &mut &'i str) -> PResult<&'i str, TreeError<&'i str>> {
+///     alt((
+///         digit1.context(StrContext::Label("number")),
+///         delimited('"', digit1, '"').context(StrContext::Label("string")),
+///     ))
+///     .context(StrContext::Label("value"))
+///     .parse_next(input)
+/// }
+///
+/// let Err(ErrMode::Backtrack(error)) = value.parse_peek("\"x\"") else {
+///     unreachable!()
+/// };
+/// let expected = r#"during one of:
+///   in slice at '"x"'
+///     0: invalid number at '"x"'
+///   in slice at 'x"'
+///     0: invalid string at '"x"'
+///   0: in alternative at '"x"'
+///   1: invalid value at '"x"'
+/// "#;
+/// assert_eq!(error.to_string(), expected);
+/// ```
+#[derive(Debug)]
+#[cfg(feature = "std")]
+pub enum TreeError<I, C = StrContext> {
     /// Initial error that kicked things off
     Base(TreeErrorBase<I>),
     /// Traces added to the error while walking back up the stack
@@ -840,7 +1547,7 @@ pub struct TreeErrorContext<I, C = StrContext> {
 }
 
 #[cfg(feature = "std")]
-impl<'i, I: ToOwned, C> TreeError<&'i I, C>
+impl<'i, I: ?Sized + ToOwned, C> TreeError<&'i I, C>
 where
     &'i I: Stream + Clone,
     <I as ToOwned>::Owned: Clone,
@@ -917,6 +1624,14 @@ where
         })
     }
 
+    fn from_literal_mismatch(input: &I, expected: &[u8], found: &[u8]) -> Self {
+        TreeError::Base(TreeErrorBase {
+            input: input.clone(),
+            kind: ErrorKind::Tag,
+            cause: Some(Box::new(LiteralMismatch::new(expected, found))),
+        })
+    }
+
     fn append(self, input: &I, token_start: &<I as Stream>::Checkpoint, kind: ErrorKind) -> Self {
         let mut input = input.clone();
         input.reset(token_start);
@@ -1056,6 +1771,19 @@ impl<
         C: fmt::Display + fmt::Debug,
     > std::error::Error for TreeError<I, C>
 {
+    /// The [cause][TreeErrorBase::cause] of the initial error
+    ///
+    /// There is no single source for [`TreeError::Alt`].
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TreeError::Base(base) => base
+                .cause
+                .as_deref()
+                .map(|e| e as &(dyn std::error::Error + 'static)),
+            TreeError::Stack { base, .. } => base.source(),
+            TreeError::Alt(_) => None,
+        }
+    }
 }
 
 #[cfg(feature = "std")]
@@ -1104,6 +1832,9 @@ pub enum ErrorKind {
   Not,
   Verify,
   Fail,
+  Fuel,
+  Depth,
+  InfiniteLoop,
 }
 
 impl ErrorKind {
@@ -1122,6 +1853,9 @@ impl ErrorKind {
       ErrorKind::Not                       => "negation",
       ErrorKind::Verify                    => "predicate verification",
       ErrorKind::Fail                      => "fail",
+      ErrorKind::Fuel                      => "fuel exhausted",
+      ErrorKind::Depth                     => "nesting too deep",
+      ErrorKind::InfiniteLoop              => "infinite loop",
     }
   }
 }
@@ -1214,6 +1948,55 @@ impl<I, E> ParseError<I, E> {
     pub fn into_inner(self) -> E {
         self.inner
     }
+
+    /// Translate the input type
+    #[inline]
+    pub fn map_input<I2, O: FnOnce(I) -> I2>(self, op: O) -> ParseError<I2, E> {
+        ParseError {
+            input: op(self.input),
+            offset: self.offset,
+            inner: self.inner,
+        }
+    }
+
+    /// Translate the [`ParserError`], like with [`InputError::into_owned`]
+    #[inline]
+    pub fn map_inner<E2, O: FnOnce(E) -> E2>(self, op: O) -> ParseError<I, E2> {
+        ParseError {
+            input: self.input,
+            offset: self.offset,
+            inner: op(self.inner),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I: ?Sized + ToOwned, E> ParseError<&I, E> {
+    /// Obtaining ownership, so the error can outlive the input
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// use winnow::ascii::digit1;
+    /// use winnow::error::InputError;
+    /// use winnow::error::ParseError;
+    ///
+    /// fn parse_owned(source: String) -> Result<usize, ParseError<String, InputError<String>>> {
+    ///     digit1
+    ///         .map(str::len)
+    ///         .parse(source.as_str())
+    ///         .map_err(|e| e.into_owned().map_inner(InputError::into_owned))
+    /// }
+    ///
+    /// let error = parse_owned("12a".to_owned()).unwrap_err();
+    /// assert_eq!(error.input(), "12a");
+    /// assert_eq!(error.offset(), 2);
+    /// assert_eq!(error.inner().input, "a");
+    /// ```
+    pub fn into_owned(self) -> ParseError<<I as ToOwned>::Owned, E> {
+        self.map_input(ToOwned::to_owned)
+    }
 }
 
 impl<I, E> core::fmt::Display for ParseError<I, E>
@@ -1225,7 +2008,7 @@ where
         let input = self.input.as_bstr();
         let span_start = self.offset;
         let span_end = span_start;
-        #[cfg(feature = "std")]
+        #[cfg(feature = "alloc")]
         if input.contains(&b'\n') {
             let (line_idx, col_idx) = translate_position(input, span_start);
             let line_num = line_idx + 1;
@@ -1282,61 +2065,1082 @@ where
     }
 }
 
+/// The [source][std::error::Error::source] is the inner error's, as its message is already part
+/// of the [`Display`][fmt::Display]ed report
 #[cfg(feature = "std")]
-fn translate_position(input: &[u8], index: usize) -> (usize, usize) {
-    if input.is_empty() {
-        return (0, index);
+impl<I, E> std::error::Error for ParseError<I, E>
+where
+    I: AsBStr + fmt::Debug,
+    E: std::error::Error,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.inner.source()
     }
+}
 
-    let safe_index = index.min(input.len() - 1);
-    let column_offset = index - safe_index;
-    let index = safe_index;
-
-    let nl = input[0..index]
-        .iter()
-        .rev()
-        .enumerate()
-        .find(|(_, b)| **b == b'\n')
-        .map(|(nl, _)| index - nl - 1);
-    let line_start = match nl {
-        Some(nl) => nl + 1,
-        None => 0,
-    };
-    let line = input[0..line_start].iter().filter(|b| **b == b'\n').count();
-
-    // HACK: This treats byte offset and column offsets the same
-    let column = crate::lib::std::str::from_utf8(&input[line_start..=index])
-        .map(|s| s.chars().count() - 1)
-        .unwrap_or_else(|_| index - line_start);
-    let column = column + column_offset;
-
-    (line, column)
+impl<I: AsBStr, E> ParseError<I, E> {
+    /// Dump `range` of the input as hex, marking the error location, see [`HexDump`]
+    #[inline]
+    pub fn hexdump(&self, range: crate::lib::std::ops::Range<usize>) -> HexDump<'_> {
+        HexDump::new(self.input.as_bstr(), range).mark(self.offset)
+    }
 }
 
-#[cfg(test)]
 #[cfg(feature = "std")]
-mod test_parse_error {
-    use super::*;
-
-    #[test]
-    fn single_line() {
-        let mut input = "0xZ123";
-        let start = input.checkpoint();
-        let _ = input.next_token().unwrap();
-        let _ = input.next_token().unwrap();
-        let inner = InputError::new(input, ErrorKind::Slice);
-        let error = ParseError::new(input, start, inner);
-        let expected = "\
-0xZ123
-  ^
-slice error starting at: Z123";
-        assert_eq!(error.to_string(), expected);
+impl<I: AsBStr> ParseError<I, ContextError> {
+    /// Render the error with a snippet of the input, see [`Report`]
+    #[inline]
+    pub fn report(&self) -> Report<'_> {
+        Report::new(self.input.as_bstr(), self.offset, &self.inner)
     }
 }
 
-#[cfg(test)]
+/// Render a [`ContextError`] with a snippet of the input, like `rustc`
+///
+/// This shows:
+/// - The innermost [`StrContext::Label`] as the headline, prefixed by the
+///   [severity][ContextError::severity]
+/// - The line and column of the error
+/// - The offending line with a caret under the error, annotated with the
+///   [`StrContext::Expected`] values
+/// - The outer [`StrContext::Label`]s and the [cause][ContextError::cause] as notes
+///
+/// When `source` is binary (not UTF-8 or has control characters), the offending line is replaced
+/// with a [`HexDump`] of the rows around the error.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// use winnow::ascii::hex_digit1;
+/// use winnow::error::StrContext;
+/// use winnow::error::StrContextValue;
+///
+/// fn color<'s>(input: &mut &'s str) -> PResult<&'s str> {
+///     ("#", hex_digit1.context(StrContext::Expected(StrContextValue::Description("hex digits"))))
+///         .take()
+///         .context(StrContext::Label("color"))
+///         .context(StrContext::Label("style"))
+///         .parse_next(input)
+/// }
+///
+/// let input = "red\n#zz0000";
+/// let error = ("red\n", color).parse(input).unwrap_err();
+/// assert_eq!(
+///     error.report().path("theme.txt").to_string(),
+///     "\
+/// error: invalid color
+///  --> theme.txt:2:2
+///   |
+/// 2 | #zz0000
+///   |  ^ expected hex digits
+///   |
+///   = while parsing style
+/// "
+/// );
+/// ```
 #[cfg(feature = "std")]
-mod test_translate_position {
+pub struct Report<'a> {
+    source: &'a [u8],
+    offset: usize,
+    error: &'a ContextError,
+    path: Option<&'a str>,
+}
+
+#[cfg(feature = "std")]
+impl<'a> Report<'a> {
+    /// Render `error` at `offset` into `source`
+    ///
+    /// `source` is the whole input, from the start of parsing. See also [`ParseError::report`].
+    #[inline]
+    pub fn new<S>(source: &'a S, offset: usize, error: &'a ContextError) -> Self
+    where
+        S: AsRef<[u8]> + ?Sized,
+    {
+        Self {
+            source: source.as_ref(),
+            offset,
+            error,
+            path: None,
+        }
+    }
+
+    /// Name the file being parsed in the location line
+    #[inline]
+    pub fn path(mut self, path: &'a str) -> Self {
+        self.path = Some(path);
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut labels = self.error.context().filter_map(|c| match c {
+            StrContext::Label(label) => Some(label),
+            _ => None,
+        });
+        let expected = self
+            .error
+            .context()
+            .filter_map(|c| match c {
+                StrContext::Expected(value) => Some(value.to_string()),
+                _ => None,
+            })
+            .collect::<crate::lib::std::vec::Vec<_>>();
+
+        let severity = self.error.severity();
+        match labels.next() {
+            Some(label) => writeln!(f, "{severity}: invalid {label}")?,
+            None => writeln!(f, "{severity}: parse error")?,
+        }
+
+        let expected = if expected.is_empty() {
+            String::new()
+        } else {
+            format!(" expected {}", OneOf(&expected))
+        };
+
+        let is_text = core::str::from_utf8(self.source).is_ok()
+            && !self
+                .source
+                .iter()
+                .any(|b| b.is_ascii_control() && !matches!(b, b'\t' | b'\n' | b'\r'));
+        let gutter = if is_text {
+            let (line_idx, col_idx) = translate_position(self.source, self.offset);
+            let line_num = line_idx + 1;
+            let col_num = col_idx + 1;
+            let gutter = line_num.to_string().len();
+            let content = self
+                .source
+                .split(|c| *c == b'\n')
+                .nth(line_idx)
+                .unwrap_or_default();
+            let content = content.strip_suffix(b"\r").unwrap_or(content);
+
+            write!(f, "{:gutter$}--> ", "")?;
+            if let Some(path) = self.path {
+                write!(f, "{path}:")?;
+            }
+            writeln!(f, "{line_num}:{col_num}")?;
+            writeln!(f, "{:gutter$} |", "")?;
+            writeln!(f, "{line_num} | {}", String::from_utf8_lossy(content))?;
+            writeln!(f, "{:gutter$} | {:col_idx$}^{expected}", "", "")?;
+            gutter
+        } else {
+            let gutter = 1;
+            let width = HexDump::DEFAULT_WIDTH;
+            let row_start = self.offset - self.offset % width;
+            let dump = HexDump::new(
+                self.source,
+                row_start.saturating_sub(width)..row_start + width,
+            )
+            .mark(self.offset)
+            .to_string();
+
+            write!(f, "{:gutter$}--> ", "")?;
+            if let Some(path) = self.path {
+                write!(f, "{path}:")?;
+            }
+            writeln!(f, "{:#x}", self.offset)?;
+            writeln!(f, "{:gutter$} |", "")?;
+            let mut rows = dump.lines().peekable();
+            while let Some(row) = rows.next() {
+                if rows.peek().is_some() {
+                    writeln!(f, "{:gutter$} | {row}", "")?;
+                } else {
+                    // The last row is the mark
+                    writeln!(f, "{:gutter$} | {row}{expected}", "")?;
+                }
+            }
+            gutter
+        };
+
+        let mut notes = labels
+            .map(|label| format!("while parsing {label}"))
+            .chain(self.error.cause().map(ToString::to_string))
+            .peekable();
+        if notes.peek().is_some() {
+            writeln!(f, "{:gutter$} |", "")?;
+        }
+        for note in notes {
+            writeln!(f, "{:gutter$} = {note}", "")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Render bytes like `hexdump -C`
+///
+/// Each row shows the offset, the bytes in hex, and (by default) an ASCII gutter with the
+/// printable bytes.  Rows are aligned to [`HexDump::width`], with bytes outside of the range left
+/// blank.
+///
+/// See also [`ParseError::hexdump`].
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::error::HexDump;
+///
+/// let input = b"\x7fELF\x02\x01\x01\x00";
+/// let dump = HexDump::new(input, 2..8).width(4).mark(4);
+/// assert_eq!(
+///     dump.to_string(),
+///     "\
+/// 00000000        4c 46  |  LF|
+/// 00000004  02 01 01 00  |....|
+///           ^^
+/// "
+/// );
+/// ```
+pub struct HexDump<'a> {
+    source: &'a [u8],
+    range: crate::lib::std::ops::Range<usize>,
+    width: usize,
+    ascii: bool,
+    mark: Option<usize>,
+}
+
+impl<'a> HexDump<'a> {
+    const DEFAULT_WIDTH: usize = 16;
+
+    /// Dump `range` of `source`
+    ///
+    /// `range` is clamped to the end of `source`.
+    #[inline]
+    pub fn new<S>(source: &'a S, range: crate::lib::std::ops::Range<usize>) -> Self
+    where
+        S: AsRef<[u8]> + ?Sized,
+    {
+        let source = source.as_ref();
+        let end = range.end.min(source.len());
+        let start = range.start.min(end);
+        Self {
+            source,
+            range: start..end,
+            width: Self::DEFAULT_WIDTH,
+            ascii: true,
+            mark: None,
+        }
+    }
+
+    /// Number of bytes per row (default: 16)
+    #[inline]
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width.max(1);
+        self
+    }
+
+    /// Whether to show the ASCII gutter (default: `true`)
+    #[inline]
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Point to `offset` with a `^^` after its row
+    ///
+    /// The dump is extended to include `offset`, if needed.  An `offset` past the end of the
+    /// source is clamped to the end, where an error at EOF would point.
+    #[inline]
+    pub fn mark(mut self, offset: usize) -> Self {
+        self.mark = Some(offset.min(self.source.len()));
+        self
+    }
+
+    fn byte(&self, offset: usize) -> Option<u8> {
+        if self.range.contains(&offset) {
+            self.source.get(offset).copied()
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.width;
+        let mut start = self.range.start;
+        let mut end = self.range.end;
+        if let Some(mark) = self.mark {
+            start = start.min(mark);
+            end = end.max(mark.saturating_add(1));
+        }
+        if end <= start {
+            return Ok(());
+        }
+
+        let mut row_start = start - start % width;
+        while row_start < end {
+            write!(f, "{row_start:08x} ")?;
+            // Defer padding so rows without a gutter don't end in whitespace
+            let mut padding = 0;
+            for offset in row_start..row_start.saturating_add(width) {
+                match self.byte(offset) {
+                    Some(byte) => {
+                        write!(f, "{:padding$} {byte:02x}", "")?;
+                        padding = 0;
+                    }
+                    None => padding += 3,
+                }
+            }
+            if self.ascii {
+                write!(f, "{:padding$}  |", "")?;
+                for offset in row_start..row_start.saturating_add(width) {
+                    let c = match self.byte(offset) {
+                        Some(byte) if byte.is_ascii_graphic() || byte == b' ' => byte as char,
+                        Some(_) => '.',
+                        None => ' ',
+                    };
+                    write!(f, "{c}")?;
+                }
+                write!(f, "|")?;
+            }
+            writeln!(f)?;
+
+            if let Some(mark) = self.mark {
+                if (row_start..row_start.saturating_add(width)).contains(&mark) {
+                    let column = 9 + 3 * (mark - row_start) + 1;
+                    writeln!(f, "{:column$}^^", "")?;
+                }
+            }
+
+            row_start = match row_start.checked_add(width) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+
+        Ok(())
+    }
+}
+
+/// The [cause][ContextError::cause] is the [source][std::error::Error::source]
+#[cfg(feature = "std")]
+impl std::error::Error for ContextError<StrContext> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause()
+            .map(|e| e as &(dyn std::error::Error + 'static))
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for ContextError<StrContext> {}
+
+#[cfg(feature = "miette")]
+impl<I: AsBStr> ParseError<I, ContextError> {
+    /// Convert to a [`miette::Diagnostic`], see [`ParseDiagnostic`]
+    pub fn into_diagnostic(self) -> ParseDiagnostic {
+        ParseDiagnostic {
+            input: String::from_utf8_lossy(self.input.as_bstr()).into_owned(),
+            offset: self.offset,
+            error: self.inner,
+        }
+    }
+}
+
+/// A [`ParseError`] for reporting through [`miette`]
+///
+/// This owns a copy of the input, so it can be returned as a [`miette::Report`] regardless of
+/// the lifetime of the input.
+///
+/// This shows:
+/// - The innermost [`StrContext::Label`] as the message
+/// - A label at the error location with the [`StrContext::Expected`] values
+/// - The outer [`StrContext::Label`]s as help
+/// - The [cause][ContextError::cause] as the [source][std::error::Error::source]
+/// - The [severity][ContextError::severity]
+///
+/// For a [`Located`][crate::stream::Located] input, the label is at the offset from the start of
+/// parsing.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// use winnow::ascii::hex_digit1;
+/// use winnow::error::StrContext;
+/// use winnow::error::StrContextValue;
+/// use winnow::stream::Located;
+///
+/// fn color<'s>(input: &mut Located<&'s str>) -> PResult<&'s str> {
+///     ("#", hex_digit1.context(StrContext::Expected(StrContextValue::Description("hex digits"))))
+///         .take()
+///         .context(StrContext::Label("color"))
+///         .parse_next(input)
+/// }
+///
+/// fn parse_color(input: &str) -> miette::Result<&str> {
+///     let color = color.parse(Located::new(input)).map_err(|e| e.into_diagnostic())?;
+///     Ok(color)
+/// }
+///
+/// let report = parse_color("#zz0000").unwrap_err();
+/// assert_eq!(report.to_string(), "invalid color");
+/// let label = report.labels().unwrap().next().unwrap();
+/// assert_eq!(label.offset(), 1);
+/// assert_eq!(label.label(), Some("expected hex digits"));
+/// ```
+#[cfg(feature = "miette")]
+#[derive(Debug)]
+pub struct ParseDiagnostic {
+    input: String,
+    offset: usize,
+    error: ContextError,
+}
+
+#[cfg(feature = "miette")]
+impl ParseDiagnostic {
+    /// The input, from the start of parsing
+    #[inline]
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// The location in [`ParseDiagnostic::input`] where parsing failed
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The original [`ContextError`]
+    #[inline]
+    pub fn inner(&self) -> &ContextError {
+        &self.error
+    }
+}
+
+#[cfg(feature = "miette")]
+impl fmt::Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = self.error.context().find_map(|c| match c {
+            StrContext::Label(label) => Some(label),
+            _ => None,
+        });
+        match label {
+            Some(label) => write!(f, "invalid {label}"),
+            None => write!(f, "parse error"),
+        }
+    }
+}
+
+#[cfg(feature = "miette")]
+impl std::error::Error for ParseDiagnostic {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error
+            .cause()
+            .map(|e| e as &(dyn std::error::Error + 'static))
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for ParseDiagnostic {
+    fn severity(&self) -> Option<miette::Severity> {
+        match self.error.severity() {
+            Severity::Hint => Some(miette::Severity::Advice),
+            Severity::Warning => Some(miette::Severity::Warning),
+            Severity::Error => Some(miette::Severity::Error),
+        }
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let outer = self
+            .error
+            .context()
+            .filter_map(|c| match c {
+                StrContext::Label(label) => Some(*label),
+                _ => None,
+            })
+            .skip(1)
+            .collect::<crate::lib::std::vec::Vec<_>>();
+        if outer.is_empty() {
+            None
+        } else {
+            Some(Box::new(format!("while parsing {}", outer.join(", "))))
+        }
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.input)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let expected = self
+            .error
+            .context()
+            .filter_map(|c| match c {
+                StrContext::Expected(value) => Some(value.to_string()),
+                _ => None,
+            })
+            .collect::<crate::lib::std::vec::Vec<_>>();
+        let label = if expected.is_empty() {
+            None
+        } else {
+            Some(format!("expected {}", OneOf(&expected)))
+        };
+        let span = miette::LabeledSpan::new(label, self.offset, 0);
+        Some(Box::new(core::iter::once(span)))
+    }
+}
+
+/// Sort errors into document order by their `span` within the input
+///
+/// Errors are ordered by where their span starts, with shorter spans first when they start
+/// together.  The sort is stable, so errors with the same span keep the order they were reported
+/// in.
+///
+/// This is intended for the errors collected by
+/// [`RecoverableParser::recoverable_parse`][crate::RecoverableParser::recoverable_parse], which
+/// are in the order they were recovered from rather than where they are in the input.  As the
+/// built-in errors don't track a span, the error type is expected to capture one in
+/// [`FromRecoverableError`].
+///
+/// See also [`group_by_span`] and [`remove_overlapping`].
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::error::sort_by_span;
+///
+/// let mut errors = vec![(4..6, "b"), (0..2, "a"), (4..5, "c")];
+/// sort_by_span(&mut errors, |(span, _)| span.clone());
+/// assert_eq!(errors, [(0..2, "a"), (4..5, "c"), (4..6, "b")]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn sort_by_span<E>(errors: &mut [E], mut span: impl FnMut(&E) -> Range<usize>) {
+    errors.sort_by_key(|e| {
+        let span = span(e);
+        (span.start, span.end)
+    });
+}
+
+/// Group errors with overlapping `span`s, in document order
+///
+/// Each group is reported with the span covering all of its errors.  Empty spans overlap spans
+/// containing them and other empty spans at the same offset.
+///
+/// This is useful for reporting a single diagnostic for a region of the input that cascaded into
+/// multiple errors.  See [`sort_by_span`] for more details.
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::error::group_by_span;
+///
+/// let errors = vec![(7..9, "d"), (0..2, "a"), (1..4, "b"), (4..4, "c")];
+/// let groups = group_by_span(errors, |(span, _)| span.clone());
+/// assert_eq!(
+///     groups,
+///     [
+///         (0..4, vec![(0..2, "a"), (1..4, "b")]),
+///         (4..4, vec![(4..4, "c")]),
+///         (7..9, vec![(7..9, "d")]),
+///     ]
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn group_by_span<E>(
+    mut errors: Vec<E>,
+    mut span: impl FnMut(&E) -> Range<usize>,
+) -> Vec<(Range<usize>, Vec<E>)> {
+    sort_by_span(&mut errors, &mut span);
+    let mut groups: Vec<(Range<usize>, Vec<E>)> = Vec::new();
+    for error in errors {
+        let error_span = span(&error);
+        match groups.last_mut() {
+            Some((group_span, group))
+                if error_span.start < group_span.end || error_span.start == group_span.start =>
+            {
+                group_span.end = group_span.end.max(error_span.end);
+                group.push(error);
+            }
+            _ => groups.push((error_span, Vec::from([error]))),
+        }
+    }
+    groups
+}
+
+/// Keep only the first error, in document order, of each group of overlapping `span`s
+///
+/// Errors within a region that already has an error are often a consequence of it, so this
+/// avoids overwhelming the user.  See [`group_by_span`] for how overlap is decided.
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::error::remove_overlapping;
+///
+/// let errors = vec![(1..4, "b"), (0..2, "a"), (7..9, "c")];
+/// let errors = remove_overlapping(errors, |(span, _)| span.clone());
+/// assert_eq!(errors, [(0..2, "a"), (7..9, "c")]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn remove_overlapping<E>(errors: Vec<E>, span: impl FnMut(&E) -> Range<usize>) -> Vec<E> {
+    group_by_span(errors, span)
+        .into_iter()
+        .filter_map(|(_, group)| group.into_iter().next())
+        .collect()
+}
+
+/// Split errors by their [`Severity`]
+///
+/// This is intended for the errors collected by
+/// [`RecoverableParser::recoverable_parse`][crate::RecoverableParser::recoverable_parse], so a
+/// lenient mode can report [`Severity::Warning`]s while only failing on [`Severity::Error`]s.
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::error::partition_by_severity;
+/// use winnow::error::Severity;
+///
+/// let errors = vec![(Severity::Warning, "w"), (Severity::Error, "e"), (Severity::Hint, "h")];
+/// let errors = partition_by_severity(errors, |(s, _)| *s);
+/// assert_eq!(errors.errors, [(Severity::Error, "e")]);
+/// assert_eq!(errors.warnings, [(Severity::Warning, "w")]);
+/// assert_eq!(errors.hints, [(Severity::Hint, "h")]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn partition_by_severity<E>(
+    errors: Vec<E>,
+    mut severity: impl FnMut(&E) -> Severity,
+) -> BySeverity<E> {
+    let mut partitioned = BySeverity {
+        errors: Vec::new(),
+        warnings: Vec::new(),
+        hints: Vec::new(),
+    };
+    for error in errors {
+        match severity(&error) {
+            Severity::Error => partitioned.errors.push(error),
+            Severity::Warning => partitioned.warnings.push(error),
+            Severity::Hint => partitioned.hints.push(error),
+        }
+    }
+    partitioned
+}
+
+/// Errors split by [`Severity`], see [`partition_by_severity`]
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BySeverity<E> {
+    /// [`Severity::Error`]s
+    pub errors: Vec<E>,
+    /// [`Severity::Warning`]s
+    pub warnings: Vec<E>,
+    /// [`Severity::Hint`]s
+    pub hints: Vec<E>,
+}
+
+#[cfg(feature = "alloc")]
+fn translate_position(input: &[u8], index: usize) -> (usize, usize) {
+    if input.is_empty() {
+        return (0, index);
+    }
+
+    let safe_index = index.min(input.len() - 1);
+    let column_offset = index - safe_index;
+    let index = safe_index;
+
+    let nl = input[0..index]
+        .iter()
+        .rev()
+        .enumerate()
+        .find(|(_, b)| **b == b'\n')
+        .map(|(nl, _)| index - nl - 1);
+    let line_start = match nl {
+        Some(nl) => nl + 1,
+        None => 0,
+    };
+    let line = input[0..line_start].iter().filter(|b| **b == b'\n').count();
+
+    // HACK: This treats byte offset and column offsets the same
+    let column = crate::lib::std::str::from_utf8(&input[line_start..=index])
+        .map(|s| s.chars().count() - 1)
+        .unwrap_or_else(|_| index - line_start);
+    let column = column + column_offset;
+
+    (line, column)
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test_parse_error {
+    use super::*;
+
+    #[test]
+    fn single_line() {
+        let mut input = "0xZ123";
+        let start = input.checkpoint();
+        let _ = input.next_token().unwrap();
+        let _ = input.next_token().unwrap();
+        let inner = InputError::new(input, ErrorKind::Slice);
+        let error = ParseError::new(input, start, inner);
+        let expected = "\
+0xZ123
+  ^
+slice error starting at: Z123";
+        assert_eq!(error.to_string(), expected);
+    }
+
+    #[test]
+    fn source_chain() {
+        use crate::ascii::digit1;
+        use std::error::Error as _;
+
+        let error = digit1::<_, ContextError>
+            .try_map(str::parse::<u8>)
+            .parse("300")
+            .unwrap_err()
+            .into_owned();
+        let source = error.source().map(ToString::to_string);
+        assert_eq!(
+            source.as_deref(),
+            Some("number too large to fit in target type")
+        );
+
+        let error = anyhow::Error::from(error);
+        assert_eq!(
+            error.chain().last().map(ToString::to_string).as_deref(),
+            Some("number too large to fit in target type")
+        );
+
+        let error = digit1::<_, ContextError>
+            .try_map(str::parse::<u8>)
+            .parse_next(&mut "300")
+            .unwrap_err();
+        let source = error.source().and_then(|e| e.source());
+        assert!(source.unwrap().is::<std::num::ParseIntError>());
+        assert!(ErrMode::<ContextError>::Incomplete(Needed::Unknown)
+            .source()
+            .is_none());
+
+        let error = digit1::<_, TreeError<&str>>
+            .try_map(str::parse::<u8>)
+            .context(StrContext::Label("byte"))
+            .parse_next(&mut "300")
+            .unwrap_err()
+            .into_inner()
+            .unwrap();
+        assert!(error.source().unwrap().is::<std::num::ParseIntError>());
+    }
+
+    #[test]
+    fn context_error_or_keeps_expected() {
+        let input = "";
+        let expected = |value: &'static str| {
+            let error: ContextError = ParserError::from_error_kind(&input, ErrorKind::Tag);
+            error.add_context(
+                &input,
+                &input.checkpoint(),
+                StrContext::Expected(StrContextValue::Description(value)),
+            )
+        };
+        let error = ParserError::<&str>::or(expected("number"), expected("string"));
+        assert_eq!(error.to_string(), "expected number or string");
+        let error = ParserError::<&str>::or(error, expected("array"));
+        assert_eq!(error.to_string(), "expected number, string, or array");
+        let error = error.add_context(&input, &input.checkpoint(), StrContext::Label("value"));
+        assert_eq!(
+            error.to_string(),
+            "invalid value\nexpected number, string, or array"
+        );
+
+        // The label comes from the last alternative
+        let labeled = |label: &'static str, value: &'static str| {
+            expected(value).add_context(&input, &input.checkpoint(), StrContext::Label(label))
+        };
+        let error = ParserError::<&str>::or(labeled("integer", "digit"), labeled("string", "`\"`"));
+        assert_eq!(error.to_string(), "invalid string\nexpected digit or `\"`");
+
+        // Only alternatives that failed at the same position are combined
+        let later = "x";
+        let error: ContextError = ParserError::from_error_kind(&later, ErrorKind::Tag);
+        let error = error.add_context(
+            &later,
+            &later.checkpoint(),
+            StrContext::Expected(StrContextValue::Description("letter")),
+        );
+        let error = ParserError::<&str>::or(error, expected("number"));
+        assert_eq!(error.to_string(), "expected number");
+    }
+
+    #[test]
+    fn tree_error_with_cause() {
+        let input = "1234567890abcdefghijklmnop\nqrst";
+        let error: TreeError<&str> =
+            FromExternalError::from_external_error(&input, ErrorKind::Verify, ErrorKind::Fail);
+        let error = error.add_context(&input, &input.checkpoint(), StrContext::Label("digits"));
+        let expected = "\
+caused by error Fail at '1234567890abcdefghij...'
+  0: invalid digits at '1234567890abcdefghij...'
+";
+        assert_eq!(error.to_string(), expected);
+    }
+
+    #[test]
+    fn into_owned_bytes() {
+        let input = &b"0xZ123"[..];
+        let error = ParseError::new(
+            input,
+            input.checkpoint(),
+            InputError::new(&input[2..], ErrorKind::Slice),
+        );
+        let error: ParseError<Vec<u8>, InputError<Vec<u8>>> =
+            error.into_owned().map_inner(InputError::into_owned);
+        assert_eq!(error.input(), b"0xZ123");
+        assert_eq!(error.inner().input, b"Z123");
+    }
+
+    #[test]
+    fn report_without_context() {
+        let error = ContextError::new();
+        let report = Report::new("a\r\nbc\r\n", 5, &error);
+        let expected = "\
+error: parse error
+ --> 2:3
+  |
+2 | bc
+  |   ^
+";
+        assert_eq!(report.to_string(), expected);
+    }
+
+    #[test]
+    fn report_with_severity() {
+        let input = "";
+        let error = ContextError::new()
+            .add_context(
+                &input,
+                &input.checkpoint(),
+                StrContext::Severity(Severity::Hint),
+            )
+            .add_context(&input, &input.checkpoint(), StrContext::Label("indent"))
+            .add_context(
+                &input,
+                &input.checkpoint(),
+                StrContext::Severity(Severity::Warning),
+            );
+        assert_eq!(error.severity(), Severity::Hint);
+        assert_eq!(error.to_string(), "invalid indent");
+        let report = Report::new("\tx", 0, &error);
+        let expected = "\
+hint: invalid indent
+ --> 1:1
+  |
+1 | \tx
+  | ^
+";
+        assert_eq!(report.to_string(), expected);
+    }
+
+    #[test]
+    fn report_with_context() {
+        let input = "";
+        let error = ContextError::new()
+            .add_context(&input, &input.checkpoint(), StrContext::Label("value"))
+            .add_context(
+                &input,
+                &input.checkpoint(),
+                StrContext::Expected(StrContextValue::CharLiteral('[')),
+            )
+            .add_context(
+                &input,
+                &input.checkpoint(),
+                StrContext::Expected(StrContextValue::CharLiteral('{')),
+            )
+            .add_context(&input, &input.checkpoint(), StrContext::Label("document"));
+        let report = Report::new("x = ?", 4, &error).path("doc.toml");
+        let expected = "\
+error: invalid value
+ --> doc.toml:1:5
+  |
+1 | x = ?
+  |     ^ expected `[` or `{`
+  |
+  = while parsing document
+";
+        assert_eq!(report.to_string(), expected);
+    }
+
+    #[test]
+    fn report_with_cause() {
+        let input = "";
+        let error: ContextError =
+            FromExternalError::from_external_error(&input, ErrorKind::Verify, ErrorKind::Fail);
+        let report = Report::new("1234567890\n", 3, &error);
+        let expected = "\
+error: parse error
+ --> 1:4
+  |
+1 | 1234567890
+  |    ^
+  |
+  = error Fail
+";
+        assert_eq!(report.to_string(), expected);
+    }
+
+    #[test]
+    fn report_binary() {
+        let input = "";
+        let error = ContextError::new()
+            .add_context(&input, &input.checkpoint(), StrContext::Label("header"))
+            .add_context(
+                &input,
+                &input.checkpoint(),
+                StrContext::Expected(StrContextValue::Description("version 1")),
+            );
+        let source = b"\x7fELF\x02\x01\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x03\x00\x3e\x00";
+        let report = Report::new(source, 18, &error).path("a.out");
+        let expected = "\
+error: invalid header
+ --> a.out:0x12
+  |
+  | 00000000  7f 45 4c 46 02 01 01 00 00 00 00 00 00 00 00 00  |.ELF............|
+  | 00000010  03 00 3e 00                                      |..>.            |
+  |                 ^^ expected version 1
+";
+        assert_eq!(report.to_string(), expected);
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test_hexdump {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        assert_eq!(HexDump::new(b"", 0..0).to_string(), "");
+        assert_eq!(HexDump::new(b"abc", 2..1).to_string(), "");
+    }
+
+    #[test]
+    fn rows() {
+        let input = b"Hello, world!\n\x00\x01\x02";
+        let expected = "\
+00000000  48 65 6c 6c 6f 2c 20 77 6f 72 6c 64 21 0a 00 01  |Hello, world!...|
+00000010  02                                               |.               |
+";
+        assert_eq!(HexDump::new(input, 0..input.len()).to_string(), expected);
+    }
+
+    #[test]
+    fn without_ascii() {
+        let input = b"\x00\x01\x02\x03\x04";
+        let expected = "\
+00000000  00 01 02
+00000003  03 04
+";
+        let dump = HexDump::new(input, 0..input.len()).width(3).ascii(false);
+        assert_eq!(dump.to_string(), expected);
+    }
+
+    #[test]
+    fn mark_eof() {
+        let input = b"\x00\x01";
+        let expected = "\
+00000000  00 01        |..  |
+                ^^
+";
+        let dump = HexDump::new(input, 0..input.len()).width(4).mark(2);
+        assert_eq!(dump.to_string(), expected);
+    }
+
+    #[test]
+    fn zero_width() {
+        let expected = "\
+00000000  61  |a|
+00000001  62  |b|
+";
+        assert_eq!(HexDump::new(b"ab", 0..2).width(0).to_string(), expected);
+    }
+
+    #[test]
+    fn range_past_end() {
+        let dump = HexDump::new(b"ab", usize::MAX - 1..usize::MAX).mark(usize::MAX);
+        let expected = "\
+00000000                                                   |                |
+                ^^
+";
+        assert_eq!(dump.to_string(), expected);
+
+        let dump = HexDump::new(b"abc", 1..usize::MAX).width(4);
+        assert_eq!(dump.to_string(), "00000000     62 63     | bc |\n");
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "miette")]
+mod test_parse_diagnostic {
+    use super::*;
+    use crate::ascii::digit1;
+    use crate::combinator::preceded;
+    use crate::Parser;
+    use miette::Diagnostic;
+    use std::error::Error as _;
+
+    fn value<'i>(input: &mut &'i str) -> PResult<&'i str> {
+        preceded(
+            "v=",
+            digit1
+                .context(StrContext::Label("number"))
+                .context(StrContext::Expected(StrContextValue::Description("digits"))),
+        )
+        .context(StrContext::Label("value"))
+        .parse_next(input)
+    }
+
+    #[test]
+    fn diagnostic() {
+        let diagnostic = value.parse("v=x").unwrap_err().into_diagnostic();
+        assert_eq!(diagnostic.to_string(), "invalid number");
+        assert_eq!(
+            diagnostic.help().map(|h| h.to_string()),
+            Some("while parsing value".to_owned())
+        );
+        let labels = diagnostic.labels().unwrap().collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            [miette::LabeledSpan::new(
+                Some("expected digits".to_owned()),
+                2,
+                0
+            )]
+        );
+        assert_eq!(diagnostic.input(), "v=x");
+        assert!(diagnostic.source().is_none());
+    }
+
+    #[test]
+    fn diagnostic_severity() {
+        let diagnostic = value.parse("v=x").unwrap_err().into_diagnostic();
+        assert_eq!(diagnostic.severity(), Some(miette::Severity::Error));
+
+        let mut lenient = value.context(StrContext::Severity(Severity::Warning));
+        let diagnostic = lenient.parse("v=x").unwrap_err().into_diagnostic();
+        assert_eq!(diagnostic.severity(), Some(miette::Severity::Warning));
+    }
+
+    #[test]
+    fn diagnostic_cause() {
+        let mut parser = digit1.try_map(|s: &str| s.parse::<u8>());
+        let diagnostic = parser.parse("300").unwrap_err().into_diagnostic();
+        assert_eq!(diagnostic.to_string(), "parse error");
+        assert!(diagnostic.help().is_none());
+        assert_eq!(
+            diagnostic.source().map(|e| e.to_string()),
+            Some("number too large to fit in target type".to_owned())
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test_translate_position {
     use super::*;
 
     #[test]
@@ -1404,6 +3208,114 @@ mod test_translate_position {
     }
 }
 
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test_array_context_error {
+    use super::*;
+
+    #[test]
+    fn keeps_innermost_context() {
+        let input = "abc";
+        let start = input.checkpoint();
+        let err = ArrayContextError::<StrContext, 2>::from_error_kind(&input, ErrorKind::Tag)
+            .add_context(&input, &start, StrContext::Label("inner"))
+            .add_context(&input, &start, StrContext::Label("middle"))
+            .add_context(&input, &start, StrContext::Label("outer"));
+        assert_eq!(
+            err.context().collect::<Vec<_>>(),
+            [&StrContext::Label("inner"), &StrContext::Label("middle")]
+        );
+        assert!(err.is_truncated());
+    }
+
+    #[test]
+    fn offset() {
+        let original = "hello world";
+        let input = &original[6..];
+        let err = ArrayContextError::<StrContext>::from_error_kind(&input, ErrorKind::Tag);
+        assert_eq!(err.eof_offset(), 5);
+        assert_eq!(err.offset_in(&original), 6);
+    }
+
+    #[test]
+    fn display() {
+        let input = "abc";
+        let start = input.checkpoint();
+        let err = ArrayContextError::<StrContext>::from_error_kind(&input, ErrorKind::Tag)
+            .add_context(
+                &input,
+                &start,
+                StrContext::Expected(StrContextValue::CharLiteral('a')),
+            )
+            .add_context(
+                &input,
+                &start,
+                StrContext::Expected(StrContextValue::StringLiteral("b")),
+            )
+            .add_context(&input, &start, StrContext::Label("value"));
+        assert_eq!(err.to_string(), "invalid value\nexpected `a` or `b`");
+
+        let empty = ArrayContextError::<StrContext>::from_error_kind(&input, ErrorKind::Tag);
+        assert_eq!(empty.to_string(), "");
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test_span_order {
+    use super::*;
+
+    fn span(e: &(Range<usize>, char)) -> Range<usize> {
+        e.0.clone()
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let mut errors = vec![(3..5, 'a'), (0..9, 'b'), (3..5, 'c'), (3..4, 'd')];
+        sort_by_span(&mut errors, span);
+        assert_eq!(errors, [(0..9, 'b'), (3..4, 'd'), (3..5, 'a'), (3..5, 'c')]);
+    }
+
+    #[test]
+    fn group_empty() {
+        assert_eq!(group_by_span(Vec::new(), span), []);
+    }
+
+    #[test]
+    fn group_nested_and_adjacent() {
+        let errors = vec![
+            (2..3, 'b'),
+            (0..10, 'a'),
+            (10..12, 'c'),
+            (12..12, 'd'),
+            (12..12, 'e'),
+        ];
+        assert_eq!(
+            group_by_span(errors, span),
+            [
+                (0..10, vec![(0..10, 'a'), (2..3, 'b')]),
+                (10..12, vec![(10..12, 'c')]),
+                (12..12, vec![(12..12, 'd'), (12..12, 'e')]),
+            ]
+        );
+    }
+
+    #[test]
+    fn group_chains_overlaps() {
+        let errors = vec![(0..3, 'a'), (2..5, 'b'), (4..8, 'c')];
+        assert_eq!(
+            group_by_span(errors, span),
+            [(0..8, vec![(0..3, 'a'), (2..5, 'b'), (4..8, 'c')])]
+        );
+    }
+
+    #[test]
+    fn remove_keeps_first() {
+        let errors = vec![(5..6, 'c'), (1..4, 'b'), (0..2, 'a'), (5..5, 'd')];
+        assert_eq!(remove_overlapping(errors, span), [(0..2, 'a'), (5..5, 'd')]);
+    }
+}
+
 /// Creates a parse error from a [`ErrorKind`]
 /// and the position in the input
 #[cfg(test)]
@@ -27,6 +27,7 @@
 //!   - Willing to break compatibility rather than batching up breaking changes in large releases
 //!   - Leverage feature flags to keep one active branch
 //! - We will support the last 6 months of rust releases (MSRV, currently 1.64.0)
+//! - Built-in parsers do not panic on any input, see [`ParserError::assert`][crate::error::ParserError::assert]
 //!
 //! See also [Special Topic: Why winnow?][crate::_topic::why]
 //!
@@ -82,7 +83,7 @@ pub(crate) mod lib {
 
         #[cfg(feature = "alloc")]
         #[doc(hidden)]
-        pub(crate) use alloc::{borrow, boxed, collections, string, vec};
+        pub(crate) use alloc::{borrow, boxed, collections, rc, string, vec};
 
         #[doc(hidden)]
         pub(crate) use core::{
@@ -96,7 +97,7 @@ pub(crate) mod lib {
         #![allow(clippy::std_instead_of_core)]
         #[doc(hidden)]
         pub(crate) use std::{
-            borrow, boxed, cmp, collections, convert, fmt, hash, iter, mem, ops, result, slice,
+            borrow, boxed, cmp, collections, convert, fmt, hash, iter, mem, ops, rc, result, slice,
             str, string, vec,
         };
     }
@@ -115,7 +116,10 @@ pub mod stream;
 pub mod ascii;
 pub mod binary;
 pub mod combinator;
+pub mod contrib;
 pub mod token;
+#[cfg(feature = "unicode")]
+pub mod unicode;
 
 #[cfg(feature = "unstable-doc")]
 pub mod _topic;
@@ -162,3 +166,5 @@ pub use stream::Located;
 pub use stream::Partial;
 pub use stream::Stateful;
 pub use stream::Str;
+#[cfg(feature = "derive")]
+pub use winnow_derive::Parse;
@@ -79,6 +79,75 @@ fn seq_struct_basics() {
     );
 }
 
+#[test]
+fn seq_struct_many_fields() {
+    #[derive(Debug, PartialEq)]
+    struct Record {
+        a: u32,
+        b: u32,
+        c: u32,
+        d: u32,
+        e: u32,
+        f: u32,
+        g: u32,
+        h: u32,
+        i: u32,
+        j: u32,
+    }
+
+    fn parser(input: &mut &str) -> PResult<Record> {
+        seq! {
+            Record {
+                a: dec_uint,
+                _: ' ',
+                b: dec_uint,
+                _: ' ',
+                c: dec_uint,
+                _: ' ',
+                d: dec_uint,
+                _: ' ',
+                e: dec_uint,
+                _: ' ',
+                f: dec_uint,
+                _: ' ',
+                g: dec_uint,
+                _: ' ',
+                h: dec_uint,
+                _: ' ',
+                i: dec_uint,
+                _: ' ',
+                j: dec_uint,
+            }
+        }
+        .parse_next(input)
+    }
+    assert_eq!(
+        parser.parse_peek("1 2 3 4 5 6 7 8 9 10 remaining"),
+        Ok((
+            " remaining",
+            Record {
+                a: 1,
+                b: 2,
+                c: 3,
+                d: 4,
+                e: 5,
+                f: 6,
+                g: 7,
+                h: 8,
+                i: 9,
+                j: 10,
+            },
+        )),
+    );
+    assert_eq!(
+        parser.parse_peek("1 2 3 4 5 6 7 8 9,10"),
+        Err(ErrMode::Backtrack(ParserError::from_error_kind(
+            &",10",
+            ErrorKind::Tag
+        )))
+    );
+}
+
 #[test]
 fn seq_struct_default_init() {
     #[derive(Debug, PartialEq, Default)]
@@ -5,8 +5,13 @@ use crate::combinator::*;
 #[cfg(feature = "unstable-recover")]
 #[cfg(feature = "std")]
 use crate::error::FromRecoverableError;
-use crate::error::{AddContext, FromExternalError, IResult, PResult, ParseError, ParserError};
-use crate::stream::{Compare, Location, ParseSlice, Stream, StreamIsPartial};
+use crate::error::{
+    AddContext, FromExternalError, IResult, PResult, ParseError, ParserError, StrContext,
+    StrContextValue,
+};
+use crate::stream::{
+    Compare, ContainsToken, LineLocation, Location, ParseSlice, Stream, StreamIsPartial,
+};
 #[cfg(feature = "unstable-recover")]
 #[cfg(feature = "std")]
 use crate::stream::{Recover, Recoverable};
@@ -65,9 +70,12 @@ pub trait Parser<I, O, E> {
         let (o, _) = (self.by_ref(), crate::combinator::eof)
             .parse_next(&mut input)
             .map_err(|e| {
-                let e = e
-                    .into_inner()
-                    .expect("complete parsers should not report `ErrMode::Incomplete(_)`");
+                let e = e.into_inner().unwrap_or_else(|| {
+                    E::assert(
+                        &input,
+                        "complete parsers should not report `ErrMode::Incomplete(_)`",
+                    )
+                });
                 ParseError::new(input, start, e)
             })?;
         Ok(o)
@@ -351,6 +359,42 @@ pub trait Parser<I, O, E> {
         WithTaken::new(self)
     }
 
+    /// Produce the consumed input, without leading and trailing tokens in `trim`
+    ///
+    /// This avoids trimming the output of [`Parser::take`] after the fact, like for whitespace
+    /// around a value.  The whole input is still consumed, so its full span is available by
+    /// following this with [`Parser::with_span`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// # use winnow::error::InputError;
+    /// use winnow::stream::Located;
+    /// use winnow::token::take_till;
+    ///
+    /// type Input<'s> = Located<&'s str>;
+    ///
+    /// fn field<'s>(
+    ///     input: &mut Input<'s>,
+    /// ) -> PResult<(&'s str, std::ops::Range<usize>), InputError<Input<'s>>> {
+    ///     take_till(0.., ',').take_trimmed(' ').with_span().parse_next(input)
+    /// }
+    ///
+    /// assert_eq!(field.parse_peek(Located::new("  ab c ,d")).unwrap().1, ("ab c", 0..7));
+    /// assert_eq!(field.parse_peek(Located::new("   ,d")).unwrap().1, ("", 0..3));
+    /// ```
+    #[doc(alias = "recognize_trimmed")]
+    #[inline(always)]
+    fn take_trimmed<S>(self, trim: S) -> TakeTrimmed<Self, I, O, E, S>
+    where
+        Self: core::marker::Sized,
+        I: Stream,
+        S: ContainsToken<<I as Stream>::Token>,
+    {
+        TakeTrimmed::new(self, trim)
+    }
+
     /// Produce the location of the consumed input as produced value.
     ///
     /// # Example
@@ -425,6 +469,69 @@ pub trait Parser<I, O, E> {
         WithSpan::new(self)
     }
 
+    /// Produce the line and column range of the consumed input as produced value.
+    ///
+    /// Like [`Parser::span`] but reporting [`Position`][crate::stream::Position]s.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// # use winnow::error::ContextError;
+    /// use winnow::stream::LineLocated;
+    /// use winnow::stream::Position;
+    /// use winnow::ascii::alpha1;
+    /// use winnow::combinator::separated_pair;
+    ///
+    /// let mut parser = separated_pair(alpha1::<_, ContextError>.line_span(), ",\n", alpha1.line_span());
+    ///
+    /// assert_eq!(
+    ///     parser.parse(LineLocated::new("abcd,\nefgh")),
+    ///     Ok((Position::new(1, 1)..Position::new(1, 5), Position::new(2, 1)..Position::new(2, 5)))
+    /// );
+    /// ```
+    #[inline(always)]
+    fn line_span(self) -> LineSpan<Self, I, O, E>
+    where
+        Self: core::marker::Sized,
+        I: Stream + LineLocation,
+    {
+        LineSpan::new(self)
+    }
+
+    /// Produce the line and column range of consumed input with the output
+    ///
+    /// Like [`Parser::with_span`] but reporting [`Position`][crate::stream::Position]s.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// # use winnow::error::ContextError;
+    /// use winnow::stream::LineLocated;
+    /// use winnow::stream::Position;
+    /// use winnow::ascii::alpha1;
+    /// use winnow::combinator::separated_pair;
+    ///
+    /// let mut parser = separated_pair(alpha1::<_, ContextError>.with_line_span(), ",\n", alpha1.with_line_span());
+    ///
+    /// assert_eq!(
+    ///     parser.parse(LineLocated::new("abcd,\nefgh")),
+    ///     Ok((
+    ///         ("abcd", Position::new(1, 1)..Position::new(1, 5)),
+    ///         ("efgh", Position::new(2, 1)..Position::new(2, 5)),
+    ///     ))
+    /// );
+    /// ```
+    #[inline(always)]
+    fn with_line_span(self) -> WithLineSpan<Self, I, O, E>
+    where
+        Self: core::marker::Sized,
+        I: Stream + LineLocation,
+    {
+        WithLineSpan::new(self)
+    }
+
     /// Maps a function over the output of a parser
     ///
     /// # Example
@@ -563,6 +670,9 @@ pub trait Parser<I, O, E> {
 
     /// Applies a second parser over the output of the first one
     ///
+    /// To report errors from `inner` at their position in the original input, see
+    /// [`within`][crate::combinator::within].
+    ///
     /// # Example
     ///
     /// ```rust
@@ -670,6 +780,48 @@ pub trait Parser<I, O, E> {
         Context::new(self, context)
     }
 
+    /// If parsing fails, report what was expected, like `"integer literal"`
+    ///
+    /// This is a shorthand for [`Parser::context`] with [`StrContext::Expected`] and
+    /// [`StrContextValue::Description`].  When every alternative of an
+    /// [`alt`][crate::combinator::alt] fails, [`ContextError`][crate::error::ContextError] keeps
+    /// what each one expected, rendering as "expected integer literal, string, or `(`".
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// use winnow::ascii::alpha1;
+    /// use winnow::ascii::digit1;
+    /// use winnow::combinator::alt;
+    /// use winnow::combinator::delimited;
+    /// use winnow::error::StrContext;
+    ///
+    /// fn value<'i>(input: &mut &'i str) -> PResult<&'i str> {
+    ///     alt((
+    ///         digit1.expected("integer literal"),
+    ///         delimited('"', alpha1, '"').expected("string"),
+    ///         '('.take().context(StrContext::Expected('('.into())),
+    ///     ))
+    ///     .parse_next(input)
+    /// }
+    ///
+    /// let error = value.parse("?").unwrap_err();
+    /// assert_eq!(error.inner().to_string(), "expected integer literal, string, or `(`");
+    /// ```
+    #[inline(always)]
+    fn expected(self, label: &'static str) -> Context<Self, I, O, E, StrContext>
+    where
+        Self: core::marker::Sized,
+        I: Stream,
+        E: AddContext<I, StrContext>,
+    {
+        Context::new(
+            self,
+            StrContext::Expected(StrContextValue::Description(label)),
+        )
+    }
+
     /// Transforms [`Incomplete`][crate::error::ErrMode::Incomplete] into [`Backtrack`][crate::error::ErrMode::Backtrack]
     ///
     /// # Example
@@ -693,6 +845,43 @@ pub trait Parser<I, O, E> {
         CompleteErr::new(self)
     }
 
+    /// Fail with [`ErrorKind::Fuel`][crate::error::ErrorKind::Fuel] if parsing burns more than
+    /// `fuel`, see [`Fueled`]
+    ///
+    /// The failure is a [`Cut`][crate::error::ErrMode::Cut] as running out of fuel is final:
+    /// the input stays exhausted, even for the enclosing parsers.  Fuel not burned is left for
+    /// them and a nested `with_fuel` can only lower the budget.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// # use winnow::error::{ErrMode, ErrorKind, InputError};
+    /// use winnow::combinator::alt;
+    /// use winnow::combinator::repeat;
+    /// use winnow::stream::Fueled;
+    ///
+    /// // Backtracks over every `a` for each `a`
+    /// fn slow<'s>(input: &mut Fueled<&'s str>) -> PResult<(), InputError<Fueled<&'s str>>> {
+    ///     repeat(0.., alt(((repeat::<_, _, (), _, _>(0.., 'a'), 'b').void(), 'a'.void())))
+    ///         .parse_next(input)
+    /// }
+    ///
+    /// let input = "a".repeat(1_000);
+    /// assert!(slow.parse(Fueled::new(input.as_str())).is_ok());
+    ///
+    /// let mut parser = slow.with_fuel(10_000);
+    /// let err = parser.parse_peek(Fueled::new(input.as_str())).unwrap_err();
+    /// assert!(matches!(err, ErrMode::Cut(e) if e.kind == ErrorKind::Fuel));
+    /// ```
+    #[inline(always)]
+    fn with_fuel(self, fuel: usize) -> WithFuel<Self>
+    where
+        Self: core::marker::Sized,
+    {
+        WithFuel::new(self, fuel)
+    }
+
     /// Convert the parser's error to another type using [`std::convert::From`]
     #[inline(always)]
     fn err_into<E2>(self) -> ErrInto<Self, I, O, E, E2>
@@ -703,6 +892,133 @@ pub trait Parser<I, O, E> {
         ErrInto::new(self)
     }
 
+    /// Cache the result of this parser for each input position
+    ///
+    /// Grammars with deeply nested [`alt`][crate::combinator::alt]s can re-parse the same input
+    /// many times. Memoizing the shared rules ("packrat parsing") makes each rule run at most once
+    /// per position. Results are stored in a [`MemoTable`][crate::combinator::MemoTable] that
+    /// `table` looks up from the input, scoping the cache to a single [`Parser::parse`] call.
+    ///
+    /// The cached output and error are cloned on each hit.
+    /// [`ErrMode::Incomplete`][crate::error::ErrMode::Incomplete] results are not cached.
+    ///
+    /// Results are keyed by [`Stream::eof_offset`] alone and a hit only advances the input past
+    /// what was consumed, so:
+    /// - Only memoize parsers whose result depends on nothing but the input.  Changes the parser
+    ///   makes to the `state` of a [`Stateful`][crate::stream::Stateful] are not replayed on a hit.
+    /// - Only use a table with one input, [clearing][crate::combinator::MemoTable::clear] it
+    ///   before reusing it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// # use winnow::error::ContextError;
+    /// use winnow::ascii::dec_uint;
+    /// use winnow::combinator::alt;
+    /// use winnow::combinator::MemoTable;
+    /// use winnow::stream::Stateful;
+    ///
+    /// #[derive(Debug, Default)]
+    /// struct Cache {
+    ///     term: MemoTable<u32, ContextError>,
+    /// }
+    ///
+    /// type Stream<'i> = Stateful<&'i str, Cache>;
+    ///
+    /// fn term(i: &mut Stream<'_>) -> PResult<u32> {
+    ///     dec_uint.memoize(|i: &mut Stream<'_>| &mut i.state.term).parse_next(i)
+    /// }
+    ///
+    /// fn expr(i: &mut Stream<'_>) -> PResult<u32> {
+    ///     alt((
+    ///         (term, '+', term).map(|(l, _, r)| l + r),
+    ///         (term, '-', term).map(|(l, _, r)| l - r),
+    ///         term,
+    ///     ))
+    ///     .parse_next(i)
+    /// }
+    ///
+    /// let mut input = Stream { input: "5-3", state: Cache::default() };
+    /// assert_eq!(expr.parse_next(&mut input), Ok(2));
+    /// // `term` only ran at offsets 0 and 2, despite being tried 3 times
+    /// assert_eq!(input.state.term.len(), 2);
+    /// ```
+    #[doc(alias = "packrat")]
+    #[doc(alias = "cache")]
+    #[inline(always)]
+    #[cfg(feature = "std")]
+    fn memoize<T>(self, table: T) -> Memoize<Self, T, I, O, E>
+    where
+        Self: core::marker::Sized,
+        T: FnMut(&mut I) -> &mut MemoTable<O, E>,
+        I: Stream,
+        O: Clone,
+        E: Clone,
+    {
+        Memoize::new(self, table)
+    }
+
+    /// Record a warning in [`Warnings`][crate::combinator::Warnings] about this parser's output,
+    /// without failing
+    ///
+    /// When `check` returns a warning, it is recorded with the span of the parsed input in the
+    /// collection that `warnings` looks up from the input, like deprecated syntax in a linter or
+    /// suspicious values in a forgiving format.  The output is returned either way.
+    ///
+    /// <div class="warning">
+    ///
+    /// **Note:** Warnings are kept even if an enclosing parser later backtracks, like a branch of
+    /// an [`alt`][crate::combinator::alt] that fails after this succeeded.  Record warnings
+    /// where the grammar has committed to the parse, like after a
+    /// [`cut_err`][crate::combinator::cut_err] or inside a
+    /// [`dispatch!`][crate::combinator::dispatch] branch.
+    ///
+    /// </div>
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// use winnow::ascii::alpha1;
+    /// use winnow::combinator::separated;
+    /// use winnow::combinator::Warnings;
+    /// use winnow::stream::Located;
+    /// use winnow::stream::Stateful;
+    ///
+    /// type Stream<'i> = Stateful<Located<&'i str>, Warnings<&'static str>>;
+    ///
+    /// fn keyword<'i>(i: &mut Stream<'i>) -> PResult<&'i str> {
+    ///     alpha1
+    ///         .warn_if(
+    ///             |k: &&str| (*k == "var").then_some("`var` is deprecated, use `let`"),
+    ///             |i: &mut Stream<'i>| &mut i.state,
+    ///         )
+    ///         .parse_next(i)
+    /// }
+    ///
+    /// let mut input = Stream {
+    ///     input: Located::new("let var let"),
+    ///     state: Warnings::new(),
+    /// };
+    /// let keywords: Vec<_> = separated(1.., keyword, ' ').parse_next(&mut input).unwrap();
+    /// assert_eq!(keywords, ["let", "var", "let"]);
+    /// assert_eq!(input.state.as_slice(), [(4..7, "`var` is deprecated, use `let`")]);
+    /// ```
+    #[doc(alias = "lint")]
+    #[doc(alias = "diagnostic")]
+    #[inline(always)]
+    #[cfg(feature = "alloc")]
+    fn warn_if<W, C, S>(self, check: C, warnings: S) -> WarnIf<Self, C, S, I, O, E, W>
+    where
+        Self: core::marker::Sized,
+        C: FnMut(&O) -> Option<W>,
+        S: FnMut(&mut I) -> &mut Warnings<W>,
+        I: Stream + Location,
+    {
+        WarnIf::new(self, check, warnings)
+    }
+
     /// Recover from an error by skipping everything `recover` consumes and trying again
     ///
     /// If `recover` consumes nothing, the error is returned, allowing an alternative recovery
@@ -728,6 +1044,31 @@ pub trait Parser<I, O, E> {
     ///
     /// This commits the parse result, preventing alternative branch paths like with
     /// [`winnow::combinator::alt`][crate::combinator::alt].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// # use winnow::error::ContextError;
+    /// use winnow::stream::Recoverable;
+    /// use winnow::ascii::digit1;
+    /// use winnow::combinator::separated;
+    /// use winnow::token::take_till;
+    ///
+    /// type Stream<'i> = Recoverable<&'i str, ContextError>;
+    ///
+    /// fn number(i: &mut Stream<'_>) -> PResult<Option<u32>> {
+    ///     digit1.parse_to().resume_after(take_till(1.., ',').void()).parse_next(i)
+    /// }
+    ///
+    /// fn list(i: &mut Stream<'_>) -> PResult<Vec<Option<u32>>> {
+    ///     separated(0.., number, ',').parse_next(i)
+    /// }
+    ///
+    /// let (_, output, errors) = list.recoverable_parse("1,x,3,y");
+    /// assert_eq!(output, Some(vec![Some(1), None, Some(3), None]));
+    /// assert_eq!(errors.len(), 2);
+    /// ```
     #[inline(always)]
     #[cfg(feature = "unstable-recover")]
     #[cfg(feature = "std")]
@@ -741,6 +1082,56 @@ pub trait Parser<I, O, E> {
     {
         ResumeAfter::new(self, recover)
     }
+
+    /// Recover from an error by deleting unexpected tokens or inserting what is missing
+    ///
+    /// Up to [`Repair::max_deletions`] tokens (default: 1) are deleted, one more at a time,
+    /// until this parser succeeds.  Failing that, the parser is treated as if `label` had been
+    /// inserted, producing `missing()` without consuming anything.  Either way, the repair is
+    /// recorded as a [`Repaired`][crate::error::Repaired] external error, e.g. for a
+    /// "missing `)` inserted" diagnostic.
+    ///
+    /// This fits parsers for a single expected token, like a closing delimiter, and commits the
+    /// parse result, preventing alternative branch paths like with
+    /// [`winnow::combinator::alt`][crate::combinator::alt].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// # use winnow::error::ContextError;
+    /// use winnow::ascii::alpha1;
+    /// use winnow::combinator::delimited;
+    /// use winnow::stream::Recoverable;
+    ///
+    /// type Stream<'i> = Recoverable<&'i str, ContextError>;
+    ///
+    /// fn call<'i>(i: &mut Stream<'i>) -> PResult<(&'i str, &'i str)> {
+    ///     (alpha1, delimited('(', alpha1, ')'.repair("`)`", || ')'))).parse_next(i)
+    /// }
+    ///
+    /// let (_, output, errors) = call.recoverable_parse("f(x");
+    /// assert_eq!(output, Some(("f", "x")));
+    /// assert_eq!(errors.len(), 1);
+    ///
+    /// let (_, output, errors) = call.recoverable_parse("f(x])");
+    /// assert_eq!(output, Some(("f", "x")));
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    #[inline(always)]
+    #[cfg(feature = "unstable-recover")]
+    #[cfg(feature = "std")]
+    fn repair<F>(self, label: &'static str, missing: F) -> Repair<Self, F, I, O, E>
+    where
+        Self: core::marker::Sized,
+        F: FnMut() -> O,
+        I: Stream,
+        I: Recover<E>,
+        E: FromRecoverableError<I, E>,
+        E: FromExternalError<I, crate::error::Repaired>,
+    {
+        Repair::new(self, label, missing)
+    }
 }
 
 impl<'a, I, O, E, F> Parser<I, O, E> for F
@@ -754,6 +1145,94 @@ where
     }
 }
 
+/// Parse a value of `Self`
+///
+/// As `Parse::parse_next` is a function, it can be used anywhere a [`Parser`] is expected.
+///
+/// With the `derive` feature, this can be generated with `#[derive(Parse)]`:
+/// - structs parse each field in order
+/// - enums try each variant in order, like [`alt`][crate::combinator::alt]
+///
+/// Fields are parsed with their type's `Parse` implementation unless overridden with attributes:
+/// - `#[winnow(tag = ...)]` on a struct or variant: a literal to parse first, see [`literal`][crate::token::literal]
+/// - `#[winnow(sep = ...)]` on a type or variant: a literal to parse between the tag and fields
+/// - `#[winnow(endian = "big" | "little" | "native")]` on a type, variant, or field: parse
+///   integer and float fields with [`winnow::binary`][crate::binary]
+/// - `#[winnow(parser = ...)]` on a field: the [`Parser`] to use for the field
+/// - `#[winnow(input = ...)]` and `#[winnow(error = ...)]` on a type: implement `Parse` for only
+///   this input and error type, needed when a `parser` is not generic over them
+///
+/// Without `input`, `Parse` is implemented for any input and error that the fields support.
+/// With `input` but without `error`, the error is [`ContextError`][crate::error::ContextError].
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "derive")] {
+/// # use winnow::prelude::*;
+/// use winnow::ascii::alpha1;
+/// use winnow::ascii::dec_uint;
+/// use winnow::Parse;
+///
+/// #[derive(Parse, Debug, PartialEq)]
+/// #[winnow(input = &'i str, sep = ',')]
+/// struct Point {
+///     #[winnow(parser = dec_uint)]
+///     x: u32,
+///     #[winnow(parser = dec_uint)]
+///     y: u32,
+/// }
+///
+/// #[derive(Parse, Debug, PartialEq)]
+/// #[winnow(input = &'i str)]
+/// enum Shape<'i> {
+///     #[winnow(tag = "point ")]
+///     Point(Point),
+///     #[winnow(tag = "named ")]
+///     Named(#[winnow(parser = alpha1)] &'i str),
+/// }
+///
+/// assert_eq!(Shape::parse_next.parse("point 1,2"), Ok(Shape::Point(Point { x: 1, y: 2 })));
+/// assert_eq!(Shape::parse_next.parse("named origin"), Ok(Shape::Named("origin")));
+/// assert!(Shape::parse_next.parse("line 1,2").is_err());
+/// # }
+/// ```
+///
+/// Binary records can be generic over the input:
+/// ```rust
+/// # #[cfg(feature = "derive")] {
+/// # use winnow::prelude::*;
+/// use winnow::error::InputError;
+/// use winnow::Parse;
+///
+/// #[derive(Parse, Debug, PartialEq)]
+/// #[winnow(endian = "big")]
+/// struct Header {
+///     #[winnow(endian = "little")]
+///     magic: u16,
+///     len: u32,
+/// }
+///
+/// #[derive(Parse, Debug, PartialEq)]
+/// #[winnow(tag = b"REC", endian = "big")]
+/// struct Record {
+///     header: Header,
+///     kind: u8,
+/// }
+///
+/// let input = &[b'R', b'E', b'C', 0x4d, 0x5a, 0, 0, 1, 0, 7][..];
+/// let record = Record::parse_next.parse(input).map_err(|e| e.into_inner());
+/// assert_eq!(
+///     record,
+///     Ok::<_, InputError<_>>(Record { header: Header { magic: 0x5a4d, len: 256 }, kind: 7 })
+/// );
+/// # }
+/// ```
+pub trait Parse<I, E = crate::error::ContextError>: Sized {
+    /// Parse the next `Self` from `input`
+    fn parse_next(input: &mut I) -> PResult<Self, E>;
+}
+
 /// This is a shortcut for [`one_of`][crate::token::one_of].
 ///
 /// # Example
@@ -994,6 +1473,38 @@ where
     }
 }
 
+/// This is a shortcut for [`literal`][crate::token::literal].
+///
+/// See [`unicode::Caseless`][crate::unicode::Caseless].
+#[cfg(feature = "unicode-case")]
+impl<'s, I, E: ParserError<I>> Parser<I, <I as Stream>::Slice, E>
+    for crate::unicode::Caseless<&'s str>
+where
+    I: Compare<crate::unicode::Caseless<&'s str>> + StreamIsPartial,
+    I: Stream,
+{
+    #[inline(always)]
+    fn parse_next(&mut self, i: &mut I) -> PResult<<I as Stream>::Slice, E> {
+        crate::token::literal(*self).parse_next(i)
+    }
+}
+
+/// This is a shortcut for [`literal`][crate::token::literal].
+///
+/// See [`unicode::CanonicalCaseless`][crate::unicode::CanonicalCaseless].
+#[cfg(feature = "unicode-case")]
+impl<'s, I, E: ParserError<I>> Parser<I, <I as Stream>::Slice, E>
+    for crate::unicode::CanonicalCaseless<&'s str>
+where
+    I: Compare<crate::unicode::CanonicalCaseless<&'s str>> + StreamIsPartial,
+    I: Stream,
+{
+    #[inline(always)]
+    fn parse_next(&mut self, i: &mut I) -> PResult<<I as Stream>::Slice, E> {
+        crate::token::literal(*self).parse_next(i)
+    }
+}
+
 impl<I: Stream, E: ParserError<I>> Parser<I, (), E> for () {
     #[inline(always)]
     fn parse_next(&mut self, _i: &mut I) -> PResult<(), E> {
@@ -1046,6 +1557,40 @@ pub trait RecoverableParser<I, O, R, E> {
     /// [`Parser::retry_after`] and [`Parser::resume_after`] throughout your parser.
     ///
     /// The empty `input` is returned to allow turning the errors into [`ParserError`]s.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// # use winnow::error::ContextError;
+    /// use winnow::stream::Recoverable;
+    /// use winnow::ascii::alpha1;
+    /// use winnow::ascii::line_ending;
+    /// use winnow::ascii::till_line_ending;
+    /// use winnow::combinator::repeat;
+    /// use winnow::combinator::terminated;
+    ///
+    /// type Stream<'i> = Recoverable<&'i str, ContextError>;
+    ///
+    /// fn word(i: &mut Stream<'_>) -> PResult<Option<String>> {
+    ///     terminated(alpha1, line_ending)
+    ///         .map(|s: &str| s.to_owned())
+    ///         .resume_after((till_line_ending, line_ending).void())
+    ///         .parse_next(i)
+    /// }
+    ///
+    /// fn words(i: &mut Stream<'_>) -> PResult<Vec<Option<String>>> {
+    ///     repeat(0.., word).parse_next(i)
+    /// }
+    ///
+    /// let (input, output, errors) = words.recoverable_parse("hello\n42\nworld\n$$\n");
+    /// assert_eq!(input, "hello\n42\nworld\n$$\n");
+    /// assert_eq!(
+    ///     output,
+    ///     Some(vec![Some("hello".to_owned()), None, Some("world".to_owned()), None])
+    /// );
+    /// assert_eq!(errors.len(), 2);
+    /// ```
     fn recoverable_parse(&mut self, input: I) -> (I, Option<O>, Vec<R>);
 }
 
@@ -1081,9 +1626,12 @@ where
         let (o, err) = match result {
             Ok((o, _)) => (Some(o), None),
             Err(err) => {
-                let err = err
-                    .into_inner()
-                    .expect("complete parsers should not report `ErrMode::Incomplete(_)`");
+                let err = err.into_inner().unwrap_or_else(|| {
+                    E::assert(
+                        &input,
+                        "complete parsers should not report `ErrMode::Incomplete(_)`",
+                    )
+                });
                 let err_start = input.checkpoint();
                 let err = R::from_recoverable_error(&start_token, &err_start, &input, err);
                 (None, Some(err))
@@ -1135,6 +1683,14 @@ impl<'a, I, O, E> Parser<I, O, E> for Box<dyn Parser<I, O, E> + 'a> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<'a, I, O, E> Parser<I, O, E> for Box<dyn Parser<I, O, E> + Send + 'a> {
+    #[inline(always)]
+    fn parse_next(&mut self, i: &mut I) -> PResult<O, E> {
+        (**self).parse_next(i)
+    }
+}
+
 /// Convert a [`Parser::parse_peek`] style parse function to be a [`Parser`]
 #[inline(always)]
 pub fn unpeek<'a, I, O, E>(
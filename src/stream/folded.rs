@@ -0,0 +1,146 @@
+use crate::lib::std::borrow::Borrow;
+
+use super::{Compare, CompareResult, ContainsToken, SliceLen};
+
+/// Mark a value as compared after folding each token, like for case-insensitive matching
+///
+/// [`Caseless`][crate::ascii::Caseless] only folds ASCII letters of `&str` and `&[u8]`.  This
+/// lets `fold` decide what tokens are the same, mapping them to a key compared with
+/// [`PartialEq`], for any token type:
+/// - [`literal`][crate::token::literal] with a `Folded<&str, F>` for `&str` input, where `F`
+///   folds `&char`, or a `Folded<&[T], F>` for `&[T]` input, where `F` folds `&T`
+/// - [`one_of`][crate::token::one_of] and [`take_while`][crate::token::take_while] with a
+///   `Folded<[T; LEN], F>` or `Folded<&[T], F>` for any input whose tokens borrow as a `T`,
+///   including [`TokenSlice`][crate::stream::TokenSlice]
+///
+/// Both the value and the input are folded, so one `fold` can serve the whole grammar.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::stream::Folded;
+/// use winnow::stream::TokenSlice;
+/// use winnow::token::literal;
+/// use winnow::token::one_of;
+///
+/// fn fold_char(c: &char) -> Option<char> {
+///     c.to_lowercase().next()
+/// }
+///
+/// let mut greeting = literal::<_, _, ContextError>(Folded("grüße", fold_char));
+/// assert_eq!(greeting.parse_peek("GRÜẞE!"), Ok(("!", "GRÜẞE")));
+/// assert!(greeting.parse_peek("GRÜSSE!").is_err());
+///
+/// #[derive(Clone, Debug, PartialEq, Eq)]
+/// enum Token {
+///     Ident(&'static str),
+///     Star,
+/// }
+///
+/// fn fold_token(token: &Token) -> Option<String> {
+///     match token {
+///         Token::Ident(ident) => Some(ident.to_ascii_lowercase()),
+///         _ => None,
+///     }
+/// }
+///
+/// let keyword = [Token::Ident("select"), Token::Ident("from")];
+/// let tokens = [Token::Ident("SELECT"), Token::Star];
+/// let mut input = TokenSlice::new(&tokens);
+/// let token = one_of::<_, _, ContextError>(Folded(keyword, fold_token))
+///     .parse_next(&mut input)
+///     .unwrap();
+/// assert_eq!(token, &Token::Ident("SELECT"));
+/// ```
+#[derive(Copy, Clone)]
+pub struct Folded<T, F>(pub T, pub F);
+
+impl<T: crate::lib::std::fmt::Debug, F> crate::lib::std::fmt::Debug for Folded<T, F> {
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        f.debug_tuple("Folded").field(&self.0).finish()
+    }
+}
+
+impl<T: SliceLen, F> SliceLen for Folded<T, F> {
+    #[inline(always)]
+    fn slice_len(&self) -> usize {
+        self.0.slice_len()
+    }
+}
+
+impl<'b, F, K> Compare<Folded<&'b str, F>> for &str
+where
+    F: Fn(&char) -> K,
+    K: PartialEq,
+{
+    #[inline]
+    fn compare(&self, t: Folded<&'b str, F>) -> CompareResult {
+        let Folded(literal, fold) = t;
+        let mut chars = self.chars();
+        for expected in literal.chars() {
+            match chars.next() {
+                Some(c) if fold(&c) == fold(&expected) => {}
+                Some(_) => return CompareResult::Error,
+                None => return CompareResult::Incomplete,
+            }
+        }
+        CompareResult::Ok(self.len() - chars.as_str().len())
+    }
+}
+
+impl<'b, T, F, K> Compare<Folded<&'b [T], F>> for &[T]
+where
+    F: Fn(&T) -> K,
+    K: PartialEq,
+{
+    #[inline]
+    fn compare(&self, t: Folded<&'b [T], F>) -> CompareResult {
+        let Folded(literal, fold) = t;
+        if literal.iter().zip(*self).any(|(a, b)| fold(a) != fold(b)) {
+            CompareResult::Error
+        } else if self.len() < literal.len() {
+            CompareResult::Incomplete
+        } else {
+            CompareResult::Ok(literal.len())
+        }
+    }
+}
+
+impl<C, T, F, K> ContainsToken<C> for Folded<&'_ [T], F>
+where
+    C: Borrow<T>,
+    F: Fn(&T) -> K,
+    K: PartialEq,
+{
+    #[inline]
+    fn contains_token(&self, token: C) -> bool {
+        let token = (self.1)(token.borrow());
+        self.0.iter().any(|t| (self.1)(t) == token)
+    }
+}
+
+impl<const LEN: usize, C, T, F, K> ContainsToken<C> for Folded<&'_ [T; LEN], F>
+where
+    C: Borrow<T>,
+    F: Fn(&T) -> K,
+    K: PartialEq,
+{
+    #[inline]
+    fn contains_token(&self, token: C) -> bool {
+        Folded(&self.0[..], &self.1).contains_token(token)
+    }
+}
+
+impl<const LEN: usize, C, T, F, K> ContainsToken<C> for Folded<[T; LEN], F>
+where
+    C: Borrow<T>,
+    F: Fn(&T) -> K,
+    K: PartialEq,
+{
+    #[inline]
+    fn contains_token(&self, token: C) -> bool {
+        Folded(&self.0[..], &self.1).contains_token(token)
+    }
+}
@@ -0,0 +1,376 @@
+use crate::error::Needed;
+use crate::lib::std::iter::Take;
+
+use super::{
+    AsBStr, AsBytes, Checkpoint, Compare, CompareResult, ContainsToken, FindSlice, LineLocation,
+    Location, Offset, Position, SliceLen, Stream, StreamIsPartial, UpdateSlice,
+};
+
+/// Bound the work done parsing the input
+///
+/// Parsing burns a unit of fuel for each token consumed, counting bytes for `&str`, and for
+/// each backtrack to an earlier position.  Once it runs out, the input looks empty so every
+/// parser fails quickly, even when adversarial input would otherwise cause exponential
+/// backtracking.
+///
+/// Set the budget with [`Parser::with_fuel`][crate::Parser::with_fuel] to fail with
+/// [`ErrorKind::Fuel`][crate::error::ErrorKind::Fuel] or with [`Fueled::set_fuel`] and check
+/// [`Fueled::is_exhausted`] after parsing.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::alpha1;
+/// use winnow::combinator::separated;
+/// use winnow::error::ErrorKind;
+/// use winnow::stream::Fueled;
+///
+/// fn words<'s>(input: &mut Fueled<&'s str>) -> PResult<Vec<&'s str>> {
+///     separated(1.., alpha1, ' ').parse_next(input)
+/// }
+///
+/// assert!(words.with_fuel(100).parse(Fueled::new("a few words")).is_ok());
+///
+/// let err = words.with_fuel(5).parse(Fueled::new("a few words")).unwrap_err();
+/// assert!(err.input().is_exhausted());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Fueled<I> {
+    input: I,
+    fuel: usize,
+    exhausted: bool,
+}
+
+impl<I> Fueled<I> {
+    /// Wrap another Stream with unlimited fuel
+    pub fn new(input: I) -> Self {
+        Self {
+            input,
+            fuel: usize::MAX,
+            exhausted: false,
+        }
+    }
+
+    /// The fuel left
+    #[inline(always)]
+    pub fn fuel(&self) -> usize {
+        self.fuel
+    }
+
+    /// Set the fuel left, clearing [`Fueled::is_exhausted`]
+    #[inline(always)]
+    pub fn set_fuel(&mut self, fuel: usize) {
+        self.fuel = fuel;
+        self.exhausted = false;
+    }
+
+    /// Whether parsing ran out of fuel, making the input look empty
+    #[inline(always)]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Unwrap the input
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+
+    #[inline(always)]
+    fn burn(&mut self, amount: usize) {
+        match self.fuel.checked_sub(amount) {
+            Some(fuel) => self.fuel = fuel,
+            None => {
+                self.fuel = 0;
+                self.exhausted = true;
+            }
+        }
+    }
+}
+
+impl<I> AsRef<I> for Fueled<I> {
+    #[inline(always)]
+    fn as_ref(&self) -> &I {
+        &self.input
+    }
+}
+
+impl<I> crate::lib::std::ops::Deref for Fueled<I> {
+    type Target = I;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.input
+    }
+}
+
+impl<I: crate::lib::std::fmt::Display> crate::lib::std::fmt::Display for Fueled<I> {
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        self.input.fmt(f)
+    }
+}
+
+impl<I> SliceLen for Fueled<I>
+where
+    I: SliceLen,
+{
+    #[inline(always)]
+    fn slice_len(&self) -> usize {
+        if self.exhausted {
+            0
+        } else {
+            self.input.slice_len()
+        }
+    }
+}
+
+impl<I: Stream> Stream for Fueled<I> {
+    type Token = <I as Stream>::Token;
+    type Slice = <I as Stream>::Slice;
+
+    type IterOffsets = Take<<I as Stream>::IterOffsets>;
+
+    type Checkpoint = Checkpoint<I::Checkpoint, Self>;
+
+    #[inline(always)]
+    fn iter_offsets(&self) -> Self::IterOffsets {
+        let len = if self.exhausted { 0 } else { usize::MAX };
+        self.input.iter_offsets().take(len)
+    }
+    #[inline(always)]
+    fn eof_offset(&self) -> usize {
+        if self.exhausted {
+            0
+        } else {
+            self.input.eof_offset()
+        }
+    }
+
+    #[inline(always)]
+    fn next_token(&mut self) -> Option<Self::Token> {
+        if self.exhausted {
+            return None;
+        }
+        self.burn(1);
+        if self.exhausted {
+            return None;
+        }
+        self.input.next_token()
+    }
+
+    #[inline(always)]
+    fn offset_for<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Token) -> bool,
+    {
+        if self.exhausted {
+            None
+        } else {
+            self.input.offset_for(predicate)
+        }
+    }
+    #[inline(always)]
+    fn offset_for_set<Set>(&self, set: &Set) -> Option<usize>
+    where
+        Set: ContainsToken<Self::Token>,
+    {
+        if self.exhausted {
+            None
+        } else {
+            self.input.offset_for_set(set)
+        }
+    }
+    #[inline(always)]
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        if self.exhausted {
+            if tokens == 0 {
+                Ok(0)
+            } else {
+                Err(Needed::new(tokens))
+            }
+        } else {
+            self.input.offset_at(tokens)
+        }
+    }
+    /// The slice is always returned, even when it burns the last of the fuel
+    #[inline(always)]
+    fn next_slice(&mut self, offset: usize) -> Self::Slice {
+        self.burn(offset);
+        self.input.next_slice(offset)
+    }
+    #[inline(always)]
+    fn try_next_slice(&mut self, offset: usize) -> Option<Self::Slice> {
+        let slice = self.input.try_next_slice(offset)?;
+        self.burn(offset);
+        Some(slice)
+    }
+
+    #[inline(always)]
+    fn checkpoint(&self) -> Self::Checkpoint {
+        Checkpoint::<_, Self>::new(self.input.checkpoint())
+    }
+    #[inline(always)]
+    fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+        if 0 < self.input.offset_from(&checkpoint.inner) {
+            self.burn(1);
+        }
+        self.input.reset(&checkpoint.inner);
+    }
+
+    #[inline(always)]
+    fn raw(&self) -> &dyn crate::lib::std::fmt::Debug {
+        &self.input
+    }
+}
+
+impl<I> Location for Fueled<I>
+where
+    I: Location,
+{
+    #[inline(always)]
+    fn location(&self) -> usize {
+        self.input.location()
+    }
+}
+
+impl<I> LineLocation for Fueled<I>
+where
+    I: LineLocation,
+{
+    #[inline(always)]
+    fn line_location(&self) -> Position {
+        self.input.line_location()
+    }
+}
+
+impl<I> StreamIsPartial for Fueled<I>
+where
+    I: StreamIsPartial,
+{
+    type PartialState = I::PartialState;
+
+    fn complete(&mut self) -> Self::PartialState {
+        self.input.complete()
+    }
+
+    fn restore_partial(&mut self, state: Self::PartialState) {
+        self.input.restore_partial(state);
+    }
+
+    #[inline(always)]
+    fn is_partial_supported() -> bool {
+        I::is_partial_supported()
+    }
+
+    /// Running out of fuel is final, so an exhausted stream is never partial
+    #[inline(always)]
+    fn is_partial(&self) -> bool {
+        !self.exhausted && self.input.is_partial()
+    }
+}
+
+impl<I> Offset for Fueled<I>
+where
+    I: Stream,
+{
+    #[inline(always)]
+    fn offset_from(&self, start: &Self) -> usize {
+        self.offset_from(&start.checkpoint())
+    }
+}
+
+impl<I> Offset<<Fueled<I> as Stream>::Checkpoint> for Fueled<I>
+where
+    I: Stream,
+{
+    #[inline(always)]
+    fn offset_from(&self, other: &<Fueled<I> as Stream>::Checkpoint) -> usize {
+        self.checkpoint().offset_from(other)
+    }
+}
+
+impl<I> AsBytes for Fueled<I>
+where
+    I: AsBytes,
+{
+    #[inline(always)]
+    fn as_bytes(&self) -> &[u8] {
+        if self.exhausted {
+            &[]
+        } else {
+            self.input.as_bytes()
+        }
+    }
+}
+
+impl<I> AsBStr for Fueled<I>
+where
+    I: AsBStr,
+{
+    #[inline(always)]
+    fn as_bstr(&self) -> &[u8] {
+        if self.exhausted {
+            &[]
+        } else {
+            self.input.as_bstr()
+        }
+    }
+}
+
+impl<I, U> Compare<U> for Fueled<I>
+where
+    I: Compare<U>,
+{
+    #[inline(always)]
+    fn compare(&self, other: U) -> CompareResult {
+        if self.exhausted {
+            CompareResult::Error
+        } else {
+            self.input.compare(other)
+        }
+    }
+
+    #[inline(always)]
+    fn compare_partial(&self, other: U) -> CompareResult {
+        if self.exhausted {
+            CompareResult::Error
+        } else {
+            self.input.compare_partial(other)
+        }
+    }
+
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, other: &U, f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        if self.exhausted {
+            None
+        } else {
+            self.input.mismatch_bytes(other, f)
+        }
+    }
+}
+
+impl<I, T> FindSlice<T> for Fueled<I>
+where
+    I: FindSlice<T>,
+{
+    #[inline(always)]
+    fn find_slice(&self, substr: T) -> Option<crate::lib::std::ops::Range<usize>> {
+        if self.exhausted {
+            None
+        } else {
+            self.input.find_slice(substr)
+        }
+    }
+}
+
+impl<I> UpdateSlice for Fueled<I>
+where
+    I: UpdateSlice,
+{
+    #[inline(always)]
+    fn update_slice(mut self, inner: Self::Slice) -> Self {
+        self.input = I::update_slice(self.input, inner);
+        self
+    }
+}
@@ -0,0 +1,251 @@
+use crate::error::Needed;
+
+use super::{
+    AsBStr, Checkpoint, Compare, CompareResult, FindSlice, Offset, SliceLen, Stream,
+    StreamIsPartial, UpdateSlice,
+};
+
+use unicode_segmentation::{GraphemeCursor, GraphemeIndices, UnicodeSegmentation};
+
+/// UTF-8 Stream whose tokens are extended grapheme clusters, what a reader sees as one
+/// character
+///
+/// A `&str` token may be several `char`s, like `e` followed by a combining accent or an emoji
+/// with a skin tone modifier, so [`any`][crate::token::any], [`one_of`][crate::token::one_of],
+/// and [`take_while`][crate::token::take_while] never split one apart.  Offsets are still in
+/// bytes, like for `&str`.
+///
+/// [`Compare`] only matches when it ends on a grapheme boundary, so a literal `"e"` won't match
+/// the start of `"e\u{301}"`.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::stream::Graphemes;
+/// use winnow::token::any;
+/// use winnow::token::take_while;
+///
+/// fn avatar<'i>(input: &mut Graphemes<'i>) -> PResult<(&'i str, &'i str)> {
+///     (any, take_while(1.., |g: &str| g.starts_with(char::is_alphabetic))).parse_next(input)
+/// }
+///
+/// // Thumbs up with a skin tone, then `Zoë` with a combining diaeresis
+/// let input = "\u{1f44d}\u{1f3fd}Zoe\u{308}!";
+/// assert_eq!(
+///     avatar.parse_peek(Graphemes::new(input)).map(|(_, o)| o),
+///     Ok(("\u{1f44d}\u{1f3fd}", "Zoe\u{308}"))
+/// );
+/// ```
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Graphemes<'i> {
+    input: &'i str,
+}
+
+impl<'i> Graphemes<'i> {
+    /// Wrap the text to be parsed
+    #[inline]
+    pub fn new(input: &'i str) -> Self {
+        Self { input }
+    }
+
+    /// Unwrap the text
+    #[inline(always)]
+    pub fn into_inner(self) -> &'i str {
+        self.input
+    }
+
+    #[inline]
+    fn is_boundary(&self, offset: usize) -> bool {
+        GraphemeCursor::new(offset, self.input.len(), true)
+            .is_boundary(self.input, 0)
+            .unwrap_or(false)
+    }
+}
+
+impl<'i> AsRef<str> for Graphemes<'i> {
+    #[inline(always)]
+    fn as_ref(&self) -> &str {
+        self.input
+    }
+}
+
+impl<'i> crate::lib::std::ops::Deref for Graphemes<'i> {
+    type Target = str;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.input
+    }
+}
+
+impl crate::lib::std::fmt::Display for Graphemes<'_> {
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        self.input.fmt(f)
+    }
+}
+
+impl crate::lib::std::fmt::Debug for Graphemes<'_> {
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        self.input.fmt(f)
+    }
+}
+
+impl SliceLen for Graphemes<'_> {
+    #[inline(always)]
+    fn slice_len(&self) -> usize {
+        self.input.len()
+    }
+}
+
+impl<'i> Stream for Graphemes<'i> {
+    type Token = &'i str;
+    type Slice = &'i str;
+
+    type IterOffsets = GraphemeIndices<'i>;
+
+    type Checkpoint = Checkpoint<&'i str, Self>;
+
+    #[inline(always)]
+    fn iter_offsets(&self) -> Self::IterOffsets {
+        self.input.grapheme_indices(true)
+    }
+    #[inline(always)]
+    fn eof_offset(&self) -> usize {
+        self.input.len()
+    }
+
+    #[inline(always)]
+    fn next_token(&mut self) -> Option<Self::Token> {
+        let grapheme = self.input.graphemes(true).next()?;
+        self.input = &self.input[grapheme.len()..];
+        Some(grapheme)
+    }
+
+    #[inline(always)]
+    fn offset_for<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Token) -> bool,
+    {
+        self.iter_offsets()
+            .find(|(_, g)| predicate(g))
+            .map(|(o, _)| o)
+    }
+    #[inline]
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        let mut cnt = 0;
+        for (offset, _) in self.iter_offsets() {
+            if cnt == tokens {
+                return Ok(offset);
+            }
+            cnt += 1;
+        }
+
+        if cnt == tokens {
+            Ok(self.eof_offset())
+        } else {
+            Err(Needed::Unknown)
+        }
+    }
+    #[inline(always)]
+    fn next_slice(&mut self, offset: usize) -> Self::Slice {
+        let (slice, next) = self.input.split_at(offset);
+        self.input = next;
+        slice
+    }
+    #[inline(always)]
+    fn try_next_slice(&mut self, offset: usize) -> Option<Self::Slice> {
+        if self.input.is_char_boundary(offset) && self.is_boundary(offset) {
+            Some(self.next_slice(offset))
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn checkpoint(&self) -> Self::Checkpoint {
+        Checkpoint::<_, Self>::new(self.input)
+    }
+    #[inline(always)]
+    fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+        self.input = checkpoint.inner;
+    }
+
+    #[inline(always)]
+    fn raw(&self) -> &dyn crate::lib::std::fmt::Debug {
+        self
+    }
+}
+
+impl StreamIsPartial for Graphemes<'_> {
+    type PartialState = ();
+
+    fn complete(&mut self) -> Self::PartialState {
+        // Already complete
+    }
+
+    fn restore_partial(&mut self, _state: Self::PartialState) {}
+
+    #[inline(always)]
+    fn is_partial_supported() -> bool {
+        false
+    }
+}
+
+impl Offset for Graphemes<'_> {
+    #[inline(always)]
+    fn offset_from(&self, start: &Self) -> usize {
+        self.input.offset_from(&start.input)
+    }
+}
+
+impl<'i> Offset<<Graphemes<'i> as Stream>::Checkpoint> for Graphemes<'i> {
+    #[inline(always)]
+    fn offset_from(&self, other: &<Graphemes<'i> as Stream>::Checkpoint) -> usize {
+        self.checkpoint().offset_from(other)
+    }
+}
+
+impl AsBStr for Graphemes<'_> {
+    #[inline(always)]
+    fn as_bstr(&self) -> &[u8] {
+        self.input.as_bytes()
+    }
+}
+
+impl<'i, T> Compare<T> for Graphemes<'i>
+where
+    &'i str: Compare<T>,
+{
+    #[inline]
+    fn compare(&self, t: T) -> CompareResult {
+        match self.input.compare(t) {
+            CompareResult::Ok(len) if !self.is_boundary(len) => CompareResult::Error,
+            result => result,
+        }
+    }
+
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, other: &T, f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        self.input.mismatch_bytes(other, f)
+    }
+}
+
+impl<'i, T> FindSlice<T> for Graphemes<'i>
+where
+    &'i str: FindSlice<T>,
+{
+    #[inline(always)]
+    fn find_slice(&self, substr: T) -> Option<crate::lib::std::ops::Range<usize>> {
+        self.input.find_slice(substr)
+    }
+}
+
+impl UpdateSlice for Graphemes<'_> {
+    #[inline(always)]
+    fn update_slice(mut self, inner: Self::Slice) -> Self {
+        self.input = inner;
+        self
+    }
+}
@@ -0,0 +1,142 @@
+use crate::lib::std::collections::HashMap;
+use crate::lib::std::vec::Vec;
+use std::sync::Arc;
+
+/// A string deduplicated by an [`Interner`]
+///
+/// Symbols are only meaningful to the [`Interner`] that created them; see [`Interner::absorb`]
+/// for moving them to another.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// The position of the string within its [`Interner`], in the order they were first interned
+    #[inline(always)]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Deduplicate strings, like identifiers, into cheap [`Symbol`]s
+///
+/// Interning takes `&mut self`, so there are no locks.  To parse records on several threads,
+/// give each its own `Interner` and [`absorb`][Interner::absorb] them into one afterwards.
+/// Lookups take `&self`, so like [`LineIndex`][crate::stream::LineIndex], the result can be
+/// shared between threads by reference or in an [`Arc`].
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::alpha1;
+/// use winnow::combinator::separated;
+/// use winnow::stream::Interner;
+/// use winnow::stream::Symbol;
+///
+/// fn words(record: &str, interner: &mut Interner) -> Vec<Symbol> {
+///     let words: Vec<&str> = separated(1.., alpha1::<_, ContextError>, ' ')
+///         .parse(record)
+///         .unwrap();
+///     words.into_iter().map(|w| interner.intern(w)).collect()
+/// }
+///
+/// let records = ["get set", "set put"];
+/// let parsed = std::thread::scope(|s| {
+///     let threads = records.map(|record| {
+///         s.spawn(move || {
+///             let mut interner = Interner::new();
+///             let symbols = words(record, &mut interner);
+///             (interner, symbols)
+///         })
+///     });
+///     threads.map(|t| t.join().unwrap())
+/// });
+///
+/// let mut interner = Interner::new();
+/// let mut symbols = Vec::new();
+/// for (local, local_symbols) in parsed {
+///     let remap = interner.absorb(local);
+///     symbols.extend(local_symbols.iter().map(|s| remap[s.index()]));
+/// }
+/// assert_eq!(interner.len(), 3);
+/// let words = symbols
+///     .iter()
+///     .map(|s| interner.resolve(*s).unwrap())
+///     .collect::<Vec<_>>();
+/// assert_eq!(words, ["get", "set", "set", "put"]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    symbols: HashMap<Arc<str>, Symbol>,
+    strings: Vec<Arc<str>>,
+}
+
+impl Interner {
+    /// Create an empty interner
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The [`Symbol`] for `string`, adding it if it's new
+    ///
+    /// # Panics
+    ///
+    /// When interning more than `u32::MAX` strings
+    pub fn intern(&mut self, string: &str) -> Symbol {
+        match self.symbols.get(string) {
+            Some(symbol) => *symbol,
+            None => self.insert(string.into()),
+        }
+    }
+
+    /// The [`Symbol`] for `string`, if it was interned
+    #[inline]
+    pub fn get(&self, string: &str) -> Option<Symbol> {
+        self.symbols.get(string).copied()
+    }
+
+    /// The string for `symbol`, if it came from this interner
+    #[inline]
+    pub fn resolve(&self, symbol: Symbol) -> Option<&str> {
+        self.strings.get(symbol.index()).map(|s| &**s)
+    }
+
+    /// The number of distinct strings
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Whether nothing was interned
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Add every string from `other`, returning the new [`Symbol`] for each of `other`'s, by
+    /// [`Symbol::index`]
+    ///
+    /// # Panics
+    ///
+    /// When interning more than `u32::MAX` strings
+    pub fn absorb(&mut self, other: Interner) -> Vec<Symbol> {
+        other
+            .strings
+            .into_iter()
+            .map(|string| match self.symbols.get(&string) {
+                Some(symbol) => *symbol,
+                None => self.insert(string),
+            })
+            .collect()
+    }
+
+    fn insert(&mut self, string: Arc<str>) -> Symbol {
+        let index = u32::try_from(self.strings.len()).expect("at most `u32::MAX` strings");
+        let symbol = Symbol(index);
+        self.strings.push(string.clone());
+        self.symbols.insert(string, symbol);
+        symbol
+    }
+}
@@ -0,0 +1,212 @@
+use crate::error::ErrMode;
+use crate::error::ErrorKind;
+use crate::error::Needed;
+use crate::error::ParserError;
+use crate::lib::std::vec::Vec;
+use crate::stream::Offset;
+use crate::stream::Partial;
+use crate::stream::Stream;
+use crate::stream::StreamIsPartial;
+use crate::Parser;
+
+/// Drive [`Partial`] parsing of the items from an [`Iterator`]
+///
+/// This allows layering grammars, with the output of one parser (e.g. from
+/// [`iterator`][crate::combinator::iterator]) being the tokens for the next, like
+/// frames → messages → commands.
+///
+/// Items are pulled from the iterator as needed and buffered until a value is parsed from them.
+/// When a parser reports [`ErrMode::Incomplete`], more items are pulled and the parser is
+/// re-run from the start of the buffered items.
+/// Once the iterator is exhausted, the input is marked as [complete][StreamIsPartial::complete] so
+/// parsers can finish the last value.
+///
+/// As the buffer is reused between values, parsed outputs and errors cannot borrow from the input.
+/// Use owned outputs and an error type like [`ContextError`][crate::error::ContextError].
+///
+/// See also [Parsing Partial Input][crate::_topic::partial].
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::dec_uint;
+/// use winnow::combinator::iterator;
+/// use winnow::combinator::terminated;
+/// use winnow::stream::IterStream;
+/// use winnow::stream::Partial;
+/// use winnow::token::any;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq)]
+/// enum Event {
+///     Key(u32),
+///     Release,
+/// }
+///
+/// // First layer: text into events
+/// fn event(i: &mut &str) -> PResult<Event> {
+///     terminated(dec_uint, ' ').map(Event::Key).parse_next(i)
+/// }
+///
+/// // Second layer: events into key presses
+/// fn press(i: &mut Partial<&[Event]>) -> PResult<u32> {
+///     let key = any
+///         .verify_map(|e| match e {
+///             Event::Key(k) => Some(k),
+///             Event::Release => None,
+///         })
+///         .parse_next(i)?;
+///     any.verify(|e| *e == Event::Release).parse_next(i)?;
+///     Ok(key)
+/// }
+///
+/// let mut events = iterator("1 2 3 ", event);
+/// let events = events.flat_map(|e| [e, Event::Release]);
+/// let mut presses = IterStream::new(events);
+/// let mut keys = Vec::new();
+/// while let Some(key) = presses.parse_next(&mut press).unwrap() {
+///     keys.push(key);
+/// }
+/// assert_eq!(keys, [1, 2, 3]);
+/// ```
+#[derive(Debug)]
+pub struct IterStream<It: Iterator> {
+    iter: It,
+    buffer: Vec<It::Item>,
+    /// Items released from the front of `buffer`
+    released: usize,
+    start: usize,
+    eof: bool,
+}
+
+impl<It> IterStream<It>
+where
+    It: Iterator,
+    It::Item: Clone + crate::lib::std::fmt::Debug,
+{
+    /// Buffer the items from `iter`
+    pub fn new(iter: It) -> Self {
+        Self {
+            iter,
+            buffer: Vec::new(),
+            released: 0,
+            start: 0,
+            eof: false,
+        }
+    }
+
+    /// Parse the next value, pulling more items as needed
+    ///
+    /// Returns `Ok(None)` once all items have been pulled and parsed.
+    ///
+    /// The parser must consume input when it succeeds, like with
+    /// [`repeat`][crate::combinator::repeat], otherwise this returns an
+    /// [`ErrorKind::InfiniteLoop`] error.
+    pub fn parse_next<O, E, P>(&mut self, parser: &mut P) -> Result<Option<O>, E>
+    where
+        P: for<'i> Parser<Partial<&'i [It::Item]>, O, E>,
+        E: for<'i> ParserError<Partial<&'i [It::Item]>>,
+    {
+        loop {
+            if self.start == self.buffer.len() {
+                if self.eof {
+                    return Ok(None);
+                }
+                self.fill(1);
+                continue;
+            }
+
+            let mut input = Partial::new(&self.buffer[self.start..]);
+            if self.eof {
+                let _ = input.complete();
+            }
+            let start = input.checkpoint();
+            match parser.parse_next(&mut input) {
+                Ok(output) => {
+                    // infinite loop check: the parser must always consume
+                    let consumed = input.offset_from(&start);
+                    if consumed == 0 {
+                        return Err(E::from_error_kind(&input, ErrorKind::InfiniteLoop));
+                    }
+                    self.start += consumed;
+                    return Ok(Some(output));
+                }
+                Err(ErrMode::Backtrack(e)) | Err(ErrMode::Cut(e)) => {
+                    return Err(e);
+                }
+                Err(ErrMode::Incomplete(_)) if self.eof => {
+                    return Err(E::from_error_kind(&input, ErrorKind::Eof));
+                }
+                Err(ErrMode::Incomplete(Needed::Size(size))) => {
+                    self.fill(size.get());
+                }
+                Err(ErrMode::Incomplete(Needed::Unknown)) => {
+                    self.fill(1);
+                }
+            }
+        }
+    }
+
+    /// Pull up to `additional` more items
+    fn fill(&mut self, additional: usize) {
+        self.compact();
+
+        for _ in 0..additional {
+            match self.iter.next() {
+                Some(item) => self.buffer.push(item),
+                None => {
+                    self.eof = true;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl<It: Iterator> IterStream<It> {
+    /// The offset into the source of the oldest item the parser can still reach
+    ///
+    /// Each call to `parse_next` starts from here and any backtracking stays at or after it, so
+    /// everything before has been parsed for good.  Copies of the source, like those kept for
+    /// error reporting, can be safely truncated up to this offset during long streaming sessions.
+    #[inline(always)]
+    pub fn watermark(&self) -> usize {
+        self.released + self.start
+    }
+
+    /// Release the parsed items before the [watermark][Self::watermark] from the buffer
+    ///
+    /// This otherwise happens lazily, when more items are needed.
+    pub fn compact(&mut self) {
+        if self.start != 0 {
+            self.buffer.drain(..self.start);
+            self.released += self.start;
+            self.start = 0;
+        }
+    }
+
+    /// The items pulled but not yet parsed
+    #[inline(always)]
+    pub fn buffer(&self) -> &[It::Item] {
+        &self.buffer[self.start..]
+    }
+
+    /// Whether the iterator has been exhausted
+    #[inline(always)]
+    pub fn is_eof(&self) -> bool {
+        self.eof
+    }
+
+    /// Access the underlying iterator
+    #[inline(always)]
+    pub fn get_ref(&self) -> &It {
+        &self.iter
+    }
+
+    /// Extract the underlying iterator, discarding any unparsed items
+    #[inline(always)]
+    pub fn into_inner(self) -> It {
+        self.iter
+    }
+}
@@ -0,0 +1,566 @@
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+use crate::error::ErrMode;
+use crate::error::Needed;
+#[cfg(feature = "alloc")]
+use crate::lib::std::ops::Range;
+#[cfg(feature = "alloc")]
+use crate::lib::std::vec::Vec;
+use crate::stream::AsBStr;
+use crate::stream::AsBytes;
+use crate::stream::AsChar;
+use crate::stream::Checkpoint;
+use crate::stream::Compare;
+use crate::stream::CompareResult;
+use crate::stream::ContainsToken;
+use crate::stream::FindSlice;
+use crate::stream::Location;
+use crate::stream::Offset;
+use crate::stream::Partial;
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+use crate::stream::Recover;
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+use crate::stream::Recoverable;
+use crate::stream::SliceLen;
+use crate::stream::Stateful;
+use crate::stream::Stream;
+use crate::stream::StreamIsPartial;
+use crate::stream::UpdateSlice;
+
+/// Allow collecting the line and column of parsed tokens
+///
+/// Like [`Located`][crate::stream::Located], this reports byte offsets through [`Location`] but it
+/// also tracks the [`Position`] as tokens are consumed, for reporting through [`LineLocation`].
+/// This avoids re-scanning the input to convert offsets to lines and columns at the cost of
+/// looking at every consumed token.
+///
+/// See [`Parser::line_span`][crate::Parser::line_span] and
+/// [`Parser::with_line_span`][crate::Parser::with_line_span] for more details
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// use winnow::ascii::alpha1;
+/// use winnow::ascii::multispace0;
+/// use winnow::combinator::preceded;
+/// use winnow::combinator::repeat;
+/// use winnow::stream::LineLocated;
+/// use winnow::stream::Position;
+///
+/// fn words(i: &mut LineLocated<&str>) -> PResult<Vec<std::ops::Range<Position>>> {
+///     repeat(1.., preceded(multispace0, alpha1.line_span())).parse_next(i)
+/// }
+///
+/// let spans = words.parse(LineLocated::new("ab\n  cd")).unwrap();
+/// assert_eq!(
+///     spans,
+///     [
+///         Position::new(1, 1)..Position::new(1, 3),
+///         Position::new(2, 3)..Position::new(2, 5),
+///     ]
+/// );
+/// ```
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LineLocated<I> {
+    initial: I,
+    input: I,
+    position: Position,
+}
+
+impl<I> LineLocated<I>
+where
+    I: Clone + Offset,
+{
+    /// Wrap another Stream with line and column tracking
+    pub fn new(input: I) -> Self {
+        let initial = input.clone();
+        Self {
+            initial,
+            input,
+            position: Position::default(),
+        }
+    }
+}
+
+impl<I> LineLocated<I>
+where
+    I: Clone + Stream + Offset,
+{
+    /// Reset the stream to the start
+    pub fn reset_to_start(&mut self) {
+        let start = self.initial.checkpoint();
+        self.input.reset(&start);
+        self.position = Position::default();
+    }
+}
+
+/// A line and column within the input, see [`LineLocation`]
+///
+/// Both are 1-based. Columns count [`Stream::Token`]s, e.g. `char`s for `&str` and bytes for
+/// `&[u8]`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column number within the line
+    pub column: usize,
+}
+
+impl Position {
+    /// Create a position for `line` and `column`
+    #[inline(always)]
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+
+    #[inline(always)]
+    fn advance(&mut self, c: char) {
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self::new(1, 1)
+    }
+}
+
+impl crate::lib::std::fmt::Display for Position {
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Convert offsets into the input to [`Position`]s after parsing
+///
+/// Unlike [`LineLocated`], this doesn't slow down parsing.  Instead, the start of every line is
+/// found up front so each lookup is a binary search plus counting the tokens before the offset
+/// within its line.
+///
+/// Lookups take `&self`, so once built the index is immutable and can be shared between threads,
+/// e.g. by reference or in an [`Arc`][std::sync::Arc], without locks.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// use winnow::stream::LineIndex;
+/// use winnow::stream::Position;
+///
+/// let source = "let a = 1;\nlet bé = 2;\n";
+/// let index = LineIndex::new(source);
+/// assert_eq!(index.position(4), Position::new(1, 5));
+/// assert_eq!(index.position(19), Position::new(2, 8));
+/// assert_eq!(
+///     index.span(15..18),
+///     Position::new(2, 5)..Position::new(2, 7)
+/// );
+///
+/// std::thread::scope(|s| {
+///     let index = &index;
+///     let lines = (0..2).map(|i| s.spawn(move || index.position(i * 11).line));
+///     let lines = lines.map(|t| t.join().unwrap()).collect::<Vec<_>>();
+///     assert_eq!(lines, [1, 2]);
+/// });
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineIndex<I> {
+    source: I,
+    line_starts: Vec<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl<I> LineIndex<I>
+where
+    I: Stream + AsBStr + Clone,
+{
+    /// Find the start of every line in `source`
+    pub fn new(source: I) -> Self {
+        let line_starts = crate::lib::std::iter::once(0)
+            .chain(
+                source
+                    .as_bstr()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, b)| **b == b'\n')
+                    .map(|(i, _)| i + 1),
+            )
+            .collect();
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    /// The input being indexed
+    #[inline(always)]
+    pub fn source(&self) -> &I {
+        &self.source
+    }
+
+    /// The number of lines, counting the one after a trailing newline
+    #[inline(always)]
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The [`Position`] of `offset`, as reported by [`Location`] or
+    /// [`ParserError`][crate::error::ParserError]s
+    ///
+    /// Offsets past the end are clamped to the end of the input.
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.source.eof_offset());
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let line_start = self.line_starts[line - 1];
+        let mut input = self.source.clone();
+        let _ = input.next_slice(line_start);
+        let column = input
+            .iter_offsets()
+            .take_while(|(o, _)| *o < offset - line_start)
+            .count();
+        Position::new(line, column + 1)
+    }
+
+    /// The [`Position`]s of the start and end of `span`, like from
+    /// [`Parser::span`][crate::Parser::span]
+    pub fn span(&self, span: Range<usize>) -> Range<Position> {
+        self.position(span.start)..self.position(span.end)
+    }
+}
+
+/// Line and column the input has advanced to since start of parsing
+///
+/// See [`LineLocated`] for adding line tracking to your [`Stream`]
+pub trait LineLocation {
+    /// Line and column the input has advanced to since start of parsing
+    fn line_location(&self) -> Position;
+}
+
+impl<I> LineLocation for LineLocated<I> {
+    #[inline(always)]
+    fn line_location(&self) -> Position {
+        self.position
+    }
+}
+
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+impl<I, E> LineLocation for Recoverable<I, E>
+where
+    I: LineLocation,
+    I: Stream,
+{
+    #[inline(always)]
+    fn line_location(&self) -> Position {
+        self.input.line_location()
+    }
+}
+
+impl<I, S> LineLocation for Stateful<I, S>
+where
+    I: LineLocation,
+{
+    #[inline(always)]
+    fn line_location(&self) -> Position {
+        self.input.line_location()
+    }
+}
+
+impl<I> LineLocation for Partial<I>
+where
+    I: LineLocation,
+{
+    #[inline(always)]
+    fn line_location(&self) -> Position {
+        self.input.line_location()
+    }
+}
+
+/// Saved state of a [`LineLocated`], see [`Stream::checkpoint`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LineCheckpoint<C> {
+    inner: C,
+    position: Position,
+}
+
+impl<C: Offset> Offset for LineCheckpoint<C> {
+    #[inline(always)]
+    fn offset_from(&self, start: &Self) -> usize {
+        self.inner.offset_from(&start.inner)
+    }
+}
+
+impl<I> AsRef<I> for LineLocated<I> {
+    #[inline(always)]
+    fn as_ref(&self) -> &I {
+        &self.input
+    }
+}
+
+impl<I> crate::lib::std::ops::Deref for LineLocated<I> {
+    type Target = I;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.input
+    }
+}
+
+impl<I: crate::lib::std::fmt::Display> crate::lib::std::fmt::Display for LineLocated<I> {
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        self.input.fmt(f)
+    }
+}
+
+impl<I: crate::lib::std::fmt::Debug> crate::lib::std::fmt::Debug for LineLocated<I> {
+    #[inline]
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        self.input.fmt(f)
+    }
+}
+
+impl<I> SliceLen for LineLocated<I>
+where
+    I: SliceLen,
+{
+    #[inline(always)]
+    fn slice_len(&self) -> usize {
+        self.input.slice_len()
+    }
+}
+
+impl<I> Stream for LineLocated<I>
+where
+    I: Stream,
+    I::Token: AsChar + Clone,
+{
+    type Token = <I as Stream>::Token;
+    type Slice = <I as Stream>::Slice;
+
+    type IterOffsets = <I as Stream>::IterOffsets;
+
+    type Checkpoint = Checkpoint<LineCheckpoint<I::Checkpoint>, Self>;
+
+    #[inline(always)]
+    fn iter_offsets(&self) -> Self::IterOffsets {
+        self.input.iter_offsets()
+    }
+    #[inline(always)]
+    fn eof_offset(&self) -> usize {
+        self.input.eof_offset()
+    }
+
+    #[inline(always)]
+    fn next_token(&mut self) -> Option<Self::Token> {
+        let token = self.input.next_token()?;
+        self.position.advance(token.clone().as_char());
+        Some(token)
+    }
+
+    #[inline(always)]
+    fn offset_for<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Token) -> bool,
+    {
+        self.input.offset_for(predicate)
+    }
+    #[inline(always)]
+    fn offset_for_set<Set>(&self, set: &Set) -> Option<usize>
+    where
+        Set: ContainsToken<Self::Token>,
+    {
+        self.input.offset_for_set(set)
+    }
+    #[inline(always)]
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        self.input.offset_at(tokens)
+    }
+    #[inline(always)]
+    fn next_slice(&mut self, offset: usize) -> Self::Slice {
+        for (_, token) in self.input.iter_offsets().take_while(|(o, _)| *o < offset) {
+            self.position.advance(token.as_char());
+        }
+        self.input.next_slice(offset)
+    }
+    #[inline(always)]
+    fn try_next_slice(&mut self, offset: usize) -> Option<Self::Slice> {
+        let mut position = self.position;
+        for (_, token) in self.input.iter_offsets().take_while(|(o, _)| *o < offset) {
+            position.advance(token.as_char());
+        }
+        let slice = self.input.try_next_slice(offset)?;
+        self.position = position;
+        Some(slice)
+    }
+
+    #[inline(always)]
+    fn checkpoint(&self) -> Self::Checkpoint {
+        Checkpoint::<_, Self>::new(LineCheckpoint {
+            inner: self.input.checkpoint(),
+            position: self.position,
+        })
+    }
+    #[inline(always)]
+    fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+        self.input.reset(&checkpoint.inner.inner);
+        self.position = checkpoint.inner.position;
+    }
+
+    #[inline(always)]
+    fn raw(&self) -> &dyn crate::lib::std::fmt::Debug {
+        &self.input
+    }
+}
+
+impl<I> Location for LineLocated<I>
+where
+    I: Clone + Offset,
+{
+    #[inline(always)]
+    fn location(&self) -> usize {
+        self.input.offset_from(&self.initial)
+    }
+}
+
+#[cfg(feature = "unstable-recover")]
+#[cfg(feature = "std")]
+impl<I, E> Recover<E> for LineLocated<I>
+where
+    I: Recover<E>,
+    I: Stream,
+    I::Token: AsChar + Clone,
+{
+    #[inline(always)]
+    fn record_err(
+        &mut self,
+        _token_start: &Self::Checkpoint,
+        _err_start: &Self::Checkpoint,
+        err: ErrMode<E>,
+    ) -> Result<(), ErrMode<E>> {
+        Err(err)
+    }
+
+    /// Report whether the [`Stream`] can save off errors for recovery
+    #[inline(always)]
+    fn is_recovery_supported() -> bool {
+        false
+    }
+}
+
+impl<I> StreamIsPartial for LineLocated<I>
+where
+    I: StreamIsPartial,
+{
+    type PartialState = I::PartialState;
+
+    fn complete(&mut self) -> Self::PartialState {
+        self.input.complete()
+    }
+
+    fn restore_partial(&mut self, state: Self::PartialState) {
+        self.input.restore_partial(state);
+    }
+
+    #[inline(always)]
+    fn is_partial_supported() -> bool {
+        I::is_partial_supported()
+    }
+
+    #[inline(always)]
+    fn is_partial(&self) -> bool {
+        self.input.is_partial()
+    }
+}
+
+impl<I> Offset for LineLocated<I>
+where
+    I: Stream,
+    I::Token: AsChar + Clone,
+{
+    #[inline(always)]
+    fn offset_from(&self, other: &Self) -> usize {
+        self.offset_from(&other.checkpoint())
+    }
+}
+
+impl<I> Offset<<LineLocated<I> as Stream>::Checkpoint> for LineLocated<I>
+where
+    I: Stream,
+    I::Token: AsChar + Clone,
+{
+    #[inline(always)]
+    fn offset_from(&self, other: &<LineLocated<I> as Stream>::Checkpoint) -> usize {
+        self.checkpoint().offset_from(other)
+    }
+}
+
+impl<I> AsBytes for LineLocated<I>
+where
+    I: AsBytes,
+{
+    #[inline(always)]
+    fn as_bytes(&self) -> &[u8] {
+        self.input.as_bytes()
+    }
+}
+
+impl<I> AsBStr for LineLocated<I>
+where
+    I: AsBStr,
+{
+    #[inline(always)]
+    fn as_bstr(&self) -> &[u8] {
+        self.input.as_bstr()
+    }
+}
+
+impl<I, U> Compare<U> for LineLocated<I>
+where
+    I: Compare<U>,
+{
+    #[inline(always)]
+    fn compare(&self, other: U) -> CompareResult {
+        self.input.compare(other)
+    }
+
+    #[inline(always)]
+    fn compare_partial(&self, other: U) -> CompareResult {
+        self.input.compare_partial(other)
+    }
+
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, other: &U, f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        self.input.mismatch_bytes(other, f)
+    }
+}
+
+impl<I, T> FindSlice<T> for LineLocated<I>
+where
+    I: FindSlice<T>,
+{
+    #[inline(always)]
+    fn find_slice(&self, substr: T) -> Option<crate::lib::std::ops::Range<usize>> {
+        self.input.find_slice(substr)
+    }
+}
+
+impl<I> UpdateSlice for LineLocated<I>
+where
+    I: UpdateSlice,
+    I::Token: AsChar + Clone,
+{
+    #[inline(always)]
+    fn update_slice(mut self, inner: Self::Slice) -> Self {
+        self.input = I::update_slice(self.input, inner);
+        self
+    }
+}
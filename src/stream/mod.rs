@@ -3,30 +3,38 @@
 //! Stream types include:
 //! - `&[u8]` and [`Bytes`] for binary data
 //! - `&str` (aliased as [`Str`]) and [`BStr`] for UTF-8 data
+//!   - `Graphemes` for UTF-8 data tokenized into grapheme clusters, with the `graphemes` feature
 //! - [`Located`] can track the location within the original buffer to report
 //!   [spans][crate::Parser::with_span]
+//!   - [`LineLocated`] can also track the line and column to report
+//!     [line spans][crate::Parser::with_line_span]
+//!   - [`LineIndex`] converts offsets to lines and columns after parsing instead
+//!   - [`Interner`] deduplicates parsed strings into [`Symbol`]s
 //! - [`Stateful`] to thread global state through your parsers
+//! - [`TokenSlice`] for parsing the output of a lexer
 //! - [`Partial`] can mark an input as partial buffer that is being streamed into
+//!   - [`ReadStream`] drives [`Partial`] parsing of a [`std::io::Read`] source
+//!     and can decompress gzip or zstd on the fly with the `gzip` and `zstd` features
 //! - [Custom stream types][crate::_topic::stream]
 
+#[cfg(feature = "std")]
 use core::hash::BuildHasher;
 use core::num::NonZeroUsize;
 
 use crate::ascii::Caseless as AsciiCaseless;
+use crate::error::ErrMode;
+use crate::error::ErrorKind;
 #[cfg(feature = "unstable-recover")]
 #[cfg(feature = "std")]
 use crate::error::FromRecoverableError;
 use crate::error::Needed;
+use crate::error::ParserError;
 use crate::lib::std::iter::{Cloned, Enumerate};
 use crate::lib::std::slice::Iter;
 use crate::lib::std::str::from_utf8;
 use crate::lib::std::str::CharIndices;
 use crate::lib::std::str::FromStr;
 
-#[allow(unused_imports)]
-#[cfg(any(feature = "unstable-doc", feature = "unstable-recover"))]
-use crate::error::ErrMode;
-
 #[cfg(feature = "alloc")]
 use crate::lib::std::collections::BTreeMap;
 #[cfg(feature = "alloc")]
@@ -40,9 +48,48 @@ use crate::lib::std::string::String;
 #[cfg(feature = "alloc")]
 use crate::lib::std::vec::Vec;
 
+mod folded;
+mod fuel;
+#[cfg(feature = "graphemes")]
+mod graphemes;
 mod impls;
+#[cfg(feature = "std")]
+mod interner;
+#[cfg(feature = "alloc")]
+mod iter;
+mod line;
+#[cfg(feature = "std")]
+mod read;
+#[cfg(feature = "alloc")]
+mod span_map;
 #[cfg(test)]
 mod tests;
+mod token;
+
+pub use folded::Folded;
+pub use fuel::Fueled;
+#[cfg(feature = "graphemes")]
+pub use graphemes::Graphemes;
+#[cfg(feature = "std")]
+pub use interner::Interner;
+#[cfg(feature = "std")]
+pub use interner::Symbol;
+#[cfg(feature = "alloc")]
+pub use iter::IterStream;
+pub use line::LineCheckpoint;
+#[cfg(feature = "alloc")]
+pub use line::LineIndex;
+pub use line::LineLocated;
+pub use line::LineLocation;
+pub use line::Position;
+#[cfg(feature = "std")]
+pub use read::ReadStream;
+#[cfg(feature = "std")]
+pub use read::ReadStreamError;
+#[cfg(feature = "alloc")]
+pub use span_map::SpanMap;
+pub use token::TokenSlice;
+pub use token::TokenSpan;
 
 /// UTF-8 Stream
 pub type Str<'i> = &'i str;
@@ -102,6 +149,7 @@ impl BStr {
 /// Converting byte offsets to line or column numbers is left up to the user, as computing column
 /// numbers requires domain knowledge (are columns byte-based, codepoint-based, or grapheme-based?)
 /// and O(n) iteration over the input to determine codepoint and line boundaries.
+/// For token-based columns, see [`LineLocated`].
 ///
 /// [The `line-span` crate](https://docs.rs/line-span/latest/line_span/) can help with converting
 /// byte offsets to line numbers.
@@ -137,6 +185,7 @@ where
     ///
     /// This is useful for formats that encode a graph with addresses relative to the start of the
     /// input.
+    /// To jump to other addresses, see [`SeekableStream`].
     #[doc(alias = "fseek")]
     pub fn reset_to_start(&mut self) {
         let start = self.initial.checkpoint();
@@ -663,6 +712,15 @@ pub trait Stream: Offset<<Self as Stream>::Checkpoint> + crate::lib::std::fmt::D
     fn offset_for<P>(&self, predicate: P) -> Option<usize>
     where
         P: Fn(Self::Token) -> bool;
+    /// Finds the offset of the next token in `set`, using `memchr` when possible
+    #[doc(hidden)]
+    #[inline(always)]
+    fn offset_for_set<Set>(&self, set: &Set) -> Option<usize>
+    where
+        Set: ContainsToken<Self::Token>,
+    {
+        self.offset_for(|t| set.contains_token(t))
+    }
     /// Get the offset for the number of `tokens` into the stream
     ///
     /// This means "0 tokens" will return `0` offset
@@ -689,6 +747,20 @@ pub trait Stream: Offset<<Self as Stream>::Checkpoint> + crate::lib::std::fmt::D
     ///   sequence boundaries.
     ///
     fn next_slice(&mut self, offset: usize) -> Self::Slice;
+    /// Split off a slice of tokens from the input, if `offset` is valid
+    ///
+    /// Unlike [`Stream::next_slice`], this will not panic, returning `None` and leaving the stream
+    /// unchanged if `offset` is out of bounds or does not uphold the invariants of the stream.
+    /// Prefer this when `offset` is derived from the input, like a length field in a
+    /// [`Parser::verify_map`][crate::Parser::verify_map].
+    #[inline(always)]
+    fn try_next_slice(&mut self, offset: usize) -> Option<Self::Slice> {
+        if offset <= self.eof_offset() {
+            Some(self.next_slice(offset))
+        } else {
+            None
+        }
+    }
     /// Split off a slice of tokens from the input
     #[inline(always)]
     fn peek_slice(&self, offset: usize) -> (Self, Self::Slice)
@@ -764,6 +836,15 @@ where
         self.iter().position(|b| predicate(b.clone()))
     }
     #[inline(always)]
+    fn offset_for_set<Set>(&self, set: &Set) -> Option<usize>
+    where
+        Set: ContainsToken<Self::Token>,
+    {
+        set.needles()
+            .and_then(|needles| set.find_in_slice(self, needles))
+            .unwrap_or_else(|| self.offset_for(|t| set.contains_token(t)))
+    }
+    #[inline(always)]
     fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
         if let Some(needed) = tokens.checked_sub(self.len()).and_then(NonZeroUsize::new) {
             Err(Needed::Size(needed))
@@ -830,6 +911,17 @@ impl<'i> Stream for &'i str {
         }
         None
     }
+    #[inline(always)]
+    fn offset_for_set<Set>(&self, set: &Set) -> Option<usize>
+    where
+        Set: ContainsToken<Self::Token>,
+    {
+        // `char` needles are always ASCII, so can't match in the middle of a UTF-8 sequence
+        match set.needles() {
+            Some(needles) => needles.find(self.as_bytes()),
+            None => self.offset_for(|t| set.contains_token(t)),
+        }
+    }
     #[inline]
     fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
         let mut cnt = 0;
@@ -852,6 +944,14 @@ impl<'i> Stream for &'i str {
         *self = next;
         slice
     }
+    #[inline(always)]
+    fn try_next_slice(&mut self, offset: usize) -> Option<Self::Slice> {
+        if self.is_char_boundary(offset) {
+            Some(self.next_slice(offset))
+        } else {
+            None
+        }
+    }
 
     #[inline(always)]
     fn checkpoint(&self) -> Self::Checkpoint {
@@ -904,6 +1004,14 @@ impl<'i> Stream for &'i Bytes {
         self.iter().position(|b| predicate(*b))
     }
     #[inline(always)]
+    fn offset_for_set<Set>(&self, set: &Set) -> Option<usize>
+    where
+        Set: ContainsToken<Self::Token>,
+    {
+        let bytes: &[u8] = &self.0;
+        bytes.offset_for_set(set)
+    }
+    #[inline(always)]
     fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
         if let Some(needed) = tokens.checked_sub(self.len()).and_then(NonZeroUsize::new) {
             Err(Needed::Size(needed))
@@ -969,6 +1077,14 @@ impl<'i> Stream for &'i BStr {
         self.iter().position(|b| predicate(*b))
     }
     #[inline(always)]
+    fn offset_for_set<Set>(&self, set: &Set) -> Option<usize>
+    where
+        Set: ContainsToken<Self::Token>,
+    {
+        let bytes: &[u8] = &self.0;
+        bytes.offset_for_set(set)
+    }
+    #[inline(always)]
     fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
         if let Some(needed) = tokens.checked_sub(self.len()).and_then(NonZeroUsize::new) {
             Err(Needed::Size(needed))
@@ -1052,9 +1168,13 @@ where
     }
     #[inline(always)]
     fn next_slice(&mut self, offset: usize) -> Self::Slice {
-        let byte_offset = (offset + self.1) / 8;
-        let end_offset = (offset + self.1) % 8;
-        let s = self.0.next_slice(byte_offset);
+        let end = offset + self.1;
+        let byte_offset = end / 8;
+        let end_offset = end % 8;
+        // Include a partially read last byte, so the slice covers every bit that was read
+        let partial = offset != 0 && end_offset != 0;
+        let (_, s) = self.0.peek_slice(byte_offset + usize::from(partial));
+        let _ = self.0.next_slice(byte_offset);
         let start_offset = self.1;
         self.1 = end_offset;
         (s, start_offset, end_offset)
@@ -1151,6 +1271,13 @@ impl<I: Stream> Stream for Located<I> {
         self.input.offset_for(predicate)
     }
     #[inline(always)]
+    fn offset_for_set<Set>(&self, set: &Set) -> Option<usize>
+    where
+        Set: ContainsToken<Self::Token>,
+    {
+        self.input.offset_for_set(set)
+    }
+    #[inline(always)]
     fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
         self.input.offset_at(tokens)
     }
@@ -1158,6 +1285,10 @@ impl<I: Stream> Stream for Located<I> {
     fn next_slice(&mut self, offset: usize) -> Self::Slice {
         self.input.next_slice(offset)
     }
+    #[inline(always)]
+    fn try_next_slice(&mut self, offset: usize) -> Option<Self::Slice> {
+        self.input.try_next_slice(offset)
+    }
 
     #[inline(always)]
     fn checkpoint(&self) -> Self::Checkpoint {
@@ -1209,6 +1340,13 @@ where
         self.input.offset_for(predicate)
     }
     #[inline(always)]
+    fn offset_for_set<Set>(&self, set: &Set) -> Option<usize>
+    where
+        Set: ContainsToken<Self::Token>,
+    {
+        self.input.offset_for_set(set)
+    }
+    #[inline(always)]
     fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
         self.input.offset_at(tokens)
     }
@@ -1216,6 +1354,10 @@ where
     fn next_slice(&mut self, offset: usize) -> Self::Slice {
         self.input.next_slice(offset)
     }
+    #[inline(always)]
+    fn try_next_slice(&mut self, offset: usize) -> Option<Self::Slice> {
+        self.input.try_next_slice(offset)
+    }
 
     #[inline(always)]
     fn checkpoint(&self) -> Self::Checkpoint {
@@ -1262,6 +1404,13 @@ impl<I: Stream, S: crate::lib::std::fmt::Debug> Stream for Stateful<I, S> {
         self.input.offset_for(predicate)
     }
     #[inline(always)]
+    fn offset_for_set<Set>(&self, set: &Set) -> Option<usize>
+    where
+        Set: ContainsToken<Self::Token>,
+    {
+        self.input.offset_for_set(set)
+    }
+    #[inline(always)]
     fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
         self.input.offset_at(tokens)
     }
@@ -1269,6 +1418,10 @@ impl<I: Stream, S: crate::lib::std::fmt::Debug> Stream for Stateful<I, S> {
     fn next_slice(&mut self, offset: usize) -> Self::Slice {
         self.input.next_slice(offset)
     }
+    #[inline(always)]
+    fn try_next_slice(&mut self, offset: usize) -> Option<Self::Slice> {
+        self.input.try_next_slice(offset)
+    }
 
     #[inline(always)]
     fn checkpoint(&self) -> Self::Checkpoint {
@@ -1315,6 +1468,13 @@ impl<I: Stream> Stream for Partial<I> {
         self.input.offset_for(predicate)
     }
     #[inline(always)]
+    fn offset_for_set<Set>(&self, set: &Set) -> Option<usize>
+    where
+        Set: ContainsToken<Self::Token>,
+    {
+        self.input.offset_for_set(set)
+    }
+    #[inline(always)]
     fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
         self.input.offset_at(tokens)
     }
@@ -1322,6 +1482,10 @@ impl<I: Stream> Stream for Partial<I> {
     fn next_slice(&mut self, offset: usize) -> Self::Slice {
         self.input.next_slice(offset)
     }
+    #[inline(always)]
+    fn try_next_slice(&mut self, offset: usize) -> Option<Self::Slice> {
+        self.input.try_next_slice(offset)
+    }
 
     #[inline(always)]
     fn checkpoint(&self) -> Self::Checkpoint {
@@ -1389,6 +1553,89 @@ where
     }
 }
 
+/// Fork the stream at an absolute offset from the start of parsing
+///
+/// This is useful for formats like ZIP, ELF, and PDF whose structures reference absolute
+/// offsets within the file.
+///
+/// See [`Located`] for tracking the start of your [`Stream`]
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::{ContextError, ErrMode, ErrorKind, ParserError};
+/// use winnow::binary::be_u8;
+/// use winnow::stream::Located;
+/// use winnow::stream::SeekableStream;
+/// use winnow::token::take;
+///
+/// fn name<'i>(i: &mut Located<&'i [u8]>) -> PResult<&'i [u8]> {
+///     let offset = be_u8.parse_next(i)?;
+///     let mut target = i
+///         .seek_to(offset as usize)
+///         .ok_or_else(|| ErrMode::from_error_kind(i, ErrorKind::Eof))?;
+///     take(3usize).parse_next(&mut target)
+/// }
+///
+/// let mut input = Located::new(&b"\x03\x06\x0aabcdef"[..]);
+/// assert_eq!(name.parse_next(&mut input), Ok(&b"abc"[..]));
+/// assert_eq!(name.parse_next(&mut input), Ok(&b"def"[..]));
+/// assert!(name.parse_next(&mut input).is_err());
+/// ```
+#[doc(alias = "fseek")]
+pub trait SeekableStream: Stream + Sized {
+    /// Create a stream starting `offset` bytes from the start of parsing
+    ///
+    /// Returns `None` if `offset` is past the end of the input.
+    fn seek_to(&self, offset: usize) -> Option<Self>;
+}
+
+impl<I> SeekableStream for Located<I>
+where
+    I: Clone + Stream + Offset + AsBytes,
+{
+    #[inline]
+    fn seek_to(&self, offset: usize) -> Option<Self> {
+        if self.initial.eof_offset() < offset {
+            return None;
+        }
+        let mut input = self.initial.clone();
+        let _ = input.next_slice(offset);
+        Some(Self {
+            initial: self.initial.clone(),
+            input,
+        })
+    }
+}
+
+impl<I, S> SeekableStream for Stateful<I, S>
+where
+    I: SeekableStream,
+    S: Clone + crate::lib::std::fmt::Debug,
+{
+    #[inline]
+    fn seek_to(&self, offset: usize) -> Option<Self> {
+        Some(Self {
+            input: self.input.seek_to(offset)?,
+            state: self.state.clone(),
+        })
+    }
+}
+
+impl<I> SeekableStream for Partial<I>
+where
+    I: SeekableStream,
+{
+    #[inline]
+    fn seek_to(&self, offset: usize) -> Option<Self> {
+        Some(Self {
+            input: self.input.seek_to(offset)?,
+            partial: self.partial,
+        })
+    }
+}
+
 /// Capture top-level errors in the middle of parsing so parsing can resume
 ///
 /// See [`Recoverable`] for adding error recovery tracking to your [`Stream`]
@@ -2122,6 +2369,24 @@ impl<'a> AsBStr for &'a str {
     }
 }
 
+/// For [`ParseError::into_owned`][crate::error::ParseError::into_owned]
+#[cfg(feature = "alloc")]
+impl AsBStr for Vec<u8> {
+    #[inline(always)]
+    fn as_bstr(&self) -> &[u8] {
+        self
+    }
+}
+
+/// For [`ParseError::into_owned`][crate::error::ParseError::into_owned]
+#[cfg(feature = "alloc")]
+impl AsBStr for String {
+    #[inline(always)]
+    fn as_bstr(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
 impl<I> AsBStr for Located<I>
 where
     I: AsBStr,
@@ -2184,6 +2449,26 @@ pub enum CompareResult {
 pub trait Compare<T> {
     /// Compares self to another value for equality
     fn compare(&self, t: T) -> CompareResult;
+
+    /// Like [`Compare::compare`], but more input may follow
+    ///
+    /// Return [`CompareResult::Incomplete`] for a match that more input could still change, like
+    /// one that a combining mark could extend.  [`Partial`] calls this while it is partial.
+    #[inline(always)]
+    fn compare_partial(&self, t: T) -> CompareResult {
+        self.compare(t)
+    }
+
+    /// Describe a failed [`Compare::compare`] byte-by-byte
+    ///
+    /// For byte streams and literals, this calls `f` with the bytes of `t` and the bytes of `self`
+    /// it was compared against, truncated to the length of `t`.  This is used by
+    /// [`literal`][crate::token::literal] to report the mismatch, see
+    /// [`ParserError::from_literal_mismatch`][crate::error::ParserError::from_literal_mismatch].
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, _t: &T, _f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        None
+    }
 }
 
 impl<'a, 'b> Compare<&'b [u8]> for &'a [u8] {
@@ -2197,6 +2482,12 @@ impl<'a, 'b> Compare<&'b [u8]> for &'a [u8] {
             CompareResult::Ok(t.slice_len())
         }
     }
+
+    #[inline]
+    fn mismatch_bytes<R>(&self, t: &&'b [u8], f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        let found = &self[..self.len().min(t.len())];
+        Some(f(t, found))
+    }
 }
 
 impl<'a, 'b> Compare<AsciiCaseless<&'b [u8]>> for &'a [u8] {
@@ -2221,6 +2512,11 @@ impl<'a, const LEN: usize> Compare<[u8; LEN]> for &'a [u8] {
     fn compare(&self, t: [u8; LEN]) -> CompareResult {
         self.compare(&t[..])
     }
+
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, t: &[u8; LEN], f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        self.mismatch_bytes(&&t[..], f)
+    }
 }
 
 impl<'a, const LEN: usize> Compare<AsciiCaseless<[u8; LEN]>> for &'a [u8] {
@@ -2235,6 +2531,11 @@ impl<'a, 'b, const LEN: usize> Compare<&'b [u8; LEN]> for &'a [u8] {
     fn compare(&self, t: &'b [u8; LEN]) -> CompareResult {
         self.compare(&t[..])
     }
+
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, t: &&'b [u8; LEN], f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        self.mismatch_bytes(&&t[..], f)
+    }
 }
 
 impl<'a, 'b, const LEN: usize> Compare<AsciiCaseless<&'b [u8; LEN]>> for &'a [u8] {
@@ -2249,6 +2550,11 @@ impl<'a, 'b> Compare<&'b str> for &'a [u8] {
     fn compare(&self, t: &'b str) -> CompareResult {
         self.compare(t.as_bytes())
     }
+
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, t: &&'b str, f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        self.mismatch_bytes(&t.as_bytes(), f)
+    }
 }
 
 impl<'a, 'b> Compare<AsciiCaseless<&'b str>> for &'a [u8] {
@@ -2267,6 +2573,11 @@ impl<'a> Compare<u8> for &'a [u8] {
             None => CompareResult::Incomplete,
         }
     }
+
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, t: &u8, f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        self.mismatch_bytes(&core::slice::from_ref(t), f)
+    }
 }
 
 impl<'a> Compare<AsciiCaseless<u8>> for &'a [u8] {
@@ -2285,6 +2596,11 @@ impl<'a> Compare<char> for &'a [u8] {
     fn compare(&self, t: char) -> CompareResult {
         self.compare(t.encode_utf8(&mut [0; 4]).as_bytes())
     }
+
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, t: &char, f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        self.mismatch_bytes(&t.encode_utf8(&mut [0; 4]).as_bytes(), f)
+    }
 }
 
 impl<'a> Compare<AsciiCaseless<char>> for &'a [u8] {
@@ -2331,6 +2647,12 @@ where
         let bytes = (*self).as_bytes();
         bytes.compare(t)
     }
+
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, t: &T, f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        let bytes = (*self).as_bytes();
+        bytes.mismatch_bytes(t, f)
+    }
 }
 
 impl<'a, T> Compare<T> for &'a BStr
@@ -2342,6 +2664,12 @@ where
         let bytes = (*self).as_bytes();
         bytes.compare(t)
     }
+
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, t: &T, f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        let bytes = (*self).as_bytes();
+        bytes.mismatch_bytes(t, f)
+    }
 }
 
 impl<I, U> Compare<U> for Located<I>
@@ -2352,6 +2680,16 @@ where
     fn compare(&self, other: U) -> CompareResult {
         self.input.compare(other)
     }
+
+    #[inline(always)]
+    fn compare_partial(&self, other: U) -> CompareResult {
+        self.input.compare_partial(other)
+    }
+
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, other: &U, f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        self.input.mismatch_bytes(other, f)
+    }
 }
 
 #[cfg(feature = "unstable-recover")]
@@ -2365,6 +2703,16 @@ where
     fn compare(&self, other: U) -> CompareResult {
         self.input.compare(other)
     }
+
+    #[inline(always)]
+    fn compare_partial(&self, other: U) -> CompareResult {
+        self.input.compare_partial(other)
+    }
+
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, other: &U, f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        self.input.mismatch_bytes(other, f)
+    }
 }
 
 impl<I, S, U> Compare<U> for Stateful<I, S>
@@ -2375,6 +2723,16 @@ where
     fn compare(&self, other: U) -> CompareResult {
         self.input.compare(other)
     }
+
+    #[inline(always)]
+    fn compare_partial(&self, other: U) -> CompareResult {
+        self.input.compare_partial(other)
+    }
+
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, other: &U, f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        self.input.mismatch_bytes(other, f)
+    }
 }
 
 impl<I, T> Compare<T> for Partial<I>
@@ -2383,7 +2741,21 @@ where
 {
     #[inline(always)]
     fn compare(&self, t: T) -> CompareResult {
-        self.input.compare(t)
+        if self.partial {
+            self.input.compare_partial(t)
+        } else {
+            self.input.compare(t)
+        }
+    }
+
+    #[inline(always)]
+    fn compare_partial(&self, t: T) -> CompareResult {
+        self.input.compare_partial(t)
+    }
+
+    #[inline(always)]
+    fn mismatch_bytes<R>(&self, t: &T, f: impl FnOnce(&[u8], &[u8]) -> R) -> Option<R> {
+        self.input.mismatch_bytes(t, f)
     }
 }
 
@@ -2979,6 +3351,30 @@ pub trait Accumulate<T>: Sized {
     fn initial(capacity: Option<usize>) -> Self;
     /// Accumulate the input into an accumulator
     fn accumulate(&mut self, acc: T);
+    /// Accumulate the input into an accumulator, returning it if there is no room left
+    ///
+    /// Bounded-capacity accumulators override this so parsers like
+    /// [`repeat`][crate::combinator::repeat] can report an [`ErrMode::Cut`] instead of panicking.
+    #[inline(always)]
+    fn try_accumulate(&mut self, acc: T) -> Result<(), T> {
+        self.accumulate(acc);
+        Ok(())
+    }
+}
+
+/// [`Accumulate::try_accumulate`], reporting a full accumulator as an [`ErrMode::Cut`]
+///
+/// The input is well-formed, so backtracking into another alternative would only obscure the
+/// cause.
+#[inline(always)]
+pub(crate) fn accumulate<I, T, C, E>(acc: &mut C, value: T, input: &I) -> Result<(), ErrMode<E>>
+where
+    I: Stream,
+    C: Accumulate<T>,
+    E: ParserError<I>,
+{
+    acc.try_accumulate(value)
+        .map_err(|_| ErrMode::Cut(E::from_error_kind(input, ErrorKind::Many)))
 }
 
 impl<T> Accumulate<T> for () {
@@ -3059,6 +3455,7 @@ impl<'i> Accumulate<&'i str> for String {
     }
 }
 
+/// A later value replaces an earlier one for the same key, see [`UniqueKeys`] to reject duplicates
 #[cfg(feature = "alloc")]
 impl<K, V> Accumulate<(K, V)> for BTreeMap<K, V>
 where
@@ -3074,6 +3471,7 @@ where
     }
 }
 
+/// A later value replaces an earlier one for the same key, see [`UniqueKeys`] to reject duplicates
 #[cfg(feature = "std")]
 impl<K, V, S> Accumulate<(K, V)> for HashMap<K, V, S>
 where
@@ -3131,56 +3529,461 @@ where
     }
 }
 
-#[cfg(feature = "alloc")]
-#[inline]
-pub(crate) fn clamp_capacity<T>(capacity: usize) -> usize {
-    /// Don't pre-allocate more than 64KiB when calling `Vec::with_capacity`.
-    ///
-    /// Pre-allocating memory is a nice optimization but count fields can't
-    /// always be trusted. We should clamp initial capacities to some reasonable
-    /// amount. This reduces the risk of a bogus count value triggering a panic
-    /// due to an OOM error.
-    ///
-    /// This does not affect correctness. `winnow` will always read the full number
-    /// of elements regardless of the capacity cap.
-    const MAX_INITIAL_CAPACITY_BYTES: usize = 65536;
-
-    let max_initial_capacity =
-        MAX_INITIAL_CAPACITY_BYTES / crate::lib::std::mem::size_of::<T>().max(1);
-    capacity.min(max_initial_capacity)
-}
-
-/// Helper trait to convert numbers to usize.
-///
-/// By default, usize implements `From<u8>` and `From<u16>` but not
-/// `From<u32>` and `From<u64>` because that would be invalid on some
-/// platforms. This trait implements the conversion for platforms
-/// with 32 and 64 bits pointer platforms
-pub trait ToUsize {
-    /// converts self to usize
-    fn to_usize(&self) -> usize;
-}
-
-impl ToUsize for u8 {
+/// A later value replaces an earlier one for the same key, see [`UniqueKeys`] to reject duplicates
+#[cfg(feature = "indexmap")]
+impl<K, V, S> Accumulate<(K, V)> for indexmap::IndexMap<K, V, S>
+where
+    K: crate::lib::std::cmp::Eq + crate::lib::std::hash::Hash,
+    S: BuildHasher + Default,
+{
     #[inline(always)]
-    fn to_usize(&self) -> usize {
-        *self as usize
+    fn initial(capacity: Option<usize>) -> Self {
+        let h = S::default();
+        match capacity {
+            Some(capacity) => {
+                indexmap::IndexMap::with_capacity_and_hasher(clamp_capacity::<(K, V)>(capacity), h)
+            }
+            None => indexmap::IndexMap::with_hasher(h),
+        }
     }
-}
-
-impl ToUsize for u16 {
     #[inline(always)]
-    fn to_usize(&self) -> usize {
-        *self as usize
+    fn accumulate(&mut self, (key, value): (K, V)) {
+        self.insert(key, value);
     }
 }
 
-impl ToUsize for usize {
+#[cfg(feature = "indexmap")]
+impl<K, S> Accumulate<K> for indexmap::IndexSet<K, S>
+where
+    K: crate::lib::std::cmp::Eq + crate::lib::std::hash::Hash,
+    S: BuildHasher + Default,
+{
     #[inline(always)]
-    fn to_usize(&self) -> usize {
-        *self
+    fn initial(capacity: Option<usize>) -> Self {
+        let h = S::default();
+        match capacity {
+            Some(capacity) => {
+                indexmap::IndexSet::with_capacity_and_hasher(clamp_capacity::<K>(capacity), h)
+            }
+            None => indexmap::IndexSet::with_hasher(h),
+        }
+    }
+    #[inline(always)]
+    fn accumulate(&mut self, key: K) {
+        self.insert(key);
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A> Accumulate<A::Item> for smallvec::SmallVec<A>
+where
+    A: smallvec::Array,
+{
+    #[inline(always)]
+    fn initial(capacity: Option<usize>) -> Self {
+        match capacity {
+            Some(capacity) => {
+                smallvec::SmallVec::with_capacity(clamp_capacity::<A::Item>(capacity))
+            }
+            None => smallvec::SmallVec::new(),
+        }
+    }
+    #[inline(always)]
+    fn accumulate(&mut self, acc: A::Item) {
+        self.push(acc);
+    }
+}
+
+/// Accumulating past the capacity `N` is an [`ErrMode::Cut`] within parsers like
+/// [`repeat`][crate::combinator::repeat], while [`Accumulate::accumulate`] panics
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> Accumulate<T> for heapless::Vec<T, N> {
+    #[inline(always)]
+    fn initial(_capacity: Option<usize>) -> Self {
+        heapless::Vec::new()
+    }
+    #[inline(always)]
+    fn accumulate(&mut self, acc: T) {
+        assert!(self.push(acc).is_ok(), "`heapless::Vec` capacity exceeded");
+    }
+    #[inline(always)]
+    fn try_accumulate(&mut self, acc: T) -> Result<(), T> {
+        self.push(acc)
+    }
+}
+
+/// Accumulating past the capacity `N` is an [`ErrMode::Cut`] within parsers like
+/// [`repeat`][crate::combinator::repeat], while [`Accumulate::accumulate`] panics
+#[cfg(feature = "heapless")]
+impl<'i, T: Clone, const N: usize> Accumulate<&'i [T]> for heapless::Vec<T, N> {
+    #[inline(always)]
+    fn initial(_capacity: Option<usize>) -> Self {
+        heapless::Vec::new()
+    }
+    #[inline(always)]
+    fn accumulate(&mut self, acc: &'i [T]) {
+        assert!(
+            self.try_accumulate(acc).is_ok(),
+            "`heapless::Vec` capacity exceeded"
+        );
+    }
+    #[inline(always)]
+    fn try_accumulate(&mut self, acc: &'i [T]) -> Result<(), &'i [T]> {
+        self.extend_from_slice(acc).map_err(|()| acc)
+    }
+}
+
+/// Accumulating past the capacity `N` is an [`ErrMode::Cut`] within parsers like
+/// [`repeat`][crate::combinator::repeat], while [`Accumulate::accumulate`] panics
+#[cfg(feature = "heapless")]
+impl<const N: usize> Accumulate<char> for heapless::String<N> {
+    #[inline(always)]
+    fn initial(_capacity: Option<usize>) -> Self {
+        heapless::String::new()
+    }
+    #[inline(always)]
+    fn accumulate(&mut self, acc: char) {
+        assert!(
+            self.try_accumulate(acc).is_ok(),
+            "`heapless::String` capacity exceeded"
+        );
+    }
+    #[inline(always)]
+    fn try_accumulate(&mut self, acc: char) -> Result<(), char> {
+        self.push(acc).map_err(|()| acc)
+    }
+}
+
+/// Accumulating past the capacity `N` is an [`ErrMode::Cut`] within parsers like
+/// [`repeat`][crate::combinator::repeat], while [`Accumulate::accumulate`] panics
+#[cfg(feature = "heapless")]
+impl<'i, const N: usize> Accumulate<&'i str> for heapless::String<N> {
+    #[inline(always)]
+    fn initial(_capacity: Option<usize>) -> Self {
+        heapless::String::new()
+    }
+    #[inline(always)]
+    fn accumulate(&mut self, acc: &'i str) {
+        assert!(
+            self.try_accumulate(acc).is_ok(),
+            "`heapless::String` capacity exceeded"
+        );
+    }
+    #[inline(always)]
+    fn try_accumulate(&mut self, acc: &'i str) -> Result<(), &'i str> {
+        self.push_str(acc).map_err(|()| acc)
+    }
+}
+
+/// [`Accumulate`] `(key, value)` pairs into the map `M`, rejecting duplicate keys
+///
+/// Maps keep the last value for a repeated key.  This instead keeps the first value and
+/// remembers the first repeated key, for [`UniqueKeys::into_result`] to report.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// # use std::collections::BTreeMap;
+/// use winnow::ascii::alpha1;
+/// use winnow::ascii::dec_uint;
+/// use winnow::combinator::separated;
+/// use winnow::combinator::separated_pair;
+/// use winnow::stream::UniqueKeys;
+///
+/// fn entries<'i>(input: &mut &'i str) -> PResult<BTreeMap<&'i str, u32>> {
+///     separated(0.., separated_pair(alpha1, '=', dec_uint), ',')
+///         .try_map(UniqueKeys::into_result)
+///         .parse_next(input)
+/// }
+///
+/// assert_eq!(entries.parse("a=1,b=2"), Ok(BTreeMap::from([("a", 1), ("b", 2)])));
+/// assert!(entries.parse("a=1,a=2").is_err());
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UniqueKeys<M, K> {
+    map: M,
+    duplicate: Option<K>,
+}
+
+#[cfg(feature = "alloc")]
+impl<M, K> UniqueKeys<M, K> {
+    /// The first key that was repeated, if any
+    #[inline]
+    pub fn duplicate(&self) -> Option<&K> {
+        self.duplicate.as_ref()
+    }
+
+    /// The accumulated map, keeping the first value for each key
+    #[inline]
+    pub fn into_inner(self) -> M {
+        self.map
+    }
+
+    /// The accumulated map, or the first key that was repeated
+    ///
+    /// Pass this to [`Parser::try_map`] to turn a duplicate into a parse error.
+    #[inline]
+    pub fn into_result(self) -> Result<M, crate::error::DuplicateKey>
+    where
+        K: crate::lib::std::fmt::Debug,
+    {
+        match self.duplicate {
+            Some(key) => Err(crate::error::DuplicateKey(alloc::format!("{key:?}"))),
+            None => Ok(self.map),
+        }
     }
-}
+
+    #[inline]
+    fn accumulate_with(
+        &mut self,
+        key: K,
+        contains: impl FnOnce(&M, &K) -> bool,
+        insert: impl FnOnce(&mut M, K),
+    ) {
+        if contains(&self.map, &key) {
+            if self.duplicate.is_none() {
+                self.duplicate = Some(key);
+            }
+        } else {
+            insert(&mut self.map, key);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> Accumulate<(K, V)> for UniqueKeys<BTreeMap<K, V>, K>
+where
+    K: crate::lib::std::cmp::Ord,
+{
+    #[inline(always)]
+    fn initial(capacity: Option<usize>) -> Self {
+        Self {
+            map: Accumulate::<(K, V)>::initial(capacity),
+            duplicate: None,
+        }
+    }
+    #[inline(always)]
+    fn accumulate(&mut self, (key, value): (K, V)) {
+        self.accumulate_with(
+            key,
+            |m, k| m.contains_key(k),
+            |m, k| {
+                m.insert(k, value);
+            },
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> Accumulate<(K, V)> for UniqueKeys<HashMap<K, V, S>, K>
+where
+    K: crate::lib::std::cmp::Eq + crate::lib::std::hash::Hash,
+    S: BuildHasher + Default,
+{
+    #[inline(always)]
+    fn initial(capacity: Option<usize>) -> Self {
+        Self {
+            map: Accumulate::<(K, V)>::initial(capacity),
+            duplicate: None,
+        }
+    }
+    #[inline(always)]
+    fn accumulate(&mut self, (key, value): (K, V)) {
+        self.accumulate_with(
+            key,
+            |m, k| m.contains_key(k),
+            |m, k| {
+                m.insert(k, value);
+            },
+        );
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> Accumulate<(K, V)> for UniqueKeys<indexmap::IndexMap<K, V, S>, K>
+where
+    K: crate::lib::std::cmp::Eq + crate::lib::std::hash::Hash,
+    S: BuildHasher + Default,
+{
+    #[inline(always)]
+    fn initial(capacity: Option<usize>) -> Self {
+        Self {
+            map: Accumulate::<(K, V)>::initial(capacity),
+            duplicate: None,
+        }
+    }
+    #[inline(always)]
+    fn accumulate(&mut self, (key, value): (K, V)) {
+        self.accumulate_with(
+            key,
+            |m, k| m.contains_key(k),
+            |m, k| {
+                m.insert(k, value);
+            },
+        );
+    }
+}
+
+/// A `Vec`-like collection holding at most `N` items, without allocating
+///
+/// This is the [`Accumulate`] target for [`repeat_bounded`][crate::combinator::repeat_bounded],
+/// which caps the number of repetitions at `N`.  Accumulating more than `N` items is an
+/// [`ErrMode::Cut`] within parsers like [`repeat`][crate::combinator::repeat], while
+/// [`Accumulate::accumulate`] panics.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundedVec<T, const N: usize> {
+    items: [Option<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> BoundedVec<T, N> {
+    /// Create an empty collection
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            items: core::array::from_fn(|_| None),
+            len: 0,
+        }
+    }
+
+    /// Append `item`, returning it if the collection is full
+    #[inline]
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if self.len < N {
+            self.items[self.len] = Some(item);
+            self.len += 1;
+            Ok(())
+        } else {
+            Err(item)
+        }
+    }
+
+    /// Remove the last item
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.items[self.len].take()
+    }
+
+    /// Access the item at `index`
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.items[..self.len].get(index)?.as_ref()
+    }
+
+    /// Iterate over the items
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items[..self.len].iter().filter_map(Option::as_ref)
+    }
+
+    /// Number of items
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no items
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Maximum number of items
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T, const N: usize> Default for BoundedVec<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for BoundedVec<T, N> {
+    type Item = T;
+    type IntoIter = core::iter::Flatten<core::array::IntoIter<Option<T>, N>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter().flatten()
+    }
+}
+
+impl<T, const N: usize> Accumulate<T> for BoundedVec<T, N> {
+    #[inline(always)]
+    fn initial(_capacity: Option<usize>) -> Self {
+        Self::new()
+    }
+    #[inline(always)]
+    fn accumulate(&mut self, acc: T) {
+        assert!(self.push(acc).is_ok(), "`BoundedVec` capacity exceeded");
+    }
+    #[inline(always)]
+    fn try_accumulate(&mut self, acc: T) -> Result<(), T> {
+        self.push(acc)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn clamp_capacity<T>(capacity: usize) -> usize {
+    /// Don't pre-allocate more than 64KiB when calling `Vec::with_capacity`.
+    ///
+    /// Pre-allocating memory is a nice optimization but count fields can't
+    /// always be trusted. We should clamp initial capacities to some reasonable
+    /// amount. This reduces the risk of a bogus count value triggering a panic
+    /// due to an OOM error.
+    ///
+    /// This does not affect correctness. `winnow` will always read the full number
+    /// of elements regardless of the capacity cap.
+    const MAX_INITIAL_CAPACITY_BYTES: usize = 65536;
+
+    let max_initial_capacity =
+        MAX_INITIAL_CAPACITY_BYTES / crate::lib::std::mem::size_of::<T>().max(1);
+    capacity.min(max_initial_capacity)
+}
+
+/// Helper trait to convert numbers to usize.
+///
+/// By default, usize implements `From<u8>` and `From<u16>` but not
+/// `From<u32>` and `From<u64>` because that would be invalid on some
+/// platforms. This trait implements the conversion for platforms
+/// with 32 and 64 bits pointer platforms
+pub trait ToUsize {
+    /// converts self to usize
+    fn to_usize(&self) -> usize;
+}
+
+impl ToUsize for u8 {
+    #[inline(always)]
+    fn to_usize(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl ToUsize for u16 {
+    #[inline(always)]
+    fn to_usize(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl ToUsize for usize {
+    #[inline(always)]
+    fn to_usize(&self) -> usize {
+        *self
+    }
+}
 
 #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
 impl ToUsize for u32 {
@@ -3239,6 +4042,17 @@ pub trait AsChar {
     fn is_space(self) -> bool;
     /// Tests if byte is ASCII newline: \n
     fn is_newline(self) -> bool;
+
+    /// Find the first of `needles` in `haystack`, if `Self` is a byte
+    #[doc(hidden)]
+    #[inline(always)]
+    fn find_needles(haystack: &[Self], needles: Needles) -> Option<Option<usize>>
+    where
+        Self: Sized,
+    {
+        let _ = (haystack, needles);
+        None
+    }
 }
 
 impl AsChar for u8 {
@@ -3246,6 +4060,10 @@ impl AsChar for u8 {
     fn as_char(self) -> char {
         self as char
     }
+    #[inline(always)]
+    fn find_needles(haystack: &[Self], needles: Needles) -> Option<Option<usize>> {
+        Some(needles.find(haystack))
+    }
     #[inline]
     fn is_alpha(self) -> bool {
         matches!(self, 0x41..=0x5A | 0x61..=0x7A)
@@ -3424,6 +4242,83 @@ impl<'a> AsChar for &'a char {
 pub trait ContainsToken<T> {
     /// Returns true if self contains the token
     fn contains_token(&self, token: T) -> bool;
+
+    /// The bytes that make up the whole set, if it is small enough for `memchr`
+    ///
+    /// For `char` tokens, these are always ASCII.
+    #[doc(hidden)]
+    #[inline(always)]
+    fn needles(&self) -> Option<Needles> {
+        None
+    }
+
+    /// Find the first of [`ContainsToken::needles`] in `haystack`, if `T` is known to be a byte
+    #[doc(hidden)]
+    #[inline(always)]
+    fn find_in_slice(&self, haystack: &[T], needles: Needles) -> Option<Option<usize>> {
+        let _ = (haystack, needles);
+        None
+    }
+}
+
+/// Up to three bytes to search for with `memchr`, see [`ContainsToken::needles`]
+#[doc(hidden)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Needles {
+    bytes: [u8; 3],
+    len: usize,
+}
+
+impl Needles {
+    #[inline(always)]
+    fn empty() -> Self {
+        Self {
+            bytes: [0; 3],
+            len: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn byte(byte: u8) -> Self {
+        Self {
+            bytes: [byte, 0, 0],
+            len: 1,
+        }
+    }
+
+    #[inline(always)]
+    fn ascii(c: char) -> Option<Self> {
+        c.is_ascii().then(|| Self::byte(c as u8))
+    }
+
+    #[inline]
+    fn ascii_all(chars: impl IntoIterator<Item = char>) -> Option<Self> {
+        chars
+            .into_iter()
+            .try_fold(Self::empty(), |needles, c| needles.union(Self::ascii(c)?))
+    }
+
+    #[inline]
+    fn union(self, other: Self) -> Option<Self> {
+        let mut union = self;
+        for &byte in &other.bytes[..other.len] {
+            if !union.bytes[..union.len].contains(&byte) {
+                *union.bytes.get_mut(union.len)? = byte;
+                union.len += 1;
+            }
+        }
+        Some(union)
+    }
+
+    #[inline(always)]
+    fn find(&self, haystack: &[u8]) -> Option<usize> {
+        match self.len {
+            0 => None,
+            1 => memchr(self.bytes[0], haystack),
+            2 => memchr2((self.bytes[0], self.bytes[1]), haystack),
+            _ => memchr3((self.bytes[0], self.bytes[1], self.bytes[2]), haystack),
+        }
+    }
 }
 
 impl ContainsToken<u8> for u8 {
@@ -3431,6 +4326,16 @@ impl ContainsToken<u8> for u8 {
     fn contains_token(&self, token: u8) -> bool {
         *self == token
     }
+
+    #[inline(always)]
+    fn needles(&self) -> Option<Needles> {
+        Some(Needles::byte(*self))
+    }
+
+    #[inline(always)]
+    fn find_in_slice(&self, haystack: &[u8], needles: Needles) -> Option<Option<usize>> {
+        Some(needles.find(haystack))
+    }
 }
 
 impl<'a> ContainsToken<&'a u8> for u8 {
@@ -3445,6 +4350,11 @@ impl ContainsToken<char> for u8 {
     fn contains_token(&self, token: char) -> bool {
         self.as_char() == token
     }
+
+    #[inline(always)]
+    fn needles(&self) -> Option<Needles> {
+        Needles::ascii(self.as_char())
+    }
 }
 
 impl<'a> ContainsToken<&'a char> for u8 {
@@ -3459,6 +4369,16 @@ impl<C: AsChar> ContainsToken<C> for char {
     fn contains_token(&self, token: C) -> bool {
         *self == token.as_char()
     }
+
+    #[inline(always)]
+    fn needles(&self) -> Option<Needles> {
+        Needles::ascii(*self)
+    }
+
+    #[inline(always)]
+    fn find_in_slice(&self, haystack: &[C], needles: Needles) -> Option<Option<usize>> {
+        C::find_needles(haystack, needles)
+    }
 }
 
 impl<C, F: Fn(C) -> bool> ContainsToken<C> for F {
@@ -3527,6 +4447,16 @@ impl<C: AsChar> ContainsToken<C> for &'_ [u8] {
         let token = token.as_char();
         self.iter().any(|t| t.as_char() == token)
     }
+
+    #[inline]
+    fn needles(&self) -> Option<Needles> {
+        Needles::ascii_all(self.iter().map(|t| t.as_char()))
+    }
+
+    #[inline(always)]
+    fn find_in_slice(&self, haystack: &[C], needles: Needles) -> Option<Option<usize>> {
+        C::find_needles(haystack, needles)
+    }
 }
 
 impl<C: AsChar> ContainsToken<C> for &'_ [char] {
@@ -3535,6 +4465,16 @@ impl<C: AsChar> ContainsToken<C> for &'_ [char] {
         let token = token.as_char();
         self.iter().any(|t| *t == token)
     }
+
+    #[inline]
+    fn needles(&self) -> Option<Needles> {
+        Needles::ascii_all(self.iter().copied())
+    }
+
+    #[inline(always)]
+    fn find_in_slice(&self, haystack: &[C], needles: Needles) -> Option<Option<usize>> {
+        C::find_needles(haystack, needles)
+    }
 }
 
 impl<const LEN: usize, C: AsChar> ContainsToken<C> for &'_ [u8; LEN] {
@@ -3543,6 +4483,16 @@ impl<const LEN: usize, C: AsChar> ContainsToken<C> for &'_ [u8; LEN] {
         let token = token.as_char();
         self.iter().any(|t| t.as_char() == token)
     }
+
+    #[inline]
+    fn needles(&self) -> Option<Needles> {
+        Needles::ascii_all(self.iter().map(|t| t.as_char()))
+    }
+
+    #[inline(always)]
+    fn find_in_slice(&self, haystack: &[C], needles: Needles) -> Option<Option<usize>> {
+        C::find_needles(haystack, needles)
+    }
 }
 
 impl<const LEN: usize, C: AsChar> ContainsToken<C> for &'_ [char; LEN] {
@@ -3551,6 +4501,16 @@ impl<const LEN: usize, C: AsChar> ContainsToken<C> for &'_ [char; LEN] {
         let token = token.as_char();
         self.iter().any(|t| *t == token)
     }
+
+    #[inline]
+    fn needles(&self) -> Option<Needles> {
+        Needles::ascii_all(self.iter().copied())
+    }
+
+    #[inline(always)]
+    fn find_in_slice(&self, haystack: &[C], needles: Needles) -> Option<Option<usize>> {
+        C::find_needles(haystack, needles)
+    }
 }
 
 impl<const LEN: usize, C: AsChar> ContainsToken<C> for [u8; LEN] {
@@ -3559,6 +4519,16 @@ impl<const LEN: usize, C: AsChar> ContainsToken<C> for [u8; LEN] {
         let token = token.as_char();
         self.iter().any(|t| t.as_char() == token)
     }
+
+    #[inline]
+    fn needles(&self) -> Option<Needles> {
+        Needles::ascii_all(self.iter().map(|t| t.as_char()))
+    }
+
+    #[inline(always)]
+    fn find_in_slice(&self, haystack: &[C], needles: Needles) -> Option<Option<usize>> {
+        C::find_needles(haystack, needles)
+    }
 }
 
 impl<const LEN: usize, C: AsChar> ContainsToken<C> for [char; LEN] {
@@ -3567,6 +4537,46 @@ impl<const LEN: usize, C: AsChar> ContainsToken<C> for [char; LEN] {
         let token = token.as_char();
         self.iter().any(|t| *t == token)
     }
+
+    #[inline]
+    fn needles(&self) -> Option<Needles> {
+        Needles::ascii_all(self.iter().copied())
+    }
+
+    #[inline(always)]
+    fn find_in_slice(&self, haystack: &[C], needles: Needles) -> Option<Option<usize>> {
+        C::find_needles(haystack, needles)
+    }
+}
+
+/// A `&str` token, like from `Graphemes`, that is a single `char`
+impl ContainsToken<&'_ str> for char {
+    #[inline(always)]
+    fn contains_token(&self, token: &'_ str) -> bool {
+        let mut chars = token.chars();
+        chars.next() == Some(*self) && chars.next().is_none()
+    }
+}
+
+impl ContainsToken<&'_ str> for &'_ str {
+    #[inline(always)]
+    fn contains_token(&self, token: &'_ str) -> bool {
+        *self == token
+    }
+}
+
+impl ContainsToken<&'_ str> for &'_ [&'_ str] {
+    #[inline]
+    fn contains_token(&self, token: &'_ str) -> bool {
+        self.contains(&token)
+    }
+}
+
+impl<const LEN: usize> ContainsToken<&'_ str> for [&'_ str; LEN] {
+    #[inline]
+    fn contains_token(&self, token: &'_ str) -> bool {
+        self.contains(&token)
+    }
 }
 
 impl<T> ContainsToken<T> for () {
@@ -3574,20 +4584,42 @@ impl<T> ContainsToken<T> for () {
     fn contains_token(&self, _token: T) -> bool {
         false
     }
+
+    #[inline(always)]
+    fn needles(&self) -> Option<Needles> {
+        Some(Needles::empty())
+    }
 }
 
 macro_rules! impl_contains_token_for_tuple {
-  ($($haystack:ident),+) => (
+  ($haystack1:ident $(, $haystack:ident)*) => (
     #[allow(non_snake_case)]
-    impl<T, $($haystack),+> ContainsToken<T> for ($($haystack),+,)
+    impl<T, $haystack1, $($haystack),*> ContainsToken<T> for ($haystack1, $($haystack),*)
     where
     T: Clone,
-      $($haystack: ContainsToken<T>),+
+      $haystack1: ContainsToken<T>,
+      $($haystack: ContainsToken<T>),*
     {
     #[inline]
       fn contains_token(&self, token: T) -> bool {
-        let ($(ref $haystack),+,) = *self;
-        $($haystack.contains_token(token.clone()) || )+ false
+        let (ref $haystack1, $(ref $haystack),*) = *self;
+        $haystack1.contains_token(token.clone()) || $($haystack.contains_token(token.clone()) || )* false
+      }
+
+    #[inline]
+      fn needles(&self) -> Option<Needles> {
+        let (ref $haystack1, $(ref $haystack),*) = *self;
+        let needles = $haystack1.needles()?;
+        $(let needles = needles.union($haystack.needles()?)?;)*
+        Some(needles)
+      }
+
+    #[inline]
+      fn find_in_slice(&self, haystack: &[T], needles: Needles) -> Option<Option<usize>> {
+        // Any element that can search `T` can search for the needles of the whole tuple
+        let (ref $haystack1, $(ref $haystack),*) = *self;
+        $haystack1.find_in_slice(haystack, needles)
+          $(.or_else(|| $haystack.find_in_slice(haystack, needles)))*
       }
     }
   )
@@ -3681,18 +4713,7 @@ fn memmem3(
 #[cfg(feature = "simd")]
 #[inline(always)]
 fn memmem_(slice: &[u8], literal: &[u8]) -> Option<crate::lib::std::ops::Range<usize>> {
-    let &prefix = match literal.first() {
-        Some(x) => x,
-        None => return Some(0..0),
-    };
-    #[allow(clippy::manual_find)] // faster this way
-    for i in memchr::memchr_iter(prefix, slice) {
-        if slice[i..].starts_with(literal) {
-            let i_end = i + literal.len();
-            return Some(i..i_end);
-        }
-    }
-    None
+    memchr::memmem::find(slice, literal).map(|i| i..i + literal.len())
 }
 
 #[cfg(feature = "simd")]
@@ -3744,6 +4765,12 @@ fn memmem3_(
     None
 }
 
+#[cfg(feature = "simd")]
+#[inline(always)]
+pub(crate) fn memrmem(slice: &[u8], literal: &[u8]) -> Option<usize> {
+    memchr::memmem::rfind(slice, literal)
+}
+
 #[cfg(not(feature = "simd"))]
 fn memmem_(slice: &[u8], literal: &[u8]) -> Option<crate::lib::std::ops::Range<usize>> {
     for i in 0..slice.len() {
@@ -3794,3 +4821,9 @@ fn memmem3_(
     }
     None
 }
+
+#[cfg(not(feature = "simd"))]
+pub(crate) fn memrmem(slice: &[u8], literal: &[u8]) -> Option<usize> {
+    let last = slice.len().checked_sub(literal.len())?;
+    (0..=last).rev().find(|i| slice[*i..].starts_with(literal))
+}
@@ -0,0 +1,274 @@
+use crate::error::ErrMode;
+use crate::error::ErrorKind;
+use crate::error::Needed;
+use crate::error::ParserError;
+use crate::lib::std::vec::Vec;
+use crate::stream::Offset;
+use crate::stream::Partial;
+use crate::stream::Stream;
+use crate::stream::StreamIsPartial;
+use crate::Parser;
+use std::io;
+
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// Drive [`Partial`] parsing of a [`std::io::Read`] source
+///
+/// Rather than reading all of the source into memory, this maintains a buffer of unparsed bytes.
+/// When a parser reports [`ErrMode::Incomplete`], the buffer is refilled from the reader and the
+/// parser is re-run from the start of the buffered data.
+/// Once the reader is exhausted, the input is marked as [complete][StreamIsPartial::complete] so
+/// parsers can finish the last value.
+///
+/// As the buffer is reused between values, parsed outputs and errors cannot borrow from the input.
+/// Use owned outputs and an error type like [`ContextError`][crate::error::ContextError].
+///
+/// See also [Parsing Partial Input][crate::_topic::partial].
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::dec_uint;
+/// use winnow::ascii::line_ending;
+/// use winnow::combinator::terminated;
+/// use winnow::stream::Partial;
+/// use winnow::stream::ReadStream;
+///
+/// fn value(i: &mut Partial<&[u8]>) -> PResult<u32> {
+///     terminated(dec_uint, line_ending).parse_next(i)
+/// }
+///
+/// let source = std::io::Cursor::new("1\n22\n333\n");
+/// let mut stream = ReadStream::with_capacity(2, source);
+/// let mut values = Vec::new();
+/// while let Some(v) = stream.parse_next(&mut value).unwrap() {
+///     values.push(v);
+/// }
+/// assert_eq!(values, [1, 22, 333]);
+/// ```
+#[derive(Debug)]
+pub struct ReadStream<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    /// Bytes released from the front of `buffer`
+    released: usize,
+    start: usize,
+    capacity: usize,
+    eof: bool,
+}
+
+impl<R> ReadStream<R>
+where
+    R: io::Read,
+{
+    /// Buffer `reader`, reading up to 8 KiB at a time
+    pub fn new(reader: R) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, reader)
+    }
+
+    /// Buffer `reader`, reading up to `capacity` bytes at a time
+    pub fn with_capacity(capacity: usize, reader: R) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            reader,
+            buffer: Vec::with_capacity(capacity),
+            released: 0,
+            start: 0,
+            capacity,
+            eof: false,
+        }
+    }
+
+    /// Parse the next value, reading more data as needed
+    ///
+    /// Returns `Ok(None)` once all data has been read and parsed.
+    ///
+    /// The parser must consume input when it succeeds, like with
+    /// [`repeat`][crate::combinator::repeat], or this would return the same value forever.
+    pub fn parse_next<O, E, P>(&mut self, parser: &mut P) -> Result<Option<O>, ReadStreamError<E>>
+    where
+        P: for<'i> Parser<Partial<&'i [u8]>, O, E>,
+        E: for<'i> ParserError<Partial<&'i [u8]>>,
+    {
+        loop {
+            if self.start == self.buffer.len() {
+                if self.eof {
+                    return Ok(None);
+                }
+                self.fill(self.capacity)?;
+                continue;
+            }
+
+            let mut input = Partial::new(&self.buffer[self.start..]);
+            if self.eof {
+                let _ = input.complete();
+            }
+            let start = input.checkpoint();
+            match parser.parse_next(&mut input) {
+                Ok(output) => {
+                    // infinite loop check: the parser must always consume
+                    let consumed = input.offset_from(&start);
+                    if consumed == 0 {
+                        return Err(ReadStreamError::Parse(E::from_error_kind(
+                            &input,
+                            ErrorKind::InfiniteLoop,
+                        )));
+                    }
+                    self.start += consumed;
+                    return Ok(Some(output));
+                }
+                Err(ErrMode::Backtrack(e)) | Err(ErrMode::Cut(e)) => {
+                    return Err(ReadStreamError::Parse(e));
+                }
+                Err(ErrMode::Incomplete(_)) if self.eof => {
+                    return Err(ReadStreamError::Io(io::ErrorKind::UnexpectedEof.into()));
+                }
+                Err(ErrMode::Incomplete(Needed::Size(size))) => {
+                    self.fill(size.get().max(self.capacity))?;
+                }
+                Err(ErrMode::Incomplete(Needed::Unknown)) => {
+                    self.fill(self.capacity)?;
+                }
+            }
+        }
+    }
+
+    /// Make room for `additional` more bytes and read into it
+    fn fill(&mut self, additional: usize) -> io::Result<()> {
+        self.compact();
+
+        let len = self.buffer.len();
+        self.buffer.resize(len + additional, 0);
+        let read = loop {
+            match self.reader.read(&mut self.buffer[len..]) {
+                Ok(read) => break read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.buffer.truncate(len);
+                    return Err(e);
+                }
+            }
+        };
+        self.buffer.truncate(len + read);
+        if read == 0 {
+            self.eof = true;
+        }
+        Ok(())
+    }
+}
+
+impl<R> ReadStream<R> {
+    /// The offset into the source of the oldest byte the parser can still reach
+    ///
+    /// Each call to `parse_next` starts from here and any backtracking stays at or after it, so
+    /// everything before has been parsed for good.  Copies of the source, like those kept for
+    /// error reporting, can be safely truncated up to this offset during long streaming sessions.
+    #[inline(always)]
+    pub fn watermark(&self) -> usize {
+        self.released + self.start
+    }
+
+    /// Release the parsed bytes before the [watermark][Self::watermark] from the buffer
+    ///
+    /// This otherwise happens lazily, when more bytes are needed.
+    pub fn compact(&mut self) {
+        if self.start != 0 {
+            self.buffer.drain(..self.start);
+            self.released += self.start;
+            self.start = 0;
+        }
+    }
+
+    /// The data read but not yet parsed
+    #[inline(always)]
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer[self.start..]
+    }
+
+    /// Whether the reader has been exhausted
+    #[inline(always)]
+    pub fn is_eof(&self) -> bool {
+        self.eof
+    }
+
+    /// Access the underlying reader
+    #[inline(always)]
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Extract the underlying reader, discarding any unparsed data
+    #[inline(always)]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl<R> ReadStream<flate2::read::MultiGzDecoder<R>>
+where
+    R: io::Read,
+{
+    /// Decompress gzip data from `reader` while parsing it
+    ///
+    /// Only the buffer of unparsed bytes is kept in memory, not the whole decompressed payload.
+    /// Concatenated gzip members, like from appending to a log, are decompressed as one stream.
+    pub fn gzip(reader: R) -> Self {
+        Self::new(flate2::read::MultiGzDecoder::new(reader))
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl<R> ReadStream<ruzstd::StreamingDecoder<R, ruzstd::FrameDecoder>>
+where
+    R: io::Read,
+{
+    /// Decompress a zstd frame from `reader` while parsing it
+    ///
+    /// Only the buffer of unparsed bytes is kept in memory, not the whole decompressed payload.
+    ///
+    /// Returns an error if the frame header is invalid.
+    pub fn zstd(reader: R) -> io::Result<Self> {
+        let decoder = ruzstd::StreamingDecoder::new(reader)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Self::new(decoder))
+    }
+}
+
+/// Failure to parse a value from a [`ReadStream`]
+#[derive(Debug)]
+pub enum ReadStreamError<E> {
+    /// Reading failed, or the data ended in the middle of a value
+    Io(io::Error),
+    /// The parser failed
+    Parse(E),
+}
+
+impl<E> From<io::Error> for ReadStreamError<E> {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl<E: crate::lib::std::fmt::Display> crate::lib::std::fmt::Display for ReadStreamError<E> {
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        match self {
+            Self::Io(e) => e.fmt(f),
+            Self::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<E> std::error::Error for ReadStreamError<E>
+where
+    E: crate::lib::std::fmt::Debug + crate::lib::std::fmt::Display,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Parse(_) => None,
+        }
+    }
+}
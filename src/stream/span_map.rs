@@ -0,0 +1,157 @@
+use crate::lib::std::ops::Range;
+use crate::lib::std::vec::Vec;
+
+/// Map offsets in preprocessed input back to the original source
+///
+/// When the input is rewritten before parsing, like removing line continuations, decoding
+/// escapes, or stripping trivia, the offsets reported by [`Location`][crate::stream::Location],
+/// [`Parser::span`][crate::Parser::span], or [`ParserError`][crate::error::ParserError]s are into
+/// the rewritten input.  Record how the original was rewritten while preprocessing and then
+/// remap those offsets for diagnostics, like with [`LineIndex`][crate::stream::LineIndex].
+///
+/// The original is described from start to end as runs that were either
+/// [copied][SpanMap::copy] unchanged or [replaced][SpanMap::replace].  Offsets within a copied
+/// run map exactly while offsets within a replaced run widen to cover all of it.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::ascii::alpha1;
+/// use winnow::stream::Located;
+/// use winnow::stream::SpanMap;
+///
+/// // Join lines ending in `\`
+/// fn preprocess(source: &str) -> (String, SpanMap) {
+///     let mut processed = String::new();
+///     let mut map = SpanMap::new();
+///     for line in source.split_inclusive('\n') {
+///         match line.strip_suffix("\\\n") {
+///             Some(content) => {
+///                 processed.push_str(content);
+///                 map.copy(content.len());
+///                 map.replace(2, 0);
+///             }
+///             None => {
+///                 processed.push_str(line);
+///                 map.copy(line.len());
+///             }
+///         }
+///     }
+///     (processed, map)
+/// }
+///
+/// let source = "hel\\\nlo";
+/// let (processed, map) = preprocess(source);
+/// assert_eq!(processed, "hello");
+///
+/// let span = alpha1::<_, ContextError>.span().parse(Located::new(processed.as_str())).unwrap();
+/// assert_eq!(span, 0..5);
+/// assert_eq!(map.original_span(span), 0..7);
+/// assert_eq!(&source[map.original_span(3..5)], "lo");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpanMap {
+    runs: Vec<Run>,
+    processed_len: usize,
+    original_len: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Run {
+    processed: Range<usize>,
+    original: Range<usize>,
+    copied: bool,
+}
+
+impl SpanMap {
+    /// Create a map for input that has not been rewritten yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that the next `len` bytes of the original were copied unchanged
+    pub fn copy(&mut self, len: usize) {
+        if len == 0 {
+            return;
+        }
+        match self.runs.last_mut() {
+            Some(last) if last.copied => {
+                last.processed.end += len;
+                last.original.end += len;
+                self.processed_len += len;
+                self.original_len += len;
+            }
+            _ => self.push(len, len, true),
+        }
+    }
+
+    /// Record that the next `original_len` bytes of the original were replaced by
+    /// `processed_len` bytes
+    ///
+    /// A `processed_len` of `0` records that the bytes were removed.
+    pub fn replace(&mut self, original_len: usize, processed_len: usize) {
+        if original_len == 0 && processed_len == 0 {
+            return;
+        }
+        self.push(original_len, processed_len, false);
+    }
+
+    fn push(&mut self, original_len: usize, processed_len: usize, copied: bool) {
+        let processed = self.processed_len;
+        let original = self.original_len;
+        self.processed_len += processed_len;
+        self.original_len += original_len;
+        self.runs.push(Run {
+            processed: processed..self.processed_len,
+            original: original..self.original_len,
+            copied,
+        });
+    }
+
+    /// The length of the rewritten input recorded so far
+    #[inline(always)]
+    pub fn processed_len(&self) -> usize {
+        self.processed_len
+    }
+
+    /// The length of the original input recorded so far
+    #[inline(always)]
+    pub fn original_len(&self) -> usize {
+        self.original_len
+    }
+
+    /// The offset in the original of `offset` in the rewritten input, as the start of a span
+    ///
+    /// Offsets past the end are clamped to the end of the original.
+    pub fn original_offset(&self, offset: usize) -> usize {
+        let index = self.runs.partition_point(|run| run.processed.end <= offset);
+        match self.runs.get(index) {
+            Some(run) if run.copied => run.original.start + (offset - run.processed.start),
+            Some(run) => run.original.start,
+            None => self.original_len,
+        }
+    }
+
+    /// The span in the original of `span` in the rewritten input, like from
+    /// [`Parser::span`][crate::Parser::span]
+    ///
+    /// Any replaced run that `span` overlaps is included in full while removed runs at either
+    /// edge are left out.
+    pub fn original_span(&self, span: Range<usize>) -> Range<usize> {
+        let start = self.original_offset(span.start);
+        if span.end <= span.start {
+            return start..start;
+        }
+        let index = self
+            .runs
+            .partition_point(|run| run.processed.end < span.end);
+        let end = match self.runs.get(index) {
+            Some(run) if run.copied => run.original.start + (span.end - run.processed.start),
+            Some(run) => run.original.end,
+            None => self.original_len,
+        };
+        start..end
+    }
+}
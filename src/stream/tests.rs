@@ -1,13 +1,12 @@
 #[cfg(feature = "std")]
 use proptest::prelude::*;
 
+#[cfg(feature = "std")]
+use crate::combinator::{separated, separated_pair};
 use crate::error::ErrMode::Backtrack;
 use crate::error::{ErrorKind, InputError};
 use crate::token::literal;
-use crate::{
-    combinator::{separated, separated_pair},
-    PResult, Parser,
-};
+use crate::{combinator::repeat, PResult, Parser};
 
 use super::*;
 
@@ -23,6 +22,102 @@ fn test_fxhashmap_compiles() {
     let _: rustc_hash::FxHashMap<char, char> = separated(0.., pair, ',').parse(input).unwrap();
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn test_unique_keys() {
+    fn entry<'i>(i: &mut &'i str) -> PResult<(&'i str, u32)> {
+        separated_pair(crate::ascii::alpha1, '=', crate::ascii::dec_uint).parse_next(i)
+    }
+
+    let map: HashMap<&str, u32> = separated(0.., entry, ',').parse("a=1,b=2,a=3").unwrap();
+    assert_eq!(map["a"], 3);
+
+    let unique: UniqueKeys<HashMap<&str, u32>, &str> =
+        separated(0.., entry, ',').parse("a=1,b=2,a=3,b=4").unwrap();
+    assert_eq!(unique.duplicate(), Some(&"a"));
+    assert_eq!(unique.clone().into_inner()["a"], 1);
+    assert_eq!(
+        unique.into_result().unwrap_err().to_string(),
+        "duplicate key `\"a\"`"
+    );
+
+    let unique: UniqueKeys<BTreeMap<&str, u32>, &str> =
+        separated(0.., entry, ',').parse("a=1,b=2").unwrap();
+    assert_eq!(
+        unique.into_result(),
+        Ok(BTreeMap::from([("a", 1), ("b", 2)]))
+    );
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn test_indexmap_accumulate() {
+    fn entry(i: &mut &str) -> PResult<(char, char)> {
+        separated_pair(crate::token::any, '=', crate::token::any).parse_next(i)
+    }
+
+    let map: indexmap::IndexMap<char, char, core::hash::BuildHasherDefault<rustc_hash::FxHasher>> =
+        separated(0.., entry, ',').parse("c=1,a=2,b=3").unwrap();
+    assert_eq!(map.keys().copied().collect::<Vec<_>>(), ['c', 'a', 'b']);
+
+    let set: indexmap::IndexSet<char, core::hash::BuildHasherDefault<rustc_hash::FxHasher>> =
+        separated(0.., crate::token::any::<_, InputError<_>>, ',')
+            .parse("c,a,c")
+            .unwrap();
+    assert_eq!(set.iter().copied().collect::<Vec<_>>(), ['c', 'a']);
+
+    let unique: UniqueKeys<
+        indexmap::IndexMap<char, char, core::hash::BuildHasherDefault<rustc_hash::FxHasher>>,
+        char,
+    > = separated(0.., entry, ',').parse("c=1,c=2").unwrap();
+    assert_eq!(unique.duplicate(), Some(&'c'));
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn test_smallvec_accumulate() {
+    let digits: smallvec::SmallVec<[char; 4]> = separated(
+        0..,
+        crate::token::one_of::<_, _, InputError<_>>('0'..='9'),
+        ',',
+    )
+    .parse("1,2,3")
+    .unwrap();
+    assert_eq!(digits.as_slice(), ['1', '2', '3']);
+    assert!(!digits.spilled());
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_heapless_accumulate() {
+    let digits: heapless::Vec<char, 4> = separated(
+        0..,
+        crate::token::one_of::<_, _, InputError<_>>('0'..='9'),
+        ',',
+    )
+    .parse("1,2,3")
+    .unwrap();
+    assert_eq!(digits.as_slice(), ['1', '2', '3']);
+
+    let word: heapless::String<3> = repeat(0.., crate::token::any::<_, InputError<_>>)
+        .parse("abc")
+        .unwrap();
+    assert_eq!(word.as_str(), "abc");
+
+    assert_eq!(
+        repeat::<_, _, heapless::String<3>, _, _>(0.., crate::token::any).parse_peek("abcd"),
+        Err(ErrMode::Cut(InputError::new("", ErrorKind::Many)))
+    );
+}
+
+#[test]
+fn test_bounded_vec_overflow() {
+    assert_eq!(
+        repeat::<_, _, BoundedVec<char, 2>, _, _>(0.., crate::token::any).parse_peek("abc"),
+        Err(ErrMode::Cut(InputError::new("", ErrorKind::Many)))
+    );
+}
+
 #[test]
 fn test_offset_u8() {
     let s = b"abcd123";
@@ -135,6 +230,323 @@ fn test_partial_complete() {
     assert!(i.is_partial(), "incomplete stream state should be restored");
 }
 
+#[cfg(feature = "std")]
+struct Trickle<'i>(&'i [u8]);
+
+#[cfg(feature = "std")]
+impl std::io::Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = buf.len().min(self.0.len()).min(1);
+        buf[..len].copy_from_slice(&self.0[..len]);
+        self.0 = &self.0[len..];
+        Ok(len)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_line_index() {
+    let source = "ab\nçd\n\ne";
+    let index = LineIndex::new(source);
+    assert_eq!(index.line_count(), 4);
+
+    let mut expected = Position::new(1, 1);
+    for (offset, c) in source.char_indices() {
+        assert_eq!(index.position(offset), expected, "offset {offset}");
+        if c == '\n' {
+            expected = Position::new(expected.line + 1, 1);
+        } else {
+            expected.column += 1;
+        }
+    }
+    assert_eq!(index.position(source.len()), Position::new(4, 2));
+    assert_eq!(index.position(100), Position::new(4, 2));
+
+    let index = LineIndex::new(&b"ab\n\xff\xfe"[..]);
+    assert_eq!(index.position(5), Position::new(2, 3));
+    assert_eq!(index.span(1..4), Position::new(1, 2)..Position::new(2, 2));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_interner() {
+    let mut interner = Interner::new();
+    assert!(interner.is_empty());
+    let a = interner.intern("a");
+    let b = interner.intern("b");
+    assert_eq!(interner.intern("a"), a);
+    assert_ne!(a, b);
+    assert_eq!(interner.get("b"), Some(b));
+    assert_eq!(interner.get("c"), None);
+    assert_eq!(interner.resolve(b), Some("b"));
+
+    let mut other = Interner::new();
+    let c = other.intern("c");
+    let other_a = other.intern("a");
+    let foreign = interner.intern("d");
+    assert_eq!(other.resolve(foreign), None);
+    let remap = interner.absorb(other);
+    assert_eq!(remap[other_a.index()], a);
+    assert_eq!(interner.resolve(remap[c.index()]), Some("c"));
+    assert_eq!(interner.len(), 4);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_span_map() {
+    // Line continuation removed and `&amp;` decoded: "ab\\\ncd&amp;e" -> "abcd&e"
+    let mut map = SpanMap::new();
+    map.replace(0, 0);
+    map.copy(2);
+    map.replace(2, 0);
+    map.copy(1);
+    map.copy(1);
+    map.replace(5, 1);
+    map.copy(1);
+    assert_eq!(map.processed_len(), 6);
+    assert_eq!(map.original_len(), 12);
+
+    let offsets = (0..=7).map(|o| map.original_offset(o)).collect::<Vec<_>>();
+    assert_eq!(offsets, [0, 1, 4, 5, 6, 11, 12, 12]);
+
+    assert_eq!(map.original_span(0..2), 0..2);
+    assert_eq!(map.original_span(2..4), 4..6);
+    assert_eq!(map.original_span(1..3), 1..5);
+    assert_eq!(map.original_span(4..5), 6..11);
+    assert_eq!(map.original_span(3..6), 5..12);
+    assert_eq!(map.original_span(2..2), 4..4);
+    assert_eq!(map.original_span(0..100), 0..12);
+
+    // Removed at the edges
+    let mut map = SpanMap::new();
+    map.replace(1, 0);
+    map.copy(3);
+    map.replace(1, 0);
+    assert_eq!(map.original_span(0..3), 1..4);
+    assert_eq!(map.original_offset(3), 5);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_read_stream() {
+    use crate::ascii::{alpha1, line_ending};
+    use crate::binary::{be_u8, length_take};
+    use crate::combinator::terminated;
+    use crate::error::ContextError;
+
+    fn word(i: &mut Partial<&[u8]>) -> PResult<Vec<u8>> {
+        terminated(alpha1, line_ending)
+            .map(|w: &[u8]| w.to_vec())
+            .parse_next(i)
+    }
+    let mut stream = ReadStream::with_capacity(1, Trickle(b"hello\nworld\n"));
+    assert_eq!(
+        stream.parse_next(&mut word).unwrap(),
+        Some(b"hello".to_vec())
+    );
+    assert_eq!(
+        stream.parse_next(&mut word).unwrap(),
+        Some(b"world".to_vec())
+    );
+    assert_eq!(stream.parse_next(&mut word).unwrap(), None);
+    assert!(stream.is_eof());
+
+    fn chunk(i: &mut Partial<&[u8]>) -> PResult<usize> {
+        length_take(be_u8).map(|c: &[u8]| c.len()).parse_next(i)
+    }
+    let mut stream = ReadStream::with_capacity(2, &b"\x05abcde\x00\x03xy"[..]);
+    assert_eq!(stream.watermark(), 0);
+    assert_eq!(stream.parse_next(&mut chunk).unwrap(), Some(5));
+    assert_eq!(stream.watermark(), 6);
+    assert_eq!(stream.parse_next(&mut chunk).unwrap(), Some(0));
+    assert_eq!(stream.watermark(), 7);
+    assert!(matches!(
+        stream.parse_next(&mut chunk),
+        Err(ReadStreamError::<ContextError>::Parse(_))
+    ));
+    assert_eq!(stream.watermark(), 7);
+    assert_eq!(stream.buffer(), b"\x03xy");
+    stream.compact();
+    assert_eq!(stream.watermark(), 7);
+    assert_eq!(stream.buffer(), b"\x03xy");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_read_stream_empty() {
+    use crate::ascii::alpha0;
+    use crate::error::ErrorKind;
+
+    fn word(i: &mut Partial<&[u8]>) -> PResult<usize, ErrorKind> {
+        alpha0.map(|w: &[u8]| w.len()).parse_next(i)
+    }
+    let mut stream = ReadStream::new(&b"123"[..]);
+    assert!(matches!(
+        stream.parse_next(&mut word),
+        Err(ReadStreamError::Parse(ErrorKind::InfiniteLoop))
+    ));
+    assert_eq!(stream.watermark(), 0);
+}
+
+#[cfg(feature = "std")]
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+fn read_numbers<R: std::io::Read>(mut stream: ReadStream<R>) -> Vec<u32> {
+    use crate::ascii::{dec_uint, line_ending};
+    use crate::combinator::terminated;
+
+    fn number(i: &mut Partial<&[u8]>) -> PResult<u32> {
+        terminated(dec_uint, line_ending).parse_next(i)
+    }
+    let mut numbers = Vec::new();
+    while let Some(n) = stream.parse_next(&mut number).unwrap() {
+        numbers.push(n);
+    }
+    numbers
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_read_stream_gzip() {
+    // `1\n22\n` and `333\n` as separate gzip members
+    let data = [
+        0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x33, 0xe4, 0x32, 0x32, 0xe2,
+        0x02, 0x00, 0xde, 0x0a, 0x94, 0x1f, 0x05, 0x00, 0x00, 0x00, 0x1f, 0x8b, 0x08, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x03, 0x33, 0x36, 0x36, 0xe6, 0x02, 0x00, 0xb4, 0x5f, 0x49, 0xf1,
+        0x04, 0x00, 0x00, 0x00,
+    ];
+    assert_eq!(read_numbers(ReadStream::gzip(Trickle(&data))), [1, 22, 333]);
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_read_stream_zstd() {
+    // `1\n22\n333\n`
+    let data = [
+        0x28, 0xb5, 0x2f, 0xfd, 0x04, 0x58, 0x49, 0x00, 0x00, 0x31, 0x0a, 0x32, 0x32, 0x0a, 0x33,
+        0x33, 0x33, 0x0a, 0xf5, 0x47, 0x7b, 0x68,
+    ];
+    assert_eq!(
+        read_numbers(ReadStream::zstd(Trickle(&data)).unwrap()),
+        [1, 22, 333]
+    );
+
+    assert!(ReadStream::zstd(&b"not zstd"[..]).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_iter_stream() {
+    use crate::binary::length_repeat;
+    use crate::error::ContextError;
+    use crate::token::any;
+
+    fn frame(i: &mut Partial<&[u32]>) -> PResult<Vec<u32>> {
+        length_repeat(any.map(|n: u32| n as usize), any).parse_next(i)
+    }
+    let mut stream = IterStream::new([2, 10, 20, 0, 1, 30].into_iter());
+    assert_eq!(stream.parse_next(&mut frame).unwrap(), Some(vec![10, 20]));
+    assert_eq!(stream.watermark(), 3);
+    assert_eq!(stream.parse_next(&mut frame).unwrap(), Some(vec![]));
+    stream.compact();
+    assert_eq!(stream.watermark(), 4);
+    assert_eq!(stream.parse_next(&mut frame).unwrap(), Some(vec![30]));
+    assert_eq!(stream.watermark(), 6);
+    assert_eq!(stream.parse_next(&mut frame).unwrap(), None);
+    assert!(stream.is_eof());
+
+    let mut stream = IterStream::new([2, 10].into_iter());
+    assert!(matches!(
+        stream.parse_next::<_, ContextError, _>(&mut frame),
+        Err(_)
+    ));
+    assert_eq!(stream.buffer(), [2, 10]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_iter_stream_empty() {
+    use crate::error::ErrorKind;
+    use crate::token::take_while;
+
+    fn zeros(i: &mut Partial<&[u32]>) -> PResult<usize, ErrorKind> {
+        take_while(0.., |n| n == 0)
+            .map(|z: &[u32]| z.len())
+            .parse_next(i)
+    }
+    let mut stream = IterStream::new([1, 2].into_iter());
+    assert_eq!(stream.parse_next(&mut zeros), Err(ErrorKind::InfiniteLoop));
+    assert_eq!(stream.watermark(), 0);
+}
+
+#[test]
+fn test_seek_to() {
+    let input = Located::new(&b"abcdef"[..]);
+    let mut input = Stateful {
+        input: Partial::new(input),
+        state: 1,
+    };
+    let _ = input.next_slice(4);
+
+    let forked = input.seek_to(2).unwrap();
+    assert_eq!(forked.location(), 2);
+    assert_eq!(**forked.input, &b"cdef"[..]);
+    assert!(forked.is_partial());
+    assert_eq!(forked.state, 1);
+
+    let forked = input.seek_to(6).unwrap();
+    assert_eq!(forked.location(), 6);
+    assert_eq!(forked.eof_offset(), 0);
+
+    assert!(input.seek_to(7).is_none());
+    assert_eq!(input.location(), 4, "original stream is untouched");
+}
+
+#[test]
+fn test_line_located() {
+    let mut input = LineLocated::new("ab\ncd\r\n\nλx");
+    assert_eq!(input.line_location(), Position::new(1, 1));
+
+    let _ = input.next_token();
+    assert_eq!(input.line_location(), Position::new(1, 2));
+    let start = input.checkpoint();
+
+    let _ = input.next_slice(2);
+    assert_eq!(input.line_location(), Position::new(2, 1));
+    let _ = input.next_slice(5);
+    assert_eq!(input.line_location(), Position::new(4, 1));
+    let _ = input.next_token();
+    assert_eq!(input.line_location(), Position::new(4, 2));
+    assert_eq!(input.location(), 10);
+
+    input.reset(&start);
+    assert_eq!(input.line_location(), Position::new(1, 2));
+    assert_eq!(input.location(), 1);
+
+    input.reset_to_start();
+    assert_eq!(input.line_location(), Position::new(1, 1));
+    assert_eq!(input.location(), 0);
+}
+
+#[test]
+fn test_line_span() {
+    use crate::ascii::{alpha1, line_ending};
+    use crate::combinator::{alt, terminated};
+
+    let mut input = LineLocated::new(&b"ab\ncd"[..]);
+    let span = terminated(alpha1::<_, InputError<_>>, line_ending)
+        .line_span()
+        .parse_next(&mut input);
+    assert_eq!(span, Ok(Position::new(1, 1)..Position::new(2, 1)));
+
+    // Backtracking restores the position
+    let span = alt((
+        (alpha1::<_, InputError<_>>, line_ending).line_span(),
+        alpha1.line_span(),
+    ))
+    .parse_next(&mut input);
+    assert_eq!(span, Ok(Position::new(2, 1)..Position::new(2, 3)));
+}
+
 #[test]
 fn test_custom_slice() {
     type Token = usize;
@@ -150,6 +562,126 @@ fn test_custom_slice() {
     assert_eq!(offset, 2);
 }
 
+#[test]
+fn test_token_slice() {
+    use crate::lib::std::ops::Range;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Token {
+        kind: char,
+        span: Range<usize>,
+    }
+
+    impl PartialEq<char> for Token {
+        fn eq(&self, other: &char) -> bool {
+            self.kind == *other
+        }
+    }
+
+    impl TokenSpan for Token {
+        fn token_span(&self) -> Range<usize> {
+            self.span.clone()
+        }
+    }
+
+    // Lexed from `a + b`
+    let tokens = [
+        Token {
+            kind: 'a',
+            span: 0..1,
+        },
+        Token {
+            kind: '+',
+            span: 2..3,
+        },
+        Token {
+            kind: 'b',
+            span: 4..5,
+        },
+    ];
+    let mut input = TokenSlice::new(&tokens);
+
+    let first = input.next_token().unwrap();
+    assert_eq!(first.kind, 'a');
+    assert_eq!(input.location(), 1);
+    assert_eq!(input.compare('+'), CompareResult::Ok(1));
+    assert_eq!(input.compare('b'), CompareResult::Error);
+
+    let start = input.checkpoint();
+    let rest = input.next_slice(2);
+    assert_eq!(rest, &tokens[1..]);
+    assert_eq!(input.offset_from(&start), 2);
+    assert_eq!(input.compare('b'), CompareResult::Incomplete);
+    input.reset(&start);
+    assert_eq!(input.location(), 1);
+
+    assert_eq!(input.source_span(0..3), 0..5);
+    assert_eq!(input.source_span(1..2), 2..3);
+    assert_eq!(input.source_span(1..1), 2..2);
+    assert_eq!(input.source_span(3..3), 5..5);
+
+    input.reset_to_start();
+    assert_eq!(input.location(), 0);
+    assert_eq!(input.source(), "");
+
+    let mut input = TokenSlice::with_source(&tokens, "a + b");
+    assert_eq!(input.token_text(&tokens[2]), "b");
+    assert_eq!(input.source_text(0..2), "a +");
+    assert_eq!(input.source_text(1..1), "");
+    input.next_token();
+    assert_eq!(input.source(), "a + b");
+    let plus = input.next_token().unwrap();
+    assert_eq!(input.token_text(plus), "+");
+}
+
+#[test]
+#[should_panic = "out of bounds"]
+fn test_token_slice_span_past_end() {
+    use crate::lib::std::ops::Range;
+
+    struct Token(Range<usize>);
+    impl TokenSpan for Token {
+        fn token_span(&self) -> Range<usize> {
+            self.0.clone()
+        }
+    }
+
+    let tokens = [Token(0..1), Token(2..3)];
+    let _ = TokenSlice::new(&tokens).source_span(1..3);
+}
+
+#[test]
+fn test_token_slice_inline_literals() {
+    use crate::error::ContextError;
+    use crate::token::any;
+
+    #[derive(Debug, PartialEq)]
+    struct Token(&'static str);
+
+    impl PartialEq<char> for Token {
+        fn eq(&self, other: &char) -> bool {
+            self.0.len() == other.len_utf8() && self.0.starts_with(*other)
+        }
+    }
+
+    impl PartialEq<&str> for Token {
+        fn eq(&self, other: &&str) -> bool {
+            self.0 == *other
+        }
+    }
+
+    fn call<'t>(i: &mut TokenSlice<'t, Token>) -> PResult<&'t str, ContextError> {
+        ("fn", any, '(', ')')
+            .map(|(_, name, _, _): (_, &Token, _, _)| name.0)
+            .parse_next(i)
+    }
+
+    let tokens = [Token("fn"), Token("main"), Token("("), Token(")")];
+    assert_eq!(call.parse(TokenSlice::new(&tokens)), Ok("main"));
+    let tokens = [Token("fn"), Token("main"), Token("["), Token(")")];
+    assert!(call.parse(TokenSlice::new(&tokens)).is_err());
+}
+
 #[test]
 fn test_literal_support_char() {
     assert_eq!(
@@ -226,3 +758,172 @@ fn test_literal_support_char() {
         Err(Backtrack(InputError::new(&b"\xCF\x80"[..], ErrorKind::Tag)))
     );
 }
+
+#[test]
+fn test_folded() {
+    use crate::error::ErrMode;
+    use crate::error::Needed;
+    use crate::token::{one_of, take_while};
+
+    fn fold(c: &char) -> char {
+        c.to_ascii_lowercase()
+    }
+    fn fold_byte(b: &u8) -> u8 {
+        b.to_ascii_lowercase()
+    }
+
+    assert_eq!(
+        literal::<_, _, InputError<_>>(Folded("Select", fold)).parse_peek("sELECT *"),
+        Ok((" *", "sELECT"))
+    );
+    assert_eq!(
+        literal::<_, _, InputError<_>>(Folded("select", fold)).parse_peek("sELEC"),
+        Err(Backtrack(InputError::new("sELEC", ErrorKind::Tag)))
+    );
+    assert_eq!(
+        literal::<_, _, InputError<_>>(Folded("select", fold)).parse_peek(Partial::new("sEL")),
+        Err(ErrMode::Incomplete(Needed::new(3)))
+    );
+    // `ẞ` is longer than `ß`, so the input is as long as the literal but still a prefix of it
+    let fold_lower = |c: &char| c.to_lowercase().next();
+    assert_eq!(
+        literal::<_, _, InputError<_>>(Folded("grüße", fold_lower))
+            .parse_peek(Partial::new("GRÜẞ")),
+        Err(ErrMode::Incomplete(Needed::Unknown))
+    );
+    assert_eq!(
+        literal::<_, _, InputError<_>>(Folded("select", fold)).parse_peek(Partial::new("sEX")),
+        Err(Backtrack(InputError::new(
+            Partial::new("sEX"),
+            ErrorKind::Tag
+        )))
+    );
+    assert_eq!(
+        literal::<_, _, InputError<_>>(Folded(&b"GET"[..], fold_byte)).parse_peek(&b"get /"[..]),
+        Ok((&b" /"[..], &b"get"[..]))
+    );
+
+    assert_eq!(
+        take_while::<_, _, InputError<_>>(1.., Folded(['x', 'Y'], fold)).parse_peek("xXyYz"),
+        Ok(("z", "xXyY"))
+    );
+    assert_eq!(
+        one_of::<_, _, InputError<_>>(Folded(&b"ab"[..], fold_byte)).parse_peek(&b"Bc"[..]),
+        Ok((&b"c"[..], b'B'))
+    );
+    let tokens = ["Fn", "x"];
+    assert_eq!(
+        one_of::<_, _, InputError<_>>(Folded(&["fn"], |t: &&str| t.to_ascii_lowercase()))
+            .parse_peek(TokenSlice::new(&tokens))
+            .map(|(_, t)| t),
+        Ok(&"Fn")
+    );
+}
+
+#[test]
+fn test_fueled() {
+    let mut input = Fueled::new("abcdef");
+    input.set_fuel(4);
+    let start = input.checkpoint();
+    assert_eq!(input.next_slice(2), "ab");
+    assert_eq!(input.fuel(), 2);
+    // Backtracking burns fuel
+    input.reset(&start);
+    assert_eq!(input.fuel(), 1);
+    // Resetting in place doesn't
+    input.reset(&start);
+    assert_eq!(input.fuel(), 1);
+    assert_eq!(input.next_token(), Some('a'));
+    assert!(!input.is_exhausted());
+    assert_eq!(input.next_token(), None);
+    assert!(input.is_exhausted());
+    assert_eq!(input.eof_offset(), 0);
+    assert_eq!(input.iter_offsets().count(), 0);
+    // Everything fails, even when the remaining input matches
+    assert!(literal::<_, _, InputError<_>>("bc")
+        .parse_next(&mut input)
+        .is_err());
+    assert_eq!(*input, "bcdef");
+
+    // An exhausted byte stream doesn't report a literal mismatch against the remaining input
+    let mut input = Fueled::new(&b"abc"[..]);
+    input.set_fuel(0);
+    assert_eq!(input.next_token(), None);
+    assert_eq!(input.mismatch_bytes(&&b"bc"[..], |_, _| ()), None);
+}
+
+#[test]
+fn test_with_fuel() {
+    fn letters<'i>(i: &mut Fueled<&'i str>) -> PResult<&'i str, InputError<Fueled<&'i str>>> {
+        crate::ascii::alpha1.parse_next(i)
+    }
+
+    // Unused fuel is left for the enclosing parser
+    let mut input = Fueled::new("abc def");
+    input.set_fuel(10);
+    assert_eq!(letters.with_fuel(5).parse_next(&mut input), Ok("abc"));
+    assert_eq!(input.fuel(), 7);
+
+    // Nesting can only lower the budget
+    let mut input = Fueled::new("abc def");
+    input.set_fuel(2);
+    let err = letters.with_fuel(5).parse_next(&mut input).unwrap_err();
+    assert!(matches!(err, ErrMode::Cut(ref e) if e.kind == ErrorKind::Fuel));
+    assert!(input.is_exhausted());
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn test_graphemes() {
+    use crate::token::{any, one_of, take_while};
+
+    // `e` with a combining acute, then a flag made of two regional indicators
+    let text = "e\u{301}\u{1f1e8}\u{1f1e6}ab";
+    let input = Graphemes::new(text);
+    assert_eq!(input.iter_offsets().count(), 4);
+    assert_eq!(input.offset_at(2), Ok(11));
+    assert_eq!(input.offset_at(4), Ok(text.len()));
+    assert_eq!(input.offset_at(5), Err(Needed::Unknown));
+
+    // Slices may not split a grapheme, even on a `char` boundary
+    let mut input = Graphemes::new(text);
+    assert_eq!(input.try_next_slice(1), None);
+    assert_eq!(input.try_next_slice(4), None);
+    assert_eq!(input.try_next_slice(3), Some("e\u{301}"));
+
+    let mut input = Graphemes::new(text);
+    assert_eq!(
+        any::<_, InputError<_>>.parse_next(&mut input),
+        Ok("e\u{301}")
+    );
+    assert_eq!(
+        one_of::<_, _, InputError<_>>(["\u{1f1e8}\u{1f1e6}", "x"]).parse_next(&mut input),
+        Ok("\u{1f1e8}\u{1f1e6}")
+    );
+    assert_eq!(
+        one_of::<_, _, InputError<_>>('a').parse_next(&mut input),
+        Ok("a")
+    );
+    assert_eq!(input.into_inner(), "b");
+
+    // A lone `char` doesn't match the start of a longer grapheme
+    let mut input = Graphemes::new(text);
+    assert!(one_of::<_, _, InputError<_>>('e')
+        .parse_next(&mut input)
+        .is_err());
+    assert!(literal::<_, _, InputError<_>>("e")
+        .parse_next(&mut input)
+        .is_err());
+    assert_eq!(
+        literal::<_, _, InputError<_>>("e\u{301}").parse_next(&mut input),
+        Ok("e\u{301}")
+    );
+
+    let mut input = Graphemes::new(text);
+    assert_eq!(
+        take_while::<_, _, InputError<_>>(0.., |g: &str| g.chars().count() > 1)
+            .parse_next(&mut input),
+        Ok("e\u{301}\u{1f1e8}\u{1f1e6}")
+    );
+    assert_eq!(input.into_inner(), "ab");
+}
@@ -0,0 +1,433 @@
+use crate::error::Needed;
+use crate::lib::std::iter::Enumerate;
+use crate::lib::std::ops::Range;
+use crate::lib::std::slice::Iter;
+use crate::stream::Checkpoint;
+use crate::stream::Compare;
+use crate::stream::CompareResult;
+use crate::stream::Location;
+use crate::stream::Offset;
+use crate::stream::SliceLen;
+use crate::stream::Stream;
+use crate::stream::StreamIsPartial;
+use crate::stream::UpdateSlice;
+use core::num::NonZeroUsize;
+
+/// Parse a slice of tokens, like the output of a lexer
+///
+/// Compared to parsing `&[T]` directly:
+/// - [`Stream::Token`] is `&T`, not requiring `T: Clone`
+/// - [`Location`] is the index of the current token, which can be mapped back to the original
+///   source with [`TokenSlice::source_span`]
+/// - [`Compare`] checks the next token with [`PartialEq`], allowing [`literal`] to match a
+///   token kind.  When tokens implement `PartialEq<char>`, `PartialEq<u8>`, or
+///   `PartialEq<&str>`, the literal [`Parser`][crate::Parser] impls work too, so grammars can
+///   still write `'('` and `"fn"` inline.
+///
+/// [`ContainsToken`] for [`one_of`] and [`take_while`] can be provided by implementing it for
+/// your token kind (and arrays of them).
+///
+/// [`ContainsToken`]: crate::stream::ContainsToken
+/// [`literal`]: crate::token::literal
+/// [`one_of`]: crate::token::one_of
+/// [`take_while`]: crate::token::take_while
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::ContextError;
+/// use winnow::combinator::separated;
+/// use winnow::stream::ContainsToken;
+/// use winnow::stream::SliceLen;
+/// use winnow::stream::TokenSlice;
+/// use winnow::stream::TokenSpan;
+/// use winnow::token::literal;
+/// use winnow::token::one_of;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// enum Kind {
+///     Ident,
+///     Comma,
+/// }
+///
+/// #[derive(Clone, Debug, PartialEq, Eq)]
+/// struct Token {
+///     kind: Kind,
+///     span: std::ops::Range<usize>,
+/// }
+///
+/// impl PartialEq<Kind> for Token {
+///     fn eq(&self, other: &Kind) -> bool {
+///         self.kind == *other
+///     }
+/// }
+///
+/// impl SliceLen for Kind {
+///     fn slice_len(&self) -> usize {
+///         1
+///     }
+/// }
+///
+/// impl ContainsToken<&'_ Token> for Kind {
+///     fn contains_token(&self, token: &'_ Token) -> bool {
+///         token.kind == *self
+///     }
+/// }
+///
+/// impl TokenSpan for Token {
+///     fn token_span(&self) -> std::ops::Range<usize> {
+///         self.span.clone()
+///     }
+/// }
+///
+/// type Tokens<'t> = TokenSlice<'t, Token>;
+///
+/// fn idents(i: &mut Tokens<'_>) -> PResult<Vec<std::ops::Range<usize>>> {
+///     separated(1.., one_of(Kind::Ident).map(|t: &Token| t.span.clone()), literal(Kind::Comma))
+///         .parse_next(i)
+/// }
+///
+/// // Lexed from `a, bc`
+/// let tokens = [
+///     Token { kind: Kind::Ident, span: 0..1 },
+///     Token { kind: Kind::Comma, span: 1..2 },
+///     Token { kind: Kind::Ident, span: 3..5 },
+/// ];
+/// let input = TokenSlice::new(&tokens);
+/// assert_eq!(idents.parse(input), Ok(vec![0..1, 3..5]));
+///
+/// let (_, (_, tokens_span)) = idents.with_span().parse_peek(input).unwrap();
+/// assert_eq!(tokens_span, 0..3);
+/// assert_eq!(input.source_span(tokens_span), 0..5);
+/// ```
+pub struct TokenSlice<'t, T> {
+    initial: &'t [T],
+    input: &'t [T],
+    source: &'t str,
+}
+
+impl<'t, T> TokenSlice<'t, T> {
+    /// Wrap the tokens to be parsed
+    #[inline]
+    pub fn new(input: &'t [T]) -> Self {
+        Self::with_source(input, "")
+    }
+
+    /// Wrap the tokens to be parsed, along with the source they were lexed from
+    ///
+    /// This lets parsers look up the text of a token with [`TokenSlice::token_text`], like to
+    /// check that identifiers are the same, without storing it in every token.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use winnow::prelude::*;
+    /// # use winnow::error::ErrMode;
+    /// # use winnow::error::ErrorKind;
+    /// # use winnow::error::ParserError;
+    /// use winnow::stream::TokenSlice;
+    /// use winnow::stream::TokenSpan;
+    /// use winnow::token::any;
+    ///
+    /// #[derive(Clone, Debug, PartialEq, Eq)]
+    /// struct Token {
+    ///     kind: char,
+    ///     span: std::ops::Range<usize>,
+    /// }
+    ///
+    /// impl TokenSpan for Token {
+    ///     fn token_span(&self) -> std::ops::Range<usize> {
+    ///         self.span.clone()
+    ///     }
+    /// }
+    ///
+    /// impl PartialEq<char> for Token {
+    ///     fn eq(&self, other: &char) -> bool {
+    ///         self.kind == *other
+    ///     }
+    /// }
+    ///
+    /// type Tokens<'t> = TokenSlice<'t, Token>;
+    ///
+    /// /// `begin name x end name`, where the names must match
+    /// fn block<'t>(i: &mut Tokens<'t>) -> PResult<&'t str> {
+    ///     let (_, open, _, _) = ('b', any, 'x', 'e').parse_next(i)?;
+    ///     let close = any.parse_next(i)?;
+    ///     if i.token_text(open) != i.token_text(close) {
+    ///         return Err(ErrMode::from_error_kind(i, ErrorKind::Verify));
+    ///     }
+    ///     Ok(i.token_text(open))
+    /// }
+    ///
+    /// let token = |kind, span| Token { kind, span };
+    /// let source = "begin main x end main";
+    /// let tokens = [
+    ///     token('b', 0..5),
+    ///     token('i', 6..10),
+    ///     token('x', 11..12),
+    ///     token('e', 13..16),
+    ///     token('i', 17..21),
+    /// ];
+    /// assert_eq!(block.parse(TokenSlice::with_source(&tokens, source)), Ok("main"));
+    ///
+    /// let source = "begin main x end test";
+    /// assert!(block.parse(TokenSlice::with_source(&tokens, source)).is_err());
+    /// ```
+    #[inline]
+    pub fn with_source(input: &'t [T], source: &'t str) -> Self {
+        Self {
+            initial: input,
+            input,
+            source,
+        }
+    }
+
+    /// The source the tokens were lexed from, see [`TokenSlice::with_source`]
+    ///
+    /// This is empty for [`TokenSlice::new`].
+    #[inline(always)]
+    pub fn source(&self) -> &'t str {
+        self.source
+    }
+
+    /// The text of the source `token` was lexed from
+    ///
+    /// # Panics
+    ///
+    /// When the token's span is not within [`TokenSlice::source`]
+    #[inline]
+    pub fn token_text(&self, token: &T) -> &'t str
+    where
+        T: TokenSpan,
+    {
+        &self.source[token.token_span()]
+    }
+
+    /// The text of the source covered by a range of token indices, see
+    /// [`TokenSlice::source_span`]
+    ///
+    /// # Panics
+    ///
+    /// When `tokens` is out of bounds, see [`TokenSlice::source_span`], or the span is not within
+    /// [`TokenSlice::source`]
+    #[inline]
+    pub fn source_text(&self, tokens: Range<usize>) -> &'t str
+    where
+        T: TokenSpan,
+    {
+        &self.source[self.source_span(tokens)]
+    }
+
+    /// Reset the stream to the start
+    #[inline]
+    pub fn reset_to_start(&mut self) {
+        self.input = self.initial;
+    }
+
+    /// Map a range of token indices, like from [`Parser::span`][crate::Parser::span], to the
+    /// range of the original source they cover
+    ///
+    /// An empty range maps to an empty range where the token would be.
+    ///
+    /// # Panics
+    ///
+    /// When `tokens` is descending or ends past the last token
+    pub fn source_span(&self, tokens: Range<usize>) -> Range<usize>
+    where
+        T: TokenSpan,
+    {
+        assert!(
+            tokens.start <= tokens.end && tokens.end <= self.initial.len(),
+            "token range {tokens:?} is out of bounds for {} tokens",
+            self.initial.len()
+        );
+        if tokens.start < tokens.end {
+            let start = self.initial[tokens.start].token_span().start;
+            start..self.initial[tokens.end - 1].token_span().end
+        } else if let Some(next) = self.initial.get(tokens.start) {
+            let start = next.token_span().start;
+            start..start
+        } else {
+            let end = self.initial.last().map(|t| t.token_span().end).unwrap_or(0);
+            end..end
+        }
+    }
+}
+
+/// The range of the original source a token was lexed from
+///
+/// See [`TokenSlice::source_span`]
+pub trait TokenSpan {
+    /// The range of the original source a token was lexed from
+    fn token_span(&self) -> Range<usize>;
+}
+
+impl<T> Clone for TokenSlice<'_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TokenSlice<'_, T> {}
+
+impl<T: PartialEq> PartialEq for TokenSlice<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.input == other.input
+    }
+}
+
+impl<T: Eq> Eq for TokenSlice<'_, T> {}
+
+impl<'t, T> crate::lib::std::ops::Deref for TokenSlice<'t, T> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.input
+    }
+}
+
+impl<T: crate::lib::std::fmt::Debug> crate::lib::std::fmt::Debug for TokenSlice<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut crate::lib::std::fmt::Formatter<'_>) -> crate::lib::std::fmt::Result {
+        self.input.fmt(f)
+    }
+}
+
+impl<T> SliceLen for TokenSlice<'_, T> {
+    #[inline(always)]
+    fn slice_len(&self) -> usize {
+        self.input.len()
+    }
+}
+
+impl<'t, T> Stream for TokenSlice<'t, T>
+where
+    T: crate::lib::std::fmt::Debug,
+{
+    type Token = &'t T;
+    type Slice = &'t [T];
+
+    type IterOffsets = Enumerate<Iter<'t, T>>;
+
+    type Checkpoint = Checkpoint<&'t [T], Self>;
+
+    #[inline(always)]
+    fn iter_offsets(&self) -> Self::IterOffsets {
+        self.input.iter().enumerate()
+    }
+    #[inline(always)]
+    fn eof_offset(&self) -> usize {
+        self.input.len()
+    }
+
+    #[inline(always)]
+    fn next_token(&mut self) -> Option<Self::Token> {
+        let (token, next) = self.input.split_first()?;
+        self.input = next;
+        Some(token)
+    }
+
+    #[inline(always)]
+    fn offset_for<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Token) -> bool,
+    {
+        self.input.iter().position(predicate)
+    }
+    #[inline(always)]
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        if let Some(needed) = tokens
+            .checked_sub(self.input.len())
+            .and_then(NonZeroUsize::new)
+        {
+            Err(Needed::Size(needed))
+        } else {
+            Ok(tokens)
+        }
+    }
+    #[inline(always)]
+    fn next_slice(&mut self, offset: usize) -> Self::Slice {
+        let (slice, next) = self.input.split_at(offset);
+        self.input = next;
+        slice
+    }
+
+    #[inline(always)]
+    fn checkpoint(&self) -> Self::Checkpoint {
+        Checkpoint::<_, Self>::new(self.input)
+    }
+    #[inline(always)]
+    fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+        self.input = checkpoint.inner;
+    }
+
+    #[inline(always)]
+    fn raw(&self) -> &dyn crate::lib::std::fmt::Debug {
+        self
+    }
+}
+
+impl<T> Location for TokenSlice<'_, T> {
+    #[inline(always)]
+    fn location(&self) -> usize {
+        self.input.offset_from(&self.initial)
+    }
+}
+
+impl<T> StreamIsPartial for TokenSlice<'_, T> {
+    type PartialState = ();
+
+    fn complete(&mut self) -> Self::PartialState {}
+
+    fn restore_partial(&mut self, _state: Self::PartialState) {}
+
+    #[inline(always)]
+    fn is_partial_supported() -> bool {
+        false
+    }
+}
+
+impl<T> Offset for TokenSlice<'_, T> {
+    #[inline(always)]
+    fn offset_from(&self, start: &Self) -> usize {
+        self.input.offset_from(&start.input)
+    }
+}
+
+impl<'t, T> Offset<<TokenSlice<'t, T> as Stream>::Checkpoint> for TokenSlice<'t, T>
+where
+    T: crate::lib::std::fmt::Debug,
+{
+    #[inline(always)]
+    fn offset_from(&self, other: &<TokenSlice<'t, T> as Stream>::Checkpoint) -> usize {
+        self.checkpoint().offset_from(other)
+    }
+}
+
+impl<T, K> Compare<K> for TokenSlice<'_, T>
+where
+    T: PartialEq<K>,
+{
+    #[inline]
+    fn compare(&self, t: K) -> CompareResult {
+        match self.input.first() {
+            Some(token) if *token == t => CompareResult::Ok(1),
+            Some(_) => CompareResult::Error,
+            None => CompareResult::Incomplete,
+        }
+    }
+}
+
+impl<T> UpdateSlice for TokenSlice<'_, T>
+where
+    T: crate::lib::std::fmt::Debug,
+{
+    #[inline(always)]
+    fn update_slice(mut self, inner: Self::Slice) -> Self {
+        self.input = inner;
+        self
+    }
+}
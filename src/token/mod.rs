@@ -10,9 +10,11 @@ use crate::error::ErrorKind;
 use crate::error::Needed;
 use crate::error::ParserError;
 use crate::lib::std::result::Result::Ok;
+#[cfg(feature = "regex")]
+use crate::stream::AsBStr;
 use crate::stream::Range;
+use crate::stream::{AsChar, StreamIsPartial, ToUsize};
 use crate::stream::{Compare, CompareResult, ContainsToken, FindSlice, SliceLen, Stream};
-use crate::stream::{StreamIsPartial, ToUsize};
 use crate::PResult;
 use crate::Parser;
 
@@ -186,17 +188,21 @@ fn literal_<T, I, Error: ParserError<I>, const PARTIAL: bool>(
 where
     I: StreamIsPartial,
     I: Stream + Compare<T>,
-    T: SliceLen + crate::lib::std::fmt::Debug,
+    T: SliceLen + Clone + crate::lib::std::fmt::Debug,
 {
     let literal_len = t.slice_len();
-    match i.compare(t) {
+    match i.compare(t.clone()) {
         CompareResult::Ok(len) => Ok(i.next_slice(len)),
         CompareResult::Incomplete if PARTIAL && i.is_partial() => Err(ErrMode::Incomplete(
-            Needed::new(literal_len - i.eof_offset()),
+            Needed::new(literal_len.saturating_sub(i.eof_offset())),
         )),
         CompareResult::Incomplete | CompareResult::Error => {
-            let e: ErrorKind = ErrorKind::Tag;
-            Err(ErrMode::from_error_kind(i, e))
+            let e = i
+                .mismatch_bytes(&t, |expected, found| {
+                    Error::from_literal_mismatch(i, expected, found)
+                })
+                .unwrap_or_else(|| Error::from_error_kind(i, ErrorKind::Tag));
+            Err(ErrMode::Backtrack(e))
         }
     }
 }
@@ -339,6 +345,9 @@ where
 /// of range (m <= len <= n).
 ///
 /// *[Partial version][crate::_topic::partial]* will return a `ErrMode::Incomplete(Needed::new(1))` if a member of the set of tokens reaches the end of the input or is too short.
+/// With an upper bound, the available tokens are returned as soon as `n` of them match, without
+/// waiting for more input.  When fewer than `m` are available, the [`Needed`] is how many more
+/// tokens are required to reach `m`.
 ///
 /// To take a series of tokens, use [`repeat`][crate::combinator::repeat] to [`Accumulate`][crate::stream::Accumulate] into a `()` and then [`Parser::take`].
 ///
@@ -534,9 +543,9 @@ where
     T: ContainsToken<<I as Stream>::Token>,
 {
     if PARTIAL && input.is_partial() {
-        take_till0_partial(input, |c| !list.contains_token(c))
+        take_till0_partial(input, |i| i.offset_for(|c| !list.contains_token(c)))
     } else {
-        take_till0_complete(input, |c| !list.contains_token(c))
+        take_till0_complete(input, |i| i.offset_for(|c| !list.contains_token(c)))
     }
 }
 
@@ -550,9 +559,9 @@ where
     T: ContainsToken<<I as Stream>::Token>,
 {
     if PARTIAL && input.is_partial() {
-        take_till1_partial(input, |c| !list.contains_token(c))
+        take_till1_partial(input, |i| i.offset_for(|c| !list.contains_token(c)))
     } else {
-        take_till1_complete(input, |c| !list.contains_token(c))
+        take_till1_complete(input, |i| i.offset_for(|c| !list.contains_token(c)))
     }
 }
 
@@ -570,30 +579,87 @@ where
     take_till_m_n::<_, _, _, PARTIAL>(input, m, n, |c| !list.contains_token(c))
 }
 
-fn take_till0_partial<P, I: Stream, E: ParserError<I>>(
+/// Recognize the longest (m <= len <= n) input slice that matches a `predicate` that carries
+/// `state` from one token to the next
+///
+/// Each parse starts with a clone of `init`.  The `predicate` is called on each token in order,
+/// including the one it stops at, like for knowing whether a token is quoted.
+///
+/// *Complete version*: It will return `Err(ErrMode::Backtrack(InputError::new(_, ErrorKind::Slice)))` if the pattern wasn't met or is out
+/// of range (m <= len <= n).
+///
+/// *[Partial version][crate::_topic::partial]* will return a `ErrMode::Incomplete(Needed::new(1))` if the pattern reaches the end of the input or is too short.
+///
+/// See also [`take_while`] for a predicate without state.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// use winnow::token::take_while_state;
+///
+/// // A CSV field, which may contain commas when quoted
+/// fn field<'i>(input: &mut &'i str) -> PResult<&'i str> {
+///     take_while_state(0.., false, |quoted: &mut bool, c: char| {
+///         if c == '"' {
+///             *quoted = !*quoted;
+///         }
+///         *quoted || (c != ',' && c != '\n')
+///     })
+///     .parse_next(input)
+/// }
+///
+/// assert_eq!(field.parse_peek("a,b"), Ok((",b", "a")));
+/// assert_eq!(field.parse_peek(r#""a,""b",c"#), Ok((",c", r#""a,""b""#)));
+/// ```
+#[inline(always)]
+pub fn take_while_state<State, Predicate, Input, Error>(
+    occurrences: impl Into<Range>,
+    init: State,
+    mut predicate: Predicate,
+) -> impl Parser<Input, <Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream,
+    State: Clone,
+    Predicate: FnMut(&mut State, <Input as Stream>::Token) -> bool,
+    Error: ParserError<Input>,
+{
+    let Range {
+        start_inclusive,
+        end_inclusive,
+    } = occurrences.into();
+    trace("take_while_state", move |i: &mut Input| {
+        let mut state = init.clone();
+        let end = end_inclusive.unwrap_or(usize::MAX);
+        let stop = |c| !predicate(&mut state, c);
+        if <Input as StreamIsPartial>::is_partial_supported() {
+            take_till_m_n::<_, _, _, true>(i, start_inclusive, end, stop)
+        } else {
+            take_till_m_n::<_, _, _, false>(i, start_inclusive, end, stop)
+        }
+    })
+}
+
+fn take_till0_partial<F, I: Stream, E: ParserError<I>>(
     input: &mut I,
-    predicate: P,
+    find: F,
 ) -> PResult<<I as Stream>::Slice, E>
 where
-    P: Fn(I::Token) -> bool,
+    F: FnOnce(&I) -> Option<usize>,
 {
-    let offset = input
-        .offset_for(predicate)
-        .ok_or_else(|| ErrMode::Incomplete(Needed::new(1)))?;
+    let offset = find(input).ok_or_else(|| ErrMode::Incomplete(Needed::new(1)))?;
     Ok(input.next_slice(offset))
 }
 
-fn take_till1_partial<P, I: Stream, E: ParserError<I>>(
+fn take_till1_partial<F, I: Stream, E: ParserError<I>>(
     input: &mut I,
-    predicate: P,
+    find: F,
 ) -> PResult<<I as Stream>::Slice, E>
 where
-    P: Fn(I::Token) -> bool,
+    F: FnOnce(&I) -> Option<usize>,
 {
     let e: ErrorKind = ErrorKind::Slice;
-    let offset = input
-        .offset_for(predicate)
-        .ok_or_else(|| ErrMode::Incomplete(Needed::new(1)))?;
+    let offset = find(input).ok_or_else(|| ErrMode::Incomplete(Needed::new(1)))?;
     if offset == 0 {
         Err(ErrMode::from_error_kind(input, e))
     } else {
@@ -601,30 +667,26 @@ where
     }
 }
 
-fn take_till0_complete<P, I: Stream, E: ParserError<I>>(
+fn take_till0_complete<F, I: Stream, E: ParserError<I>>(
     input: &mut I,
-    predicate: P,
+    find: F,
 ) -> PResult<<I as Stream>::Slice, E>
 where
-    P: Fn(I::Token) -> bool,
+    F: FnOnce(&I) -> Option<usize>,
 {
-    let offset = input
-        .offset_for(predicate)
-        .unwrap_or_else(|| input.eof_offset());
+    let offset = find(input).unwrap_or_else(|| input.eof_offset());
     Ok(input.next_slice(offset))
 }
 
-fn take_till1_complete<P, I: Stream, E: ParserError<I>>(
+fn take_till1_complete<F, I: Stream, E: ParserError<I>>(
     input: &mut I,
-    predicate: P,
+    find: F,
 ) -> PResult<<I as Stream>::Slice, E>
 where
-    P: Fn(I::Token) -> bool,
+    F: FnOnce(&I) -> Option<usize>,
 {
     let e: ErrorKind = ErrorKind::Slice;
-    let offset = input
-        .offset_for(predicate)
-        .unwrap_or_else(|| input.eof_offset());
+    let offset = find(input).unwrap_or_else(|| input.eof_offset());
     if offset == 0 {
         Err(ErrMode::from_error_kind(input, e))
     } else {
@@ -636,12 +698,12 @@ fn take_till_m_n<P, I, Error: ParserError<I>, const PARTIAL: bool>(
     input: &mut I,
     m: usize,
     n: usize,
-    predicate: P,
+    mut predicate: P,
 ) -> PResult<<I as Stream>::Slice, Error>
 where
     I: StreamIsPartial,
     I: Stream,
-    P: Fn(I::Token) -> bool,
+    P: FnMut(I::Token) -> bool,
 {
     if n < m {
         return Err(ErrMode::assert(
@@ -669,11 +731,7 @@ where
         if final_count == n {
             Ok(input.finish())
         } else {
-            let needed = if m > input.eof_offset() {
-                m - input.eof_offset()
-            } else {
-                1
-            };
+            let needed = if m > final_count { m - final_count } else { 1 };
             Err(ErrMode::Incomplete(Needed::new(needed)))
         }
     } else {
@@ -761,16 +819,16 @@ where
         match (start_inclusive, end_inclusive) {
             (0, None) => {
                 if <Input as StreamIsPartial>::is_partial_supported() {
-                    take_till0_partial(i, |c| set.contains_token(c))
+                    take_till0_partial(i, |i| i.offset_for_set(&set))
                 } else {
-                    take_till0_complete(i, |c| set.contains_token(c))
+                    take_till0_complete(i, |i| i.offset_for_set(&set))
                 }
             }
             (1, None) => {
                 if <Input as StreamIsPartial>::is_partial_supported() {
-                    take_till1_partial(i, |c| set.contains_token(c))
+                    take_till1_partial(i, |i| i.offset_for_set(&set))
                 } else {
-                    take_till1_complete(i, |c| set.contains_token(c))
+                    take_till1_complete(i, |i| i.offset_for_set(&set))
                 }
             }
             (start, end) => {
@@ -1098,3 +1156,338 @@ where
         None => Err(ErrMode::from_error_kind(i, ErrorKind::Slice)),
     }
 }
+
+/// Recognize a nested structure, from its opening `open` through the matching `close`
+///
+/// This only counts delimiters, without parsing the contents, so sub-documents can be skipped
+/// cheaply before deciding whether to parse them fully.  Use [`TakeBalanced::escape`] and
+/// [`TakeBalanced::quote`] to ignore delimiters that are escaped or inside quoted strings.
+///
+/// *Complete version*: It will return `Err(ErrMode::Backtrack(_))` if the structure is not closed.
+///
+/// *[Partial version][crate::_topic::partial]*: will return a `ErrMode::Incomplete(Needed::Unknown)` if the structure is not closed.
+///
+/// # Panic
+///
+/// Panics if `open` and `close` are the same.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
+/// # use winnow::prelude::*;
+/// use winnow::token::take_balanced;
+///
+/// fn object(s: &str) -> IResult<&str, &str> {
+///   take_balanced('{', '}').escape('\\').quote('"').parse_peek(s)
+/// }
+///
+/// assert_eq!(object(r#"{"a": {"b": "}"}}, 1"#), Ok((", 1", r#"{"a": {"b": "}"}}"#)));
+/// assert_eq!(object(r#"{"a": "\"}"}"#), Ok(("", r#"{"a": "\"}"}"#)));
+/// assert_eq!(object("{{}"), Err(ErrMode::Backtrack(InputError::new("{{}", ErrorKind::Slice))));
+/// assert_eq!(object("[]"), Err(ErrMode::Backtrack(InputError::new("[]", ErrorKind::Tag))));
+/// ```
+///
+/// ```rust
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
+/// # use winnow::prelude::*;
+/// # use winnow::Partial;
+/// use winnow::token::take_balanced;
+///
+/// fn list(s: Partial<&[u8]>) -> IResult<Partial<&[u8]>, &[u8]> {
+///   take_balanced('[', ']').parse_peek(s)
+/// }
+///
+/// assert_eq!(list(Partial::new(b"[1, [2]]3")), Ok((Partial::new(&b"3"[..]), &b"[1, [2]]"[..])));
+/// assert_eq!(list(Partial::new(b"[1, [2]")), Err(ErrMode::Incomplete(Needed::Unknown)));
+/// ```
+#[inline(always)]
+pub fn take_balanced<Input, Error>(open: char, close: char) -> TakeBalanced<Input, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar,
+    Error: ParserError<Input>,
+{
+    assert_ne!(open, close, "`take_balanced` needs distinct delimiters");
+    TakeBalanced {
+        open,
+        close,
+        escape: None,
+        quote: None,
+        i: Default::default(),
+        e: Default::default(),
+    }
+}
+
+/// Implementation of [`take_balanced`]
+pub struct TakeBalanced<I, E> {
+    open: char,
+    close: char,
+    escape: Option<char>,
+    quote: Option<char>,
+    i: core::marker::PhantomData<I>,
+    e: core::marker::PhantomData<E>,
+}
+
+impl<I, E> TakeBalanced<I, E> {
+    /// Ignore the token following `escape`
+    #[inline(always)]
+    pub fn escape(mut self, escape: char) -> Self {
+        self.escape = Some(escape);
+        self
+    }
+
+    /// Ignore delimiters between a pair of `quote`s
+    #[inline(always)]
+    pub fn quote(mut self, quote: char) -> Self {
+        self.quote = Some(quote);
+        self
+    }
+}
+
+impl<I, E> Parser<I, <I as Stream>::Slice, E> for TakeBalanced<I, E>
+where
+    I: StreamIsPartial + Stream,
+    <I as Stream>::Token: AsChar,
+    E: ParserError<I>,
+{
+    #[inline(always)]
+    fn parse_next(&mut self, input: &mut I) -> PResult<<I as Stream>::Slice, E> {
+        trace("take_balanced", |i: &mut I| {
+            if <I as StreamIsPartial>::is_partial_supported() {
+                take_balanced_::<_, _, true>(i, self)
+            } else {
+                take_balanced_::<_, _, false>(i, self)
+            }
+        })
+        .parse_next(input)
+    }
+}
+
+fn take_balanced_<I, Error: ParserError<I>, const PARTIAL: bool>(
+    i: &mut I,
+    delimiters: &TakeBalanced<I, Error>,
+) -> PResult<<I as Stream>::Slice, Error>
+where
+    I: StreamIsPartial,
+    I: Stream,
+    <I as Stream>::Token: AsChar,
+{
+    let mut depth = 0usize;
+    let mut escaped = false;
+    let mut quoted = false;
+    let mut closed = false;
+    for (offset, token) in i.iter_offsets() {
+        if closed {
+            return Ok(i.next_slice(offset));
+        }
+        let c = token.as_char();
+        if depth == 0 {
+            if c != delimiters.open {
+                return Err(ErrMode::from_error_kind(i, ErrorKind::Tag));
+            }
+            depth = 1;
+        } else if escaped {
+            escaped = false;
+        } else if Some(c) == delimiters.escape {
+            escaped = true;
+        } else if quoted {
+            quoted = Some(c) != delimiters.quote;
+        } else if Some(c) == delimiters.quote {
+            quoted = true;
+        } else if c == delimiters.open {
+            depth += 1;
+        } else if c == delimiters.close {
+            depth -= 1;
+            closed = depth == 0;
+        }
+    }
+    if closed {
+        return Ok(i.finish());
+    }
+    if PARTIAL && i.is_partial() {
+        if depth == 0 {
+            Err(ErrMode::Incomplete(Needed::new(1)))
+        } else {
+            Err(ErrMode::Incomplete(Needed::Unknown))
+        }
+    } else if depth == 0 {
+        Err(ErrMode::from_error_kind(i, ErrorKind::Tag))
+    } else {
+        Err(ErrMode::from_error_kind(i, ErrorKind::Slice))
+    }
+}
+
+/// Recognize the input slice matched by a regular expression
+///
+/// The match is anchored at the current location and follows the
+/// [`regex`](https://docs.rs/regex) crate's syntax and leftmost-first semantics.  As matching is
+/// done on bytes, a pattern that isn't Unicode-aware, like `(?-u:.)`, can match part of a `char`;
+/// for `&str` input, that is reported as an error.
+///
+/// The pattern is compiled each time `regex` is called, which is far slower than running the
+/// parser.  Call it once and reuse the parser, rather than from inside a parser function that runs
+/// for every token.
+///
+/// *Complete version*: Will return an error if the pattern does not match.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if the match could extend past the end of the input.
+///
+/// # Panics
+///
+/// When `pattern` is not a valid regular expression or uses Unicode word boundaries (`\b`); use
+/// an ASCII word boundary (`(?-u:\b)`) instead.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// # use winnow::error::ContextError;
+/// pub fn regex<'i>(pattern: &str) -> impl Parser<&'i str, &'i str, ContextError>
+/// # {
+/// #     winnow::token::regex(pattern)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
+/// # use winnow::prelude::*;
+/// use winnow::token::regex;
+///
+/// fn version(s: &str) -> IResult<&str, &str> {
+///   regex(r"[0-9]+(\.[0-9]+)*").parse_peek(s)
+/// }
+///
+/// assert_eq!(version("1.2.3-beta"), Ok(("-beta", "1.2.3")));
+/// assert_eq!(version("1."), Ok((".", "1")));
+/// assert_eq!(version("v1.2"), Err(ErrMode::Backtrack(InputError::new("v1.2", ErrorKind::Slice))));
+/// ```
+///
+/// ```rust
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
+/// # use winnow::prelude::*;
+/// # use winnow::Partial;
+/// use winnow::token::regex;
+///
+/// fn version(s: Partial<&str>) -> IResult<Partial<&str>, &str> {
+///   regex(r"[0-9]+(\.[0-9]+)*").parse_peek(s)
+/// }
+///
+/// assert_eq!(version(Partial::new("1.2.3-beta")), Ok((Partial::new("-beta"), "1.2.3")));
+/// assert_eq!(version(Partial::new("1.2")), Err(ErrMode::Incomplete(Needed::Unknown)));
+/// assert_eq!(version(Partial::new("1.")), Err(ErrMode::Incomplete(Needed::Unknown)));
+/// assert_eq!(version(Partial::new("v1.2")), Err(ErrMode::Backtrack(InputError::new(Partial::new("v1.2"), ErrorKind::Slice))));
+/// ```
+#[cfg(feature = "regex")]
+pub fn regex<Input, Error>(pattern: &str) -> impl Parser<Input, <Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream + AsBStr,
+    Error: ParserError<Input>,
+{
+    // Allow byte patterns, like `(?-u:\xFF)`, for `&[u8]` input
+    let dfa = regex_automata::hybrid::dfa::DFA::builder()
+        .syntax(regex_automata::util::syntax::Config::new().utf8(false))
+        .build(pattern)
+        .unwrap_or_else(|err| panic!("invalid regex `{pattern}`: {err}"));
+    let mut cache = dfa.create_cache();
+    trace("regex", move |i: &mut Input| {
+        if <Input as StreamIsPartial>::is_partial_supported() {
+            regex_::<_, _, true>(i, &dfa, &mut cache)
+        } else {
+            regex_::<_, _, false>(i, &dfa, &mut cache)
+        }
+    })
+}
+
+#[cfg(feature = "regex")]
+fn regex_<I, Error: ParserError<I>, const PARTIAL: bool>(
+    i: &mut I,
+    dfa: &regex_automata::hybrid::dfa::DFA,
+    cache: &mut regex_automata::hybrid::dfa::Cache,
+) -> PResult<<I as Stream>::Slice, Error>
+where
+    I: StreamIsPartial,
+    I: Stream + AsBStr,
+{
+    let haystack = i.as_bstr();
+    let search = regex_automata::Input::new(haystack).anchored(regex_automata::Anchored::Yes);
+    let Ok(mut state) = dfa.start_state_forward(cache, &search) else {
+        return Err(ErrMode::assert(i, "anchored searches are supported"));
+    };
+
+    // Run until the DFA can't match any further, noting the longest match.  Matches are reported
+    // by the DFA one byte late.
+    let mut match_len = None;
+    let mut dead = false;
+    for (offset, byte) in haystack.iter().enumerate() {
+        let Ok(next) = dfa.next_state(cache, state, *byte) else {
+            return Err(ErrMode::assert(i, "regex cache should not give up"));
+        };
+        state = next;
+        if state.is_match() {
+            match_len = Some(offset);
+        } else if state.is_dead() || state.is_quit() {
+            dead = true;
+            break;
+        }
+    }
+    if !dead {
+        if PARTIAL && i.is_partial() {
+            match regex_can_extend(dfa, cache, state) {
+                Ok(true) => return Err(ErrMode::Incomplete(Needed::Unknown)),
+                Ok(false) => {}
+                Err(_) => return Err(ErrMode::assert(i, "regex cache should not give up")),
+            }
+        }
+        let Ok(eoi) = dfa.next_eoi_state(cache, state) else {
+            return Err(ErrMode::assert(i, "regex cache should not give up"));
+        };
+        if eoi.is_match() {
+            match_len = Some(haystack.len());
+        }
+    }
+
+    match match_len.and_then(|len| i.try_next_slice(len)) {
+        Some(slice) => Ok(slice),
+        None => Err(ErrMode::from_error_kind(i, ErrorKind::Slice)),
+    }
+}
+
+/// Whether more input could make a longer match than is possible at `state`
+///
+/// As matches are reported one byte late, reaching a match state on the next byte only means
+/// there is a match ending at `state`, so look one byte further for those.
+#[cfg(feature = "regex")]
+fn regex_can_extend(
+    dfa: &regex_automata::hybrid::dfa::DFA,
+    cache: &mut regex_automata::hybrid::dfa::Cache,
+    state: regex_automata::hybrid::LazyStateID,
+) -> Result<bool, regex_automata::hybrid::CacheError> {
+    let is_alive = |s: regex_automata::hybrid::LazyStateID| !s.is_dead() && !s.is_quit();
+    let mut checked = crate::lib::std::vec::Vec::new();
+    for byte in 0..=u8::MAX {
+        let next = dfa.next_state(cache, state, byte)?;
+        if !is_alive(next) {
+            continue;
+        }
+        if !next.is_match() {
+            return Ok(true);
+        }
+        if checked.contains(&next) {
+            continue;
+        }
+        checked.push(next);
+        if dfa.next_eoi_state(cache, next)?.is_match() {
+            return Ok(true);
+        }
+        for byte in 0..=u8::MAX {
+            if is_alive(dfa.next_state(cache, next, byte)?) {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
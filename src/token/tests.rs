@@ -10,7 +10,10 @@ use crate::error::ErrorKind;
 use crate::error::InputError;
 use crate::error::Needed;
 use crate::stream::AsChar;
+#[cfg(feature = "std")]
+use crate::stream::ContainsToken;
 use crate::token::literal;
+#[cfg(feature = "std")]
 use crate::unpeek;
 use crate::IResult;
 use crate::Parser;
@@ -66,6 +69,53 @@ proptest! {
   }
 }
 
+#[cfg(feature = "std")]
+proptest! {
+  #[test]
+  #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
+  fn take_till_needles_match_predicate(input in "[a-e\\r\\n\u{e9}]{0,40}") {
+      fn check<S: ContainsToken<char> + ContainsToken<u8> + Clone>(input: &str, set: S) {
+          let fast: IResult<&str, &str> = take_till(0.., set.clone()).parse_peek(input);
+          let slow: IResult<&str, &str> =
+              take_till(0.., |c: char| set.contains_token(c)).parse_peek(input);
+          assert_eq!(fast, slow);
+
+          let fast: IResult<&[u8], &[u8]> = take_till(1.., set.clone()).parse_peek(input.as_bytes());
+          let slow: IResult<&[u8], &[u8]> =
+              take_till(1.., |c: u8| set.contains_token(c)).parse_peek(input.as_bytes());
+          assert_eq!(fast, slow);
+
+          let fast: IResult<Partial<&str>, &str> =
+              take_till(1.., set.clone()).parse_peek(Partial::new(input));
+          let slow: IResult<Partial<&str>, &str> =
+              take_till(1.., |c: char| set.contains_token(c)).parse_peek(Partial::new(input));
+          assert_eq!(fast, slow);
+      }
+
+      check(&input, 'c');
+      check(&input, b'c');
+      check(&input, ('\r', '\n'));
+      check(&input, (b'a', b'b', b'e'));
+      check(&input, ['a', 'b', 'c', 'd']);
+      check(&input, [b'a', b'b']);
+      check(&input, ('a', ['b', 'c']));
+      check(&input, ());
+      check(&input, ((), 'a'));
+      check(&input, ((), (b'b', ())));
+      check(&input, '\u{e9}');
+      check(&input, ['\u{e9}', 'a']);
+  }
+}
+
+#[test]
+fn take_till_byte_needle_non_ascii() {
+    let input = &b"ab\xe9cd"[..];
+    let result: IResult<&[u8], &[u8]> = take_till(0.., 0xe9_u8).parse_peek(input);
+    assert_eq!(result, Ok((&b"\xe9cd"[..], &b"ab"[..])));
+    let result: IResult<&[u8], &[u8]> = take_till(0.., (b'x', 0xe9_u8)).parse_peek(input);
+    assert_eq!(result, Ok((&b"\xe9cd"[..], &b"ab"[..])));
+}
+
 #[test]
 fn complete_take_until() {
     fn take_until_5_10(i: &str) -> IResult<&str, &str> {
@@ -228,6 +278,67 @@ fn complete_literal_byte() {
     );
 }
 
+#[test]
+#[cfg(feature = "std")]
+fn complete_literal_mismatch() {
+    use crate::error::ContextError;
+    use crate::error::LiteralMismatch;
+    use crate::error::TreeError;
+
+    fn context(i: &[u8]) -> IResult<&[u8], &[u8], ContextError> {
+        literal("\x7fELF").parse_peek(i)
+    }
+    // Not recorded, to keep failed alternatives cheap
+    let err = context(&b"\x7fEL\x00\x01"[..])
+        .unwrap_err()
+        .into_inner()
+        .unwrap();
+    assert!(err.cause().is_none());
+
+    fn tree(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, &[u8], TreeError<Partial<&[u8]>>> {
+        literal(b'B').parse_peek(i)
+    }
+    let err = tree(Partial::new(&b"A"[..]))
+        .unwrap_err()
+        .into_inner()
+        .unwrap();
+    let TreeError::Base(base) = err else {
+        panic!("expected a `TreeError::Base`");
+    };
+    assert_eq!(base.kind, ErrorKind::Tag);
+    let cause = base.cause.unwrap();
+    assert_eq!(
+        cause.downcast_ref::<LiteralMismatch>(),
+        Some(&LiteralMismatch::new(b"B", b"A"))
+    );
+
+    fn str_context(i: &str) -> IResult<&str, &str, ContextError> {
+        literal("ELF").parse_peek(i)
+    }
+    let err = str_context("EXE").unwrap_err().into_inner().unwrap();
+    assert!(err.cause().is_none());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn literal_mismatch_display() {
+    use crate::error::LiteralMismatch;
+
+    let mismatch = LiteralMismatch::new(b"REC", b"RAC");
+    assert_eq!(mismatch.offset(), 1);
+    assert_eq!(
+        mismatch.to_string(),
+        r#"expected b"REC", found b"RAC" (differs at byte 1)"#
+    );
+
+    let mismatch = LiteralMismatch::new(b"REC", b"RE");
+    assert_eq!(mismatch.offset(), 2);
+    assert_eq!(
+        mismatch.to_string(),
+        r#"expected b"REC", found b"RE" (input ends at byte 2)"#
+    );
+}
+
 #[test]
 fn partial_any_str() {
     use super::any;
@@ -522,6 +633,32 @@ fn partial_take_while_m_n() {
     );
 }
 
+#[test]
+fn partial_take_while_m_n_bounded() {
+    fn upto(i: Partial<&str>) -> IResult<Partial<&str>, &str> {
+        take_while(0..=3, AsChar::is_alpha).parse_peek(i)
+    }
+    assert_eq!(upto(Partial::new("ab1")), Ok((Partial::new("1"), "ab")));
+    assert_eq!(upto(Partial::new("abc")), Ok((Partial::new(""), "abc")));
+    assert_eq!(
+        upto(Partial::new("ab")),
+        Err(ErrMode::Incomplete(Needed::new(1)))
+    );
+
+    fn greek(i: Partial<&str>) -> IResult<Partial<&str>, &str> {
+        take_while(4..=5, |c: char| c.is_alphabetic()).parse_peek(i)
+    }
+    // `Needed` counts tokens still required, not bytes already buffered
+    assert_eq!(
+        greek(Partial::new("αβ")),
+        Err(ErrMode::Incomplete(Needed::new(2)))
+    );
+    assert_eq!(
+        greek(Partial::new("αβγδε")),
+        Ok((Partial::new(""), "αβγδε"))
+    );
+}
+
 #[test]
 fn partial_take_till0() {
     fn f(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, &[u8]> {
@@ -834,3 +971,228 @@ fn partial_literal_fixed_size_array() {
     assert_eq!(test(input), Ok((Partial::new(&b"\x00"[..]), &b"\x42"[..])));
     assert_eq!(test2(input), Ok((Partial::new(&b"\x00"[..]), &b"\x42"[..])));
 }
+
+#[test]
+fn complete_take_balanced() {
+    fn parens(i: &str) -> IResult<&str, &str> {
+        take_balanced('(', ')').parse_peek(i)
+    }
+    assert_eq!(parens("(a(b)(c))d"), Ok(("d", "(a(b)(c))")));
+    assert_eq!(parens("()"), Ok(("", "()")));
+    assert_eq!(parens("(é)ü"), Ok(("ü", "(é)")));
+    assert_eq!(
+        parens("(a(b)"),
+        Err(ErrMode::Backtrack(InputError::new(
+            "(a(b)",
+            ErrorKind::Slice
+        )))
+    );
+    assert_eq!(
+        parens("a()"),
+        Err(ErrMode::Backtrack(InputError::new("a()", ErrorKind::Tag)))
+    );
+    assert_eq!(
+        parens(""),
+        Err(ErrMode::Backtrack(InputError::new("", ErrorKind::Tag)))
+    );
+
+    fn escaped(i: &str) -> IResult<&str, &str> {
+        take_balanced('(', ')')
+            .escape('\\')
+            .quote('\'')
+            .parse_peek(i)
+    }
+    assert_eq!(escaped(r"(\)\\)x"), Ok(("x", r"(\)\\)")));
+    assert_eq!(escaped("(')' '\\'')x"), Ok(("x", "(')' '\\'')")));
+}
+
+#[test]
+fn partial_take_balanced() {
+    fn brackets(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, &[u8]> {
+        take_balanced('[', ']').parse_peek(i)
+    }
+    assert_eq!(
+        brackets(Partial::new(b"[[]]")),
+        Ok((Partial::new(&b""[..]), &b"[[]]"[..]))
+    );
+    assert_eq!(
+        brackets(Partial::new(b"[[]")),
+        Err(ErrMode::Incomplete(Needed::Unknown))
+    );
+    assert_eq!(
+        brackets(Partial::new(b"")),
+        Err(ErrMode::Incomplete(Needed::new(1)))
+    );
+}
+
+#[cfg(feature = "regex")]
+mod regex {
+    use super::*;
+    use crate::token::regex;
+
+    #[test]
+    fn complete_regex() {
+        fn number(i: &[u8]) -> IResult<&[u8], &[u8]> {
+            regex(r"-?[0-9]+").parse_peek(i)
+        }
+        assert_eq!(number(&b"-12,"[..]), Ok((&b","[..], &b"-12"[..])));
+        assert_eq!(number(&b"42"[..]), Ok((&b""[..], &b"42"[..])));
+        assert_eq!(
+            number(&b"-"[..]),
+            Err(ErrMode::Backtrack(error_position!(
+                &&b"-"[..],
+                ErrorKind::Slice
+            )))
+        );
+        assert_eq!(
+            number(&b""[..]),
+            Err(ErrMode::Backtrack(error_position!(
+                &&b""[..],
+                ErrorKind::Slice
+            )))
+        );
+    }
+
+    #[test]
+    fn complete_regex_empty_match() {
+        fn spaces(i: &str) -> IResult<&str, &str> {
+            regex(r" *").parse_peek(i)
+        }
+        assert_eq!(spaces("x"), Ok(("x", "")));
+        assert_eq!(spaces(""), Ok(("", "")));
+    }
+
+    #[test]
+    fn complete_regex_leftmost_first() {
+        fn keyword(i: &str) -> IResult<&str, &str> {
+            regex(r"in|int").parse_peek(i)
+        }
+        assert_eq!(keyword("int"), Ok(("t", "in")));
+    }
+
+    #[test]
+    fn complete_regex_end_anchor() {
+        fn line(i: &str) -> IResult<&str, &str> {
+            regex(r"(?m)[a-z]+$").parse_peek(i)
+        }
+        assert_eq!(line("abc"), Ok(("", "abc")));
+        assert_eq!(line("abc\ndef"), Ok(("\ndef", "abc")));
+        assert!(line("abc def").is_err());
+    }
+
+    #[test]
+    fn complete_regex_unicode() {
+        fn word(i: &str) -> IResult<&str, &str> {
+            regex(r"\w+").parse_peek(i)
+        }
+        assert_eq!(word("øne two"), Ok((" two", "øne")));
+    }
+
+    #[test]
+    fn complete_regex_partial_char() {
+        fn byte(i: &str) -> IResult<&str, &str> {
+            regex(r"(?-u:.)").parse_peek(i)
+        }
+        assert_eq!(byte("ab"), Ok(("b", "a")));
+        assert_eq!(
+            byte("øb"),
+            Err(ErrMode::Backtrack(error_position!(&"øb", ErrorKind::Slice)))
+        );
+
+        fn high(i: &[u8]) -> IResult<&[u8], &[u8]> {
+            regex(r"(?-u:\xFF)+").parse_peek(i)
+        }
+        assert_eq!(high(&b"\xff\xffa"[..]), Ok((&b"a"[..], &b"\xff\xff"[..])));
+    }
+
+    #[test]
+    fn partial_regex() {
+        fn number(i: Partial<&[u8]>) -> IResult<Partial<&[u8]>, &[u8]> {
+            regex(r"[0-9]+").parse_peek(i)
+        }
+        assert_eq!(
+            number(Partial::new(&b"12,"[..])),
+            Ok((Partial::new(&b","[..]), &b"12"[..]))
+        );
+        assert_eq!(
+            number(Partial::new(&b"12"[..])),
+            Err(ErrMode::Incomplete(Needed::Unknown))
+        );
+        assert_eq!(
+            number(Partial::new(&b""[..])),
+            Err(ErrMode::Incomplete(Needed::Unknown))
+        );
+        assert_eq!(
+            number(Partial::new(&b"x"[..])),
+            Err(ErrMode::Backtrack(error_position!(
+                &Partial::new(&b"x"[..]),
+                ErrorKind::Slice
+            )))
+        );
+    }
+
+    #[test]
+    fn partial_regex_fixed_length() {
+        fn hex(i: Partial<&str>) -> IResult<Partial<&str>, &str> {
+            regex(r"[0-9a-f]{2}").parse_peek(i)
+        }
+        assert_eq!(hex(Partial::new("ff")), Ok((Partial::new(""), "ff")));
+        assert_eq!(
+            hex(Partial::new("f")),
+            Err(ErrMode::Incomplete(Needed::Unknown))
+        );
+    }
+
+    #[test]
+    #[should_panic = "invalid regex"]
+    fn invalid_regex() {
+        let _ = regex::<&str, InputError<&str>>(r"(");
+    }
+}
+
+#[test]
+fn take_while_state_carries_state() {
+    fn field(i: &str) -> IResult<&str, &str> {
+        take_while_state(0.., false, |quoted: &mut bool, c: char| {
+            if c == '"' {
+                *quoted = !*quoted;
+            }
+            *quoted || c != ','
+        })
+        .parse_peek(i)
+    }
+    assert_eq!(field(r#"a"b,c"d,e"#), Ok((",e", r#"a"b,c"d"#)));
+    assert_eq!(field(",e"), Ok((",e", "")));
+    assert_eq!(field(r#""a,b"#), Ok(("", r#""a,b"#)));
+
+    // Each parse starts from `init`
+    let mut two_dots =
+        take_while_state::<_, _, _, InputError<_>>(1..=6, 0, |dots: &mut usize, c: char| {
+            if c == '.' {
+                *dots += 1;
+            }
+            *dots <= 2
+        });
+    assert_eq!(two_dots.parse_peek("1.2.3.4"), Ok((".4", "1.2.3")));
+    assert_eq!(two_dots.parse_peek("1.2.3.4"), Ok((".4", "1.2.3")));
+    assert_eq!(two_dots.parse_peek("123456789"), Ok(("789", "123456")));
+    assert_eq!(two_dots.parse_peek("..."), Ok((".", "..")));
+
+    fn partial_field(i: Partial<&str>) -> IResult<Partial<&str>, &str> {
+        take_while_state(0.., false, |quoted: &mut bool, c: char| {
+            if c == '"' {
+                *quoted = !*quoted;
+            }
+            *quoted || c != ','
+        })
+        .parse_peek(i)
+    }
+    assert_eq!(
+        partial_field(Partial::new(r#""a,b"#)),
+        Err(ErrMode::Incomplete(Needed::new(1)))
+    );
+    assert_eq!(
+        partial_field(Partial::new(r#""a,b",c"#)),
+        Ok((Partial::new(",c"), r#""a,b""#))
+    );
+}
@@ -0,0 +1,161 @@
+use crate::lib::std::iter::once;
+
+use crate::stream::{Compare, CompareResult, SliceLen};
+
+use caseless::Caseless as _;
+use unicode_normalization::char::canonical_combining_class;
+use unicode_normalization::UnicodeNormalization;
+
+/// Mark a value as case-insensitive under Unicode full case folding
+///
+/// Unlike [`ascii::Caseless`][crate::ascii::Caseless], this folds every script and folds
+/// characters that expand, like `ß` matching `ss`.  This is
+/// [default caseless matching](https://www.unicode.org/versions/latest/core-spec/chapter-3/#G34145);
+/// for text that may not be normalized, see [`CanonicalCaseless`].
+///
+/// A match must end on a `char` boundary of the input, so `Caseless("s")` doesn't match the
+/// start of `"ß"`.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::InputError;
+/// use winnow::unicode::Caseless;
+///
+/// fn street<'s>(input: &mut &'s str) -> PResult<&'s str, InputError<&'s str>> {
+///     Caseless("straße").parse_next(input)
+/// }
+///
+/// assert_eq!(street.parse_peek("STRASSE 5"), Ok((" 5", "STRASSE")));
+/// assert_eq!(street.parse_peek("Straße 5"), Ok((" 5", "Straße")));
+/// assert!(street.parse_peek("STRAS").is_err());
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Caseless<T>(pub T);
+
+impl<S: SliceLen> SliceLen for Caseless<S> {
+    #[inline(always)]
+    fn slice_len(&self) -> usize {
+        self.0.slice_len()
+    }
+}
+
+impl<'b> Compare<Caseless<&'b str>> for &str {
+    #[inline]
+    fn compare(&self, t: Caseless<&'b str>) -> CompareResult {
+        compare_folded(
+            t.0.chars().default_case_fold(),
+            self.char_indices()
+                .map(|(i, c)| (i + c.len_utf8(), once(c).default_case_fold())),
+        )
+    }
+}
+
+/// Mark a value as case-insensitive under Unicode full case folding, ignoring differences in
+/// normalization
+///
+/// Like [`Caseless`], but canonically equivalent text also matches, like `é` written as one
+/// code point (`U+00E9`) or as `e` plus a combining accent (`U+0065 U+0301`).  This is
+/// [canonical caseless matching](https://www.unicode.org/versions/latest/core-spec/chapter-3/#G34145).
+///
+/// A match must end before a `char` that isn't combining, so `CanonicalCaseless("e")`
+/// doesn't match the start of `"e\u{301}"`.  On [`Partial`][crate::Partial] input, a match that
+/// reaches the end of the input is incomplete, as a combining mark could still follow.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// # use winnow::error::InputError;
+/// use winnow::unicode::CanonicalCaseless;
+///
+/// fn cafe<'s>(input: &mut &'s str) -> PResult<&'s str, InputError<&'s str>> {
+///     CanonicalCaseless("caf\u{e9}").parse_next(input)
+/// }
+///
+/// assert_eq!(cafe.parse_peek("CAFE\u{301}!"), Ok(("!", "CAFE\u{301}")));
+/// assert_eq!(cafe.parse_peek("Caf\u{c9}!"), Ok(("!", "Caf\u{c9}")));
+/// assert!(cafe.parse_peek("CAFE\u{301}\u{327}").is_err());
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct CanonicalCaseless<T>(pub T);
+
+impl<S: SliceLen> SliceLen for CanonicalCaseless<S> {
+    #[inline(always)]
+    fn slice_len(&self) -> usize {
+        self.0.slice_len()
+    }
+}
+
+impl<'b> Compare<CanonicalCaseless<&'b str>> for &str {
+    #[inline]
+    fn compare(&self, t: CanonicalCaseless<&'b str>) -> CompareResult {
+        compare_canonical(self, t.0, false)
+    }
+
+    #[inline]
+    fn compare_partial(&self, t: CanonicalCaseless<&'b str>) -> CompareResult {
+        compare_canonical(self, t.0, true)
+    }
+}
+
+fn compare_canonical(input: &str, literal: &str, partial: bool) -> CompareResult {
+    let result = compare_folded(
+        literal.chars().nfd().default_case_fold().nfd(),
+        combining_sequences(input)
+            .map(|(end, sequence)| (end, sequence.chars().nfd().default_case_fold().nfd())),
+    );
+    match result {
+        // A combining mark may still follow the last sequence
+        CompareResult::Ok(end) if partial && end != 0 && end == input.len() => {
+            CompareResult::Incomplete
+        }
+        result => result,
+    }
+}
+
+/// Match `expected` against the start of the input, folded a piece at a time by `pieces` that
+/// each end at a byte offset
+///
+/// Running out of input on a matching prefix is [`CompareResult::Incomplete`], as folding
+/// changes the length.
+fn compare_folded<Expected, Pieces, Piece>(expected: Expected, pieces: Pieces) -> CompareResult
+where
+    Expected: Iterator<Item = char>,
+    Pieces: Iterator<Item = (usize, Piece)>,
+    Piece: Iterator<Item = char>,
+{
+    let mut expected = expected.peekable();
+    if expected.peek().is_none() {
+        return CompareResult::Ok(0);
+    }
+    for (end, piece) in pieces {
+        for c in piece {
+            if expected.next() != Some(c) {
+                return CompareResult::Error;
+            }
+        }
+        if expected.peek().is_none() {
+            return CompareResult::Ok(end);
+        }
+    }
+    CompareResult::Incomplete
+}
+
+/// Split `input` before each `char` that isn't combining, as normalization only reorders
+/// within these, with the byte offset each ends at
+fn combining_sequences(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut start = 0;
+    crate::lib::std::iter::from_fn(move || {
+        let rest = &input[start..];
+        let mut chars = rest.char_indices();
+        chars.next()?;
+        let len = chars
+            .find(|(_, c)| canonical_combining_class(*c) == 0)
+            .map(|(i, _)| i)
+            .unwrap_or(rest.len());
+        start += len;
+        Some((start, &rest[..len]))
+    })
+}
@@ -0,0 +1,123 @@
+use crate::combinator::trace;
+use crate::error::ParserError;
+use crate::stream::{AsChar, Stream, StreamIsPartial};
+use crate::token::one_of;
+use crate::token::take_while;
+use crate::PResult;
+use crate::Parser;
+
+/// Tests if a character can start an identifier (property `XID_Start`)
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::unicode::is_xid_start;
+///
+/// assert!(is_xid_start('a'));
+/// assert!(is_xid_start('δ'));
+/// assert!(is_xid_start('変'));
+/// assert!(!is_xid_start('_'));
+/// assert!(!is_xid_start('1'));
+/// ```
+#[inline]
+pub fn is_xid_start(c: char) -> bool {
+    unicode_ident::is_xid_start(c)
+}
+
+/// Tests if a character can continue an identifier (property `XID_Continue`)
+///
+/// This includes every [`is_xid_start`] character.
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::unicode::is_xid_continue;
+///
+/// assert!(is_xid_continue('_'));
+/// assert!(is_xid_continue('1'));
+/// assert!(is_xid_continue('\u{301}'));
+/// assert!(!is_xid_continue('-'));
+/// ```
+#[inline]
+pub fn is_xid_continue(c: char) -> bool {
+    unicode_ident::is_xid_continue(c)
+}
+
+/// Recognizes an identifier following the default syntax of
+/// [UAX #31](https://www.unicode.org/reports/tr31/): an [`is_xid_start`] character followed by
+/// [`is_xid_continue`] characters
+///
+/// Many languages also let identifiers start with `_`, like Rust.  For those, combine the
+/// predicates directly, as in the example.
+///
+/// This only needs the `unicode-ident` feature, without `alloc`.  To also normalize identifiers
+/// as UAX #31 recommends, wrap this in `nfkc` from the `unicode-normalize` feature.
+///
+/// *Complete version*: Will return an error if there's not enough input data,
+/// or the whole input if no terminating token is found (a non identifier character).
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there's not enough input data,
+/// or if no terminating token is found (a non identifier character).
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// pub fn ident<'i>(input: &mut &'i str) -> PResult<&'i str>
+/// # {
+/// #     winnow::unicode::ident.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use winnow::prelude::*;
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
+/// # use winnow::unicode::ident;
+/// fn parser<'s>(input: &mut &'s str) -> PResult<&'s str, InputError<&'s str>> {
+///     ident.parse_next(input)
+/// }
+///
+/// assert_eq!(parser.parse_peek("größe = 1"), Ok((" = 1", "größe")));
+/// assert_eq!(parser.parse_peek("x_1+y"), Ok(("+y", "x_1")));
+/// assert_eq!(parser.parse_peek("_x"), Err(ErrMode::Backtrack(InputError::new("_x", ErrorKind::Verify))));
+/// assert_eq!(parser.parse_peek(""), Err(ErrMode::Backtrack(InputError::new("", ErrorKind::Token))));
+/// ```
+///
+/// Rust-style identifiers, which may start with `_`:
+/// ```
+/// # use winnow::prelude::*;
+/// use winnow::token::one_of;
+/// use winnow::token::take_while;
+/// use winnow::unicode::is_xid_continue;
+/// use winnow::unicode::is_xid_start;
+///
+/// fn rust_ident<'s>(input: &mut &'s str) -> PResult<&'s str> {
+///     (one_of((is_xid_start, '_')), take_while(0.., is_xid_continue))
+///         .take()
+///         .parse_next(input)
+/// }
+///
+/// assert_eq!(rust_ident.parse_peek("_größe: u8"), Ok((": u8", "_größe")));
+/// ```
+#[inline(always)]
+pub fn ident<Input, Error>(input: &mut Input) -> PResult<<Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar + Clone,
+    Error: ParserError<Input>,
+{
+    trace(
+        "ident",
+        (
+            one_of(|c: <Input as Stream>::Token| is_xid_start(c.as_char())),
+            take_while(0.., |c: <Input as Stream>::Token| {
+                is_xid_continue(c.as_char())
+            }),
+        )
+            .take(),
+    )
+    .parse_next(input)
+}
@@ -0,0 +1,334 @@
+//! Unicode-aware character parsers
+//!
+//! Unlike [`ascii`][crate::ascii], these recognize characters from any script, like the
+//! localized digits of Arabic-Indic (`٠١٢`) or Devanagari (`०१२`).
+//!
+//! The digit parsers only need the `unicode` feature.  Normalizing what was matched, so
+//! equivalent text compares equal, needs `unicode-normalize`, which also pulls in `alloc`.
+
+#[cfg(feature = "unicode-case")]
+mod caseless;
+#[cfg(feature = "unicode-ident")]
+mod ident;
+#[cfg(feature = "unicode-normalize")]
+mod normalize;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "unicode-case")]
+pub use caseless::CanonicalCaseless;
+#[cfg(feature = "unicode-case")]
+pub use caseless::Caseless;
+#[cfg(feature = "unicode-ident")]
+pub use ident::ident;
+#[cfg(feature = "unicode-ident")]
+pub use ident::is_xid_continue;
+#[cfg(feature = "unicode-ident")]
+pub use ident::is_xid_start;
+#[cfg(feature = "unicode-normalize")]
+pub use normalize::nfc;
+#[cfg(feature = "unicode-normalize")]
+pub use normalize::nfkc;
+
+use crate::combinator::alt;
+use crate::combinator::trace;
+use crate::error::ParserError;
+use crate::stream::{AsBStr, AsChar, Stream, StreamIsPartial};
+use crate::token::any;
+use crate::token::one_of;
+use crate::token::take_while;
+use crate::PResult;
+use crate::Parser;
+
+/// Tests if a character is a Unicode decimal digit (general category `Nd`)
+///
+/// This includes the ASCII digits `'0'..='9'`.
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::unicode::is_dec_digit;
+///
+/// assert!(is_dec_digit('7'));
+/// assert!(is_dec_digit('٧'));
+/// assert!(is_dec_digit('७'));
+/// assert!(!is_dec_digit('Ⅶ'));
+/// assert!(!is_dec_digit('a'));
+/// ```
+#[inline]
+pub fn is_dec_digit(c: char) -> bool {
+    dec_digit_value(c).is_some()
+}
+
+/// The value of a Unicode decimal digit (general category `Nd`)
+///
+/// Returns `None` for any other character, including numerals that aren't decimal digits, like
+/// `'Ⅶ'` or `'½'`.
+///
+/// # Example
+///
+/// ```rust
+/// use winnow::unicode::dec_digit_value;
+///
+/// assert_eq!(dec_digit_value('7'), Some(7));
+/// assert_eq!(dec_digit_value('٧'), Some(7));
+/// assert_eq!(dec_digit_value('७'), Some(7));
+/// assert_eq!(dec_digit_value('Ⅶ'), None);
+/// ```
+#[inline]
+pub fn dec_digit_value(c: char) -> Option<u32> {
+    dec_digit_zero(c).map(|zero| c as u32 - zero)
+}
+
+/// The code point of the `0` in the same run of digits as `c`
+fn dec_digit_zero(c: char) -> Option<u32> {
+    let c = c as u32;
+    let zero = match DEC_DIGIT_ZEROS.binary_search(&c) {
+        Ok(i) => DEC_DIGIT_ZEROS[i],
+        Err(0) => return None,
+        Err(i) => DEC_DIGIT_ZEROS[i - 1],
+    };
+    (c - zero < 10).then_some(zero)
+}
+
+/// Recognizes one Unicode decimal digit, returning its value
+///
+/// *Complete version*: Will return an error if there's not enough input data.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there's not enough input data.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// pub fn dec_digit(input: &mut &str) -> PResult<u32>
+/// # {
+/// #     winnow::unicode::dec_digit.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use winnow::prelude::*;
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
+/// # use winnow::unicode::dec_digit;
+/// fn parser<'s>(input: &mut &'s str) -> PResult<u32, InputError<&'s str>> {
+///     dec_digit.parse_next(input)
+/// }
+///
+/// assert_eq!(parser.parse_peek("3c"), Ok(("c", 3)));
+/// assert_eq!(parser.parse_peek("٣c"), Ok(("c", 3)));
+/// assert_eq!(parser.parse_peek("c3"), Err(ErrMode::Backtrack(InputError::new("c3", ErrorKind::Verify))));
+/// assert_eq!(parser.parse_peek(""), Err(ErrMode::Backtrack(InputError::new("", ErrorKind::Token))));
+/// ```
+#[inline(always)]
+pub fn dec_digit<Input, Error>(input: &mut Input) -> PResult<u32, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar,
+    Error: ParserError<Input>,
+{
+    trace(
+        "dec_digit",
+        any.verify_map(|c: <Input as Stream>::Token| dec_digit_value(c.as_char())),
+    )
+    .parse_next(input)
+}
+
+/// Recognizes zero or more Unicode decimal digits (general category `Nd`)
+///
+/// Digits from different scripts may be mixed, see [`dec_uint`] to reject that.
+///
+/// *Complete version*: Will return the whole input if no terminating token is found (a non
+/// digit character).
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there's not enough input data,
+/// or if no terminating token is found (a non digit character).
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// pub fn digit0<'i>(input: &mut &'i str) -> PResult<&'i str>
+/// # {
+/// #     winnow::unicode::digit0.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use winnow::prelude::*;
+/// # use winnow::{error::ErrMode, error::ErrorKind, error::InputError, error::Needed};
+/// # use winnow::unicode::digit0;
+/// fn parser<'s>(input: &mut &'s str) -> PResult<&'s str, InputError<&'s str>> {
+///     digit0.parse_next(input)
+/// }
+///
+/// assert_eq!(parser.parse_peek("21c"), Ok(("c", "21")));
+/// assert_eq!(parser.parse_peek("२१c"), Ok(("c", "२१")));
+/// assert_eq!(parser.parse_peek("a21c"), Ok(("a21c", "")));
+/// assert_eq!(parser.parse_peek(""), Ok(("", "")));
+/// ```
+#[inline(always)]
+pub fn digit0<Input, Error>(input: &mut Input) -> PResult<<Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar,
+    Error: ParserError<Input>,
+{
+    trace(
+        "digit0",
+        take_while(0.., |c: <Input as Stream>::Token| is_dec_digit(c.as_char())),
+    )
+    .parse_next(input)
+}
+
+/// Recognizes one or more Unicode decimal digits (general category `Nd`)
+///
+/// Digits from different scripts may be mixed, see [`dec_uint`] to reject that.
+///
+/// *Complete version*: Will return an error if there's not enough input data,
+/// or the whole input if no terminating token is found (a non digit character).
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there's not enough input data,
+/// or if no terminating token is found (a non digit character).
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream]:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// pub fn digit1<'i>(input: &mut &'i str) -> PResult<&'i str>
+/// # {
+/// #     winnow::unicode::digit1.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use winnow::prelude::*;
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
+/// # use winnow::unicode::digit1;
+/// fn parser<'s>(input: &mut &'s str) -> PResult<&'s str, InputError<&'s str>> {
+///     digit1.parse_next(input)
+/// }
+///
+/// assert_eq!(parser.parse_peek("21c"), Ok(("c", "21")));
+/// assert_eq!(parser.parse_peek("٢١c"), Ok(("c", "٢١")));
+/// assert_eq!(parser.parse_peek("c1"), Err(ErrMode::Backtrack(InputError::new("c1", ErrorKind::Slice))));
+/// assert_eq!(parser.parse_peek(""), Err(ErrMode::Backtrack(InputError::new("", ErrorKind::Slice))));
+/// ```
+#[inline(always)]
+pub fn digit1<Input, Error>(input: &mut Input) -> PResult<<Input as Stream>::Slice, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Token: AsChar,
+    Error: ParserError<Input>,
+{
+    trace(
+        "digit1",
+        take_while(1.., |c: <Input as Stream>::Token| is_dec_digit(c.as_char())),
+    )
+    .parse_next(input)
+}
+
+/// Decode a decimal unsigned integer (e.g. [`u32`]) written in any script's digits
+///
+/// All digits must come from the same script, so `"٤٢"` is `42` but `"4٢"` is rejected.
+/// Like [`ascii::dec_uint`][crate::ascii::dec_uint], a leading zero is only accepted on its
+/// own.
+///
+/// *Complete version*: can parse until the end of input.
+///
+/// *[Partial version][crate::_topic::partial]*: Will return `Err(winnow::error::ErrMode::Incomplete(_))` if there's not enough input data.
+///
+/// # Effective Signature
+///
+/// Assuming you are parsing a `&str` [Stream] into a `u32`:
+/// ```rust
+/// # use winnow::prelude::*;;
+/// pub fn dec_uint(input: &mut &str) -> PResult<u32>
+/// # {
+/// #     winnow::unicode::dec_uint.parse_next(input)
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use winnow::prelude::*;
+/// # use winnow::{error::ErrMode, error::{InputError, ErrorKind}, error::Needed};
+/// # use winnow::unicode::dec_uint;
+/// fn parser<'s>(input: &mut &'s str) -> PResult<u32, InputError<&'s str>> {
+///     dec_uint.parse_next(input)
+/// }
+///
+/// assert_eq!(parser.parse_peek("42 apples"), Ok((" apples", 42)));
+/// assert_eq!(parser.parse_peek("٤٢ تفاحة"), Ok((" تفاحة", 42)));
+/// assert_eq!(parser.parse_peek("४२ सेब"), Ok((" सेब", 42)));
+/// assert_eq!(parser.parse_peek("4٢"), Err(ErrMode::Backtrack(InputError::new("4٢", ErrorKind::Verify))));
+/// ```
+#[doc(alias = "u8")]
+#[doc(alias = "u16")]
+#[doc(alias = "u32")]
+#[doc(alias = "u64")]
+#[doc(alias = "u128")]
+pub fn dec_uint<Input, Output, Error>(input: &mut Input) -> PResult<Output, Error>
+where
+    Input: StreamIsPartial + Stream,
+    <Input as Stream>::Slice: AsBStr,
+    <Input as Stream>::Token: AsChar + Clone,
+    Output: crate::ascii::Uint,
+    Error: ParserError<Input>,
+{
+    trace("dec_uint", move |input: &mut Input| {
+        alt((
+            (
+                one_of(|c: <Input as Stream>::Token| {
+                    matches!(dec_digit_value(c.as_char()), Some(1..=9))
+                }),
+                digit0,
+            )
+                .void(),
+            one_of(|c: <Input as Stream>::Token| dec_digit_value(c.as_char()) == Some(0)).void(),
+        ))
+        .take()
+        .verify_map(|s: <Input as Stream>::Slice| {
+            // Only `&str` streams can produce non-ASCII digits, so this is always UTF-8
+            let s = crate::lib::std::str::from_utf8(s.as_bstr()).ok()?;
+            let zero = dec_digit_zero(s.chars().next()?)?;
+
+            // Re-encode as ASCII; `u128::MAX` has 39 digits
+            let mut buffer = [0; 39];
+            let mut len = 0;
+            for c in s.chars() {
+                if dec_digit_zero(c)? != zero {
+                    return None;
+                }
+                *buffer.get_mut(len)? = b'0' + (c as u32 - zero) as u8;
+                len += 1;
+            }
+            let ascii = crate::lib::std::str::from_utf8(&buffer[..len]).ok()?;
+            Output::try_from_dec_uint(ascii)
+        })
+        .parse_next(input)
+    })
+    .parse_next(input)
+}
+
+/// The `0` of each run of ten decimal digits (general category `Nd`), as of Unicode 15.0
+#[rustfmt::skip]
+const DEC_DIGIT_ZEROS: [u32; 68] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6,
+    0x0C66, 0x0CE6, 0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0,
+    0x1810, 0x1946, 0x19D0, 0x1A80, 0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620,
+    0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10, 0x104A0, 0x10D30, 0x11066,
+    0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0, 0x11650, 0x116C0, 0x11730, 0x118E0,
+    0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x11F50, 0x16A60, 0x16AC0, 0x16B50, 0x1D7CE, 0x1D7D8,
+    0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E950, 0x1FBF0,
+];
@@ -0,0 +1,92 @@
+use crate::combinator::trace;
+use crate::error::ParserError;
+use crate::lib::std::borrow::Cow;
+use crate::stream::Stream;
+use crate::Parser;
+
+use unicode_normalization::{is_nfc_quick, is_nfkc_quick, IsNormalized, UnicodeNormalization};
+
+/// Normalize the `&str` matched by `parser` to Normalization Form C (NFC)
+///
+/// Canonically equivalent text, like `"é"` written as one code point (`U+00E9`) or as `e` plus
+/// a combining accent (`U+0065 U+0301`), renders the same but compares as different strings.
+/// Normalizing identifiers as they are parsed keeps these from being treated as distinct.
+///
+/// The slice is borrowed when it is already normalized, which is the common case.
+///
+/// See also [`nfkc`].
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// use winnow::token::take_till;
+/// use winnow::unicode::nfc;
+///
+/// fn ident<'s>(input: &mut &'s str) -> PResult<std::borrow::Cow<'s, str>> {
+///     nfc(take_till(1.., char::is_whitespace)).parse_next(input)
+/// }
+///
+/// let composed = "caf\u{e9}";
+/// let decomposed = "cafe\u{301}";
+/// assert_ne!(composed, decomposed);
+/// assert_eq!(ident.parse(composed).unwrap(), ident.parse(decomposed).unwrap());
+/// ```
+#[doc(alias = "normalize")]
+pub fn nfc<'s, Input, Error, ParseNext>(
+    parser: ParseNext,
+) -> impl Parser<Input, Cow<'s, str>, Error>
+where
+    Input: Stream,
+    ParseNext: Parser<Input, &'s str, Error>,
+    Error: ParserError<Input>,
+{
+    trace(
+        "nfc",
+        parser.map(|s: &'s str| match is_nfc_quick(s.chars()) {
+            IsNormalized::Yes => Cow::Borrowed(s),
+            IsNormalized::No | IsNormalized::Maybe => Cow::Owned(s.nfc().collect()),
+        }),
+    )
+}
+
+/// Normalize the `&str` matched by `parser` to Normalization Form KC (NFKC)
+///
+/// Like [`nfc`], but also folds compatibility variants into their plain form, like the `ﬁ`
+/// ligature into `fi` or fullwidth `Ａ` into `A`. This is the normalization recommended for
+/// identifiers by [UAX #31](https://www.unicode.org/reports/tr31/), though it doesn't catch
+/// every confusable, like Cyrillic `а` and Latin `a`.
+///
+/// The slice is borrowed when it is already normalized, which is the common case.
+///
+/// # Example
+///
+/// ```rust
+/// # use winnow::prelude::*;
+/// use winnow::token::take_while;
+/// use winnow::unicode::nfkc;
+///
+/// fn ident<'s>(input: &mut &'s str) -> PResult<std::borrow::Cow<'s, str>> {
+///     nfkc(take_while(1.., char::is_alphanumeric)).parse_next(input)
+/// }
+///
+/// assert_eq!(ident.parse("\u{fb01}le").unwrap(), "file");
+/// assert_eq!(ident.parse("\u{ff21}1").unwrap(), "A1");
+/// ```
+#[doc(alias = "normalize")]
+pub fn nfkc<'s, Input, Error, ParseNext>(
+    parser: ParseNext,
+) -> impl Parser<Input, Cow<'s, str>, Error>
+where
+    Input: Stream,
+    ParseNext: Parser<Input, &'s str, Error>,
+    Error: ParserError<Input>,
+{
+    trace(
+        "nfkc",
+        parser.map(|s: &'s str| match is_nfkc_quick(s.chars()) {
+            IsNormalized::Yes => Cow::Borrowed(s),
+            IsNormalized::No | IsNormalized::Maybe => Cow::Owned(s.nfkc().collect()),
+        }),
+    )
+}
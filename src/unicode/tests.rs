@@ -0,0 +1,251 @@
+use super::*;
+
+use crate::error::ErrMode;
+use crate::error::ErrorKind;
+use crate::error::InputError;
+use crate::error::Needed;
+use crate::stream::Partial;
+
+macro_rules! assert_parse(
+    ($left: expr, $right: expr) => {
+        let res: $crate::IResult<_, _, InputError<_>> = $left;
+        assert_eq!(res, $right);
+    };
+);
+
+#[test]
+fn every_digit_run_has_ten_values() {
+    for zero in DEC_DIGIT_ZEROS {
+        for value in 0..10 {
+            let c = char::from_u32(zero + value).unwrap();
+            assert_eq!(dec_digit_value(c), Some(value), "{c:?}");
+        }
+        if !DEC_DIGIT_ZEROS.contains(&(zero + 10)) {
+            assert_eq!(dec_digit_value(char::from_u32(zero + 10).unwrap()), None);
+        }
+    }
+}
+
+#[test]
+fn digit_runs_are_sorted() {
+    assert!(DEC_DIGIT_ZEROS.windows(2).all(|w| w[0] + 10 <= w[1]));
+}
+
+#[test]
+fn non_decimal_numerals() {
+    for c in ['Ⅶ', '½', '²', '①', '〇', 'a', '\0', char::MAX] {
+        assert_eq!(dec_digit_value(c), None, "{c:?}");
+    }
+}
+
+#[test]
+fn digit1_mixed_scripts() {
+    assert_parse!(digit1.parse_peek("1٢३x"), Ok(("x", "1٢३")));
+}
+
+#[test]
+fn dec_uint_scripts() {
+    assert_parse!(dec_uint.parse_peek("𝟙𝟚𝟛"), Ok(("", 123_u32)));
+    assert_parse!(dec_uint.parse_peek("१२३"), Ok(("", 123_u32)));
+    assert_parse!(dec_uint.parse_peek("٠١"), Ok(("١", 0_u32)));
+    assert_parse!(
+        dec_uint::<_, u32, _>.parse_peek("१2"),
+        Err(ErrMode::Backtrack(InputError::new("१2", ErrorKind::Verify)))
+    );
+}
+
+#[test]
+fn dec_uint_overflow() {
+    assert_parse!(dec_uint.parse_peek("٢٥٥"), Ok(("", 255_u8)));
+    assert_parse!(
+        dec_uint::<_, u8, _>.parse_peek("٢٥٦"),
+        Err(ErrMode::Backtrack(InputError::new(
+            "٢٥٦",
+            ErrorKind::Verify
+        )))
+    );
+    let max = "٣٤٠٢٨٢٣٦٦٩٢٠٩٣٨٤٦٣٤٦٣٣٧٤٦٠٧٤٣١٧٦٨٢١١٤٥٥";
+    assert_parse!(dec_uint.parse_peek(max), Ok(("", u128::MAX)));
+    let too_long = "١٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠٠";
+    assert_parse!(
+        dec_uint::<_, u128, _>.parse_peek(too_long),
+        Err(ErrMode::Backtrack(InputError::new(
+            too_long,
+            ErrorKind::Verify
+        )))
+    );
+}
+
+#[test]
+fn bytes_only_ascii() {
+    assert_parse!(dec_uint.parse_peek(&b"42x"[..]), Ok((&b"x"[..], 42_u32)));
+    assert_parse!(dec_digit.parse_peek(&b"7"[..]), Ok((&b""[..], 7)));
+}
+
+#[test]
+fn partial() {
+    assert_parse!(
+        digit1.parse_peek(Partial::new("٤٢")),
+        Err(ErrMode::Incomplete(Needed::new(1)))
+    );
+    assert_parse!(
+        dec_uint::<_, u32, _>.parse_peek(Partial::new("٤٢")),
+        Err(ErrMode::Incomplete(Needed::new(1)))
+    );
+    assert_parse!(
+        dec_uint.parse_peek(Partial::new("٤٢ ")),
+        Ok((Partial::new(" "), 42_u32))
+    );
+}
+
+#[cfg(feature = "unicode-normalize")]
+#[test]
+fn nfc_borrows_normalized() {
+    use crate::lib::std::borrow::Cow;
+    use crate::token::take_till;
+    use crate::IResult;
+
+    let mut parser = nfc(take_till(1.., char::is_whitespace));
+    let res: IResult<&str, Cow<'_, str>, InputError<_>> = parser.parse_peek("caf\u{e9} ");
+    assert!(matches!(res, Ok((" ", Cow::Borrowed("caf\u{e9}")))));
+    let res: IResult<&str, Cow<'_, str>, InputError<_>> = parser.parse_peek("cafe\u{301} ");
+    assert!(matches!(res, Ok((" ", Cow::Owned(s))) if s == "caf\u{e9}"));
+}
+
+#[cfg(feature = "unicode-normalize")]
+#[test]
+fn nfkc_folds_compatibility() {
+    use crate::token::take_while;
+
+    let mut parser = nfkc(take_while(1.., char::is_alphanumeric));
+    assert_parse!(parser.parse_peek("x\u{b2}+"), Ok(("+", "x2".into())));
+    assert_parse!(parser.parse_peek("\u{ff21}\u{ff22}"), Ok(("", "AB".into())));
+    assert_parse!(
+        nfc(take_while(1.., char::is_alphanumeric)).parse_peek("x\u{b2}+"),
+        Ok(("+", "x\u{b2}".into()))
+    );
+}
+
+#[cfg(feature = "unicode-case")]
+#[test]
+fn caseless_full_folding() {
+    use crate::token::literal;
+
+    let mut street = literal(Caseless("stra\u{df}e"));
+    assert_parse!(street.parse_peek("STRASSE!"), Ok(("!", "STRASSE")));
+    assert_parse!(street.parse_peek("strasse"), Ok(("", "strasse")));
+    assert_parse!(
+        street.parse_peek("STRA\u{1e9e}E"),
+        Ok(("", "STRA\u{1e9e}E"))
+    );
+    assert_parse!(
+        street.parse_peek("STRASE"),
+        Err(ErrMode::Backtrack(InputError::new(
+            "STRASE",
+            ErrorKind::Tag
+        )))
+    );
+    assert_parse!(
+        literal(Caseless("s")).parse_peek("\u{df}"),
+        Err(ErrMode::Backtrack(InputError::new(
+            "\u{df}",
+            ErrorKind::Tag
+        )))
+    );
+    assert_parse!(
+        literal(Caseless("\u{3a3}\u{39f}\u{3a3}")).parse_peek("\u{3c3}\u{3bf}\u{3c2} "),
+        Ok((" ", "\u{3c3}\u{3bf}\u{3c2}"))
+    );
+    // KELVIN SIGN is 3 bytes but folds to `k`
+    assert_parse!(
+        literal(Caseless("ka")).parse_peek(Partial::new("\u{212a}")),
+        Err(ErrMode::Incomplete(Needed::Unknown))
+    );
+    assert_parse!(
+        literal(Caseless("ka")).parse_peek("\u{212a}"),
+        Err(ErrMode::Backtrack(InputError::new(
+            "\u{212a}",
+            ErrorKind::Tag
+        )))
+    );
+    assert_parse!(
+        literal(Caseless("STRASSE")).parse_peek(Partial::new("stra")),
+        Err(ErrMode::Incomplete(Needed::new(3)))
+    );
+    assert_parse!(
+        literal(Caseless("e")).parse_peek("E\u{301}"),
+        Ok(("\u{301}", "E"))
+    );
+}
+
+#[cfg(feature = "unicode-case")]
+#[test]
+fn canonical_caseless_normalizes() {
+    use crate::token::literal;
+
+    let mut cafe = literal(CanonicalCaseless("caf\u{e9}"));
+    assert_parse!(cafe.parse_peek("CAFE\u{301} "), Ok((" ", "CAFE\u{301}")));
+    assert_parse!(cafe.parse_peek("caf\u{e9}"), Ok(("", "caf\u{e9}")));
+    assert_parse!(
+        literal(CanonicalCaseless("e\u{301}")).parse_peek("\u{c9}x"),
+        Ok(("x", "\u{c9}"))
+    );
+    assert_parse!(
+        literal(CanonicalCaseless("e")).parse_peek("E\u{301}"),
+        Err(ErrMode::Backtrack(InputError::new(
+            "E\u{301}",
+            ErrorKind::Tag
+        )))
+    );
+    // Combining marks compare in canonical order
+    assert_parse!(
+        literal(CanonicalCaseless("a\u{323}\u{302}")).parse_peek("A\u{302}\u{323}"),
+        Ok(("", "A\u{302}\u{323}"))
+    );
+    assert_parse!(
+        literal(CanonicalCaseless("")).parse_peek("x"),
+        Ok(("x", ""))
+    );
+    // A combining mark could still follow
+    assert_parse!(
+        literal(CanonicalCaseless("e")).parse_peek(Partial::new("E")),
+        Err(ErrMode::Incomplete(Needed::Unknown))
+    );
+    assert_parse!(
+        literal(CanonicalCaseless("e")).parse_peek(Partial::new("E!")),
+        Ok((Partial::new("!"), "E"))
+    );
+    assert_parse!(
+        literal(CanonicalCaseless("caf\u{e9}")).parse_peek(Partial::new("CAFE")),
+        Err(ErrMode::Incomplete(Needed::new(1)))
+    );
+}
+
+#[cfg(feature = "unicode-ident")]
+#[test]
+fn xid_ident() {
+    assert_parse!(ident.parse_peek("x\u{301}y1 "), Ok((" ", "x\u{301}y1")));
+    assert_parse!(
+        ident.parse_peek("\u{5909}\u{6570}.len"),
+        Ok((".len", "\u{5909}\u{6570}"))
+    );
+    assert_parse!(
+        ident.parse_peek("1x"),
+        Err(ErrMode::Backtrack(InputError::new("1x", ErrorKind::Verify)))
+    );
+    assert_parse!(
+        ident.parse_peek("\u{301}x"),
+        Err(ErrMode::Backtrack(InputError::new(
+            "\u{301}x",
+            ErrorKind::Verify
+        )))
+    );
+    assert_parse!(
+        ident.parse_peek(Partial::new("abc")),
+        Err(ErrMode::Incomplete(Needed::new(1)))
+    );
+    assert_parse!(
+        ident.parse_peek(Partial::new("abc-")),
+        Ok((Partial::new("-"), "abc"))
+    );
+}
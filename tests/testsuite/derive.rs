@@ -0,0 +1,127 @@
+#![cfg(feature = "derive")]
+
+use winnow::ascii::alpha1;
+use winnow::ascii::dec_uint;
+use winnow::error::ErrMode;
+use winnow::error::ErrorKind;
+use winnow::error::InputError;
+use winnow::error::Needed;
+use winnow::prelude::*;
+use winnow::Parse;
+use winnow::Partial;
+
+#[derive(Parse, Debug, PartialEq)]
+#[winnow(input = &'i str, sep = ',')]
+struct Point {
+    #[winnow(parser = dec_uint)]
+    x: u32,
+    #[winnow(parser = dec_uint)]
+    y: u32,
+}
+
+#[derive(Parse, Debug, PartialEq)]
+#[winnow(input = &'i str, sep = ' ')]
+enum Shape<'i> {
+    #[winnow(tag = "point")]
+    Point(Point),
+    #[winnow(tag = "line")]
+    Line(Point, Point),
+    #[winnow(tag = "named")]
+    Named {
+        #[winnow(parser = alpha1)]
+        name: &'i str,
+    },
+    #[winnow(tag = "empty")]
+    Empty,
+}
+
+#[test]
+fn struct_fields() {
+    assert_eq!(Point::parse_next.parse("1,2"), Ok(Point { x: 1, y: 2 }));
+    assert!(Point::parse_next.parse("1 2").is_err());
+}
+
+#[test]
+fn enum_variants() {
+    assert_eq!(
+        Shape::parse_next.parse("point 1,2"),
+        Ok(Shape::Point(Point { x: 1, y: 2 }))
+    );
+    assert_eq!(
+        Shape::parse_next.parse("line 1,2 3,4"),
+        Ok(Shape::Line(Point { x: 1, y: 2 }, Point { x: 3, y: 4 }))
+    );
+    assert_eq!(
+        Shape::parse_next.parse("named origin"),
+        Ok(Shape::Named { name: "origin" })
+    );
+    assert_eq!(Shape::parse_next.parse("empty"), Ok(Shape::Empty));
+}
+
+#[test]
+fn enum_backtracks() {
+    #[derive(Parse, Debug, PartialEq)]
+    #[winnow(input = &'i str)]
+    enum Keyword {
+        #[winnow(tag = "if")]
+        If,
+        #[winnow(tag = "in")]
+        In,
+    }
+
+    assert_eq!(Keyword::parse_next.parse_peek("in"), Ok(("", Keyword::In)));
+    assert!(Keyword::parse_next.parse_peek("else").is_err());
+}
+
+#[derive(Parse, Debug, PartialEq)]
+#[winnow(endian = "big")]
+struct Header {
+    #[winnow(endian = "little")]
+    magic: u16,
+    len: u32,
+    kind: i8,
+}
+
+#[derive(Parse, Debug, PartialEq)]
+#[winnow(tag = b"REC")]
+struct Record<T> {
+    header: Header,
+    body: T,
+}
+
+#[derive(Parse, Debug, PartialEq)]
+#[winnow(endian = "little")]
+struct Body(f32);
+
+#[test]
+fn binary_generic_input() {
+    let input = &[
+        b'R', b'E', b'C', 0x4d, 0x5a, 0, 0, 1, 0, 0xff, 0, 0, 0x80, 0x3f,
+    ][..];
+    let expected = Record {
+        header: Header {
+            magic: 0x5a4d,
+            len: 256,
+            kind: -1,
+        },
+        body: Body(1.0),
+    };
+    let result: IResult<_, _, InputError<_>> = Record::<Body>::parse_next.parse_peek(input);
+    assert_eq!(result, Ok((&[][..], expected)));
+
+    let result: IResult<_, Record<Body>, _> = Record::parse_next.parse_peek(&b"RAC"[..]);
+    assert_eq!(
+        result,
+        Err(ErrMode::Backtrack(InputError::new(
+            &b"RAC"[..],
+            ErrorKind::Tag
+        )))
+    );
+}
+
+#[test]
+fn binary_partial() {
+    let input = Partial::new(&[0x4d, 0x5a, 0, 0][..]);
+    let result: IResult<_, Header, InputError<_>> = Header::parse_next.parse_peek(input);
+    assert_eq!(result, Err(ErrMode::Incomplete(Needed::new(2))));
+}
@@ -0,0 +1,150 @@
+//! Built-in parsers must not panic, regardless of the input
+#![cfg(feature = "std")]
+
+use proptest::prelude::*;
+
+use winnow::ascii::{
+    alpha1, dec_int, dec_uint, digit1, float, hex_uint, line_ending, multispace0, till_line_ending,
+};
+use winnow::binary::bits;
+use winnow::binary::{be_u16, be_u32, le_u64, length_repeat, length_take, u8};
+use winnow::combinator::{alt, delimited, opt, preceded, repeat, separated, terminated};
+use winnow::error::ContextError;
+use winnow::prelude::*;
+use winnow::stream::Fueled;
+use winnow::stream::Stream;
+use winnow::token::{any, literal, one_of, take, take_till, take_until, take_while};
+use winnow::Partial;
+
+type Error = ContextError;
+
+// Define the same parser for complete and partial input
+macro_rules! text {
+    ($name:ident, $input:ty) => {
+        fn $name(input: &mut $input) -> PResult<(), Error> {
+            repeat::<_, _, (), _, _>(
+                0..,
+                alt((
+                    dec_int::<_, i8, _>.void(),
+                    dec_uint::<_, u64, _>.void(),
+                    float::<_, f64, _>.void(),
+                    delimited('"', take_till(0.., '"'), '"').void(),
+                    preceded("/*", take_until(0.., "*/")).void(),
+                    terminated(alpha1, multispace0).void(),
+                    (digit1, opt(line_ending)).void(),
+                    (till_line_ending, line_ending).void(),
+                    separated::<_, _, (), _, _, _, _>(1.., one_of(['a', 'b']), ',').void(),
+                    take(3usize).void(),
+                    any.void(),
+                )),
+            )
+            .parse_next(input)
+        }
+    };
+}
+
+text!(complete_text, &str);
+text!(partial_text, Partial<&str>);
+text!(fueled_text, Fueled<&str>);
+
+macro_rules! binary {
+    ($name:ident, $input:ty) => {
+        fn $name(input: &mut $input) -> PResult<(), Error> {
+            repeat::<_, _, (), _, _>(
+                0..,
+                alt((
+                    preceded(literal(&b"\x00"[..]), be_u16).void(),
+                    preceded(literal(&b"\x01"[..]), le_u64).void(),
+                    preceded(literal(&b"\x02"[..]), hex_uint::<_, u32, _>.void()).void(),
+                    preceded(literal(&b"\x03"[..]), length_take(be_u32)).void(),
+                    preceded(
+                        literal(&b"\x04"[..]),
+                        length_repeat::<_, _, Vec<u8>, _, _, _, _>(u8, any),
+                    )
+                    .void(),
+                    preceded(
+                        literal(&b"\x05"[..]),
+                        bits::bits::<_, _, Error, _, _>((
+                            bits::take::<_, u8, _, _>(3usize),
+                            bits::bool,
+                            bits::take::<_, u16, _, _>(12usize),
+                            bits::take::<_, u8, _, _>(17usize),
+                        )),
+                    )
+                    .void(),
+                    take_while(1.., |b: u8| b.is_ascii_alphanumeric()).void(),
+                    any.void(),
+                )),
+            )
+            .parse_next(input)
+        }
+    };
+}
+
+binary!(complete_binary, &[u8]);
+binary!(partial_binary, Partial<&[u8]>);
+
+proptest! {
+  #[test]
+  #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
+  fn text_complete(s in "\\PC*") {
+      let _ = complete_text.parse_peek(s.as_str());
+      let _ = complete_text.parse(s.as_str());
+  }
+
+  #[test]
+  #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
+  fn text_partial(s in "\\PC*") {
+      let _ = partial_text.parse_peek(Partial::new(s.as_str()));
+  }
+
+  #[test]
+  #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
+  fn text_fueled(s in "\\PC*", fuel in 0..64usize) {
+      let mut input = Fueled::new(s.as_str());
+      input.set_fuel(fuel);
+      let _ = fueled_text.parse_next(&mut input);
+  }
+
+  #[test]
+  #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
+  fn binary_complete(b in proptest::collection::vec(proptest::num::u8::ANY, 0..64)) {
+      let _ = complete_binary.parse_peek(&b[..]);
+      let _ = complete_binary.parse(&b[..]);
+  }
+
+  #[test]
+  #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
+  fn binary_partial(b in proptest::collection::vec(proptest::num::u8::ANY, 0..64)) {
+      let _ = partial_binary.parse_peek(Partial::new(&b[..]));
+  }
+
+  #[test]
+  #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
+  fn try_next_slice(s in "\\PC*", offset in 0..64usize) {
+      let mut input = s.as_str();
+      match input.try_next_slice(offset) {
+          Some(slice) => {
+              assert_eq!(slice.len(), offset);
+              assert_eq!(input.len(), s.len() - offset);
+          }
+          None => {
+              assert!(!s.is_char_boundary(offset));
+              assert_eq!(input, s.as_str());
+          }
+      }
+  }
+}
+
+#[test]
+#[cfg(feature = "regex")]
+fn fueled_regex_char_boundary() {
+    use winnow::error::InputError;
+    use winnow::token::regex;
+
+    let mut input = Fueled::new("øb");
+    assert!(regex::<_, InputError<_>>(r"(?-u:.)")
+        .parse_next(&mut input)
+        .is_err());
+    assert_eq!(*input, "øb");
+}